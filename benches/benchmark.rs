@@ -8,7 +8,13 @@ use fxhash::FxHasher64;
 use xxhash_rust::xxh3::xxh3_64;
 
 use nthash_rs::SeedNtHashBuilder;
-use nthash_rs::{kmer::NtHashBuilder, BlindNtHashBuilder};
+use nthash_rs::{
+    hash_block, hash_into,
+    kmer::{base_forward_hash, base_reverse_hash, NtHashBuilder},
+    packed::PackedHash,
+    stats::digest,
+    BlindNtHashBuilder, NtHash,
+};
 
 /// Generate a pseudo‐random DNA sequence of length `len` by
 /// repeating "ACGT" and inserting occasional "N"s.
@@ -35,19 +41,56 @@ fn bench_nthash(c: &mut Criterion) {
     group.bench_with_input(BenchmarkId::new("NtHash", seq.len()), &seq, |b, seq| {
         b.iter(|| {
             // build a new rolling iterator each iteration
-            let mut iter = NtHashBuilder::new(seq.as_bytes())
+            let iter = NtHashBuilder::new(seq.as_bytes())
                 .k(k)
                 .num_hashes(m)
                 .pos(0)
                 .finish()
                 .unwrap();
             // consume it
-            while let Some((_pos, _hashes)) = iter.next() {
+            for (_pos, _hashes) in iter {
                 // no-op
             }
         })
     });
 
+    // whole-sequence digest: only the result needs black_box
+    group.bench_with_input(
+        BenchmarkId::new("stats::digest", seq.len()),
+        &seq,
+        |b, seq| b.iter(|| criterion::black_box(digest(seq.as_bytes(), k, m).unwrap())),
+    );
+
+    // four hashes per k-mer: heap buffer vs stack array
+    group.bench_with_input(
+        BenchmarkId::new("NtHash::hashes x4", seq.len()),
+        &seq,
+        |b, seq| {
+            b.iter(|| {
+                let mut h = NtHash::new(seq.as_bytes(), k, 4, 0).unwrap();
+                let mut acc = 0;
+                while h.roll() {
+                    acc ^= h.hashes()[3];
+                }
+                acc
+            })
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("NtHash::hashes_array::<4>", seq.len()),
+        &seq,
+        |b, seq| {
+            b.iter(|| {
+                let mut h = NtHash::new(seq.as_bytes(), k, 1, 0).unwrap();
+                let mut acc = 0;
+                while h.roll() {
+                    acc ^= h.hashes_array::<4>()[3];
+                }
+                acc
+            })
+        },
+    );
+
     group.finish();
 }
 
@@ -64,14 +107,14 @@ fn bench_blindnthash(c: &mut Criterion) {
         &seq,
         |b, seq| {
             b.iter(|| {
-                let mut iter = BlindNtHashBuilder::new(seq.as_bytes())
+                let iter = BlindNtHashBuilder::new(seq.as_bytes())
                     .k(k)
                     .num_hashes(m)
                     .pos(0)
                     .finish()
                     .unwrap();
                 // consume it
-                while let Some((_pos, _hashes)) = iter.next() {
+                for (_pos, _hashes) in iter {
                     // no-op
                 }
             })
@@ -91,7 +134,7 @@ fn bench_seednthash(c: &mut Criterion) {
 
     group.bench_with_input(BenchmarkId::new("SeedNtHash", seq.len()), &seq, |b, seq| {
         b.iter(|| {
-            let mut iter = SeedNtHashBuilder::new(seq.as_bytes())
+            let iter = SeedNtHashBuilder::new(seq.as_bytes())
                 .k(k)
                 .masks(vec!["0000000000000000000000000000000".to_string()])
                 .num_hashes(m)
@@ -99,7 +142,7 @@ fn bench_seednthash(c: &mut Criterion) {
                 .finish()
                 .unwrap();
             // consume it
-            while let Some((_pos, _hashes)) = iter.next() {
+            for (_pos, _hashes) in iter {
                 // no-op
             }
         })
@@ -192,6 +235,110 @@ fn bench_fxhash(c: &mut Criterion) {
     group.finish();
 }
 
+/// Initial (non‑rolling) k‑mer hash of long k‑mers; compare runs with and
+/// without `--features simd`.
+/// ntHash against the table‑free packed hasher at minimizer‑sized k.
+fn bench_small_k(c: &mut Criterion) {
+    let seq = generate_dna(1_000_000).replace('N', "A");
+    let mut group = c.benchmark_group("small_k");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    for k in [15u16, 21, 31] {
+        group.bench_with_input(BenchmarkId::new("NtHash", k), &k, |b, &k| {
+            b.iter(|| {
+                let mut h = NtHash::new(seq.as_bytes(), k, 1, 0).unwrap();
+                let mut acc = 0;
+                while h.roll() {
+                    acc ^= h.hashes()[0];
+                }
+                acc
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("PackedHash", k), &k, |b, &k| {
+            b.iter(|| {
+                let mut h = PackedHash::new(seq.as_bytes(), k, 1, 0).unwrap();
+                let mut acc = 0;
+                while h.roll() {
+                    acc ^= h.hashes()[0];
+                }
+                acc
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Whole‑sequence hashing into a position‑indexed buffer.
+fn bench_hash_block(c: &mut Criterion) {
+    // scattered Ns, one per ~500 bases
+    let seq: Vec<u8> = generate_dna(1_000_000)
+        .replace('N', "A")
+        .bytes()
+        .enumerate()
+        .map(|(i, c)| if i % 499 == 0 { b'N' } else { c })
+        .collect();
+    let k: u16 = 31;
+    let mut out = vec![0u64; seq.len() - k as usize + 1];
+    let mut group = c.benchmark_group("hash_block");
+    group.throughput(Throughput::Bytes(seq.len() as u64));
+
+    group.bench_function("hash_into", |b| {
+        b.iter(|| hash_into(&seq, k, &mut out).unwrap())
+    });
+    group.bench_function("hash_block", |b| {
+        b.iter(|| hash_block(&seq, k, &mut out).unwrap())
+    });
+
+    group.finish();
+}
+
+/// Starting and seeking mid‑way through a long `N`‑free sequence; each
+/// should cost O(k), not O(distance to the next `N`).
+fn bench_mid_sequence(c: &mut Criterion) {
+    let seq = generate_dna(10_000_000).replace('N', "A");
+    let (k, mid) = (21u16, seq.len() / 2);
+    let mut group = c.benchmark_group("mid_sequence");
+
+    group.bench_function("new_and_roll", |b| {
+        b.iter(|| {
+            let mut h = NtHash::new(seq.as_bytes(), k, 1, mid).unwrap();
+            h.roll();
+            h.roll();
+            h.hashes()[0]
+        })
+    });
+    group.bench_function("seek_and_roll", |b| {
+        let mut h = NtHash::new(seq.as_bytes(), k, 1, 0).unwrap();
+        let mut far = false;
+        b.iter(|| {
+            far = !far;
+            h.seek(if far { mid } else { 1000 }).unwrap();
+            h.roll();
+            h.hashes()[0]
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_base_hash(c: &mut Criterion) {
+    let seq = generate_dna(4096).replace('N', "A");
+    let mut group = c.benchmark_group("base_hash");
+
+    for k in [32u16, 256, 4096] {
+        group.throughput(Throughput::Bytes(k as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(k), &k, |b, &k| {
+            b.iter(|| {
+                let s = criterion::black_box(seq.as_bytes());
+                (base_forward_hash(s, k), base_reverse_hash(s, k))
+            })
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_nthash,
@@ -200,6 +347,10 @@ criterion_group!(
     bench_xxh3,
     bench_fnv,
     bench_fxhash,
-    bench_ahash
+    bench_ahash,
+    bench_small_k,
+    bench_hash_block,
+    bench_mid_sequence,
+    bench_base_hash
 );
 criterion_main!(benches);
@@ -3,19 +3,16 @@ use nthash_rs::{Result, SeedNtHash, SeedNtHashBuilder};
 fn main() -> Result<()> {
     println!("# SeedNtHash");
     let seq = "ATCGTACGATGCATGCATGCTGACG";
-    let seed_masks = vec![
-        "000111".to_string(),
-        "010101".to_string(),
-    ];
-    let k   = 6u16;
-    let m2  = 2usize;
+    let seed_masks = vec!["000111".to_string(), "010101".to_string()];
+    let k = 6u16;
+    let m2 = 2usize;
 
     println!("## NtHash Low-Level API");
     let mut h = SeedNtHash::new(seq.as_bytes(), &seed_masks, m2, k, 0)?;
     while h.roll() {
-        let pos   = h.pos() as usize;
+        let pos = h.pos() as usize;
         let end = pos + k as usize;
-        let kmer  = &seq[pos..end];
+        let kmer = &seq[pos..end];
         let hashes = h.hashes();
         println!("{} {:x?}", kmer, hashes);
     }
@@ -29,8 +26,7 @@ fn main() -> Result<()> {
         .finish()?;
 
     for (pos, hashes) in iter {
-        let end = pos + k as usize;
-        let kmer  = &seq[pos..end];
+        let kmer = &seq[pos.range()];
         println!("{} {:x?}", kmer, hashes);
     }
 
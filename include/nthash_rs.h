@@ -0,0 +1,295 @@
+#ifndef NTHASH_RS_H
+#define NTHASH_RS_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Rolling hash over a *fixed‑width* window that the caller rolls manually.
+//
+// With a [`TrackedWindow`] storage (`VecDeque<u8>` by default):
+// - `roll()` removes the **front** base and pushes a new base at the **back**.
+// - `roll_back()` does the opposite.
+// - `peek()` / `peek_back()` compute hashes for the next / previous window
+//   into [`peeked_hashes`](Self::peeked_hashes) **without** moving.
+//
+// Every storage, including [`NoWindow`], supports the `*_with` variants that
+// take the outgoing base as an argument.
+//
+// With the `serde` feature the complete state (window, position, strand
+// hashes, and hash buffer) can be checkpointed and restored mid‑sequence.
+typedef struct BlindNtHash BlindNtHash;
+
+// Rolling k‑mer hasher over a contiguous DNA sequence.
+//
+// - Initialization is deferred until the first valid k‑mer (skips any
+//   windows containing `N`).
+// - `roll()` / `roll_back()` advance by one base, handling skips transparently.
+// - Each valid k‑mer emits `num_hashes` values: the canonical hash plus
+//   extra mixes.
+typedef struct NtHash NtHash;
+
+// Struct for computing spaced-seed ntHash values in a re-computational manner.
+// Can handle multiple seeds and generates multiple hashes per k-mer.
+typedef struct SeedNtHash SeedNtHash;
+
+// Start position and canonical hash of one k‑mer (16 bytes).
+typedef struct PosHash {
+  // Start of the k‑mer in the hashed sequence.
+  uint64_t pos;
+  // Canonical ntHash value.
+  uint64_t hash;
+} PosHash;
+
+// A minimizer shared by a query and a reference (16 bytes).
+typedef struct Anchor {
+  // Reference index.
+  uint32_t ref_id;
+  // k‑mer start on the reference.
+  uint32_t ref_pos;
+  // k‑mer start on the query.
+  uint32_t query_pos;
+  // `1` if the k‑mer has the same orientation in both, `0` otherwise.
+  uint32_t forward;
+} Anchor;
+
+// Fixed‑size header preceding a sketch's `len` hash values (24 bytes).
+typedef struct SketchHeader {
+  // [`SKETCH_MINHASH`] (1) or [`SKETCH_SCALED`] (2).
+  uint16_t kind;
+  // k‑mer length the hashes were computed with.
+  uint16_t k;
+  // Number of `u64` hashes that follow.
+  uint32_t len;
+  // Sketch size for MinHash; largest retained hash for FracMinHash.
+  uint64_t param;
+  // Reserved, zero.
+  uint64_t reserved;
+} SketchHeader;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create an `NtHash` over `seq[0..seq_len]` starting at `pos`.
+//
+// Returns `NULL` if `seq` is `NULL`, `k == 0`, `seq_len < k`, or `pos` is out
+// of range.
+//
+// # Safety
+//
+// `seq` must point to `seq_len` readable bytes that outlive the handle.
+struct NtHash *nthash_new(const char *seq,
+                          size_t seq_len,
+                          uint8_t num_hashes,
+                          uint16_t k,
+                          size_t pos);
+
+// Advance by one base, skipping k‑mers with non‑ACGT bases.
+//
+// Returns `false` once the end of the sequence is reached.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+bool nthash_roll(struct NtHash *h);
+
+// Step back by one base, skipping k‑mers with non‑ACGT bases.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+bool nthash_roll_back(struct NtHash *h);
+
+// Pointer to the current [`nthash_get_hash_num`] hash values.
+//
+// The pointer is invalidated by the next roll or by [`nthash_free`].
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+const uint64_t *nthash_hashes(const struct NtHash *h);
+
+// Number of hash values per k‑mer.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+size_t nthash_get_hash_num(const struct NtHash *h);
+
+// Start position of the current k‑mer.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+size_t nthash_get_pos(const struct NtHash *h);
+
+// Forward‑strand hash of the current k‑mer.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+uint64_t nthash_get_forward_hash(const struct NtHash *h);
+
+// Reverse‑complement hash of the current k‑mer.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`nthash_new`].
+uint64_t nthash_get_reverse_hash(const struct NtHash *h);
+
+// Release a handle.  `NULL` is ignored.
+//
+// # Safety
+//
+// `h` must be `NULL` or a handle returned by [`nthash_new`] that has not
+// been freed yet.
+void nthash_free(struct NtHash *h);
+
+// Create a `BlindNtHash` whose first window is `seq[pos..pos + k]`.
+//
+// The window is copied, so `seq` need not outlive the handle.  Returns
+// `NULL` if `seq` is `NULL`, `k == 0`, or the window does not fit.
+//
+// # Safety
+//
+// `seq` must point to `seq_len` readable bytes.
+struct BlindNtHash *blind_nthash_new(const char *seq,
+                                     size_t seq_len,
+                                     uint8_t num_hashes,
+                                     uint16_t k,
+                                     ptrdiff_t pos);
+
+// Slide the window forward, appending `char_in`.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`blind_nthash_new`].
+bool blind_nthash_roll(struct BlindNtHash *h, char char_in);
+
+// Slide the window backward, prepending `char_in`.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`blind_nthash_new`].
+bool blind_nthash_roll_back(struct BlindNtHash *h, char char_in);
+
+// Pointer to the current [`blind_nthash_get_hash_num`] hash values.
+//
+// The pointer is invalidated by the next roll or by [`blind_nthash_free`].
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`blind_nthash_new`].
+const uint64_t *blind_nthash_hashes(const struct BlindNtHash *h);
+
+// Number of hash values per k‑mer.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`blind_nthash_new`].
+size_t blind_nthash_get_hash_num(const struct BlindNtHash *h);
+
+// Start position of the current window.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`blind_nthash_new`].
+ptrdiff_t blind_nthash_get_pos(const struct BlindNtHash *h);
+
+// Release a handle.  `NULL` is ignored.
+//
+// # Safety
+//
+// `h` must be `NULL` or a handle returned by [`blind_nthash_new`] that has
+// not been freed yet.
+void blind_nthash_free(struct BlindNtHash *h);
+
+// Create a `SeedNtHash` from `num_seeds` NUL‑terminated '0'/'1' masks of
+// length `k`.
+//
+// Hash values are laid out seed by seed, `num_hashes_per_seed` each.
+// Returns `NULL` on a `NULL` pointer, an invalid mask, or a sequence that is
+// too short.
+//
+// # Safety
+//
+// `seq` must point to `seq_len` readable bytes that outlive the handle, and
+// `seeds` must point to `num_seeds` valid C strings.
+struct SeedNtHash *seed_nthash_new(const char *seq,
+                                   size_t seq_len,
+                                   const char *const *seeds,
+                                   size_t num_seeds,
+                                   uint8_t num_hashes_per_seed,
+                                   uint16_t k,
+                                   size_t pos);
+
+// Advance to the next k‑mer whose care positions are all ACGT.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`seed_nthash_new`].
+bool seed_nthash_roll(struct SeedNtHash *h);
+
+// Pointer to the current [`seed_nthash_get_hash_num`] hash values.
+//
+// The pointer is invalidated by the next roll or by [`seed_nthash_free`].
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`seed_nthash_new`].
+const uint64_t *seed_nthash_hashes(const struct SeedNtHash *h);
+
+// Total number of hash values (seeds × hashes per seed).
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`seed_nthash_new`].
+size_t seed_nthash_get_hash_num(const struct SeedNtHash *h);
+
+// Start position of the current k‑mer.
+//
+// # Safety
+//
+// `h` must be a live handle returned by [`seed_nthash_new`].
+size_t seed_nthash_get_pos(const struct SeedNtHash *h);
+
+// Release a handle.  `NULL` is ignored.
+//
+// # Safety
+//
+// `h` must be `NULL` or a handle returned by [`seed_nthash_new`] that has
+// not been freed yet.
+void seed_nthash_free(struct SeedNtHash *h);
+
+// Returns `true` if `mask` is a valid spaced‑seed mask ('0'/'1', non‑empty).
+//
+// # Safety
+//
+// `mask` must be `NULL` or a valid C string.
+bool nthash_seed_is_valid(const char *mask);
+
+// Write a [`PosHash`] for each valid k‑mer of `seq[0..seq_len]` into
+// `out[0..out_len]`.
+//
+// Returns the total number of valid k‑mers, which may exceed `out_len`;
+// call with `out_len == 0` (and `out` `NULL`) to size the buffer first.
+// Returns 0 if `seq` is `NULL`, `k == 0`, or `seq_len < k`.
+//
+// # Safety
+//
+// `seq` must point to `seq_len` readable bytes and `out` to `out_len`
+// writable records.
+size_t nthash_pos_hashes(const char *seq,
+                         size_t seq_len,
+                         uint16_t k,
+                         struct PosHash *out,
+                         size_t out_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NTHASH_RS_H */
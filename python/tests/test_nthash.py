@@ -0,0 +1,40 @@
+import numpy as np
+import pytest
+
+import nthash_rs
+
+SEQ = "ACGTCAGTNNNNACGTACGTTGCA"
+
+
+def test_iterator_matches_arrays():
+    rows = list(nthash_rs.NtHash(SEQ, k=4, num_hashes=2))
+    positions, hashes = nthash_rs.NtHash(SEQ, k=4, num_hashes=2).arrays()
+    assert positions.tolist() == [p for p, _ in rows]
+    assert hashes.dtype == np.uint64
+    assert hashes.shape == (len(rows), 2)
+    assert hashes.tolist() == [h for _, h in rows]
+
+
+def test_str_and_bytes_agree():
+    assert list(nthash_rs.NtHash(SEQ, 5)) == list(nthash_rs.NtHash(SEQ.encode(), 5))
+
+
+def test_blind_roll_all_matches_nthash():
+    seq = "ACGTCAGTACGTACGT"
+    blind = nthash_rs.BlindNtHash(seq, k=4)
+    rolled = blind.roll_all(seq[4:])
+    expected = [h for _, h in nthash_rs.NtHash(seq, 4)][1:]
+    assert rolled.tolist() == expected
+
+
+def test_seed_hashes_per_kmer():
+    h = nthash_rs.SeedNtHash(SEQ, ["1101", "1001"], k=4, num_hashes=3)
+    pos, hashes = next(h)
+    assert len(hashes) == 6
+
+
+def test_invalid_arguments_raise_value_error():
+    with pytest.raises(ValueError):
+        nthash_rs.NtHash(SEQ, k=0)
+    with pytest.raises(ValueError):
+        nthash_rs.SeedNtHash(SEQ, ["11x1"], k=4)
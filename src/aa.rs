@@ -0,0 +1,145 @@
+//! **Rolling hash over peptide k‑mers** (amino‑acid sequences).
+//!
+//! [`AaHash`] applies the ntHash recurrence to the 20 standard amino acids:
+//! each residue has a random 64‑bit seed, a k‑mer hashes to the XOR of its
+//! residues' seeds split‑rotated by their distance from the end, and moving
+//! the window by one residue costs O(1).  Proteins have no reverse
+//! complement, so the forward hash is the only hash; extra hashes are
+//! derived from it exactly as for DNA.  It is the generic
+//! [`RollingHash`] over the [`Protein`] alphabet.
+//!
+//! Windows containing anything other than the 20 standard one‑letter codes
+//! (either case) — `X`, `B`, `Z`, `J`, `U`, `O`, stop `*`, gaps — are
+//! skipped, just as [`NtHash`](crate::NtHash) skips `N`.
+//!
+//! ```
+//! use nthash_rs::aa::AaHash;
+//!
+//! let protein = b"MKTAYIAKQRQISFVKSHFSRQ";
+//! let mut h = AaHash::new(protein, 5, 2, 0).unwrap();
+//! let mut first = Vec::new();
+//! while h.roll() {
+//!     first.push(h.hashes()[0]);
+//! }
+//! assert_eq!(first.len(), protein.len() - 4);
+//!
+//! // the same peptide hashes the same wherever it occurs
+//! let mut other = AaHash::new(b"QISFVK", 5, 1, 0).unwrap();
+//! assert!(other.roll());
+//! assert_eq!(other.hashes()[0], first[10]);
+//! ```
+
+use crate::alphabet::{Alphabet, RollingHash};
+
+/// The 20 standard amino acids, in the order of [`AA_SEEDS`].
+pub const AMINO_ACIDS: &[u8; 20] = b"ACDEFGHIKLMNPQRSTVWY";
+
+/// Marker in [`AA_CODE`] for residues that are not hashed.
+const AA_INVALID: u8 = 0xFF;
+
+/// ASCII → index into [`AMINO_ACIDS`], or `0xFF`.
+const AA_CODE: [u8; 256] = {
+    let mut t = [AA_INVALID; 256];
+    let mut i = 0;
+    while i < AMINO_ACIDS.len() {
+        t[AMINO_ACIDS[i] as usize] = i as u8;
+        t[AMINO_ACIDS[i].to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    t
+};
+
+/// 64‑bit seeds of the amino acids, in the order of [`AMINO_ACIDS`].
+///
+/// Drawn from SplitMix64 with a fixed starting state, so they are stable
+/// across releases.
+pub const AA_SEEDS: [u64; 20] = {
+    let mut seeds = [0u64; 20];
+    let mut state: u64 = 0x6e74_4861_7368_4141; // "ntHashAA"
+    let mut i = 0;
+    while i < 20 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        seeds[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    seeds
+};
+
+/// Seed of an ASCII residue, or `None` if it is not one of the 20
+/// standard amino acids.
+#[inline(always)]
+pub const fn aa_seed(residue: u8) -> Option<u64> {
+    match AA_CODE[residue as usize] {
+        AA_INVALID => None,
+        code => Some(AA_SEEDS[code as usize]),
+    }
+}
+
+/// The 20 standard amino acids, single‑stranded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Protein;
+
+impl Alphabet for Protein {
+    #[inline(always)]
+    fn seed(&self, symbol: u8) -> Option<u64> {
+        aa_seed(symbol)
+    }
+}
+
+/// Rolling hasher over the peptide k‑mers of a protein sequence.
+///
+/// Mirrors [`NtHash`](crate::NtHash): initialization is deferred to the
+/// first valid k‑mer, and `roll()` / `roll_back()` skip windows with
+/// non‑standard residues.
+pub type AaHash<'a> = RollingHash<'a, Protein>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::srol;
+
+    fn direct(kmer: &[u8]) -> u64 {
+        kmer.iter().fold(0, |h, &c| srol(h) ^ aa_seed(c).unwrap())
+    }
+
+    #[test]
+    fn rolling_matches_direct_hash() {
+        let seq = b"MKTAYIAKQRQXSFVKSHFSRQLEERLGLIEVQAPIL*mktayiakqrq";
+        for k in [1u16, 4, 9] {
+            let mut h = AaHash::new(seq, k, 3, 0).unwrap();
+            let mut fwd = Vec::new();
+            while h.roll() {
+                let w = &seq[h.pos()..h.pos() + k as usize];
+                assert_eq!(h.hash(), direct(w), "k={k} pos={}", h.pos());
+                assert_eq!(h.hashes()[0], h.hash());
+                fwd.push((h.pos(), h.hashes().to_vec()));
+            }
+            let mut back = vec![fwd.last().unwrap().clone()];
+            while h.roll_back() {
+                let w = &seq[h.pos()..h.pos() + k as usize];
+                assert_eq!(h.hash(), direct(w), "k={k} pos={}", h.pos());
+                back.push((h.pos(), h.hashes().to_vec()));
+            }
+            back.reverse();
+            assert_eq!(back, fwd, "k={k}");
+        }
+        // case‑insensitive
+        let mut upper = AaHash::new(b"MKTAYIAKQRQ", 11, 1, 0).unwrap();
+        let mut lower = AaHash::new(b"mktayiakqrq", 11, 1, 0).unwrap();
+        assert!(upper.roll() && lower.roll());
+        assert_eq!(upper.hashes(), lower.hashes());
+
+        assert!(
+            AaHash::new(b"XXXXX", 3, 1, 0)
+                .unwrap()
+                .drive(&mut |_, _: &[u64]| {})
+                == 0
+        );
+        assert!(AaHash::new(b"MK", 3, 1, 0).is_err());
+        let distinct: std::collections::HashSet<u64> = AA_SEEDS.iter().copied().collect();
+        assert_eq!(distinct.len(), 20);
+    }
+}
@@ -0,0 +1,152 @@
+//! **Per‑window aggregation** of k‑mer hash streams.
+//!
+//! Coarse‑grained similarity scans rarely need every k‑mer hash.  The
+//! [`Aggregated`] adapter folds the canonical hashes (`hashes[0]`) of all
+//! k‑mers whose start falls into the same non‑overlapping window of `W`
+//! positions into a single value — the minimum, maximum, or XOR — cutting
+//! output volume by roughly a factor of `W`.
+//!
+//! Windows are aligned to multiples of `W`, so the same sequence always
+//! yields the same windows regardless of where the scan started; windows in
+//! which every k‑mer was skipped (e.g. runs of `N`) are omitted.
+//!
+//! ```
+//! use nthash_rs::aggregate::{Aggregate, AggregateExt};
+//! use nthash_rs::NtHashBuilder;
+//!
+//! let seq = b"ACGTCAGTACGTACGTTTGACA";
+//! let mins: Vec<(usize, u64)> = NtHashBuilder::new(seq)
+//!     .k(5)
+//!     .finish()
+//!     .unwrap()
+//!     .aggregate(8, Aggregate::Min)
+//!     .collect();
+//! // 18 k‑mers → windows starting at 0, 8, 16
+//! assert_eq!(mins.iter().map(|w| w.0).collect::<Vec<_>>(), [0, 8, 16]);
+//! ```
+
+use std::iter::Peekable;
+
+/// How the canonical hashes of one window are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aggregate {
+    /// Smallest hash in the window.
+    Min,
+    /// Largest hash in the window.
+    Max,
+    /// XOR of all hashes in the window.
+    Xor,
+}
+
+impl Aggregate {
+    #[inline]
+    fn fold(self, acc: u64, h: u64) -> u64 {
+        match self {
+            Aggregate::Min => acc.min(h),
+            Aggregate::Max => acc.max(h),
+            Aggregate::Xor => acc ^ h,
+        }
+    }
+}
+
+/// Iterator adapter yielding `(window_start, value)` per non‑empty window.
+pub struct Aggregated<I: Iterator> {
+    inner: Peekable<I>,
+    width: usize,
+    mode: Aggregate,
+}
+
+impl<I, P, H> Aggregated<I>
+where
+    I: Iterator<Item = (P, H)>,
+    P: Copy + Into<usize>,
+    H: AsRef<[u64]>,
+{
+    /// Aggregate `inner` over windows of `width` positions (`0` is treated
+    /// as 1).
+    pub fn new(inner: I, width: usize, mode: Aggregate) -> Self {
+        Self {
+            inner: inner.peekable(),
+            width: width.max(1),
+            mode,
+        }
+    }
+}
+
+impl<I, P, H> Iterator for Aggregated<I>
+where
+    I: Iterator<Item = (P, H)>,
+    P: Copy + Into<usize>,
+    H: AsRef<[u64]>,
+{
+    type Item = (usize, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, hashes) = self.inner.next()?;
+        let pos: usize = pos.into();
+        let start = pos - pos % self.width;
+        let end = start + self.width;
+        let mut acc = hashes.as_ref()[0];
+        while let Some((_, hashes)) = self.inner.next_if(|(p, _)| (*p).into() < end) {
+            acc = self.mode.fold(acc, hashes.as_ref()[0]);
+        }
+        Some((start, acc))
+    }
+}
+
+/// Adds [`aggregate`](AggregateExt::aggregate) to every `(pos, hashes)`
+/// iterator, where `pos` is a [`KmerPos`](crate::KmerPos) (aggregated by
+/// window start) or a plain `usize`.
+pub trait AggregateExt: Iterator + Sized {
+    /// Emit one aggregated value per window of `width` positions.
+    fn aggregate(self, width: usize, mode: Aggregate) -> Aggregated<Self>;
+}
+
+impl<I, P, H> AggregateExt for I
+where
+    I: Iterator<Item = (P, H)>,
+    P: Copy + Into<usize>,
+    H: AsRef<[u64]>,
+{
+    fn aggregate(self, width: usize, mode: Aggregate) -> Aggregated<Self> {
+        Aggregated::new(self, width, mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NtHashBuilder;
+
+    #[test]
+    fn matches_manual_folding() {
+        let seq = b"ACGTCAGTACGTNNNNACGTTTGACAGGATCCA";
+        let all: Vec<_> = NtHashBuilder::new(seq).k(4).finish().unwrap().collect();
+        for mode in [Aggregate::Min, Aggregate::Max, Aggregate::Xor] {
+            let got: Vec<_> = all.iter().cloned().aggregate(6, mode).collect();
+            let mut want: Vec<(usize, u64)> = Vec::new();
+            for (pos, h) in &all {
+                let start = pos.start / 6 * 6;
+                match want.last_mut() {
+                    Some(w) if w.0 == start => w.1 = mode.fold(w.1, h[0]),
+                    _ => want.push((start, h[0])),
+                }
+            }
+            assert_eq!(got, want);
+        }
+    }
+
+    #[test]
+    fn skipped_windows_are_omitted() {
+        // k‑mer starts 0..=1 and 14..=15; window 6..12 is all N
+        let seq = b"ACGTANNNNNNNNNACGTA";
+        let starts: Vec<_> = NtHashBuilder::new(seq)
+            .k(4)
+            .finish()
+            .unwrap()
+            .aggregate(6, Aggregate::Xor)
+            .map(|w| w.0)
+            .collect();
+        assert_eq!(starts, [0, 12]);
+    }
+}
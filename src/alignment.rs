@@ -0,0 +1,420 @@
+//! **Aligned‑read hashing** for SAM / BAM / CRAM records.
+//!
+//! Aligners store a read's bases on the reference's forward strand, so a
+//! reverse‑strand read appears reverse‑complemented, soft‑clipped bases are
+//! kept but unaligned, and hard‑clipped bases are dropped altogether.
+//! [`hash_aligned`] undoes that bookkeeping: it hashes the stored bases
+//! (optionally without the soft clips), and reports every k‑mer at its
+//! position in the read *as sequenced*, counting hard‑clipped bases, in
+//! increasing order.  Hashes are canonical, hence identical on both strands,
+//! so a read hashes the same whichever strand it aligned to.
+//!
+//! [`ToReference`] instead maps each k‑mer of an ungapped read onto the
+//! reference's forward strand, yielding `(ref_pos, hash)`.
+//!
+//! ```
+//! use nthash_rs::alignment::{hash_aligned, ReadLayout, SoftClips};
+//! use nthash_rs::NtHashBuilder;
+//!
+//! let read = b"CCACGTTGCATG"; // as sequenced
+//! // aligned to the reverse strand, its first two bases hard‑clipped
+//! let stored = b"CATGCAACGT"; // reverse complement of read[2..]
+//! let layout = ReadLayout { hard_clip: (0, 2), reverse: true, ..Default::default() };
+//! let aligned: Vec<_> = hash_aligned(stored, layout, 4, 1, SoftClips::Keep).unwrap().collect();
+//! let direct: Vec<_> = NtHashBuilder::new(read).k(4).pos(2).into_iter().collect();
+//! assert_eq!(aligned, direct);
+//! ```
+
+use crate::{
+    kmer::{Direction, KmerPos, NtHashIter},
+    NtHashBuilder, NtHashError, Result,
+};
+
+/// Clipping and strand of an aligned read, as given by its CIGAR string and
+/// FLAG field.
+///
+/// Clip lengths are `(leading, trailing)` in the stored (reference‑forward)
+/// orientation, as they appear in the CIGAR.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReadLayout {
+    /// `H` operations at the start and end of the CIGAR.
+    pub hard_clip: (usize, usize),
+    /// `S` operations at the start and end of the CIGAR.
+    pub soft_clip: (usize, usize),
+    /// FLAG bit `0x10`: the stored bases are the read's reverse complement.
+    pub reverse: bool,
+}
+
+/// Whether [`hash_aligned`] hashes soft‑clipped bases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SoftClips {
+    /// Hash the whole stored sequence.
+    #[default]
+    Keep,
+    /// Hash only the aligned bases.
+    Skip,
+}
+
+/// Hash the stored bases `seq` of an aligned read, yielding each k‑mer's
+/// position in the read as sequenced, in increasing order.
+///
+/// # Errors
+///
+/// As [`NtHashBuilder::finish`] over the hashed bases; in particular
+/// [`NtHashError::SequenceTooShort`] if fewer than `k` bases remain, and
+/// [`NtHashError::PositionOutOfRange`] if the soft clips are longer than
+/// `seq`.
+pub fn hash_aligned(
+    seq: &[u8],
+    layout: ReadLayout,
+    k: u16,
+    num_hashes: u8,
+    soft_clips: SoftClips,
+) -> Result<AlignedHashes<'_>> {
+    let (left, right) = match soft_clips {
+        SoftClips::Keep => (0, 0),
+        SoftClips::Skip => layout.soft_clip,
+    };
+    if left + right > seq.len() {
+        return Err(NtHashError::PositionOutOfRange {
+            pos: left + right,
+            seq_len: seq.len(),
+        });
+    }
+    let hashed = &seq[left..seq.len() - right];
+    let direction = if layout.reverse {
+        Direction::Reverse
+    } else {
+        Direction::Forward
+    };
+    let inner = NtHashBuilder::new(hashed)
+        .k(k)
+        .num_hashes(num_hashes)
+        .direction(direction)
+        .finish()?;
+    let k = k as usize;
+    // stored index `i` is read position `hard_5' + i` forward, and
+    // `hard_5' + (len − i − k)` reversed, where the 5' end is on the right
+    let origin = if layout.reverse {
+        layout.hard_clip.1 + seq.len() - left - k
+    } else {
+        layout.hard_clip.0 + left
+    };
+    Ok(AlignedHashes {
+        inner,
+        origin,
+        reverse: layout.reverse,
+        k,
+    })
+}
+
+/// Iterator returned by [`hash_aligned`].
+#[derive(Debug)]
+pub struct AlignedHashes<'a> {
+    inner: NtHashIter<'a>,
+    /// Read position of the k‑mer at the start of the hashed bases.
+    origin: usize,
+    reverse: bool,
+    k: usize,
+}
+
+impl Iterator for AlignedHashes<'_> {
+    type Item = (KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, hashes) = self.inner.next()?;
+        let start = if self.reverse {
+            self.origin - pos.start
+        } else {
+            self.origin + pos.start
+        };
+        Some((KmerPos::new(start, self.k), hashes))
+    }
+}
+
+/// Where an ungapped read lies on the reference, for [`ToReference`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Placement {
+    /// Reference coordinate of the read's leftmost aligned base.
+    pub ref_start: usize,
+    /// Length of the hashed read sequence.
+    pub read_len: usize,
+    /// Whether the hashed sequence is the reverse complement of the
+    /// reference (a reverse‑strand read hashed as sequenced).
+    pub reverse: bool,
+}
+
+/// Iterator adapter yielding `(ref_pos, hash)`: the reference start of each
+/// k‑mer on the forward strand, and its canonical hash (`hashes[0]`).
+///
+/// Positions are converted one item at a time, so a reverse‑strand read
+/// hashed forward yields decreasing reference positions; hash it with
+/// [`Direction::Reverse`] to get them in increasing order.
+#[derive(Debug, Clone)]
+pub struct RefCoords<I> {
+    inner: I,
+    placement: Placement,
+}
+
+impl<I> RefCoords<I> {
+    /// Convert the k‑mer positions of `inner`, a hasher iterator over the
+    /// read, to reference coordinates.
+    pub fn new(inner: I, placement: Placement) -> Self {
+        Self { inner, placement }
+    }
+}
+
+impl<I, H> Iterator for RefCoords<I>
+where
+    I: Iterator<Item = (KmerPos, H)>,
+    H: AsRef<[u64]>,
+{
+    type Item = (usize, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, hashes) = self.inner.next()?;
+        let Placement {
+            ref_start,
+            read_len,
+            reverse,
+        } = self.placement;
+        // read base `i` of a reverse read lies on reference base
+        // `ref_start + read_len − 1 − i`, so the k‑mer's last base comes first
+        let offset = if reverse {
+            read_len - pos.end
+        } else {
+            pos.start
+        };
+        Some((ref_start + offset, hashes.as_ref()[0]))
+    }
+}
+
+/// Adds [`to_reference`](ToReference::to_reference) to every
+/// `(KmerPos, hashes)` iterator, e.g. a
+/// [`BlindNtHashIter`](crate::blind::BlindNtHashIter) over a read.
+///
+/// ```
+/// use nthash_rs::alignment::{Placement, ToReference};
+/// use nthash_rs::{kmer::Direction, BlindNtHashBuilder, NtHashBuilder};
+///
+/// let reference = b"TTTTACGTTGCAAGTTTT";
+/// let read = b"CTTGCAACGT"; // reverse strand of reference[4..14]
+/// let placement = Placement { ref_start: 4, read_len: read.len(), reverse: true };
+/// let on_ref: Vec<(usize, u64)> = BlindNtHashBuilder::new(read)
+///     .k(5)
+///     .direction(Direction::Reverse)
+///     .finish()
+///     .unwrap()
+///     .to_reference(placement)
+///     .collect();
+/// let direct: Vec<(usize, u64)> = NtHashBuilder::new(reference)
+///     .k(5)
+///     .into_iter()
+///     .map(|(pos, h)| (pos.start, h[0]))
+///     .filter(|&(start, _)| (4..=9).contains(&start))
+///     .collect();
+/// assert_eq!(on_ref, direct);
+/// ```
+pub trait ToReference: Iterator + Sized {
+    /// Report each k‑mer at its reference start instead of its read
+    /// position.
+    fn to_reference(self, placement: Placement) -> RefCoords<Self>;
+}
+
+impl<I, H> ToReference for I
+where
+    I: Iterator<Item = (KmerPos, H)>,
+    H: AsRef<[u64]>,
+{
+    fn to_reference(self, placement: Placement) -> RefCoords<Self> {
+        RefCoords::new(self, placement)
+    }
+}
+
+#[cfg(feature = "htslib")]
+mod htslib {
+    use rust_htslib::bam;
+
+    use super::*;
+
+    impl ReadLayout {
+        /// The layout of a `rust_htslib` record, from its CIGAR and FLAG.
+        pub fn from_record(record: &bam::Record) -> Self {
+            let cigar = record.cigar();
+            let clip = |n: i64| n as usize;
+            Self {
+                hard_clip: (
+                    clip(cigar.leading_hardclips()),
+                    clip(cigar.trailing_hardclips()),
+                ),
+                soft_clip: (
+                    clip(cigar.leading_softclips()),
+                    clip(cigar.trailing_softclips()),
+                ),
+                reverse: record.is_reverse(),
+            }
+        }
+    }
+
+    /// [`hash_aligned`] over a `rust_htslib` record, decoding its bases into
+    /// `buf` (reused across records to avoid an allocation per read).
+    ///
+    /// # Errors
+    ///
+    /// As [`hash_aligned`].
+    pub fn hash_record<'b>(
+        record: &bam::Record,
+        buf: &'b mut Vec<u8>,
+        k: u16,
+        num_hashes: u8,
+        soft_clips: SoftClips,
+    ) -> Result<AlignedHashes<'b>> {
+        let seq = record.seq();
+        buf.clear();
+        buf.extend((0..seq.len()).map(|i| seq[i]));
+        hash_aligned(
+            buf,
+            ReadLayout::from_record(record),
+            k,
+            num_hashes,
+            soft_clips,
+        )
+    }
+}
+
+#[cfg(feature = "htslib")]
+pub use htslib::hash_record;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BlindNtHashBuilder;
+
+    fn revcomp(seq: &[u8]) -> Vec<u8> {
+        seq.iter()
+            .rev()
+            .map(|&c| match c {
+                b'A' => b'T',
+                b'C' => b'G',
+                b'G' => b'C',
+                b'T' => b'A',
+                _ => b'N',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn positions_are_in_sequenced_read_coordinates() {
+        let read: Vec<u8> = (0..60u32)
+            .map(|i| b"ACGTTGCAGNTCA"[(i * 7 % 13) as usize])
+            .collect();
+        let k = 5;
+        let direct: Vec<_> = NtHashBuilder::new(&read).k(k).into_iter().collect();
+        let within = |lo: usize, hi: usize| -> Vec<_> {
+            let inside = |p: &KmerPos| p.start >= lo && p.end <= hi;
+            direct.iter().filter(|(p, _)| inside(p)).cloned().collect()
+        };
+        // clips at the read's 5' and 3' ends
+        let (hard5, soft5, soft3, hard3) = (3, 4, 6, 2);
+        let kept = &read[hard5..read.len() - hard3];
+        for reverse in [false, true] {
+            let (stored, hard_clip, soft_clip) = if reverse {
+                (revcomp(kept), (hard3, hard5), (soft3, soft5))
+            } else {
+                (kept.to_vec(), (hard5, hard3), (soft5, soft3))
+            };
+            let layout = ReadLayout {
+                hard_clip,
+                soft_clip,
+                reverse,
+            };
+            let hashes = |clips| -> Vec<_> {
+                hash_aligned(&stored, layout, k, 1, clips)
+                    .unwrap()
+                    .collect()
+            };
+            let hi = read.len() - hard3;
+            assert_eq!(
+                hashes(SoftClips::Keep),
+                within(hard5, hi),
+                "reverse {reverse}"
+            );
+            assert_eq!(hashes(SoftClips::Skip), within(hard5 + soft5, hi - soft3));
+        }
+    }
+
+    #[test]
+    fn reference_positions_match_hashing_the_reference() {
+        let reference: Vec<u8> = (0..80u32)
+            .map(|i| b"ACGTTGCAGNTCAGG"[(i * i % 15) as usize])
+            .collect();
+        let k = 6;
+        let direct: Vec<(usize, u64)> = NtHashBuilder::new(&reference)
+            .k(k)
+            .into_iter()
+            .map(|(pos, h)| (pos.start, h[0]))
+            .collect();
+        let (ref_start, read_len) = (17, 40);
+        let want: Vec<_> = direct
+            .into_iter()
+            .filter(|&(start, _)| start >= ref_start && start + k as usize <= ref_start + read_len)
+            .collect();
+        let forward = reference[ref_start..ref_start + read_len].to_vec();
+        for (read, reverse) in [(revcomp(&forward), true), (forward, false)] {
+            let placement = Placement {
+                ref_start,
+                read_len,
+                reverse,
+            };
+            let mut got: Vec<_> = NtHashBuilder::new(&read)
+                .k(k)
+                .into_iter()
+                .to_reference(placement)
+                .collect();
+            if reverse {
+                got.reverse();
+            }
+            assert_eq!(got, want, "reverse {reverse}");
+        }
+        // the blind hasher, over an N‑free copy
+        let clean: Vec<u8> = reference
+            .iter()
+            .map(|&c| if c == b'N' { b'A' } else { c })
+            .collect();
+        let read = revcomp(&clean[ref_start..ref_start + read_len]);
+        let placement = Placement {
+            ref_start,
+            read_len,
+            reverse: true,
+        };
+        let blind: Vec<_> = BlindNtHashBuilder::new(&read)
+            .k(k)
+            .direction(Direction::Reverse)
+            .finish()
+            .unwrap()
+            .to_reference(placement)
+            .collect();
+        let direct: Vec<_> = NtHashBuilder::new(&clean[ref_start..ref_start + read_len])
+            .k(k)
+            .into_iter()
+            .map(|(pos, h)| (ref_start + pos.start, h[0]))
+            .collect();
+        assert_eq!(blind, direct);
+    }
+
+    #[test]
+    fn clips_longer_than_the_read_are_rejected() {
+        let layout = ReadLayout {
+            soft_clip: (4, 3),
+            ..Default::default()
+        };
+        assert_eq!(
+            hash_aligned(b"ACGTAC", layout, 2, 1, SoftClips::Skip).err(),
+            Some(NtHashError::PositionOutOfRange { pos: 7, seq_len: 6 })
+        );
+        assert_eq!(
+            hash_aligned(b"ACGTAC", layout, 7, 1, SoftClips::Keep).err(),
+            Some(NtHashError::SequenceTooShort { seq_len: 6, k: 7 })
+        );
+    }
+}
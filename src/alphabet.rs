@@ -0,0 +1,613 @@
+//! **Rolling hashing over arbitrary alphabets.**
+//!
+//! [`RollingHash`] is the ntHash recurrence written once against the
+//! [`Alphabet`] trait, which supplies a 64‑bit seed per symbol and, for
+//! double‑stranded alphabets, the seed of each symbol's complement.  DNA
+//! ([`Dna`]), bisulfite‑converted DNA ([`Bisulfite`]), RNA ([`Rna`]),
+//! protein ([`Protein`](crate::aa::Protein)), and caller‑defined alphabets
+//! ([`SeedAlphabet`]) all share it.
+//!
+//! [`NtHash`](crate::NtHash) remains the table‑driven fast path for DNA;
+//! `RollingHash<Dna>` produces exactly the same hashes.
+//!
+//! ```
+//! use nthash_rs::{alphabet::{Dna, Rna, RollingHash}, NtHash};
+//!
+//! let mut dna = RollingHash::<Dna>::new(b"ACGTTGCANAGCT", 4, 2, 0).unwrap();
+//! let mut nt = NtHash::new(b"ACGTTGCANAGCT", 4, 2, 0).unwrap();
+//! while dna.roll() {
+//!     assert!(nt.roll());
+//!     assert_eq!((dna.pos(), dna.hashes()), (nt.pos(), nt.hashes()));
+//! }
+//!
+//! // RNA hashes U as T
+//! let mut rna = RollingHash::<Rna>::new(b"ACGUUGCA", 8, 1, 0).unwrap();
+//! let mut dna = RollingHash::<Dna>::new(b"ACGTTGCA", 8, 1, 0).unwrap();
+//! assert!(rna.roll() && dna.roll());
+//! assert_eq!(rna.hashes(), dna.hashes());
+//! ```
+
+use crate::{
+    constants::{CP_OFF, SEED_A, SEED_C, SEED_G, SEED_T, SEED_TAB},
+    kmer::KmerPos,
+    sink::HashSink,
+    tables::{srol, sror, sror_n},
+    util::{extend_hashes_with, same_seq, CanonicalPolicy, HashMixer, Mixer},
+    NtHashError, Result,
+};
+
+/// Per‑symbol seeds for [`RollingHash`].
+pub trait Alphabet {
+    /// Seed of `symbol`, or `None` if k‑mers containing it are skipped.
+    fn seed(&self, symbol: u8) -> Option<u64>;
+
+    /// Whether k‑mers are hashed on both strands and reported canonically.
+    fn double_stranded(&self) -> bool {
+        false
+    }
+
+    /// Seed of the complement of a valid `symbol`.  Only called when
+    /// [`double_stranded`](Self::double_stranded) is `true`.
+    fn complement_seed(&self, symbol: u8) -> u64 {
+        let _ = symbol;
+        0
+    }
+}
+
+/// `A/C/G/T` in either case, complemented as in ntHash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dna;
+
+impl Alphabet for Dna {
+    #[inline(always)]
+    fn seed(&self, symbol: u8) -> Option<u64> {
+        match symbol.to_ascii_uppercase() {
+            b'A' => Some(SEED_A),
+            b'C' => Some(SEED_C),
+            b'G' => Some(SEED_G),
+            b'T' => Some(SEED_T),
+            _ => None,
+        }
+    }
+
+    fn double_stranded(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn complement_seed(&self, symbol: u8) -> u64 {
+        SEED_TAB[(symbol & CP_OFF) as usize]
+    }
+}
+
+/// `A/C/G/U` in either case, hashed as the DNA with `U` read as `T`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rna;
+
+impl Alphabet for Rna {
+    #[inline(always)]
+    fn seed(&self, symbol: u8) -> Option<u64> {
+        match symbol {
+            b'U' | b'u' => Some(SEED_T),
+            b'T' | b't' => None,
+            _ => Dna.seed(symbol),
+        }
+    }
+
+    fn double_stranded(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn complement_seed(&self, symbol: u8) -> u64 {
+        match symbol {
+            b'U' | b'u' => SEED_A,
+            _ => Dna.complement_seed(symbol),
+        }
+    }
+}
+
+/// DNA under bisulfite conversion: `C` is hashed as `T`, so on the
+/// reverse strand `G` is hashed as `A`.
+///
+/// Unmethylated cytosines read as `T` after bisulfite treatment; collapsing
+/// `C` into `T` gives a converted read the same hashes as the reference it
+/// came from, whatever its methylation.  Reads from the opposite strand
+/// match the reverse complement of the reference instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bisulfite;
+
+impl Alphabet for Bisulfite {
+    #[inline(always)]
+    fn seed(&self, symbol: u8) -> Option<u64> {
+        match symbol {
+            b'C' | b'c' => Some(SEED_T),
+            _ => Dna.seed(symbol),
+        }
+    }
+
+    fn double_stranded(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn complement_seed(&self, symbol: u8) -> u64 {
+        match symbol {
+            b'C' | b'c' => SEED_A,
+            _ => Dna.complement_seed(symbol),
+        }
+    }
+}
+
+/// An alphabet assembled from caller‑chosen symbols and seeds.
+///
+/// Symbols are case‑sensitive bytes.  Declaring any complement pair makes
+/// the alphabet double‑stranded; every symbol must then have a complement.
+///
+/// ```
+/// use nthash_rs::alphabet::{RollingHash, SeedAlphabet};
+///
+/// // a 2‑letter purine/pyrimidine alphabet
+/// let ry = SeedAlphabet::new()
+///     .symbol(b'R', 0x3c8b_fbb3_95c6_0474)
+///     .symbol(b'Y', 0x3193_c185_62a0_2b4c)
+///     .complement(b'R', b'Y');
+/// let mut h = RollingHash::with_alphabet(ry, b"RRYRYYR", 5, 1, 0).unwrap();
+/// let mut n = 0;
+/// while h.roll() {
+///     n += 1;
+/// }
+/// assert_eq!(n, 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedAlphabet {
+    seeds: [u64; 256],
+    valid: [bool; 256],
+    complements: [u8; 256],
+    double_stranded: bool,
+}
+
+impl Default for SeedAlphabet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeedAlphabet {
+    /// An alphabet with no symbols.
+    pub fn new() -> Self {
+        Self {
+            seeds: [0; 256],
+            valid: [false; 256],
+            complements: [0; 256],
+            double_stranded: false,
+        }
+    }
+
+    /// Add `symbol` with the given seed (replacing any previous seed).
+    pub fn symbol(mut self, symbol: u8, seed: u64) -> Self {
+        self.seeds[symbol as usize] = seed;
+        self.valid[symbol as usize] = true;
+        self
+    }
+
+    /// Declare `a` and `b` complements of each other.
+    pub fn complement(mut self, a: u8, b: u8) -> Self {
+        self.complements[a as usize] = b;
+        self.complements[b as usize] = a;
+        self.double_stranded = true;
+        self
+    }
+}
+
+impl Alphabet for SeedAlphabet {
+    #[inline(always)]
+    fn seed(&self, symbol: u8) -> Option<u64> {
+        self.valid[symbol as usize].then_some(self.seeds[symbol as usize])
+    }
+
+    fn double_stranded(&self) -> bool {
+        self.double_stranded
+    }
+
+    #[inline(always)]
+    fn complement_seed(&self, symbol: u8) -> u64 {
+        self.seeds[self.complements[symbol as usize] as usize]
+    }
+}
+
+/// Rolling hasher over the k‑mers of `seq` in alphabet `A`.
+///
+/// Mirrors [`NtHash`](crate::NtHash): initialization is deferred to the
+/// first valid k‑mer, `roll()` / `roll_back()` skip windows containing
+/// symbols the alphabet rejects, and [`hashes`](Self::hashes) holds the
+/// canonical hash (the forward hash for single‑stranded alphabets)
+/// followed by its extensions.
+///
+/// Like [`NtHash`](crate::NtHash), it is cheap to clone, compares equal
+/// to another hasher over the same sequence in the same state, and never
+/// panics once constructed (for alphabets whose seeds are consistent).
+#[derive(Clone)]
+pub struct RollingHash<'a, A: Alphabet> {
+    alphabet: A,
+    seq: &'a [u8],
+    k: u16,
+    /// Start of the last window, `seq.len() - k`.
+    pub(crate) end: usize,
+    pos: usize,
+    initialized: bool,
+    fwd: u64,
+    rev: u64,
+    /// `sror_n` distance equal to a left split‑rotate by `k`.
+    rot_k: u32,
+    hashes: Vec<u64>,
+    mixer: Mixer,
+    policy: CanonicalPolicy,
+}
+
+impl<A: Alphabet + std::fmt::Debug> std::fmt::Debug for RollingHash<'_, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RollingHash")
+            .field("alphabet", &self.alphabet)
+            .field("seq_len", &self.seq.len())
+            .field("k", &self.k)
+            .field("pos", &self.pos)
+            .field("initialized", &self.initialized)
+            .field("hashes", &self.hashes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Alphabet + PartialEq> PartialEq for RollingHash<'_, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k
+            && self.pos == other.pos
+            && self.initialized == other.initialized
+            && (self.fwd, self.rev) == (other.fwd, other.rev)
+            && self.hashes == other.hashes
+            && self.alphabet == other.alphabet
+            && same_seq(self.seq, other.seq)
+    }
+}
+
+impl<A: Alphabet + Eq> Eq for RollingHash<'_, A> {}
+
+impl<'a, A: Alphabet + Default> RollingHash<'a, A> {
+    /// Create a hasher over `seq` starting at `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns if `k == 0`, `seq.len() < k`, or `pos` too large.
+    pub fn new(seq: &'a [u8], k: u16, num_hashes: u8, pos: usize) -> Result<Self> {
+        Self::with_alphabet(A::default(), seq, k, num_hashes, pos)
+    }
+}
+
+impl<'a, A: Alphabet> RollingHash<'a, A> {
+    /// Create a hasher over `seq` in `alphabet`, starting at `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns if `k == 0`, `seq.len() < k`, or `pos` too large.
+    pub fn with_alphabet(
+        alphabet: A,
+        seq: &'a [u8],
+        k: u16,
+        num_hashes: u8,
+        pos: usize,
+    ) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        let k_usz = k as usize;
+        if seq.len() < k_usz {
+            return Err(NtHashError::SequenceTooShort {
+                seq_len: seq.len(),
+                k,
+            });
+        }
+        let end = seq.len() - k_usz;
+        if pos > end {
+            return Err(NtHashError::PositionOutOfRange {
+                pos,
+                seq_len: seq.len(),
+            });
+        }
+        Ok(Self {
+            alphabet,
+            seq,
+            k,
+            end,
+            pos,
+            initialized: false,
+            fwd: 0,
+            rev: 0,
+            // both halves' periods divide 31 · 33 = 1023
+            rot_k: 1023 - k as u32 % 1023,
+            hashes: vec![0; num_hashes as usize],
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
+        })
+    }
+
+    /// Derive the extra hashes with `mixer` instead of the reference
+    /// scheme; see [`HashMixer`].
+    pub fn with_mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        if self.initialized {
+            self.update_hashes();
+        }
+        self
+    }
+
+    /// Combine the strand hashes under `policy` instead of summing them;
+    /// see [`CanonicalPolicy`].
+    pub fn with_canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
+        if self.initialized {
+            self.update_hashes();
+        }
+        self
+    }
+
+    /// Advance by one symbol, skipping k‑mers with invalid symbols.
+    /// Returns `true` if a new valid hash was produced.
+    pub fn roll(&mut self) -> bool {
+        if !self.initialized {
+            return self.init();
+        }
+        let k_usz = self.k as usize;
+        if self.pos >= self.end {
+            return false;
+        }
+        let (out, inc) = (self.seq[self.pos], self.seq[self.pos + k_usz]);
+        let Some(seed_in) = self.alphabet.seed(inc) else {
+            let last = self.pos;
+            self.pos += k_usz + 1;
+            if !self.init() {
+                // stay on the last valid k‑mer
+                self.pos = last;
+                return false;
+            }
+            return true;
+        };
+        let seed_out = self.alphabet.seed(out).expect("window is valid");
+        self.fwd = srol(self.fwd) ^ seed_in ^ self.srol_k(seed_out);
+        if self.alphabet.double_stranded() {
+            let (c_out, c_in) = (
+                self.alphabet.complement_seed(out),
+                self.alphabet.complement_seed(inc),
+            );
+            self.rev = sror(self.rev ^ self.srol_k(c_in) ^ c_out);
+        }
+        self.update_hashes();
+        self.pos += 1;
+        true
+    }
+
+    /// Move back by one symbol, skipping k‑mers with invalid symbols.
+    pub fn roll_back(&mut self) -> bool {
+        if !self.initialized && !self.init() {
+            return false;
+        }
+        if self.pos == 0 {
+            return false;
+        }
+        let k_usz = self.k as usize;
+        let (inc, out) = (self.seq[self.pos - 1], self.seq[self.pos + k_usz - 1]);
+        let Some(seed_in) = self.alphabet.seed(inc) else {
+            return self.init_back(self.pos - 1);
+        };
+        let seed_out = self.alphabet.seed(out).expect("window is valid");
+        self.fwd = sror(self.fwd ^ self.srol_k(seed_in) ^ seed_out);
+        if self.alphabet.double_stranded() {
+            let (c_out, c_in) = (
+                self.alphabet.complement_seed(out),
+                self.alphabet.complement_seed(inc),
+            );
+            self.rev = srol(self.rev) ^ c_in ^ self.srol_k(c_out);
+        }
+        self.update_hashes();
+        self.pos -= 1;
+        true
+    }
+
+    /// Roll to the end of the sequence, passing every valid k‑mer to
+    /// `sink`; returns the number of k‑mers passed.
+    pub fn drive<S: HashSink + ?Sized>(&mut self, sink: &mut S) -> usize {
+        let mut n = 0;
+        while self.roll() {
+            sink.accept(self.pos, &self.hashes);
+            n += 1;
+        }
+        n
+    }
+
+    /// Returns the most recent hash buffer.
+    #[inline(always)]
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// The current k‑mer's canonical hash before extension (the forward
+    /// hash for single‑stranded alphabets).
+    #[inline(always)]
+    pub fn hash(&self) -> u64 {
+        self.policy.combine(self.fwd, self.rev)
+    }
+
+    /// Forward‑strand hash of the current k‑mer.
+    #[inline(always)]
+    pub fn forward_hash(&self) -> u64 {
+        self.fwd
+    }
+
+    /// Reverse‑complement hash of the current k‑mer (zero for
+    /// single‑stranded alphabets).
+    #[inline(always)]
+    pub fn reverse_hash(&self) -> u64 {
+        self.rev
+    }
+
+    /// Returns the current k‑mer start index.
+    #[inline(always)]
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The current window as a [`KmerPos`].
+    #[inline(always)]
+    pub fn kmer_pos(&self) -> KmerPos {
+        KmerPos::new(self.pos, self.k as usize)
+    }
+
+    /// The alphabet being hashed.
+    pub fn alphabet(&self) -> &A {
+        &self.alphabet
+    }
+
+    /// Left split‑rotate by `k`.
+    #[inline(always)]
+    fn srol_k(&self, x: u64) -> u64 {
+        sror_n(x, self.rot_k)
+    }
+
+    /// Initialize on the first valid k‑mer at or after `pos`.
+    fn init(&mut self) -> bool {
+        let k_usz = self.k as usize;
+        while self.pos <= self.end {
+            let window = &self.seq[self.pos..self.pos + k_usz];
+            if let Some(bad) = window
+                .iter()
+                .rposition(|&c| self.alphabet.seed(c).is_none())
+            {
+                self.pos += bad + 1;
+                continue;
+            }
+            self.hash_window();
+            return true;
+        }
+        false
+    }
+
+    /// Move to the last valid k‑mer starting at or before `pos`, for
+    /// backward iteration; on failure the position is unchanged.
+    pub(crate) fn init_back_from(&mut self, pos: usize) -> bool {
+        self.init_back(pos + self.k as usize)
+    }
+
+    /// Re‑initialize on the first valid k‑mer at or after `pos` at the
+    /// next `roll()`; `false` if no k‑mer starts there.
+    pub(crate) fn restart_at(&mut self, pos: usize) -> bool {
+        if pos > self.end {
+            return false;
+        }
+        self.pos = pos;
+        self.initialized = false;
+        true
+    }
+
+    /// The sequence being hashed.
+    pub(crate) fn seq(&self) -> &'a [u8] {
+        self.seq
+    }
+
+    /// Move to the last valid k‑mer ending at or before `end`; on failure
+    /// `pos` is unchanged.
+    fn init_back(&mut self, mut end: usize) -> bool {
+        let k_usz = self.k as usize;
+        while end >= k_usz {
+            let window = &self.seq[end - k_usz..end];
+            if let Some(bad) = window.iter().position(|&c| self.alphabet.seed(c).is_none()) {
+                end -= k_usz - bad;
+                continue;
+            }
+            self.pos = end - k_usz;
+            self.hash_window();
+            return true;
+        }
+        false
+    }
+
+    /// Hash the window at `pos` from scratch; all its symbols are valid.
+    fn hash_window(&mut self) {
+        let window = &self.seq[self.pos..self.pos + self.k as usize];
+        let alphabet = &self.alphabet;
+        self.fwd = window.iter().fold(0, |h, &c| {
+            srol(h) ^ alphabet.seed(c).expect("checked above")
+        });
+        self.rev = if alphabet.double_stranded() {
+            window
+                .iter()
+                .rev()
+                .fold(0, |h, &c| srol(h) ^ alphabet.complement_seed(c))
+        } else {
+            0
+        };
+        self.update_hashes();
+        self.initialized = true;
+    }
+
+    #[inline(always)]
+    fn update_hashes(&mut self) {
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
+            self.fwd,
+            self.rev,
+            self.k as u32,
+            &mut self.hashes,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NtHash;
+
+    #[test]
+    fn dna_alphabet_matches_nthash_both_directions() {
+        let seq = b"NATCGTACGATNGCATGCATGCTGACGTTNNAGCTAGCATCGGAacgtgcatgca\
+                    TTGACCAGTAGGCATCAAGTCNNNN";
+        for k in [1u16, 3, 4, 11, 31, 32, 33] {
+            let mut generic = RollingHash::<Dna>::new(seq, k, 3, 0).unwrap();
+            let mut nt = NtHash::new(seq, k, 3, 0).unwrap();
+            let mut fwd = Vec::new();
+            while generic.roll() {
+                assert!(nt.roll());
+                assert_eq!(generic.pos(), nt.pos(), "k={k}");
+                assert_eq!(generic.hashes(), nt.hashes(), "k={k}");
+                fwd.push((generic.pos(), generic.hashes().to_vec()));
+            }
+            assert!(!nt.roll());
+
+            // back over every valid k‑mer, jumping the N runs
+            let mut back = vec![fwd.last().unwrap().clone()];
+            while generic.roll_back() {
+                back.push((generic.pos(), generic.hashes().to_vec()));
+            }
+            back.reverse();
+            assert_eq!(back, fwd, "k={k}");
+        }
+
+        // a custom alphabet with DNA's seeds and complements is DNA
+        let custom = SeedAlphabet::new()
+            .symbol(b'A', SEED_A)
+            .symbol(b'C', SEED_C)
+            .symbol(b'G', SEED_G)
+            .symbol(b'T', SEED_T)
+            .complement(b'A', b'T')
+            .complement(b'C', b'G');
+        let upper = seq.to_ascii_uppercase();
+        let mut a = RollingHash::with_alphabet(custom, &upper, 9, 2, 0).unwrap();
+        let mut b = RollingHash::<Dna>::new(&upper, 9, 2, 0).unwrap();
+        while a.roll() {
+            assert!(b.roll());
+            assert_eq!((a.pos(), a.hashes()), (b.pos(), b.hashes()));
+        }
+        assert!(!b.roll());
+        let mut rna = RollingHash::<Rna>::new(b"ACGT", 4, 1, 0).unwrap();
+        assert_eq!(rna.drive(&mut |_, _: &[u64]| {}), 0);
+    }
+}
@@ -0,0 +1,313 @@
+//! **Asynchronous streaming** (`async` feature).
+//!
+//! [`AsyncNtHash`] hashes a sequence as it arrives, from a
+//! `Stream<Item = io::Result<Bytes>>` (an HTTP body, an S3 object, an
+//! htsget slice, …) or from any tokio [`AsyncRead`].  Only the last `k − 1`
+//! bases of each chunk are kept past it, so memory stays proportional to
+//! the chunk size however long the sequence is, and k‑mers are hashed one
+//! per poll rather than a chunk at a time.
+//!
+//! The input is the raw sequence: every byte is a base, and positions are
+//! offsets from the first byte of the stream.  Output is identical to
+//! [`NtHash`] over the concatenated chunks.
+//!
+//! Dropping the stream stops the scan; to stop it from elsewhere, wrap it
+//! in [`Cancellable`](crate::cancel::Cancellable), which checks a
+//! [`CancellationToken`](crate::cancel::CancellationToken) every *N*
+//! k‑mers.
+//!
+//! ```
+//! use std::{io, pin::Pin, task::{Context, Poll, Waker}};
+//! use bytes::Bytes;
+//! use futures_core::Stream;
+//! use nthash_rs::{AsyncNtHash, NtHashBuilder};
+//!
+//! let chunks = ["ACGTCA", "GTNAC", "GTTGCA"].map(|c| Ok::<_, io::Error>(Bytes::from(c)));
+//! let mut stream = AsyncNtHash::new(Chunks(chunks.into_iter()), 4, 2).unwrap();
+//!
+//! // Poll by hand; in an async fn this is `while let Some(item) = stream.next().await`.
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let mut streamed = Vec::new();
+//! while let Poll::Ready(Some(item)) = Pin::new(&mut stream).poll_next(&mut cx) {
+//!     streamed.push(item.unwrap());
+//! }
+//! let whole: Vec<_> =
+//!     NtHashBuilder::new(b"ACGTCAGTNACGTTGCA").k(4).num_hashes(2).into_iter().collect();
+//! assert_eq!(streamed, whole);
+//!
+//! # struct Chunks<I>(I);
+//! # impl<I: Iterator + Unpin> Stream for Chunks<I> {
+//! #     type Item = I::Item;
+//! #     fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+//! #         Poll::Ready(self.0.next())
+//! #     }
+//! # }
+//! ```
+
+use std::{
+    io, mem,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::{owned::OwnedSeq, KmerPos, NtHash, NtHashError, Result};
+
+/// Bytes requested per read by [`AsyncNtHash::from_reader`].
+const READ_CHUNK: usize = 64 * 1024;
+
+/// Stream of `(KmerPos, hashes)` over a sequence arriving in chunks.
+///
+/// The source must be [`Unpin`]; pin other sources with `Box::pin` first.
+#[derive(Debug)]
+pub struct AsyncNtHash<S> {
+    source: S,
+    k: u16,
+    num_hashes: u8,
+    /// Hasher over `seq`, rolled once per yielded k‑mer; declared before
+    /// `seq` so it is dropped first.
+    hasher: Option<NtHash<'static>>,
+    /// The last `k − 1` bases of the previous chunk, followed by the chunk
+    /// being hashed.
+    seq: Option<OwnedSeq>,
+    /// Stream offset of the first base of `seq`, or of `carry` between
+    /// chunks.
+    offset: usize,
+    /// Bases waiting for the next chunk.
+    carry: Vec<u8>,
+    done: bool,
+}
+
+impl<S> AsyncNtHash<S>
+where
+    S: Stream<Item = io::Result<Bytes>> + Unpin,
+{
+    /// Hash the chunks yielded by `source`.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidK`] if `k == 0` and
+    /// [`NtHashError::InvalidNumHashes`] if `num_hashes == 0`.
+    pub fn new(source: S, k: u16, num_hashes: u8) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if num_hashes == 0 {
+            return Err(NtHashError::InvalidNumHashes);
+        }
+        Ok(Self {
+            source,
+            k,
+            num_hashes,
+            hasher: None,
+            seq: None,
+            offset: 0,
+            carry: Vec::new(),
+            done: false,
+        })
+    }
+
+    /// Start hashing the k‑mers ending in `chunk`, once the carried bases
+    /// and `chunk` hold at least one.
+    fn start_chunk(&mut self, chunk: &[u8]) {
+        self.carry.extend_from_slice(chunk);
+        if self.carry.len() < self.k as usize {
+            return;
+        }
+        let seq = OwnedSeq::new(mem::take(&mut self.carry));
+        // SAFETY: `hasher` is dropped before `seq`, here and in `end_chunk`.
+        let bases = unsafe { seq.as_static() };
+        self.hasher = Some(
+            NtHash::new(bases, self.k, self.num_hashes, 0)
+                .expect("buffer holds at least one k-mer"),
+        );
+        self.seq = Some(seq);
+    }
+
+    /// Drop the exhausted hasher, carrying the chunk's last `k − 1` bases.
+    fn end_chunk(&mut self) {
+        self.hasher = None;
+        let Some(seq) = self.seq.take() else {
+            return;
+        };
+        // SAFETY: the slice is copied out before `seq` is dropped.
+        let bases = unsafe { seq.as_static() };
+        let consumed = bases.len() - (self.k as usize - 1);
+        self.carry.extend_from_slice(&bases[consumed..]);
+        self.offset += consumed;
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncNtHash<ReaderChunks<R>> {
+    /// Hash the bytes read from `reader`.
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new).
+    pub fn from_reader(reader: R, k: u16, num_hashes: u8) -> Result<Self> {
+        Self::new(
+            ReaderChunks {
+                reader,
+                buf: vec![0; READ_CHUNK],
+            },
+            k,
+            num_hashes,
+        )
+    }
+}
+
+impl<S> Stream for AsyncNtHash<S>
+where
+    S: Stream<Item = io::Result<Bytes>> + Unpin,
+{
+    type Item = io::Result<(KmerPos, Vec<u64>)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(h) = this.hasher.as_mut() {
+                if h.roll() {
+                    let pos = KmerPos::new(this.offset + h.pos(), this.k as usize);
+                    return Poll::Ready(Some(Ok((pos, h.hashes().to_vec()))));
+                }
+                this.end_chunk();
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+            match ready!(Pin::new(&mut this.source).poll_next(cx)) {
+                Some(Ok(chunk)) => this.start_chunk(&chunk),
+                Some(Err(e)) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                None => this.done = true,
+            }
+        }
+    }
+}
+
+/// An [`AsyncRead`] as a stream of [`Bytes`] chunks, for
+/// [`AsyncNtHash::from_reader`].
+#[derive(Debug)]
+pub struct ReaderChunks<R> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> Stream for ReaderChunks<R> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut buf = ReadBuf::new(&mut this.buf);
+        if let Err(e) = ready!(Pin::new(&mut this.reader).poll_read(cx, &mut buf)) {
+            return Poll::Ready(Some(Err(e)));
+        }
+        let filled = buf.filled();
+        Poll::Ready((!filled.is_empty()).then(|| Ok(Bytes::copy_from_slice(filled))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, task::Waker};
+
+    use super::*;
+    use crate::{
+        cancel::{Cancellable, CancellationToken},
+        NtHashBuilder,
+    };
+
+    /// Drain a stream whose sources never return `Pending`.
+    fn drain<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut out = Vec::new();
+        while let Poll::Ready(Some(item)) = Pin::new(&mut stream).poll_next(&mut cx) {
+            out.push(item);
+        }
+        out
+    }
+
+    struct Chunks(VecDeque<io::Result<Bytes>>);
+
+    impl Stream for Chunks {
+        type Item = io::Result<Bytes>;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.0.pop_front())
+        }
+    }
+
+    #[test]
+    fn any_chunking_matches_whole_sequence() {
+        let seq: Vec<u8> = (0..3000u32)
+            .map(|i| b"ACGTACGTNacgt"[(i * i / 11 % 13) as usize])
+            .collect();
+        let k = 9;
+        let whole: Vec<_> = NtHashBuilder::new(&seq)
+            .k(k)
+            .num_hashes(2)
+            .into_iter()
+            .collect();
+        for size in [1, 2, 8, 9, 10, 257, 5000] {
+            let chunks = seq
+                .chunks(size)
+                .map(|c| Ok(Bytes::copy_from_slice(c)))
+                .collect();
+            let streamed = drain(AsyncNtHash::new(Chunks(chunks), k, 2).unwrap());
+            let streamed: Vec<_> = streamed.into_iter().map(|r| r.unwrap()).collect();
+            assert_eq!(streamed, whole, "chunk size {size}");
+        }
+        let read = drain(AsyncNtHash::from_reader(&seq[..], k, 2).unwrap());
+        assert_eq!(
+            read.into_iter().map(|r| r.unwrap()).collect::<Vec<_>>(),
+            whole
+        );
+    }
+
+    #[test]
+    fn cancellation_stops_at_the_next_checkpoint() {
+        let token = CancellationToken::new();
+        let chunks = VecDeque::from([Ok(Bytes::from_static(b"ACGTACGTACGTACGTACGT"))]);
+        let mut stream =
+            Cancellable::new(AsyncNtHash::new(Chunks(chunks), 4, 1).unwrap(), &token, 5);
+        let mut cx = Context::from_waker(Waker::noop());
+        for _ in 0..3 {
+            assert!(matches!(
+                Pin::new(&mut stream).poll_next(&mut cx),
+                Poll::Ready(Some(Ok(_)))
+            ));
+        }
+        token.cancel();
+        let rest = drain(&mut stream);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(
+            stream.status(),
+            Err(NtHashError::Cancelled { processed: 4 })
+        );
+    }
+
+    #[test]
+    fn source_errors_end_the_stream() {
+        let chunks = VecDeque::from([
+            Ok(Bytes::from_static(b"ACGTAC")),
+            Err(io::Error::other("connection reset")),
+            Ok(Bytes::from_static(b"GTTGCA")),
+        ]);
+        let items = drain(AsyncNtHash::new(Chunks(chunks), 4, 1).unwrap());
+        assert_eq!(items.len(), 4);
+        assert!(items[..3].iter().all(Result::is_ok));
+        assert_eq!(
+            items[3].as_ref().unwrap_err().to_string(),
+            "connection reset"
+        );
+        assert_eq!(
+            AsyncNtHash::from_reader(&b""[..], 0, 1).err(),
+            Some(NtHashError::InvalidK)
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! `nthash` command‑line tool; see [`nthash_rs::cli`].
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read},
+    process::ExitCode,
+};
+
+use nthash_rs::cli::{run, CliError, Options, USAGE};
+
+fn main() -> ExitCode {
+    let opts = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("nthash: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    let input: Box<dyn Read> = match &opts.input {
+        Some(path) => match File::open(path) {
+            Ok(f) => Box::new(f),
+            Err(e) => {
+                eprintln!("nthash: {}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Box::new(io::stdin().lock()),
+    };
+    let out = BufWriter::new(io::stdout().lock());
+    match run(&opts, BufReader::new(input), out) {
+        Ok(()) => ExitCode::SUCCESS,
+        // a closed pipe (`nthash … | head`) is not an error
+        Err(CliError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("nthash: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! **`bio` crate integration** (`bio` feature).
+//!
+//! Builders can be created straight from a `bio::io::fasta::Record`, and
+//! optionally checked against one of the `bio::alphabets::dna` alphabets
+//! first.  Lower‑case (soft‑masked) bases hash exactly like upper‑case
+//! ones, so records need no normalization copy.
+//!
+//! ```
+//! use bio::{alphabets::dna, io::fasta::Record};
+//! use nthash_rs::{NtHashBuilder, NtHashError};
+//!
+//! let record = Record::with_attrs("chr1", None, b"ACGTacgtNAC");
+//! let hashes: Vec<u64> = NtHashBuilder::from_record(&record)
+//!     .k(4)
+//!     .finish()
+//!     .unwrap()
+//!     .map(|(_, h)| h[0])
+//!     .collect();
+//! assert_eq!(hashes[0], hashes[4]); // ACGT == acgt
+//!
+//! let err = NtHashBuilder::from_checked_record(&record, &dna::alphabet()).err();
+//! assert_eq!(err, Some(NtHashError::InvalidBase { pos: 8, byte: b'N' }));
+//! assert!(NtHashBuilder::from_checked_record(&record, &dna::n_alphabet()).is_ok());
+//! ```
+
+use ::bio::{alphabets::Alphabet, io::fasta::Record};
+
+use crate::{BlindNtHashBuilder, NtHashBuilder, NtHashError, Result, SeedNtHashBuilder};
+
+/// The first byte of `seq` outside `alphabet`.
+fn check(seq: &[u8], alphabet: &Alphabet) -> Result<()> {
+    match seq
+        .iter()
+        .position(|&c| !alphabet.symbols.contains(c as usize))
+    {
+        Some(pos) => Err(NtHashError::InvalidBase {
+            pos,
+            byte: seq[pos],
+        }),
+        None => Ok(()),
+    }
+}
+
+impl<'a> NtHashBuilder<'a> {
+    /// Begin building over the sequence of a FASTA `record`.
+    pub fn from_record(record: &'a Record) -> Self {
+        Self::new(record.seq())
+    }
+
+    /// As [`from_record`](Self::from_record), after checking every base
+    /// against `alphabet` (e.g. `bio::alphabets::dna::n_alphabet()`).
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidBase`] for the first symbol outside `alphabet`.
+    pub fn from_checked_record(record: &'a Record, alphabet: &Alphabet) -> Result<Self> {
+        check(record.seq(), alphabet)?;
+        Ok(Self::from_record(record))
+    }
+}
+
+impl<'a> SeedNtHashBuilder<'a> {
+    /// Begin building over the sequence of a FASTA `record`.
+    pub fn from_record(record: &'a Record) -> Self {
+        Self::new(record.seq())
+    }
+
+    /// As [`from_record`](Self::from_record), after checking every base
+    /// against `alphabet`.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidBase`] for the first symbol outside `alphabet`.
+    pub fn from_checked_record(record: &'a Record, alphabet: &Alphabet) -> Result<Self> {
+        check(record.seq(), alphabet)?;
+        Ok(Self::from_record(record))
+    }
+}
+
+impl<'a> BlindNtHashBuilder<'a> {
+    /// Begin building over the sequence of a FASTA `record`.
+    pub fn from_record(record: &'a Record) -> Self {
+        Self::new(record.seq())
+    }
+
+    /// As [`from_record`](Self::from_record), after checking every base
+    /// against `alphabet`.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidBase`] for the first symbol outside `alphabet`.
+    pub fn from_checked_record(record: &'a Record, alphabet: &Alphabet) -> Result<Self> {
+        check(record.seq(), alphabet)?;
+        Ok(Self::from_record(record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::bio::alphabets::dna;
+
+    use super::*;
+
+    #[test]
+    fn records_hash_like_their_upper_case_sequence() {
+        let lower = Record::with_attrs("r", None, b"acgttgcaNnagct");
+        let upper = lower.seq().to_ascii_uppercase();
+        let k = 5;
+        let from_record: Vec<_> = NtHashBuilder::from_record(&lower)
+            .k(k)
+            .into_iter()
+            .collect();
+        let from_seq: Vec<_> = NtHashBuilder::new(&upper).k(k).into_iter().collect();
+        assert_eq!(from_record, from_seq);
+
+        let masks = ["10101"];
+        let seeded = |b: SeedNtHashBuilder| b.k(k).masks(masks).into_iter().collect::<Vec<_>>();
+        assert_eq!(
+            seeded(SeedNtHashBuilder::from_record(&lower)),
+            seeded(SeedNtHashBuilder::new(&upper))
+        );
+
+        let blind = BlindNtHashBuilder::from_record(&lower)
+            .k(k)
+            .finish()
+            .unwrap();
+        assert_eq!(blind.take(3).collect::<Vec<_>>(), from_seq[..3]);
+
+        let iupac = Record::with_attrs("r", None, b"ACGRT");
+        assert!(BlindNtHashBuilder::from_checked_record(&iupac, &dna::iupac_alphabet()).is_ok());
+        assert_eq!(
+            SeedNtHashBuilder::from_checked_record(&iupac, &dna::n_alphabet()).err(),
+            Some(NtHashError::InvalidBase { pos: 3, byte: b'R' })
+        );
+    }
+}
@@ -4,9 +4,12 @@
 //! skips windows containing  'N', **`BlindNtHash` works on a pre‑cleaned input
 //! where every window of length *k* is known to be valid**.  
 //!
-//! The hasher maintains an *exact* k‑base sliding window in a small ring
-//! buffer and lets the caller **feed the next / previous character** to move
-//! the window forward or backward.
+//! The hasher maintains an *exact* k‑base sliding window and lets the caller
+//! **feed the next / previous character** to move the window forward or
+//! backward.  Where that window lives is pluggable via [`WindowStorage`]:
+//! a heap `VecDeque` (the default), a stack ring buffer sized by a const
+//! generic ([`ArrayWindow`]), or no storage at all ([`NoWindow`]) when the
+//! caller already knows which base leaves the window.
 //!
 //! Heavy bit‑twiddling is delegated to the `tables` (split‑rotate) and
 //! `constants` (lookup tables) modules, plus `util::extend_hashes` for
@@ -20,43 +23,452 @@ use std::collections::VecDeque;
 
 use crate::{
     constants::*,
-    kmer::{base_forward_hash, base_reverse_hash},
+    kmer::{base_forward_hash, base_reverse_hash, is_acgt, sub_hash, Direction, KmerPos},
     tables::{srol, srol_table, sror},
-    util::extend_hashes,
+    util::{extend_hashes, extend_hashes_with, same_seq, CanonicalPolicy, HashMixer, Mixer},
     NtHashError, Result,
 };
 
+/// Storage for the bases inside a [`BlindNtHash`] window.
+pub trait WindowStorage: Sized {
+    /// Build the storage from the initial window, or `None` if this storage
+    /// cannot hold a window of that length.
+    fn from_window(window: &[u8]) -> Option<Self>;
+
+    /// Replace the stored bases with `window`, in place; `window` has the
+    /// length the storage was built with.
+    fn refill(&mut self, window: &[u8]);
+
+    /// Number of bases held, or `None` if the storage keeps none.
+    fn stored_len(&self) -> Option<usize>;
+
+    /// Longest window this storage can hold, reported when
+    /// [`from_window`](Self::from_window) refuses one (unbounded by
+    /// default).
+    const CAPACITY: usize = usize::MAX;
+}
+
+/// Window storage that remembers its bases, so the outgoing base of a roll
+/// does not have to be supplied by the caller.
+pub trait TrackedWindow: WindowStorage {
+    /// First base of the window.
+    fn front(&self) -> u8;
+    /// Last base of the window.
+    fn back(&self) -> u8;
+    /// Append `c` at the back and return the base removed from the front.
+    fn push_back(&mut self, c: u8) -> u8;
+    /// Prepend `c` at the front and return the base removed from the back.
+    fn push_front(&mut self, c: u8) -> u8;
+    /// Base at `offset` from the front (`offset < k`).
+    fn get(&self, offset: usize) -> u8;
+}
+
+impl WindowStorage for VecDeque<u8> {
+    fn from_window(window: &[u8]) -> Option<Self> {
+        Some(window.iter().copied().collect())
+    }
+
+    fn refill(&mut self, window: &[u8]) {
+        self.clear();
+        self.extend(window);
+    }
+
+    fn stored_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl TrackedWindow for VecDeque<u8> {
+    #[inline(always)]
+    fn front(&self) -> u8 {
+        *VecDeque::front(self).expect("window length is always k > 0")
+    }
+
+    #[inline(always)]
+    fn back(&self) -> u8 {
+        *VecDeque::back(self).expect("window length is always k > 0")
+    }
+
+    #[inline(always)]
+    fn push_back(&mut self, c: u8) -> u8 {
+        let out = self.pop_front().expect("window length is always k > 0");
+        VecDeque::push_back(self, c);
+        out
+    }
+
+    #[inline(always)]
+    fn push_front(&mut self, c: u8) -> u8 {
+        let out = self.pop_back().expect("window length is always k > 0");
+        VecDeque::push_front(self, c);
+        out
+    }
+
+    #[inline(always)]
+    fn get(&self, offset: usize) -> u8 {
+        self[offset]
+    }
+}
+
+/// Fixed‑size ring buffer holding exactly `K` bases on the stack.
+///
+/// Only windows of length `K` are accepted, so `K` must equal the hasher's `k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayWindow<const K: usize> {
+    buf: [u8; K],
+    head: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<const K: usize> ArrayWindow<K> {
+    /// The window's bases in order, front first.
+    fn bases(&self) -> impl Iterator<Item = u8> + '_ {
+        self.buf[self.head..]
+            .iter()
+            .chain(&self.buf[..self.head])
+            .copied()
+    }
+}
+
+// Serialized as the window's bases in order (serde cannot derive `[u8; K]`).
+#[cfg(feature = "serde")]
+impl<const K: usize> serde::Serialize for ArrayWindow<K> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.bases())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const K: usize> serde::Deserialize<'de> for ArrayWindow<K> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let bases = Vec::<u8>::deserialize(deserializer)?;
+        Self::from_window(&bases).ok_or_else(|| {
+            serde::de::Error::invalid_length(bases.len(), &"a window of exactly K bases")
+        })
+    }
+}
+
+impl<const K: usize> WindowStorage for ArrayWindow<K> {
+    const CAPACITY: usize = K;
+
+    fn from_window(window: &[u8]) -> Option<Self> {
+        Some(Self {
+            buf: window.try_into().ok()?,
+            head: 0,
+        })
+    }
+
+    fn refill(&mut self, window: &[u8]) {
+        self.buf.copy_from_slice(window);
+        self.head = 0;
+    }
+
+    fn stored_len(&self) -> Option<usize> {
+        Some(K)
+    }
+}
+
+impl<const K: usize> TrackedWindow for ArrayWindow<K> {
+    #[inline(always)]
+    fn front(&self) -> u8 {
+        self.buf[self.head]
+    }
+
+    #[inline(always)]
+    fn back(&self) -> u8 {
+        self.buf[(self.head + K - 1) % K]
+    }
+
+    #[inline(always)]
+    fn push_back(&mut self, c: u8) -> u8 {
+        let out = std::mem::replace(&mut self.buf[self.head], c);
+        self.head = (self.head + 1) % K;
+        out
+    }
+
+    #[inline(always)]
+    fn push_front(&mut self, c: u8) -> u8 {
+        self.head = (self.head + K - 1) % K;
+        std::mem::replace(&mut self.buf[self.head], c)
+    }
+
+    #[inline(always)]
+    fn get(&self, offset: usize) -> u8 {
+        self.buf[(self.head + offset) % K]
+    }
+}
+
+/// No window storage: the caller supplies outgoing bases explicitly through
+/// [`BlindNtHash::roll_with`] and friends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoWindow;
+
+impl WindowStorage for NoWindow {
+    fn from_window(_window: &[u8]) -> Option<Self> {
+        Some(NoWindow)
+    }
+
+    fn refill(&mut self, _window: &[u8]) {}
+
+    fn stored_len(&self) -> Option<usize> {
+        None
+    }
+}
+
 /// Rolling hash over a *fixed‑width* window that the caller rolls manually.
 ///
-/// The window is stored in a `VecDeque<u8>`:
+/// With a [`TrackedWindow`] storage (`VecDeque<u8>` by default):
 /// - `roll()` removes the **front** base and pushes a new base at the **back**.
 /// - `roll_back()` does the opposite.
 /// - `peek()` / `peek_back()` compute hashes for the next / previous window
-///   **without** mutating internal state.
-pub struct BlindNtHash {
-    window: VecDeque<u8>,
+///   into [`peeked_hashes`](Self::peeked_hashes) **without** moving.
+///
+/// Every storage, including [`NoWindow`], supports the `*_with` variants that
+/// take the outgoing base as an argument.
+///
+/// With the `serde` feature the complete state (window, position, strand
+/// hashes, and hash buffer) can be checkpointed and restored mid‑sequence;
+/// a restored state must have `k > 0`, at least one hash, and a window of
+/// `k` bases.  Clones fork that state; equality compares it, ignoring any
+/// peeked hashes.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "BlindNtHashDef<W>",
+        bound(deserialize = "W: WindowStorage + serde::Deserialize<'de>")
+    )
+)]
+pub struct BlindNtHash<W = VecDeque<u8>> {
+    window: W,
+    k: u16,
+    pos: isize,
+    fwd_hash: u64,
+    rev_hash: u64,
+    hashes: Vec<u64>,
+    /// Refuse non‑ACGT incoming bases (see [`BlindNtHash::new_checked`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    checked: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    peeked: Vec<u64>,
+    /// Not serialized: restored hashers use the reference mixing.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    mixer: Mixer,
+    #[cfg_attr(feature = "serde", serde(default))]
+    policy: CanonicalPolicy,
+}
+
+/// Serialized form of a [`BlindNtHash`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BlindNtHashDef<W> {
+    window: W,
     k: u16,
     pos: isize,
     fwd_hash: u64,
     rev_hash: u64,
     hashes: Vec<u64>,
+    #[serde(default)]
+    checked: bool,
+    #[serde(default)]
+    policy: CanonicalPolicy,
 }
 
+#[cfg(feature = "serde")]
+impl<W: WindowStorage> TryFrom<BlindNtHashDef<W>> for BlindNtHash<W> {
+    type Error = NtHashError;
+
+    fn try_from(def: BlindNtHashDef<W>) -> Result<Self> {
+        if def.k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if def.hashes.is_empty() {
+            return Err(NtHashError::InvalidNumHashes);
+        }
+        if let Some(len) = def.window.stored_len().filter(|&len| len != def.k as usize) {
+            return Err(NtHashError::WindowCapacityMismatch {
+                k: def.k as usize,
+                capacity: len,
+            });
+        }
+        Ok(Self {
+            window: def.window,
+            k: def.k,
+            pos: def.pos,
+            fwd_hash: def.fwd_hash,
+            rev_hash: def.rev_hash,
+            hashes: def.hashes,
+            checked: def.checked,
+            peeked: Vec::new(),
+            mixer: None,
+            policy: def.policy,
+        })
+    }
+}
+
+impl<W: PartialEq> PartialEq for BlindNtHash<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k
+            && self.pos == other.pos
+            && (self.fwd_hash, self.rev_hash) == (other.fwd_hash, other.rev_hash)
+            && self.hashes == other.hashes
+            && self.checked == other.checked
+            && self.policy == other.policy
+            && self.window == other.window
+    }
+}
+
+impl<W: Eq> Eq for BlindNtHash<W> {}
+
 impl BlindNtHash {
     /// Create a new `BlindNtHash` whose initial window is `seq[pos..pos+k]`.
     ///
-    /// * The caller must guarantee* that the slice contains **no ambiguous
+    /// *The caller must guarantee* that the slice contains **no ambiguous
     /// bases (‘N’)** – the blind variant will not skip over invalid windows.
     ///
     /// # Errors
     ///
     /// Returns if `k == 0`, `seq.len() < k`, or `pos` too large.
     pub fn new(seq: &[u8], k: u16, num_hashes: u8, pos: isize) -> Result<Self> {
+        Self::with_storage(seq, k, num_hashes, pos)
+    }
+
+    /// Like [`new`](Self::new), but validating bases instead of trusting
+    /// the caller: the initial window must be all ACGT, and
+    /// [`roll`](BlindNtHash::roll) / [`roll_back`](BlindNtHash::roll_back)
+    /// (and their `*_with` forms) refuse a non‑ACGT incoming base, returning
+    /// `false` with the state unchanged.
+    ///
+    /// ```
+    /// use nthash_rs::{BlindNtHash, NtHashError};
+    ///
+    /// assert_eq!(
+    ///     BlindNtHash::new_checked(b"ACNTA", 4, 1, 0).err(),
+    ///     Some(NtHashError::InvalidBase { pos: 2, byte: b'N' })
+    /// );
+    /// let mut h = BlindNtHash::new_checked(b"ACGTA", 4, 1, 0).unwrap();
+    /// assert!(!h.roll(b'N'));
+    /// assert!(h.roll(b'A') && h.pos() == 1);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new), and [`NtHashError::InvalidBase`] for the first
+    /// non‑ACGT base of the window.
+    pub fn new_checked(seq: &[u8], k: u16, num_hashes: u8, pos: isize) -> Result<Self> {
+        let mut h = Self::new(seq, k, num_hashes, pos)?;
+        let start = pos as usize;
+        if let Some(i) = seq[start..start + k as usize]
+            .iter()
+            .position(|&c| !is_acgt(c))
+        {
+            return Err(NtHashError::InvalidBase {
+                pos: start + i,
+                byte: seq[start + i],
+            });
+        }
+        h.checked = true;
+        Ok(h)
+    }
+
+    /// Create a hasher directly from its first window, with `k = window.len()`.
+    ///
+    /// Useful in streaming contexts where only the first k bases have been
+    /// received.  The window starts at position 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns if `window` is empty or longer than `u16::MAX`.
+    pub fn from_window(window: &[u8], num_hashes: u8) -> Result<Self> {
+        let k = u16::try_from(window.len()).map_err(|_| NtHashError::InvalidK)?;
+        Self::with_storage(window, k, num_hashes, 0)
+    }
+}
+
+impl BlindNtHash<NoWindow> {
+    /// Resume from previously computed strand hashes, without any sequence.
+    ///
+    /// The window contents are unknown, so the resulting hasher has
+    /// [`NoWindow`] storage and must be rolled with
+    /// [`roll_with`](BlindNtHash::roll_with) /
+    /// [`roll_back_with`](BlindNtHash::roll_back_with).  The position starts
+    /// at 0.
+    ///
+    /// The hashes are derived with the reference mixing and the strand sum,
+    /// as by [`new`](BlindNtHash::new).  To resume a hasher built with
+    /// another [`HashMixer`] or [`CanonicalPolicy`], chain
+    /// [`with_mixer`](BlindNtHash::with_mixer) and
+    /// [`with_canonical_policy`](BlindNtHash::with_canonical_policy), which
+    /// recompute them from the strand hashes.
+    ///
+    /// ```
+    /// use nthash_rs::BlindNtHash;
+    ///
+    /// let full = BlindNtHash::new(b"ACGTTGCA", 4, 2, 0).unwrap();
+    /// let mut resumed =
+    ///     BlindNtHash::from_hashes(full.forward_hash(), full.reverse_hash(), 4, 2).unwrap();
+    /// assert_eq!(resumed.hashes(), full.hashes());
+    /// resumed.roll_with(b'A', b'T');
+    /// assert_eq!(resumed.hashes(), BlindNtHash::new(b"ACGTTGCA", 4, 2, 1).unwrap().hashes());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns if `k == 0`.
+    pub fn from_hashes(fwd_hash: u64, rev_hash: u64, k: u16, num_hashes: u8) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        let mut hashes = vec![0; num_hashes as usize];
+        extend_hashes(fwd_hash, rev_hash, k as u32, &mut hashes);
+        Ok(Self {
+            window: NoWindow,
+            k,
+            pos: 0,
+            fwd_hash,
+            rev_hash,
+            hashes,
+            checked: false,
+            peeked: Vec::new(),
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
+        })
+    }
+}
+
+impl<W: WindowStorage> BlindNtHash<W> {
+    /// Like [`BlindNtHash::new`], but storing the window in `W`.
+    ///
+    /// ```
+    /// use nthash_rs::blind::{ArrayWindow, BlindNtHash};
+    ///
+    /// let mut h = BlindNtHash::<ArrayWindow<4>>::with_storage(b"ACGTA", 4, 1, 0).unwrap();
+    /// h.roll(b'A');
+    /// assert_eq!(h.hashes(), BlindNtHash::new(b"ACGTA", 4, 1, 1).unwrap().hashes());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k == 0`,
+    /// [`NtHashError::SequenceTooShort`] if `seq.len() < k`,
+    /// [`NtHashError::PositionOutOfRange`] if `pos` is too large, and
+    /// [`NtHashError::WindowCapacityMismatch`] if `W` cannot hold a window
+    /// of length `k`.
+    pub fn with_storage(seq: &[u8], k: u16, num_hashes: u8, pos: isize) -> Result<Self> {
         if k == 0 {
             return Err(NtHashError::InvalidK);
         }
         let len = seq.len();
         let k_usz = k as usize;
+        if len < k_usz {
+            return Err(NtHashError::SequenceTooShort { seq_len: len, k });
+        }
 
         if pos < 0 || (pos as usize) > len - k_usz {
             return Err(NtHashError::PositionOutOfRange {
@@ -66,8 +478,7 @@ impl BlindNtHash {
         }
 
         let slice = &seq[(pos as usize)..(pos as usize + k_usz)];
-        let mut window = VecDeque::with_capacity(k_usz);
-        window.extend(slice.iter().copied());
+        let window = storage_for(slice)?;
 
         let fwd_hash = base_forward_hash(slice, k);
         let rev_hash = base_reverse_hash(slice, k);
@@ -82,62 +493,238 @@ impl BlindNtHash {
             fwd_hash,
             rev_hash,
             hashes,
+            checked: false,
+            peeked: Vec::new(),
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
         })
     }
+}
+
+/// `W` holding `window`, or the capacity it falls short of.
+fn storage_for<W: WindowStorage>(window: &[u8]) -> Result<W> {
+    W::from_window(window).ok_or(NtHashError::WindowCapacityMismatch {
+        k: window.len(),
+        capacity: W::CAPACITY,
+    })
+}
+
+impl<W: WindowStorage> BlindNtHash<W> {
+    /// Re‑seat the window on `seq[pos..pos + k]` in O(k), keeping `k` and
+    /// the hash buffer.
+    ///
+    /// `seq` need not be the sequence the hasher was created from; like
+    /// [`new`](BlindNtHash::new), the window must hold only ACGT bases.
+    ///
+    /// ```
+    /// use nthash_rs::BlindNtHash;
+    ///
+    /// let seq = b"ACGTTGCAAGCT";
+    /// let mut h = BlindNtHash::new(seq, 4, 2, 0).unwrap();
+    /// h.seek(seq, 6).unwrap();
+    /// assert_eq!(h.hashes(), BlindNtHash::new(seq, 4, 2, 6).unwrap().hashes());
+    /// h.roll(b'A');
+    /// assert_eq!(h.pos(), 7);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::SequenceTooShort`] if `seq` is shorter than
+    /// `k`, [`NtHashError::PositionOutOfRange`] if the window does not fit
+    /// in `seq`, and [`NtHashError::WindowCapacityMismatch`] if `W` cannot
+    /// hold it.  The hasher is unchanged on error.
+    pub fn seek(&mut self, seq: &[u8], pos: isize) -> Result<()> {
+        let k_usz = self.k as usize;
+        if seq.len() < k_usz {
+            return Err(NtHashError::SequenceTooShort {
+                seq_len: seq.len(),
+                k: self.k,
+            });
+        }
+        if pos < 0 || pos as usize > seq.len() - k_usz {
+            return Err(NtHashError::PositionOutOfRange {
+                pos: pos as usize,
+                seq_len: seq.len(),
+            });
+        }
+        let slice = &seq[pos as usize..pos as usize + k_usz];
+        self.window = storage_for(slice)?;
+        self.fwd_hash = base_forward_hash(slice, self.k);
+        self.rev_hash = base_reverse_hash(slice, self.k);
+        self.update_hashes();
+        self.pos = pos;
+        Ok(())
+    }
+}
 
+impl<W: TrackedWindow> BlindNtHash<W> {
     /// Returns `true` if a new valid hash was produced.
     pub fn roll(&mut self, char_in: u8) -> bool {
-        let char_out = self
-            .window
-            .pop_front()
-            .expect("window length is always k > 0");
-        self.window.push_back(char_in);
+        if self.refuses(char_in) {
+            return false;
+        }
+        let char_out = self.window.push_back(char_in);
+        self.roll_with(char_out, char_in)
+    }
+
+    pub fn roll_back(&mut self, char_in: u8) -> bool {
+        if self.refuses(char_in) {
+            return false;
+        }
+        let char_out = self.window.push_front(char_in);
+        self.roll_back_with(char_out, char_in)
+    }
 
+    /// Roll forward over every base of `incoming`, as one
+    /// [`roll`](Self::roll) per base would, but filling the hash buffer
+    /// only once.  When `incoming` holds `k` or more ACGT bases the new
+    /// window is rehashed directly rather than rolled into.
+    ///
+    /// A [checked](BlindNtHash::new_checked) hasher refuses the whole run,
+    /// returning `false` without moving, if any base is not ACGT.
+    ///
+    /// ```
+    /// use nthash_rs::BlindNtHash;
+    ///
+    /// let mut jump = BlindNtHash::new(b"ACGTT", 5, 2, 0).unwrap();
+    /// let mut step = jump.clone();
+    /// assert!(jump.roll_n(b"GCAAGCT"));
+    /// b"GCAAGCT".iter().for_each(|&c| assert!(step.roll(c)));
+    /// assert_eq!(jump, step);
+    /// ```
+    pub fn roll_n(&mut self, incoming: &[u8]) -> bool {
+        if self.checked && !incoming.iter().all(|&c| is_acgt(c)) {
+            return false;
+        }
+        let k_usz = self.k as usize;
+        match incoming.len().checked_sub(k_usz) {
+            Some(start) if incoming[start..].iter().all(|&c| is_acgt(c)) => {
+                let window = &incoming[start..];
+                self.window.refill(window);
+                self.fwd_hash = base_forward_hash(window, self.k);
+                self.rev_hash = base_reverse_hash(window, self.k);
+            }
+            _ => {
+                for &char_in in incoming {
+                    let char_out = self.window.push_back(char_in);
+                    self.fwd_hash = next_forward_hash(self.fwd_hash, self.k, char_out, char_in);
+                    self.rev_hash = next_reverse_hash(self.rev_hash, self.k, char_out, char_in);
+                }
+            }
+        }
+        self.update_hashes();
+        self.pos += incoming.len() as isize;
+        true
+    }
+
+    /// Compute hashes for the **next** window into
+    /// [`peeked_hashes`](BlindNtHash::peeked_hashes), leaving
+    /// [`hashes`](BlindNtHash::hashes) on the current one.
+    pub fn peek(&mut self, char_in: u8) {
+        let char_out = self.window.front();
+        self.peek_with(char_out, char_in);
+    }
+
+    pub fn peek_back(&mut self, char_in: u8) {
+        let char_out = self.window.back();
+        self.peek_back_with(char_out, char_in);
+    }
+
+    /// Canonical hash of the current window with the base at `offset`
+    /// replaced by `new_base`, in O(1); the state is left untouched.
+    ///
+    /// Returns `None` if `offset >= k` or `new_base` is not ACGT.
+    ///
+    /// ```
+    /// use nthash_rs::BlindNtHash;
+    ///
+    /// let h = BlindNtHash::new(b"ACGTTGCA", 8, 1, 0).unwrap();
+    /// let snp = BlindNtHash::new(b"ACGATGCA", 8, 1, 0).unwrap();
+    /// assert_eq!(h.substitute(3, b'A'), Some(snp.hashes()[0]));
+    /// ```
+    pub fn substitute(&self, offset: usize, new_base: u8) -> Option<u64> {
+        if offset >= self.k as usize {
+            return None;
+        }
+        self.substitute_with(offset, self.window.get(offset), new_base)
+    }
+}
+
+impl<W> BlindNtHash<W> {
+    /// Derive the extra hashes with `mixer` instead of the reference
+    /// scheme, recomputing the current ones; see [`HashMixer`].
+    pub fn with_mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        self.update_hashes();
+        self
+    }
+
+    /// Combine the strand hashes under `policy` instead of summing them,
+    /// recomputing the current hashes; see [`CanonicalPolicy`].
+    pub fn with_canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
+        self.update_hashes();
+        self
+    }
+
+    /// Roll forward given both the base leaving (`char_out`, the current
+    /// first base) and the base entering the window.
+    ///
+    /// The window storage is not updated; use this with [`NoWindow`] or
+    /// when the storage is managed elsewhere.
+    pub fn roll_with(&mut self, char_out: u8, char_in: u8) -> bool {
+        if self.refuses(char_in) {
+            return false;
+        }
         self.fwd_hash = next_forward_hash(self.fwd_hash, self.k, char_out, char_in);
         self.rev_hash = next_reverse_hash(self.rev_hash, self.k, char_out, char_in);
-        extend_hashes(
-            self.fwd_hash,
-            self.rev_hash,
-            self.k as u32,
-            &mut self.hashes,
-        );
+        self.update_hashes();
         self.pos += 1;
         true
     }
 
-    pub fn roll_back(&mut self, char_in: u8) -> bool {
-        debug_assert_eq!(self.window.len(), self.k as usize);
-        let char_out = self
-            .window
-            .pop_back()
-            .expect("window length is always k > 0");
-        self.window.push_front(char_in);
-
+    /// Roll backward given the base leaving (`char_out`, the current last
+    /// base) and the base entering at the front.
+    pub fn roll_back_with(&mut self, char_out: u8, char_in: u8) -> bool {
+        if self.refuses(char_in) {
+            return false;
+        }
         self.fwd_hash = prev_forward_hash(self.fwd_hash, self.k, char_out, char_in);
         self.rev_hash = prev_reverse_hash(self.rev_hash, self.k, char_out, char_in);
-        extend_hashes(
+        self.update_hashes();
+        self.pos -= 1;
+        true
+    }
+
+    /// [`substitute`](BlindNtHash::substitute) given the base currently at
+    /// `offset` (`old_base`), for storages that do not track the window.
+    pub fn substitute_with(&self, offset: usize, old_base: u8, new_base: u8) -> Option<u64> {
+        if offset >= self.k as usize || !is_acgt(new_base) {
+            return None;
+        }
+        let (fwd, rev) = sub_hash(
             self.fwd_hash,
             self.rev_hash,
-            self.k as u32,
-            &mut self.hashes,
+            self.k,
+            offset,
+            old_base,
+            new_base,
         );
-        self.pos -= 1;
-        true
+        Some(self.policy.combine(fwd, rev))
     }
 
-    /// Compute hashes for the **next** window without mutating `self`.
-    pub fn peek(&mut self, char_in: u8) {
-        let char_out = *self.window.front().unwrap();
+    /// Peek forward with an explicit outgoing base.
+    pub fn peek_with(&mut self, char_out: u8, char_in: u8) {
         let fwd = next_forward_hash(self.fwd_hash, self.k, char_out, char_in);
         let rev = next_reverse_hash(self.rev_hash, self.k, char_out, char_in);
-        extend_hashes(fwd, rev, self.k as u32, &mut self.hashes);
+        self.fill_peeked(fwd, rev);
     }
 
-    pub fn peek_back(&mut self, char_in: u8) {
-        let char_out = *self.window.back().unwrap();
+    /// Peek backward with an explicit outgoing base.
+    pub fn peek_back_with(&mut self, char_out: u8, char_in: u8) {
         let fwd = prev_forward_hash(self.fwd_hash, self.k, char_out, char_in);
         let rev = prev_reverse_hash(self.rev_hash, self.k, char_out, char_in);
-        extend_hashes(fwd, rev, self.k as u32, &mut self.hashes);
+        self.fill_peeked(fwd, rev);
     }
 
     #[inline(always)]
@@ -145,11 +732,51 @@ impl BlindNtHash {
         &self.hashes
     }
 
+    /// Hashes of the window reached by the last peek (empty before the
+    /// first).
+    #[inline(always)]
+    pub fn peeked_hashes(&self) -> &[u64] {
+        &self.peeked
+    }
+
+    /// The current window's first `M` hash values, on the stack; see
+    /// [`NtHash::hashes_array`](crate::NtHash::hashes_array).
+    #[inline(always)]
+    pub fn hashes_array<const M: usize>(&self) -> [u64; M] {
+        let mut out = [0; M];
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
+            self.fwd_hash,
+            self.rev_hash,
+            self.k as u32,
+            &mut out,
+        );
+        out
+    }
+
+    /// Position counter: the start of the current window (same as
+    /// [`window_start`](Self::window_start)).
     #[inline(always)]
     pub fn pos(&self) -> isize {
         self.pos
     }
 
+    /// Start of the current window, counted from the construction position.
+    ///
+    /// Signed because [`roll_back`](Self::roll_back) may move the window
+    /// before the first base the hasher was given.
+    #[inline(always)]
+    pub fn window_start(&self) -> isize {
+        self.pos
+    }
+
+    /// One past the end of the current window (`window_start() + k`).
+    #[inline(always)]
+    pub fn window_end(&self) -> isize {
+        self.pos + self.k as isize
+    }
+
     #[inline(always)]
     pub fn forward_hash(&self) -> u64 {
         self.fwd_hash
@@ -159,6 +786,37 @@ impl BlindNtHash {
     pub fn reverse_hash(&self) -> u64 {
         self.rev_hash
     }
+
+    fn fill_peeked(&mut self, fwd: u64, rev: u64) {
+        self.peeked.resize(self.hashes.len(), 0);
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
+            fwd,
+            rev,
+            self.k as u32,
+            &mut self.peeked,
+        );
+    }
+
+    #[inline(always)]
+    fn update_hashes(&mut self) {
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
+            self.fwd_hash,
+            self.rev_hash,
+            self.k as u32,
+            &mut self.hashes,
+        );
+    }
+
+    /// Whether a [checked](BlindNtHash::new_checked) hasher must reject
+    /// `char_in`.
+    #[inline(always)]
+    fn refuses(&self, char_in: u8) -> bool {
+        self.checked && !is_acgt(char_in)
+    }
 }
 
 #[inline(always)]
@@ -185,7 +843,10 @@ pub struct BlindNtHashBuilder<'a> {
     seq: &'a [u8],
     k: u16,
     num_hashes: u8,
-    start_pos: usize,
+    start_pos: Option<usize>,
+    direction: Direction,
+    mixer: Mixer,
+    policy: CanonicalPolicy,
 }
 
 impl<'a> BlindNtHashBuilder<'a> {
@@ -194,7 +855,10 @@ impl<'a> BlindNtHashBuilder<'a> {
             seq,
             k: 0,
             num_hashes: 1,
-            start_pos: 0,
+            start_pos: None,
+            direction: Direction::Forward,
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
         }
     }
 
@@ -208,53 +872,179 @@ impl<'a> BlindNtHashBuilder<'a> {
         self
     }
 
+    /// Set the first window: default `0` going forward, the final window
+    /// going in reverse.
     pub fn pos(mut self, pos: usize) -> Self {
-        self.start_pos = pos;
+        self.start_pos = Some(pos);
         self
     }
 
+    /// Set the iteration order (default [`Direction::Forward`]).  In
+    /// reverse, windows are rolled back from the start position to the
+    /// start of the sequence.
+    ///
+    /// ```
+    /// use nthash_rs::{kmer::Direction, BlindNtHashBuilder};
+    ///
+    /// let seq = b"ACGTTGCAAG";
+    /// let back: Vec<_> = BlindNtHashBuilder::new(seq)
+    ///     .k(4)
+    ///     .pos(3)
+    ///     .direction(Direction::Reverse)
+    ///     .finish()
+    ///     .unwrap()
+    ///     .collect();
+    /// let fwd: Vec<_> = BlindNtHashBuilder::new(seq).k(4).finish().unwrap().take(4).collect();
+    /// assert_eq!(back, fwd.into_iter().rev().collect::<Vec<_>>());
+    /// ```
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Derive the extra hashes with `mixer` (default the reference
+    /// scheme); see [`HashMixer`].
+    pub fn mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        self
+    }
+
+    /// Combine the strand hashes under `policy` (default
+    /// [`CanonicalPolicy::Sum`]); see [`CanonicalPolicy`].
+    pub fn canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Build the iterator.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidK`] if `k` was not set,
+    /// [`NtHashError::InvalidNumHashes`] for zero hashes, and otherwise as
+    /// [`BlindNtHash::new`].
     pub fn finish(self) -> Result<BlindNtHashIter<'a>> {
-        let hasher = BlindNtHash::new(self.seq, self.k, self.num_hashes, self.start_pos as isize)?;
-        let end = self.seq.len() - self.k as usize;
+        if self.k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if self.num_hashes == 0 {
+            return Err(NtHashError::InvalidNumHashes);
+        }
+        let end =
+            self.seq
+                .len()
+                .checked_sub(self.k as usize)
+                .ok_or(NtHashError::SequenceTooShort {
+                    seq_len: self.seq.len(),
+                    k: self.k,
+                })?;
+        let start = match self.direction {
+            Direction::Forward => self.start_pos.unwrap_or(0),
+            Direction::Reverse => self.start_pos.unwrap_or(end),
+        };
+        // The iterator reads outgoing bases straight from `seq`.
+        let mut hasher = BlindNtHash::<NoWindow>::with_storage(
+            self.seq,
+            self.k,
+            self.num_hashes,
+            start as isize,
+        )?;
+        hasher.mixer = self.mixer;
+        hasher.policy = self.policy;
+        hasher.update_hashes();
         Ok(BlindNtHashIter {
             seq: self.seq,
             end,
             hasher,
             first: true,
+            reverse: self.direction == Direction::Reverse,
         })
     }
 }
 
+#[derive(Clone)]
 pub struct BlindNtHashIter<'a> {
     seq: &'a [u8],
     end: usize,
-    hasher: BlindNtHash,
+    hasher: BlindNtHash<NoWindow>,
     first: bool,
+    reverse: bool,
 }
 
-impl<'a> Iterator for BlindNtHashIter<'a> {
-    type Item = (usize, Vec<u64>);
+impl std::fmt::Debug for BlindNtHashIter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlindNtHashIter")
+            .field("seq_len", &self.seq.len())
+            .field("hasher", &self.hasher)
+            .field("first", &self.first)
+            .field("reverse", &self.reverse)
+            .finish_non_exhaustive()
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.first {
-            self.first = false;
-            return Some((self.hasher.pos() as usize, self.hasher.hashes().to_vec()));
+impl PartialEq for BlindNtHashIter<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.hasher == other.hasher
+            && (self.end, self.first, self.reverse) == (other.end, other.first, other.reverse)
+            && same_seq(self.seq, other.seq)
+    }
+}
+
+impl Eq for BlindNtHashIter<'_> {}
+
+impl BlindNtHashIter<'_> {
+    /// Move to the next window, reading the incoming base from the
+    /// sequence; the first call stays on the start window.
+    pub fn roll(&mut self) -> bool {
+        if std::mem::take(&mut self.first) {
+            return true;
         }
+        let k = self.hasher.k as usize;
+        let cur = self.pos();
+        cur < self.end && self.hasher.roll_with(self.seq[cur], self.seq[cur + k])
+    }
 
-        let cur = self.hasher.pos() as usize;
-        if cur >= self.end {
-            return None;
+    /// Move to the previous window; the first call stays on the start
+    /// window.
+    pub fn roll_back(&mut self) -> bool {
+        if std::mem::take(&mut self.first) {
+            return true;
         }
+        let k = self.hasher.k as usize;
+        let cur = self.pos();
+        cur > 0
+            && self
+                .hasher
+                .roll_back_with(self.seq[cur + k - 1], self.seq[cur - 1])
+    }
 
-        let incoming = self.seq[cur + self.hasher.k as usize];
-        self.hasher.roll(incoming);
+    /// Start of the current window in the sequence.
+    pub fn pos(&self) -> usize {
+        self.hasher.pos() as usize
+    }
 
-        Some((self.hasher.pos() as usize, self.hasher.hashes().to_vec()))
+    /// The underlying hasher.
+    pub fn hasher(&self) -> &BlindNtHash<NoWindow> {
+        &self.hasher
+    }
+}
+
+impl<'a> Iterator for BlindNtHashIter<'a> {
+    type Item = (KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let moved = if self.reverse {
+            self.roll_back()
+        } else {
+            self.roll()
+        };
+        let k = self.hasher.k as usize;
+        moved.then(|| (KmerPos::new(self.pos(), k), self.hasher.hashes().to_vec()))
     }
 }
 
 impl<'a> IntoIterator for BlindNtHashBuilder<'a> {
-    type Item = (usize, Vec<u64>);
+    type Item = (KmerPos, Vec<u64>);
     type IntoIter = BlindNtHashIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -262,3 +1052,255 @@ impl<'a> IntoIterator for BlindNtHashBuilder<'a> {
             .expect("invalid BlindNtHashBuilder configuration")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEQ: &[u8] = b"ATCGTACGATGCATGCATGCTGACG";
+
+    fn roll_through<W: TrackedWindow>(mut h: BlindNtHash<W>) -> Vec<u64> {
+        let mut out = vec![h.hashes()[0]];
+        for &c in &SEQ[6..] {
+            h.roll(c);
+            out.push(h.hashes()[0]);
+        }
+        for i in (0..SEQ.len() - 6).rev() {
+            h.roll_back(SEQ[i]);
+            assert_eq!(h.hashes()[0], out[i]);
+        }
+        out
+    }
+
+    #[test]
+    fn storages_agree() {
+        let deque = roll_through(BlindNtHash::new(SEQ, 6, 1, 0).unwrap());
+        let array =
+            roll_through(BlindNtHash::<ArrayWindow<6>>::with_storage(SEQ, 6, 1, 0).unwrap());
+        assert_eq!(deque, array);
+
+        let mut untracked = BlindNtHash::<NoWindow>::with_storage(SEQ, 6, 1, 0).unwrap();
+        for (i, &c) in SEQ[6..].iter().enumerate() {
+            untracked.roll_with(SEQ[i], c);
+            assert_eq!(untracked.hashes()[0], deque[i + 1]);
+        }
+    }
+
+    #[test]
+    fn checked_hasher_refuses_invalid_bases() {
+        let mut h = BlindNtHash::new_checked(SEQ, 6, 2, 0).unwrap();
+        let before = (h.pos(), h.hashes().to_vec());
+        for c in [b'N', b'n', b'-', 1] {
+            assert!(!h.roll(c) && !h.roll_back(c) && !h.roll_with(b'A', c));
+            assert_eq!((h.pos(), h.hashes().to_vec()), before);
+        }
+        // the window was left intact: rolling on matches an unchecked hasher
+        let expected = roll_through(BlindNtHash::new(SEQ, 6, 2, 0).unwrap());
+        assert_eq!(roll_through(h), expected);
+
+        let mut unchecked = BlindNtHash::new(SEQ, 6, 1, 0).unwrap();
+        assert!(unchecked.roll(b'N'));
+        assert_eq!(
+            BlindNtHash::new_checked(b"ACGTNAC", 3, 1, 2).err(),
+            Some(NtHashError::InvalidBase { pos: 4, byte: b'N' })
+        );
+        assert!(BlindNtHash::new_checked(b"ACGTNAC", 3, 1, 9).is_err());
+    }
+
+    #[test]
+    fn builder_reverse_mirrors_forward() {
+        let build = |k| BlindNtHashBuilder::new(SEQ).k(k).num_hashes(3);
+        for k in [1, 5, SEQ.len() as u16] {
+            let fwd: Vec<_> = build(k).finish().unwrap().collect();
+            let back_iter = build(k).direction(Direction::Reverse).finish().unwrap();
+            let mut back: Vec<_> = back_iter.collect();
+            back.reverse();
+            assert_eq!(back, fwd, "k={k}");
+        }
+        let from_mid = build(5)
+            .direction(Direction::Reverse)
+            .pos(7)
+            .finish()
+            .unwrap();
+        assert_eq!(
+            from_mid.map(|(p, _)| p.start).collect::<Vec<_>>(),
+            [7, 6, 5, 4, 3, 2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn builder_reports_bad_parameters() {
+        let err = |b: BlindNtHashBuilder| b.finish().err();
+        assert_eq!(
+            err(BlindNtHashBuilder::new(SEQ)),
+            Some(NtHashError::InvalidK)
+        );
+        assert_eq!(
+            err(BlindNtHashBuilder::new(SEQ).k(4).num_hashes(0)),
+            Some(NtHashError::InvalidNumHashes)
+        );
+        for direction in [Direction::Forward, Direction::Reverse] {
+            assert_eq!(
+                err(BlindNtHashBuilder::new(b"ACG").k(4).direction(direction)),
+                Some(NtHashError::SequenceTooShort { seq_len: 3, k: 4 })
+            );
+        }
+    }
+
+    #[test]
+    fn peeks_leave_current_hashes() {
+        let mut h = BlindNtHash::new(SEQ, 6, 3, 1).unwrap();
+        let current = h.hashes().to_vec();
+        assert!(h.peeked_hashes().is_empty());
+        h.peek(SEQ[7]);
+        assert_eq!(h.hashes(), current);
+        assert_eq!(
+            h.peeked_hashes(),
+            BlindNtHash::new(SEQ, 6, 3, 2).unwrap().hashes()
+        );
+        h.peek_back(SEQ[0]);
+        assert_eq!(h.hashes(), current);
+        assert_eq!(
+            h.peeked_hashes(),
+            BlindNtHash::new(SEQ, 6, 3, 0).unwrap().hashes()
+        );
+        // peeking is not part of the compared state
+        assert_eq!(h, BlindNtHash::new(SEQ, 6, 3, 1).unwrap());
+        let mut fork = h.clone();
+        fork.roll(SEQ[7]);
+        assert_eq!(fork, BlindNtHash::new(SEQ, 6, 3, 2).unwrap());
+        assert_ne!(fork, h);
+    }
+
+    #[test]
+    fn from_window_matches_new() {
+        let mut a = BlindNtHash::from_window(&SEQ[3..9], 3).unwrap();
+        let b = BlindNtHash::new(SEQ, 6, 3, 3).unwrap();
+        assert_eq!(a.hashes(), b.hashes());
+        assert_eq!(a.pos(), 0);
+        a.roll(SEQ[9]);
+        assert_eq!(a.hashes(), BlindNtHash::new(SEQ, 6, 3, 4).unwrap().hashes());
+        assert!(BlindNtHash::from_window(b"", 1).is_err());
+        assert!(BlindNtHash::from_hashes(1, 2, 0, 1).is_err());
+        assert_eq!(
+            BlindNtHash::new(b"ACG", 4, 1, 0).err(),
+            Some(NtHashError::SequenceTooShort { seq_len: 3, k: 4 })
+        );
+    }
+
+    #[test]
+    fn from_hashes_resumes_with_a_mixer_and_policy() {
+        let (mixer, policy) = (&crate::util::XxhMixer, CanonicalPolicy::Min);
+        let mut full = BlindNtHash::new(SEQ, 6, 3, 0)
+            .unwrap()
+            .with_mixer(mixer)
+            .with_canonical_policy(policy);
+        let mut resumed = BlindNtHash::from_hashes(full.forward_hash(), full.reverse_hash(), 6, 3)
+            .unwrap()
+            .with_mixer(mixer)
+            .with_canonical_policy(policy);
+        assert_eq!(resumed.hashes(), full.hashes());
+        full.roll(SEQ[6]);
+        resumed.roll_with(SEQ[0], SEQ[6]);
+        assert_eq!(resumed.hashes(), full.hashes());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn checkpoint_and_resume_mid_sequence() {
+        fn check<W>(mut h: BlindNtHash<W>)
+        where
+            W: TrackedWindow + serde::Serialize + serde::de::DeserializeOwned,
+        {
+            for &c in &SEQ[6..12] {
+                h.roll(c);
+            }
+            let json = serde_json::to_string(&h).unwrap();
+            let mut resumed: BlindNtHash<W> = serde_json::from_str(&json).unwrap();
+            for &c in &SEQ[12..] {
+                h.roll(c);
+                resumed.roll(c);
+                assert_eq!(h.hashes(), resumed.hashes());
+                assert_eq!(h.pos(), resumed.pos());
+            }
+        }
+        check(BlindNtHash::new(SEQ, 6, 2, 0).unwrap());
+        check(BlindNtHash::<ArrayWindow<6>>::with_storage(SEQ, 6, 2, 0).unwrap());
+        assert!(serde_json::from_str::<ArrayWindow<6>>("[65, 67]").is_err());
+
+        // states that would panic or hash wrongly once rolled
+        let json = serde_json::to_value(BlindNtHash::new(SEQ, 6, 2, 0).unwrap()).unwrap();
+        for (field, value) in [
+            ("window", serde_json::json!([])),
+            ("window", serde_json::json!([65, 67, 71])),
+            ("k", serde_json::json!(0)),
+            ("hashes", serde_json::json!([])),
+        ] {
+            let mut bad = json.clone();
+            bad[field] = value;
+            assert!(
+                serde_json::from_value::<BlindNtHash>(bad).is_err(),
+                "{field}"
+            );
+        }
+        assert!(serde_json::from_value::<BlindNtHash>(json).is_ok());
+    }
+
+    #[test]
+    fn substitute_matches_rehashing_the_variant() {
+        let mut deque = BlindNtHash::new(SEQ, 6, 1, 0).unwrap();
+        let mut array = BlindNtHash::<ArrayWindow<6>>::with_storage(SEQ, 6, 1, 0).unwrap();
+        for pos in 0..SEQ.len() - 6 {
+            let window = &SEQ[pos..pos + 6];
+            for offset in 0..6 {
+                for &base in b"ACGTa" {
+                    let mut variant = window.to_vec();
+                    variant[offset] = base;
+                    let expected = Some(BlindNtHash::from_window(&variant, 1).unwrap().hashes()[0]);
+                    assert_eq!(deque.substitute(offset, base), expected);
+                    assert_eq!(array.substitute(offset, base), expected);
+                }
+            }
+            assert_eq!(deque.substitute(6, b'A'), None);
+            assert_eq!(deque.substitute(0, b'N'), None);
+            assert_eq!(deque.substitute(0, 0x01), None);
+            deque.roll(SEQ[pos + 6]);
+            array.roll(SEQ[pos + 6]);
+        }
+    }
+
+    #[test]
+    fn array_window_rejects_wrong_k() {
+        assert_eq!(
+            BlindNtHash::<ArrayWindow<5>>::with_storage(SEQ, 6, 1, 0).err(),
+            Some(NtHashError::WindowCapacityMismatch { k: 6, capacity: 5 })
+        );
+        let mut h = BlindNtHash::new(SEQ, 6, 1, 0).unwrap();
+        assert_eq!(
+            h.seek(b"ACG", 0),
+            Err(NtHashError::SequenceTooShort { seq_len: 3, k: 6 })
+        );
+    }
+
+    #[test]
+    fn roll_n_matches_single_rolls() {
+        let incoming = b"GCATNNGCATTGACGTACGTTAGCCA";
+        for len in [0, 1, 3, 5, 6, 10, incoming.len()] {
+            let mut jump = BlindNtHash::new(SEQ, 6, 3, 0).unwrap();
+            let mut step = jump.clone();
+            assert!(jump.roll_n(&incoming[..len]));
+            incoming[..len].iter().for_each(|&c| _ = step.roll(c));
+            assert_eq!(jump, step, "{len} bases");
+            let mut array = BlindNtHash::<ArrayWindow<6>>::with_storage(SEQ, 6, 3, 0).unwrap();
+            assert!(array.roll_n(&incoming[..len]));
+            assert_eq!(array.hashes(), step.hashes());
+            // the refilled window rolls on like the stepped one
+            assert!(array.roll(b'T') && step.roll(b'T'));
+            assert_eq!(array.hashes(), step.hashes());
+        }
+        let mut checked = BlindNtHash::new_checked(SEQ, 6, 1, 0).unwrap();
+        let before = checked.clone();
+        assert!(!checked.roll_n(b"ACGN"));
+        assert_eq!(checked, before);
+    }
+}
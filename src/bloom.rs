@@ -0,0 +1,327 @@
+//! **Bloom filters** over ntHash k‑mer hashes.
+//!
+//! Both filters take the `num_hashes` values emitted per k‑mer by the
+//! rolling hashers directly, one bit (or counter) per hash, as in the
+//! reference ntHash / btllib filters.  Storage is pluggable through the
+//! [`storage`](crate::storage) traits.
+//!
+//! ```
+//! use nthash_rs::bloom::BloomFilter;
+//!
+//! let mut bf = BloomFilter::new(1 << 12, 3);
+//! bf.insert_seq(b"ACGTACGTTGCA", 5).unwrap();
+//! assert_eq!(bf.query_seq(b"ACGTACGT", 5).unwrap(), 4);
+//! ```
+
+use crate::{
+    kmer::sub_hash,
+    storage::{BitStorage, Buckets},
+    util::extend_hashes,
+    NtHash, NtHashError, Result,
+};
+
+/// Number of hashes per k‑mer as the `u8` the hashers expect.
+fn hash_count(num_hashes: usize) -> Result<u8> {
+    u8::try_from(num_hashes).map_err(|_| NtHashError::InvalidCapacity)
+}
+
+/// Classic Bloom filter: one bit per hash value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BloomFilter<S = Vec<u64>> {
+    bits: S,
+    num_hashes: usize,
+}
+
+impl BloomFilter {
+    /// An in‑memory filter of at least `num_bits` bits (rounded up to a
+    /// multiple of 64) using `num_hashes` hashes per k‑mer.
+    pub fn new(num_bits: usize, num_hashes: usize) -> Self {
+        Self {
+            bits: vec![0; num_bits.div_ceil(64).max(1)],
+            num_hashes,
+        }
+    }
+}
+
+impl<S: BitStorage> BloomFilter<S> {
+    /// A filter over caller‑provided storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] if `bits` holds no bits.
+    pub fn with_storage(bits: S, num_hashes: usize) -> Result<Self> {
+        if bits.num_bits() == 0 {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        Ok(Self { bits, num_hashes })
+    }
+
+    /// Hashes used per k‑mer.
+    pub fn num_hashes(&self) -> usize {
+        self.num_hashes
+    }
+
+    /// Insert one k‑mer given its hash values; returns `true` if every bit
+    /// was already set (i.e. the k‑mer was probably present).
+    pub fn insert(&mut self, hashes: &[u64]) -> bool {
+        let n = self.bits.num_bits() as u64;
+        let mut present = true;
+        for &h in &hashes[..self.num_hashes.min(hashes.len())] {
+            present &= self.bits.set_bit((h % n) as usize);
+        }
+        present
+    }
+
+    /// Returns `true` if the k‑mer with these hash values may be present.
+    pub fn contains(&self, hashes: &[u64]) -> bool {
+        let n = self.bits.num_bits() as u64;
+        hashes[..self.num_hashes.min(hashes.len())]
+            .iter()
+            .all(|&h| self.bits.get_bit((h % n) as usize))
+    }
+
+    /// Insert every valid k‑mer of `seq`.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn insert_seq(&mut self, seq: &[u8], k: u16) -> Result<()> {
+        let mut h = NtHash::new(seq, k, hash_count(self.num_hashes)?, 0)?;
+        while h.roll() {
+            self.insert(h.hashes());
+        }
+        Ok(())
+    }
+
+    /// Number of valid k‑mers of `seq` that the filter may contain.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn query_seq(&self, seq: &[u8], k: u16) -> Result<usize> {
+        let mut h = NtHash::new(seq, k, hash_count(self.num_hashes)?, 0)?;
+        let mut hits = 0;
+        while h.roll() {
+            hits += self.contains(h.hashes()) as usize;
+        }
+        Ok(hits)
+    }
+
+    /// Fraction of bits set.
+    pub fn occupancy(&self) -> f64 {
+        self.bits.count_ones() as f64 / self.bits.num_bits() as f64
+    }
+
+    /// Expected false‑positive rate at the current occupancy.
+    pub fn estimated_fpr(&self) -> f64 {
+        self.occupancy().powi(self.num_hashes as i32)
+    }
+
+    /// Borrow the backing storage.
+    pub fn storage(&self) -> &S {
+        &self.bits
+    }
+
+    /// Unwrap the backing storage.
+    pub fn into_storage(self) -> S {
+        self.bits
+    }
+}
+
+/// Filter support for every single‑base substitution of `read`.
+///
+/// Entry `[p][b]` counts the k‑mers covering position `p` that `filter`
+/// contains once base `p` is replaced by `b` (`A`, `C`, `G`, `T` order);
+/// the read's own base gives the current support.  Alternatives that gain
+/// support over the current base are candidate corrections — the inner
+/// loop of reference‑free error correction.  K‑mers containing non‑ACGT
+/// bases are not scored.
+///
+/// ```
+/// use nthash_rs::bloom::{scan_substitutions, BloomFilter};
+///
+/// let mut solid = BloomFilter::new(1 << 14, 3);
+/// solid.insert_seq(b"ACGTTGCAAGGCTTAC", 5).unwrap();
+/// // the read has an error at position 7 (A → T)
+/// let scores = scan_substitutions(b"ACGTTGCTAGGCTTAC", 5, &solid).unwrap();
+/// assert_eq!(scores[7], [5, 0, 0, 0]);
+/// ```
+///
+/// # Errors
+///
+/// Propagates [`NtHash::new`] errors.
+pub fn scan_substitutions<S: BitStorage>(
+    read: &[u8],
+    k: u16,
+    filter: &BloomFilter<S>,
+) -> Result<Vec<[u32; 4]>> {
+    let mut scores = vec![[0u32; 4]; read.len()];
+    let mut buf = vec![0u64; filter.num_hashes()];
+    let mut h = NtHash::new(read, k, 1, 0)?;
+    while h.roll() {
+        let start = h.pos();
+        for offset in 0..k as usize {
+            let old = read[start + offset];
+            for (b, &new) in b"ACGT".iter().enumerate() {
+                let (fwd, rev) = sub_hash(h.forward_hash(), h.reverse_hash(), k, offset, old, new);
+                extend_hashes(fwd, rev, k as u32, &mut buf);
+                scores[start + offset][b] += filter.contains(&buf) as u32;
+            }
+        }
+    }
+    Ok(scores)
+}
+
+/// Counting Bloom filter with saturating 8‑bit counters.
+///
+/// [`count`](Self::count) returns the minimum counter over a k‑mer's hashes,
+/// an upper bound on how many times it was inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountingBloomFilter<B = Vec<u8>> {
+    counters: B,
+    num_hashes: usize,
+}
+
+impl CountingBloomFilter {
+    /// An in‑memory filter with `num_counters` counters (at least one).
+    pub fn new(num_counters: usize, num_hashes: usize) -> Self {
+        Self {
+            counters: vec![0; num_counters.max(1)],
+            num_hashes,
+        }
+    }
+}
+
+impl<B: Buckets<u8>> CountingBloomFilter<B> {
+    /// A filter over caller‑provided counters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] if `counters` is empty.
+    pub fn with_storage(counters: B, num_hashes: usize) -> Result<Self> {
+        if counters.num_buckets() == 0 {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        Ok(Self {
+            counters,
+            num_hashes,
+        })
+    }
+
+    /// Hashes used per k‑mer.
+    pub fn num_hashes(&self) -> usize {
+        self.num_hashes
+    }
+
+    #[inline]
+    fn slots<'h>(&self, hashes: &'h [u64]) -> impl Iterator<Item = usize> + 'h {
+        let n = self.counters.num_buckets() as u64;
+        hashes[..self.num_hashes.min(hashes.len())]
+            .iter()
+            .map(move |&h| (h % n) as usize)
+    }
+
+    /// Insert one k‑mer; returns its count afterwards.
+    ///
+    /// Only the minimal counters are incremented ("conservative update"),
+    /// which keeps over‑estimates low.
+    pub fn insert(&mut self, hashes: &[u64]) -> u8 {
+        let next = self.count(hashes).saturating_add(1);
+        let slots: Vec<usize> = self.slots(hashes).collect();
+        for i in slots {
+            if self.counters.get(i) < next {
+                self.counters.set(i, next);
+            }
+        }
+        next
+    }
+
+    /// Estimated number of insertions of the k‑mer (0 if absent).
+    pub fn count(&self, hashes: &[u64]) -> u8 {
+        self.slots(hashes)
+            .map(|i| self.counters.get(i))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Insert every valid k‑mer of `seq`.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn insert_seq(&mut self, seq: &[u8], k: u16) -> Result<()> {
+        let mut h = NtHash::new(seq, k, hash_count(self.num_hashes)?, 0)?;
+        while h.roll() {
+            self.insert(h.hashes());
+        }
+        Ok(())
+    }
+
+    /// Borrow the backing counters.
+    pub fn storage(&self) -> &B {
+        &self.counters
+    }
+
+    /// Unwrap the backing counters.
+    pub fn into_storage(self) -> B {
+        self.counters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEQ: &[u8] = b"ATCGTACGATGCATGCATGCTGACGTTAGCATCGGATCCATGCA";
+
+    #[test]
+    fn no_false_negatives() {
+        let mut bf = BloomFilter::new(1 << 10, 3);
+        bf.insert_seq(SEQ, 11).unwrap();
+        let n = SEQ.len() - 11 + 1;
+        assert_eq!(bf.query_seq(SEQ, 11).unwrap(), n);
+        assert!(bf.occupancy() > 0.0 && bf.estimated_fpr() < 0.01);
+        assert!(BloomFilter::with_storage(Vec::<u64>::new(), 3).is_err());
+    }
+
+    #[test]
+    fn borrowed_storage_is_shared() {
+        let mut words = [0u64; 8];
+        {
+            let mut bf = BloomFilter::with_storage(&mut words[..], 2).unwrap();
+            bf.insert_seq(SEQ, 7).unwrap();
+        }
+        let bf = BloomFilter::with_storage(&mut words[..], 2).unwrap();
+        assert_eq!(bf.query_seq(SEQ, 7).unwrap(), SEQ.len() - 6);
+    }
+
+    #[test]
+    fn substitution_scan_finds_the_error() {
+        let mut solid = BloomFilter::new(1 << 16, 3);
+        solid.insert_seq(SEQ, 9).unwrap();
+        let mut read = SEQ.to_vec();
+        read[20] = b'A'; // was 'T'
+        let scores = scan_substitutions(&read, 9, &solid).unwrap();
+        // all nine k‑mers covering the error are rescued by the true base
+        assert_eq!(scores[20][3], 9);
+        assert!(scores[20][..3].iter().all(|&s| s < 9));
+        // positions far from the error keep their support
+        assert_eq!(scores[0][0], 1);
+        assert_eq!(scores[40][3], 4);
+    }
+
+    #[test]
+    fn counting_filter_counts_repeats() {
+        let mut cbf = CountingBloomFilter::new(1 << 12, 3);
+        cbf.insert_seq(b"ACGTAACGTAACGTA", 5).unwrap();
+        let mut h = NtHash::new(b"ACGTA", 5, 3, 0).unwrap();
+        assert!(h.roll());
+        assert_eq!(cbf.count(h.hashes()), 3);
+        let mut counters = vec![0u8; 1 << 12];
+        let mut borrowed = CountingBloomFilter::with_storage(&mut counters[..], 3).unwrap();
+        assert_eq!(borrowed.insert(h.hashes()), 1);
+        assert_eq!(borrowed.insert(h.hashes()), 2);
+    }
+}
@@ -0,0 +1,212 @@
+//! **Cooperative cancellation** for long hashing scans.
+//!
+//! Scanning a whole genome can take seconds to minutes; services embedding
+//! such scans need a way to abort a request promptly.  A
+//! [`CancellationToken`] is a cheap, cloneable flag that the controlling side
+//! sets, and the [`Cancellable`] iterator adapter polls it at fixed
+//! checkpoints (every *N* k‑mers) so the per‑k‑mer hot path stays free of
+//! atomic loads.
+//!
+//! The adapter works with every hash iterator in this crate (or any other
+//! iterator):
+//!
+//! ```
+//! use nthash_rs::{cancel::{CancellableExt, CancellationToken}, NtHashBuilder};
+//!
+//! let token = CancellationToken::new();
+//! let mut scan = NtHashBuilder::new(b"ACGTACGTACGTACGT")
+//!     .k(4)
+//!     .finish()
+//!     .unwrap()
+//!     .cancellable(&token, 2);
+//!
+//! assert!(scan.next().is_some());
+//! token.cancel();
+//! // Stops at the next checkpoint.
+//! while scan.next().is_some() {}
+//! assert!(scan.status().is_err());
+//! ```
+//!
+//! With the `async` feature, [`Cancellable`] is also a `Stream` adapter,
+//! so [`AsyncNtHash`](crate::AsyncNtHash) scans can be stopped the same way
+//! without dropping them.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(feature = "async")]
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+#[cfg(feature = "async")]
+use futures_core::Stream;
+
+use crate::{NtHashError, Result};
+
+/// Shared cancellation flag.
+///
+/// Clones refer to the same flag; cancelling any clone cancels them all.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a fresh, non‑cancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every scan observing this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once [`cancel`](Self::cancel) has been called.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Iterator adapter that stops at the first checkpoint after cancellation.
+pub struct Cancellable<I> {
+    inner: I,
+    token: CancellationToken,
+    every: usize,
+    countdown: usize,
+    processed: usize,
+    cancelled: bool,
+}
+
+impl<I> Cancellable<I> {
+    /// Wrap `inner`, polling `token` every `every` items (`0` is treated as 1).
+    pub fn new(inner: I, token: &CancellationToken, every: usize) -> Self {
+        let every = every.max(1);
+        Self {
+            inner,
+            token: token.clone(),
+            every,
+            countdown: every,
+            processed: 0,
+            cancelled: false,
+        }
+    }
+
+    /// Number of items yielded so far.
+    #[inline]
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+
+    /// Returns `true` if the scan was stopped by the token.
+    #[inline]
+    pub fn was_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    /// `Ok(())` unless the scan was cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::Cancelled`] with the number of items processed.
+    pub fn status(&self) -> Result<()> {
+        if self.cancelled {
+            Err(NtHashError::Cancelled {
+                processed: self.processed,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Unwrap the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I: Iterator> Iterator for Cancellable<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cancelled {
+            return None;
+        }
+        self.countdown -= 1;
+        if self.countdown == 0 {
+            self.countdown = self.every;
+            if self.token.is_cancelled() {
+                self.cancelled = true;
+                return None;
+            }
+        }
+        let item = self.inner.next()?;
+        self.processed += 1;
+        Some(item)
+    }
+}
+
+/// Polls the token before every `every`‑th item; a `Pending` inner stream
+/// does not count towards the checkpoint.
+#[cfg(feature = "async")]
+impl<S: Stream + Unpin> Stream for Cancellable<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.cancelled {
+            return Poll::Ready(None);
+        }
+        if this.countdown == 1 && this.token.is_cancelled() {
+            this.cancelled = true;
+            return Poll::Ready(None);
+        }
+        let item = ready!(Pin::new(&mut this.inner).poll_next(cx));
+        this.countdown -= 1;
+        if this.countdown == 0 {
+            this.countdown = this.every;
+        }
+        this.processed += usize::from(item.is_some());
+        Poll::Ready(item)
+    }
+}
+
+/// Adds [`cancellable`](CancellableExt::cancellable) to every iterator.
+pub trait CancellableExt: Iterator + Sized {
+    /// Poll `token` every `every` items and stop once it is cancelled.
+    fn cancellable(self, token: &CancellationToken, every: usize) -> Cancellable<Self> {
+        Cancellable::new(self, token, every)
+    }
+}
+
+impl<I: Iterator> CancellableExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_at_next_checkpoint() {
+        let token = CancellationToken::new();
+        let mut it = (0..100).cancellable(&token, 10);
+        for _ in 0..3 {
+            it.next();
+        }
+        token.clone().cancel();
+        // Items up to the checkpoint are still yielded.
+        let rest: Vec<_> = it.by_ref().collect();
+        assert_eq!(rest, (3..9).collect::<Vec<_>>());
+        assert_eq!(it.processed(), 9);
+        assert_eq!(it.status(), Err(NtHashError::Cancelled { processed: 9 }));
+    }
+
+    #[test]
+    fn uncancelled_scan_runs_to_completion() {
+        let token = CancellationToken::new();
+        let mut it = (0..25).cancellable(&token, 0);
+        assert_eq!(it.by_ref().count(), 25);
+        assert!(!it.was_cancelled());
+        assert_eq!(it.status(), Ok(()));
+    }
+}
@@ -0,0 +1,201 @@
+//! **Chunked hashing** of a shared reference across threads.
+//!
+//! Splitting a sequence for parallel hashing needs each piece to extend
+//! `k − 1` bases past the last k‑mer it owns, so that every k‑mer start is
+//! hashed by exactly one thread.  [`ChunkedNtHash`] does that arithmetic
+//! once over an `Arc<[u8]>` and hands out owned [`Chunk`]s that can be
+//! moved into threads (or a thread pool) without borrowing the reference.
+//!
+//! ```
+//! use std::{sync::Arc, thread};
+//! use nthash_rs::{ChunkedNtHash, NtHash};
+//!
+//! let genome: Arc<[u8]> = Arc::from(&b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACG"[..]);
+//! let chunks = ChunkedNtHash::new(genome.clone(), 0..genome.len(), 11, 1, 4).unwrap();
+//! let handles: Vec<_> = chunks
+//!     .into_iter()
+//!     .map(|chunk| {
+//!         thread::spawn(move || {
+//!             let mut out = Vec::new();
+//!             chunk.drive(&mut |pos, hashes: &[u64]| out.push((pos, hashes[0])));
+//!             out
+//!         })
+//!     })
+//!     .collect();
+//! let parallel: Vec<(usize, u64)> =
+//!     handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+//!
+//! let mut serial = Vec::new();
+//! NtHash::new(&genome, 11, 1, 0)
+//!     .unwrap()
+//!     .drive(&mut |pos, hashes: &[u64]| serial.push((pos, hashes[0])));
+//! assert_eq!(parallel, serial);
+//! ```
+
+use std::{ops::Range, sync::Arc, thread};
+
+use crate::{sink::HashSink, NtHash, NtHashError, Result};
+
+/// A reference range split into independently hashable chunks.
+#[derive(Debug, Clone)]
+pub struct ChunkedNtHash {
+    chunks: Vec<Chunk>,
+}
+
+impl ChunkedNtHash {
+    /// Split the k‑mers lying entirely inside `range` of `seq` into at most
+    /// `parts` chunks (0 = available parallelism) of near‑equal size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k == 0`,
+    /// [`NtHashError::PositionOutOfRange`] if `range` does not lie within
+    /// `seq`, and [`NtHashError::SequenceTooShort`] if it is shorter than
+    /// `k`.
+    pub fn new(
+        seq: Arc<[u8]>,
+        range: Range<usize>,
+        k: u16,
+        num_hashes: u8,
+        parts: usize,
+    ) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if range.start > range.end || range.end > seq.len() {
+            return Err(NtHashError::PositionOutOfRange {
+                pos: range.end.max(range.start),
+                seq_len: seq.len(),
+            });
+        }
+        if range.len() < k as usize {
+            return Err(NtHashError::SequenceTooShort {
+                seq_len: range.len(),
+                k,
+            });
+        }
+        let windows = range.len() - k as usize + 1;
+        let chunks = split_windows(windows, parts)
+            .map(|w| Chunk {
+                seq: Arc::clone(&seq),
+                kmers: range.start + w.start..range.start + w.end,
+                k,
+                num_hashes,
+            })
+            .collect();
+        Ok(Self { chunks })
+    }
+
+    /// The chunks, in sequence order.
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+}
+
+impl IntoIterator for ChunkedNtHash {
+    type Item = Chunk;
+    type IntoIter = std::vec::IntoIter<Chunk>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chunks.into_iter()
+    }
+}
+
+/// One thread's share of a [`ChunkedNtHash`]: a contiguous range of k‑mer
+/// start positions and the bases they span.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    seq: Arc<[u8]>,
+    kmers: Range<usize>,
+    k: u16,
+    num_hashes: u8,
+}
+
+impl Chunk {
+    /// Start positions of the k‑mers owned by this chunk, in coordinates
+    /// of the whole sequence.
+    pub fn kmer_starts(&self) -> Range<usize> {
+        self.kmers.clone()
+    }
+
+    /// The bases those k‑mers cover (`k − 1` past the last start).
+    pub fn bases(&self) -> &[u8] {
+        &self.seq[self.kmers.start..self.kmers.end + self.k as usize - 1]
+    }
+
+    /// A hasher over [`bases`](Self::bases); add
+    /// [`offset`](Self::offset) to its positions for whole‑sequence
+    /// coordinates.
+    pub fn hasher(&self) -> NtHash<'_> {
+        NtHash::new(self.bases(), self.k, self.num_hashes, 0)
+            .expect("chunk holds at least one k-mer")
+    }
+
+    /// Whole‑sequence coordinate of [`bases`](Self::bases)`[0]`.
+    pub fn offset(&self) -> usize {
+        self.kmers.start
+    }
+
+    /// Pass every valid k‑mer of the chunk to `sink`, with whole‑sequence
+    /// positions; returns the number of k‑mers passed.
+    pub fn drive<S: HashSink + ?Sized>(&self, sink: &mut S) -> usize {
+        let offset = self.offset();
+        self.hasher()
+            .drive(&mut |pos, hashes: &[u64]| sink.accept(offset + pos, hashes))
+    }
+}
+
+/// Split `windows` k‑mer starts into at most `parts` (0 = available
+/// parallelism) contiguous, non‑empty ranges.
+pub(crate) fn split_windows(windows: usize, parts: usize) -> impl Iterator<Item = Range<usize>> {
+    let parts = match parts {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let chunk = windows.div_ceil(parts.min(windows).max(1)).max(1);
+    (0..windows)
+        .step_by(chunk)
+        .map(move |start| start..(start + chunk).min(windows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_partition_a_subrange() {
+        let seq: Arc<[u8]> = Arc::from(&b"NNACGTTGCANGCTTAGCGATCGATCGGGCTAGCATCGACTACGNN"[..]);
+        let range = 3..40;
+        let mut expected = Vec::new();
+        NtHash::new(&seq[range.clone()], 7, 2, 0)
+            .unwrap()
+            .drive(&mut |pos, hashes: &[u64]| expected.push((range.start + pos, hashes.to_vec())));
+
+        for parts in [1, 2, 3, 7, 31, 100] {
+            let chunks = ChunkedNtHash::new(seq.clone(), range.clone(), 7, 2, parts).unwrap();
+            assert!(chunks.chunks().len() <= parts);
+            let starts: Vec<usize> = chunks
+                .chunks()
+                .iter()
+                .flat_map(Chunk::kmer_starts)
+                .collect();
+            assert_eq!(starts, (3..34).collect::<Vec<_>>());
+            let mut got = Vec::new();
+            for c in chunks {
+                assert_eq!(c.bases().len(), c.kmer_starts().len() + 6);
+                c.drive(&mut |pos, hashes: &[u64]| got.push((pos, hashes.to_vec())));
+            }
+            assert_eq!(got, expected, "parts={parts}");
+        }
+
+        assert_eq!(
+            ChunkedNtHash::new(seq.clone(), 40..50, 7, 1, 2).unwrap_err(),
+            NtHashError::PositionOutOfRange {
+                pos: 50,
+                seq_len: 46
+            }
+        );
+        assert!(ChunkedNtHash::new(seq.clone(), 3..8, 7, 1, 2).is_err());
+        assert!(ChunkedNtHash::new(seq, 0..10, 0, 1, 2).is_err());
+    }
+}
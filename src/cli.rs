@@ -0,0 +1,354 @@
+//! **Command‑line front end** (`cli` feature) behind the `nthash` binary.
+//!
+//! The binary only parses its arguments with [`Options::parse`], opens the
+//! input, and calls [`run`]; everything else lives here so it can be
+//! tested without spawning a process.
+//!
+//! ```text
+//! nthash hashes     [-k K] [-m M]      [--binary] [FILE]
+//! nthash minimizers [-k K] [-w W]      [--binary] [FILE]
+//! nthash sketch     [-k K] [-s SIZE]   [--binary] [FILE]
+//! ```
+//!
+//! Input is FASTA or FASTQ (`-` or no `FILE` reads standard input).  TSV
+//! output has one line per k‑mer (`id  pos  hash…`), per minimizer
+//! (`id  pos  hash  strand`), or per record (`id  hash…`, the bottom‑`SIZE`
+//! MinHash in ascending order).  `--binary` writes the same values as a
+//! k‑mer spectrum file readable with [`SpectrumReader`](crate::io::SpectrumReader);
+//! sketches are stored without positions.
+//!
+//! ```
+//! use nthash_rs::cli::{run, Options};
+//!
+//! let args = ["minimizers", "-k", "5", "-w", "4"].map(String::from);
+//! let opts = Options::parse(args).unwrap().unwrap();
+//! let mut out = Vec::new();
+//! run(&opts, &b">r1\nACGTTGCAAGTC\n"[..], &mut out).unwrap();
+//! let out = String::from_utf8(out).unwrap();
+//! assert!(out.lines().all(|line| line.starts_with("r1\t")));
+//! ```
+
+use std::{
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+use crate::{
+    io::{Reader, SpectrumWriter},
+    minimizer::minimizers,
+    sketch::{MinHash, Sketcher},
+    NtHash, NtHashError,
+};
+
+/// Usage text printed for `--help` and after argument errors.
+pub const USAGE: &str = "\
+usage: nthash <hashes|minimizers|sketch> [options] [FILE]
+
+Hash every record of a FASTA/FASTQ file (default: standard input).
+
+options:
+  -k, --kmer K          k-mer length (default 21)
+  -m, --hashes M        hashes per k-mer, `hashes` only (default 1)
+  -w, --window W        k-mers per window, `minimizers` only (default 10)
+  -s, --sketch-size S   MinHash size, `sketch` only (default 1000)
+      --binary          write a binary k-mer spectrum instead of TSV
+  -h, --help            print this help";
+
+/// Errors of the command‑line front end.
+#[derive(thiserror::Error, Debug)]
+pub enum CliError {
+    /// The arguments could not be parsed.
+    #[error("{0}")]
+    Usage(String),
+    /// Reading the input or writing the output failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The hashers rejected the options.
+    #[error(transparent)]
+    Hash(#[from] NtHashError),
+}
+
+/// What to emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Every valid k‑mer's hashes.
+    Hashes,
+    /// (w, k)‑minimizers.
+    Minimizers,
+    /// A bottom‑s MinHash per record.
+    Sketch,
+}
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Subcommand.
+    pub command: Command,
+    /// k‑mer length.
+    pub k: u16,
+    /// Hashes per k‑mer for [`Command::Hashes`].
+    pub num_hashes: u8,
+    /// Window length for [`Command::Minimizers`].
+    pub window: usize,
+    /// MinHash size for [`Command::Sketch`].
+    pub sketch_size: usize,
+    /// Write a spectrum file instead of TSV.
+    pub binary: bool,
+    /// Input file; `None` for standard input.
+    pub input: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parse the arguments following the program name; `Ok(None)` if help
+    /// was requested.
+    ///
+    /// # Errors
+    ///
+    /// [`CliError::Usage`] for unknown subcommands or options, missing or
+    /// malformed values, and more than one input file.
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Option<Self>, CliError> {
+        let usage = |msg: String| Err(CliError::Usage(msg));
+        let mut args = args.into_iter();
+        let command = match args.next().as_deref() {
+            Some("hashes") => Command::Hashes,
+            Some("minimizers") => Command::Minimizers,
+            Some("sketch") => Command::Sketch,
+            Some("-h" | "--help") => return Ok(None),
+            Some(other) => return usage(format!("unknown command {other:?}")),
+            None => return usage("missing command".into()),
+        };
+        let mut opts = Self {
+            command,
+            k: 21,
+            num_hashes: 1,
+            window: 10,
+            sketch_size: 1000,
+            binary: false,
+            input: None,
+        };
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| match args.next() {
+                Some(v) => Ok(v),
+                None => Err(CliError::Usage(format!("{name} needs a value"))),
+            };
+            match arg.as_str() {
+                "-k" | "--kmer" => opts.k = number(&arg, value(&arg)?)?,
+                "-m" | "--hashes" => opts.num_hashes = number(&arg, value(&arg)?)?,
+                "-w" | "--window" => opts.window = number(&arg, value(&arg)?)?,
+                "-s" | "--sketch-size" => opts.sketch_size = number(&arg, value(&arg)?)?,
+                "--binary" => opts.binary = true,
+                "-h" | "--help" => return Ok(None),
+                flag if flag.starts_with('-') && flag != "-" => {
+                    return usage(format!("unknown option {flag:?}"));
+                }
+                path if opts.input.is_some() => {
+                    return usage(format!("unexpected argument {path:?}"));
+                }
+                "-" => opts.input = None,
+                path => opts.input = Some(path.into()),
+            }
+        }
+        Ok(Some(opts))
+    }
+}
+
+fn number<T: std::str::FromStr>(name: &str, value: String) -> Result<T, CliError> {
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("invalid value {value:?} for {name}")))
+}
+
+/// Hash every record of `input` as `opts` asks and write the result to
+/// `out`.  Records too short to hold a k‑mer produce no output.
+///
+/// # Errors
+///
+/// Propagates read and write errors and [`NtHashError`]s for invalid
+/// `k`, hash count, or window.
+pub fn run<R: BufRead, W: Write>(opts: &Options, input: R, out: W) -> Result<(), CliError> {
+    if opts.k == 0 {
+        return Err(NtHashError::InvalidK.into());
+    }
+    if opts.num_hashes == 0 {
+        return Err(NtHashError::InvalidNumHashes.into());
+    }
+    if opts.window == 0 && opts.command == Command::Minimizers {
+        return Err(NtHashError::InvalidWindow.into());
+    }
+    let (num_hashes, with_pos) = match opts.command {
+        Command::Hashes => (opts.num_hashes, true),
+        Command::Minimizers => (1, true),
+        Command::Sketch => (1, false),
+    };
+    let mut out = if opts.binary {
+        Output::Binary(SpectrumWriter::new(out, opts.k, num_hashes, with_pos)?)
+    } else {
+        Output::Tsv(out)
+    };
+    for rec in Reader::new(input) {
+        let rec = rec?;
+        let id = String::from_utf8_lossy(rec.id());
+        if rec.seq.len() < opts.k as usize {
+            continue;
+        }
+        match opts.command {
+            Command::Hashes => {
+                let mut h = NtHash::new(&rec.seq, opts.k, opts.num_hashes, 0)?;
+                while h.roll() {
+                    out.write(&id, Some(h.pos()), h.hashes(), None)?;
+                }
+            }
+            Command::Minimizers => {
+                for m in minimizers(&rec.seq, opts.k, opts.window)? {
+                    let strand = if m.forward { "+" } else { "-" };
+                    out.write(&id, Some(m.pos), &[m.hash], Some(strand))?;
+                }
+            }
+            Command::Sketch => {
+                let mut sketcher = Sketcher::new(opts.k, MinHash::new(opts.sketch_size))?;
+                sketcher.extend(&rec.seq);
+                let hashes: Vec<u64> = sketcher.sketch().hashes().collect();
+                if matches!(out, Output::Tsv(_)) {
+                    out.write(&id, None, &hashes, None)?;
+                } else {
+                    for h in hashes {
+                        out.write(&id, None, &[h], None)?;
+                    }
+                }
+            }
+        }
+    }
+    match out {
+        Output::Tsv(mut w) => w.flush()?,
+        Output::Binary(w) => {
+            w.finish()?;
+        }
+    }
+    Ok(())
+}
+
+enum Output<W: Write> {
+    Tsv(W),
+    Binary(SpectrumWriter<W>),
+}
+
+impl<W: Write> Output<W> {
+    fn write(
+        &mut self,
+        id: &str,
+        pos: Option<usize>,
+        hashes: &[u64],
+        strand: Option<&str>,
+    ) -> io::Result<()> {
+        match self {
+            Output::Binary(w) => w.write(pos.unwrap_or(0), hashes),
+            Output::Tsv(w) => {
+                write!(w, "{id}")?;
+                if let Some(pos) = pos {
+                    write!(w, "\t{pos}")?;
+                }
+                for h in hashes {
+                    write!(w, "\t{h}")?;
+                }
+                if let Some(strand) = strand {
+                    write!(w, "\t{strand}")?;
+                }
+                writeln!(w)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{io::SpectrumReader, minimizer::Minimizer, NtHashBuilder};
+
+    const FASTQ: &[u8] =
+        b"@r1 first\nACGTTGCAAGTCNACGTACG\n+\nIIIIIIIIIIIIIIIIIIII\n@r2\nACG\n+\nIII\n";
+
+    fn parse(args: &str) -> Options {
+        Options::parse(args.split_whitespace().map(String::from))
+            .unwrap()
+            .unwrap()
+    }
+
+    fn tsv(args: &str) -> Vec<Vec<String>> {
+        let mut out = Vec::new();
+        run(&parse(args), FASTQ, &mut out).unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| l.split('\t').map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn arguments_are_parsed() {
+        let opts = parse("hashes --kmer 7 -m 3 --binary reads.fq");
+        assert_eq!(
+            (opts.command, opts.k, opts.num_hashes),
+            (Command::Hashes, 7, 3)
+        );
+        assert!(opts.binary);
+        assert_eq!(opts.input, Some(PathBuf::from("reads.fq")));
+        assert_eq!(parse("sketch -").input, None);
+        assert_eq!(Options::parse(["--help".to_string()]).unwrap(), None);
+        for bad in [
+            "",
+            "count",
+            "hashes -k",
+            "hashes -k x",
+            "hashes -q",
+            "hashes a b",
+        ] {
+            let err = Options::parse(bad.split_whitespace().map(String::from));
+            assert!(matches!(err, Err(CliError::Usage(_))), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn tsv_output_matches_the_library() {
+        let seq = b"ACGTTGCAAGTCNACGTACG";
+        let want: Vec<Vec<String>> = NtHashBuilder::new(seq)
+            .k(5)
+            .num_hashes(2)
+            .into_iter()
+            .map(|(pos, h)| {
+                vec![
+                    "r1".into(),
+                    pos.start.to_string(),
+                    h[0].to_string(),
+                    h[1].to_string(),
+                ]
+            })
+            .collect();
+        assert_eq!(tsv("hashes -k 5 -m 2"), want);
+
+        let mins = minimizers(seq, 5, 3).unwrap();
+        let rows = tsv("minimizers -k 5 -w 3");
+        assert_eq!(rows.len(), mins.len());
+        let Minimizer { pos, hash, forward } = mins[0];
+        let strand = if forward { "+" } else { "-" };
+        assert_eq!(rows[0], ["r1", &pos.to_string(), &hash.to_string(), strand]);
+
+        let rows = tsv("sketch -k 5 -s 4");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].len(), 5);
+        let hashes: Vec<u64> = rows[0][1..].iter().map(|h| h.parse().unwrap()).collect();
+        assert!(hashes.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn binary_output_is_a_spectrum() {
+        let mut out = Vec::new();
+        run(&parse("hashes -k 5 -m 2 --binary"), FASTQ, &mut out).unwrap();
+        let reader = SpectrumReader::new(&out[..]).unwrap();
+        assert_eq!((reader.header().k, reader.header().num_hashes), (5, 2));
+        assert_eq!(reader.count(), tsv("hashes -k 5 -m 2").len());
+        assert!(matches!(
+            run(&parse("minimizers -w 0"), FASTQ, Vec::new()),
+            Err(CliError::Hash(NtHashError::InvalidWindow))
+        ));
+    }
+}
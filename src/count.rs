@@ -0,0 +1,493 @@
+//! **In‑memory k‑mer counting** keyed by canonical ntHash values.
+//!
+//! [`KmerCounter`] rolls an [`NtHash`] over each sequence and tallies the
+//! canonical hash of every valid k‑mer.  Two distinct k‑mers with the same
+//! 64‑bit hash are counted together; in a [`KmerCounter::verified`]
+//! counter each k‑mer's canonical 2‑bit encoding is stored next to its
+//! hash, so colliding k‑mers keep separate counts (for `k ≤ 64`).
+//!
+//! ```
+//! use nthash_rs::{count::KmerCounter, CanonicalKmer};
+//!
+//! let mut counter = KmerCounter::count_seq(b"ACGTACGTNACGT", 4).unwrap();
+//! counter.add_seq(b"CGTA"); // reverse complement of TACG
+//! assert_eq!(counter.get(b"ACGT"), 3);
+//! assert_eq!(counter.get(b"TACG"), 3);
+//!
+//! let hash = CanonicalKmer::new(b"ACGT").nthash();
+//! assert!(counter.iter().any(|(h, n)| h == hash && n == 3));
+//! ```
+//!
+//! [`ExternalCounter`] counts inputs whose k‑mers do not fit in memory at
+//! once, KMC‑style: each sequence is cut into super‑k‑mers (runs of
+//! consecutive k‑mers sharing a minimizer), which are spilled to one of
+//! several partition files on disk by minimizer hash.  Every occurrence of
+//! a k‑mer lands in the same partition, so each partition is then counted
+//! on its own with a [`KmerCounter`].
+
+use std::{
+    collections::{hash_map, HashMap},
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{kmer::CanonicalKmer, minimizer::superkmers, NtHash, NtHashError, Result};
+
+/// Counts of the canonical k‑mers of one or more sequences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmerCounter {
+    k: u16,
+    verify: bool,
+    counts: HashMap<Key, u64>,
+}
+
+/// Canonical hash and canonical 2‑bit k‑mer (`0` without verification).
+type Key = (u64, u128);
+
+impl KmerCounter {
+    /// An empty counter for `k`‑mers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k == 0`.
+    pub fn new(k: u16) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        Ok(Self {
+            k,
+            verify: false,
+            counts: HashMap::new(),
+        })
+    }
+
+    /// Count the `k`‑mers of a single sequence.
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new).
+    pub fn count_seq(seq: &[u8], k: u16) -> Result<Self> {
+        let mut counter = Self::new(k)?;
+        counter.add_seq(seq);
+        Ok(counter)
+    }
+
+    /// An empty counter that keeps k‑mers whose hashes collide apart by
+    /// also keying on their canonical 2‑bit encoding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k == 0` and
+    /// [`NtHashError::KTooLarge`] if `k > 64`.
+    pub fn verified(k: u16) -> Result<Self> {
+        if k > 64 {
+            return Err(NtHashError::KTooLarge { k, max: 64 });
+        }
+        let mut counter = Self::new(k)?;
+        counter.verify = true;
+        Ok(counter)
+    }
+
+    /// The k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Add every valid k‑mer of `seq`; returns how many were counted.
+    /// Sequences shorter than `k` add nothing.
+    pub fn add_seq(&mut self, seq: &[u8]) -> usize {
+        let Ok(h) = NtHash::new(seq, self.k, 1, 0) else {
+            return 0;
+        };
+        let mut h = if self.verify {
+            h.with_packed_kmers().expect("k <= 64 checked when enabled")
+        } else {
+            h
+        };
+        let mut n = 0;
+        while h.roll() {
+            let packed = h.packed_kmer().unwrap_or(0);
+            *self.counts.entry((h.hashes()[0], packed)).or_insert(0) += 1;
+            n += 1;
+        }
+        n
+    }
+
+    /// Count of `kmer`, `0` if it was never seen or has a length other
+    /// than `k`.  Without verification this includes every k‑mer sharing
+    /// its hash.
+    pub fn get(&self, kmer: &[u8]) -> u64 {
+        if kmer.len() != self.k as usize {
+            return 0;
+        }
+        let packed = if self.verify {
+            let Some(packed) = pack_canonical(kmer) else {
+                return 0;
+            };
+            packed
+        } else {
+            0
+        };
+        let hash = CanonicalKmer::new(kmer).nthash();
+        self.counts.get(&(hash, packed)).copied().unwrap_or(0)
+    }
+
+    /// Add the counts of `other` to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two counters differ in `k` or verification.
+    pub fn merge(&mut self, other: KmerCounter) {
+        assert!(
+            self.k == other.k && self.verify == other.verify,
+            "counters built with different k or verification"
+        );
+        for (key, n) in other.counts {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// Number of distinct k‑mers (distinct hashes without verification).
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns `true` if nothing has been counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Total number of k‑mers counted, with multiplicity.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// `(canonical hash, count)` of every distinct k‑mer, in arbitrary
+    /// order.  With verification, colliding k‑mers appear as separate
+    /// entries with the same hash.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.counts.iter().map(|(&(hash, _), &n)| (hash, n))
+    }
+}
+
+impl IntoIterator for KmerCounter {
+    type Item = (u64, u64);
+    type IntoIter = std::iter::Map<hash_map::IntoIter<Key, u64>, fn((Key, u64)) -> (u64, u64)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter().map(|((hash, _), n)| (hash, n))
+    }
+}
+
+/// Disk‑backed k‑mer counter partitioning super‑k‑mers by minimizer.
+///
+/// A k‑mer's minimizer is the smallest canonical hash of its `m`‑mers, so
+/// a k‑mer and its reverse complement share a partition.  Partition files
+/// are created in the given directory and removed once counted (or when
+/// the counter is dropped).
+///
+/// ```
+/// use nthash_rs::count::{ExternalCounter, KmerCounter};
+///
+/// let seqs: [&[u8]; 2] = [b"ACGTTGCAAGCTTAGCGATCGATCGG", b"GCTTAGCGATNNCGTTGCAAGC"];
+/// let mut ext = ExternalCounter::new(std::env::temp_dir(), 9, 4, 8).unwrap();
+/// let mut mem = KmerCounter::new(9).unwrap();
+/// for seq in seqs {
+///     ext.add_seq(seq).unwrap();
+///     mem.add_seq(seq);
+/// }
+///
+/// let mut total = 0;
+/// ext.count_partitions(|part| {
+///     for (hash, n) in part.iter() {
+///         assert!(mem.iter().any(|e| e == (hash, n)));
+///         total += 1;
+///     }
+/// })
+/// .unwrap();
+/// assert_eq!(total, mem.len());
+/// ```
+#[derive(Debug)]
+pub struct ExternalCounter {
+    k: u16,
+    m: u16,
+    verify: bool,
+    paths: Vec<PathBuf>,
+    writers: Vec<BufWriter<File>>,
+}
+
+/// Distinguishes the partition files of counters alive at the same time.
+static NEXT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl ExternalCounter {
+    /// Count `k`‑mers with `m`‑mer minimizers through `num_partitions`
+    /// files created in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidInput`] wrapping
+    /// [`NtHashError::InvalidK`] unless `0 < m ≤ k`, or
+    /// [`NtHashError::InvalidCapacity`] for zero partitions; otherwise any
+    /// error creating the files.
+    pub fn new(dir: impl AsRef<Path>, k: u16, m: u16, num_partitions: usize) -> io::Result<Self> {
+        let invalid = |e: NtHashError| io::Error::new(io::ErrorKind::InvalidInput, e);
+        if m == 0 || m > k {
+            return Err(invalid(NtHashError::InvalidK));
+        }
+        if num_partitions == 0 {
+            return Err(invalid(NtHashError::InvalidCapacity));
+        }
+        let id = NEXT_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let mut counter = Self {
+            k,
+            m,
+            verify: false,
+            paths: Vec::with_capacity(num_partitions),
+            writers: Vec::with_capacity(num_partitions),
+        };
+        for i in 0..num_partitions {
+            let name = format!("nthash-count-{}-{id}-{i}.part", std::process::id());
+            let path = dir.as_ref().join(name);
+            let file = File::create(&path)?;
+            counter.paths.push(path);
+            counter.writers.push(BufWriter::new(file));
+        }
+        Ok(counter)
+    }
+
+    /// Count partitions with [`KmerCounter::verified`] counters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidInput`] wrapping
+    /// [`NtHashError::KTooLarge`] if `k > 64`.
+    pub fn with_verification(mut self) -> io::Result<Self> {
+        if self.k > 64 {
+            let e = NtHashError::KTooLarge { k: self.k, max: 64 };
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, e));
+        }
+        self.verify = true;
+        Ok(self)
+    }
+
+    /// The k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Spill the super‑k‑mers of `seq` to their partitions; returns how
+    /// many were written.
+    ///
+    /// # Errors
+    ///
+    /// Any error writing the partition files.
+    pub fn add_seq(&mut self, seq: &[u8]) -> io::Result<usize> {
+        let w = (self.k - self.m) as usize + 1;
+        let Ok(spans) = superkmers(seq, self.m, w) else {
+            return Ok(0); // shorter than m
+        };
+        let parts = self.writers.len() as u64;
+        let mut n = 0;
+        for sk in spans {
+            let out = &mut self.writers[(sk.minimizer % parts) as usize];
+            let bases = &seq[sk.range()];
+            out.write_all(&(bases.len() as u32).to_le_bytes())?;
+            out.write_all(bases)?;
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    /// Count each partition in turn, handing its [`KmerCounter`] to `f`
+    /// and deleting its file.  Partitions hold disjoint sets of k‑mers,
+    /// so only one partition's counts are in memory at a time.
+    ///
+    /// # Errors
+    ///
+    /// Any error reading or removing the partition files.
+    pub fn count_partitions<F: FnMut(KmerCounter)>(mut self, mut f: F) -> io::Result<()> {
+        let template = if self.verify {
+            KmerCounter::verified(self.k).expect("0 < k <= 64 checked when enabled")
+        } else {
+            KmerCounter::new(self.k).expect("k > 0 checked in new")
+        };
+        let writers = std::mem::take(&mut self.writers);
+        for (writer, path) in writers.into_iter().zip(&self.paths) {
+            drop(
+                writer
+                    .into_inner()
+                    .map_err(io::IntoInnerError::into_error)?,
+            );
+            let mut counter = template.clone();
+            let mut input = BufReader::new(File::open(path)?);
+            let mut len = [0u8; 4];
+            let mut bases = Vec::new();
+            loop {
+                match input.read_exact(&mut len) {
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                    r => r?,
+                }
+                bases.resize(u32::from_le_bytes(len) as usize, 0);
+                input.read_exact(&mut bases)?;
+                counter.add_seq(&bases);
+            }
+            fs::remove_file(path)?;
+            f(counter);
+        }
+        self.paths.clear();
+        Ok(())
+    }
+
+    /// Count every partition and merge the results.
+    ///
+    /// # Errors
+    ///
+    /// As [`count_partitions`](Self::count_partitions).
+    pub fn finish(self) -> io::Result<KmerCounter> {
+        let mut total: Option<KmerCounter> = None;
+        self.count_partitions(|part| match &mut total {
+            Some(t) => t.merge(part),
+            None => total = Some(part),
+        })?;
+        Ok(total.expect("at least one partition"))
+    }
+}
+
+impl Drop for ExternalCounter {
+    fn drop(&mut self) {
+        self.writers.clear();
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// The smaller 2‑bit encoding (`A=0, C=1, G=2, T=3`, first base most
+/// significant) of `kmer` and its reverse complement, in either case, as
+/// produced by [`NtHash::packed_kmer`]; `None` for a non‑ACGT base.
+fn pack_canonical(kmer: &[u8]) -> Option<u128> {
+    let (mut fwd, mut rev) = (0u128, 0u128);
+    for (i, &b) in kmer.iter().enumerate() {
+        let code = match b.to_ascii_uppercase() {
+            b'A' => 0,
+            b'C' => 1,
+            b'G' => 2,
+            b'T' => 3,
+            _ => return None,
+        };
+        fwd = fwd << 2 | code;
+        rev |= (3 - code) << (2 * i);
+    }
+    Some(fwd.min(rev))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_match_a_naive_canonical_tally() {
+        let seqs: [&[u8]; 3] = [b"ACGTTGCANNACGTTGCAAC", b"GTTGCAACGT", b"AC"];
+        for verify in [false, true] {
+            let mut counter = if verify {
+                KmerCounter::verified(5).unwrap()
+            } else {
+                KmerCounter::new(5).unwrap()
+            };
+            let mut naive: HashMap<CanonicalKmer, u64> = HashMap::new();
+            let mut parts = Vec::new();
+            for seq in seqs {
+                let mut part = counter.clone();
+                let n = part.add_seq(seq);
+                let windows: Vec<&[u8]> = seq
+                    .windows(5)
+                    .filter(|w| w.iter().all(|&c| c != b'N'))
+                    .collect();
+                assert_eq!(n, windows.len());
+                for w in windows {
+                    *naive.entry(CanonicalKmer::new(w)).or_insert(0) += 1;
+                }
+                parts.push(part);
+            }
+            for part in parts {
+                counter.merge(part);
+            }
+            assert_eq!(counter.len(), naive.len(), "verify={verify}");
+            assert_eq!(counter.total(), naive.values().sum::<u64>());
+            for (kmer, &n) in &naive {
+                assert_eq!(counter.get(kmer.as_bytes()), n, "verify={verify}");
+                let mut soft = kmer.as_bytes().to_vec();
+                soft.iter_mut()
+                    .step_by(2)
+                    .for_each(u8::make_ascii_lowercase);
+                assert_eq!(counter.get(&soft), n, "verify={verify}");
+            }
+            assert_eq!(counter.get(b"ACGT"), 0);
+            assert_eq!(counter.get(b"NCGTT"), 0);
+
+            let mut pairs: Vec<(u64, u64)> = counter.clone().into_iter().collect();
+            let mut expected: Vec<(u64, u64)> =
+                naive.iter().map(|(kmer, &n)| (kmer.nthash(), n)).collect();
+            pairs.sort();
+            expected.sort();
+            assert_eq!(pairs, expected);
+        }
+        for mut counter in [
+            KmerCounter::new(4).unwrap(),
+            KmerCounter::verified(4).unwrap(),
+        ] {
+            counter.add_seq(b"ACGG");
+            assert_eq!((counter.get(b"aCGG"), counter.get(b"CCGt")), (1, 1));
+        }
+        assert_eq!(KmerCounter::new(0).err(), Some(NtHashError::InvalidK));
+        assert_eq!(KmerCounter::verified(0).err(), Some(NtHashError::InvalidK));
+        assert_eq!(
+            KmerCounter::verified(65).err(),
+            Some(NtHashError::KTooLarge { k: 65, max: 64 })
+        );
+    }
+
+    #[test]
+    fn external_counts_match_in_memory_counts() {
+        let seqs: [&[u8]; 4] = [
+            b"NATCGTACGATNGCATGCATGCTGACGTTNNAGCTAGCATCGGATTACAGATTACA",
+            b"TGTAATCTGTAATCCGATGCTAGCTNNAACGTCAGCATGCATGC",
+            b"ACGTACGTAC",
+            b"ACG",
+        ];
+        let dir = std::env::temp_dir();
+        for (m, verify) in [(1u16, false), (5, true), (11, false)] {
+            let mut ext = ExternalCounter::new(&dir, 11, m, 3).unwrap();
+            let mut mem = KmerCounter::new(11).unwrap();
+            if verify {
+                ext = ext.with_verification().unwrap();
+                mem = KmerCounter::verified(11).unwrap();
+            }
+            for seq in seqs {
+                ext.add_seq(seq).unwrap();
+                mem.add_seq(seq);
+            }
+            let paths = ext.paths.clone();
+            assert!(paths.iter().all(|p| p.exists()));
+            assert_eq!(ext.finish().unwrap(), mem, "m={m}");
+            assert!(paths.iter().all(|p| !p.exists()));
+        }
+
+        let dropped = ExternalCounter::new(&dir, 11, 5, 2).unwrap();
+        let paths = dropped.paths.clone();
+        drop(dropped);
+        assert!(paths.iter().all(|p| !p.exists()));
+
+        let kind = |r: io::Result<ExternalCounter>| r.unwrap_err().kind();
+        assert_eq!(
+            kind(ExternalCounter::new(&dir, 11, 12, 2)),
+            io::ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(ExternalCounter::new(&dir, 11, 5, 0)),
+            io::ErrorKind::InvalidInput
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! **Redundant contig detection** for assemblies.
+//!
+//! Each contig is summarised by its set of canonical (w, k)‑minimizers.  A
+//! contig whose minimizers are (almost) all found in another, larger contig
+//! is reported as contained in it — the usual signature of haplotigs,
+//! duplicated fragments, or reverse‑complemented copies left behind by an
+//! assembler.
+//!
+//! ```
+//! use nthash_rs::dedup::{find_containments, DedupParams};
+//!
+//! let big = b"ATCGGCTAGCTAGGCTAACGTTGACGATCGATGCATCGACTAGCATCGAGCTAGCGCATT";
+//! let copy = &big[10..50];
+//! let other = b"TTTTGGGGCCCCAAAATTTTGGGGCCCCAAAA";
+//! let found = find_containments([&big[..], copy, other], &DedupParams::new(11, 5)).unwrap();
+//! assert_eq!((found[0].contained, found[0].container), (1, 0));
+//! assert_eq!(found.len(), 1);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{minimizer::minimizers, Result};
+
+/// Settings for [`find_containments`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DedupParams {
+    k: u16,
+    w: usize,
+    min_containment: f64,
+}
+
+impl DedupParams {
+    /// Compare contigs by their (w, k)‑minimizers, reporting containment of
+    /// at least 0.9 by default.
+    pub fn new(k: u16, w: usize) -> Self {
+        Self {
+            k,
+            w,
+            min_containment: 0.9,
+        }
+    }
+
+    /// Fraction of a contig's minimizers that must occur in the container.
+    pub fn min_containment(mut self, fraction: f64) -> Self {
+        self.min_containment = fraction;
+        self
+    }
+}
+
+/// Contig `contained` is (mostly) covered by contig `container`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Containment {
+    /// Index of the redundant contig.
+    pub contained: usize,
+    /// Index of the contig covering it.
+    pub container: usize,
+    /// Fraction of `contained`'s distinct minimizers found in `container`.
+    pub fraction: f64,
+}
+
+/// Every containment relationship between `contigs` above the threshold,
+/// ordered by `contained` then decreasing fraction.
+///
+/// A contig is only reported inside a container with at least as many
+/// distinct minimizers; identical contigs are reported once, the later one
+/// contained in the earlier.  Contigs shorter than `k` have no minimizers
+/// and are never reported.
+///
+/// # Errors
+///
+/// Propagates [`minimizers`] errors for invalid `k` or `w`.
+pub fn find_containments<I, S>(contigs: I, params: &DedupParams) -> Result<Vec<Containment>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<[u8]>,
+{
+    let mut sets: Vec<Vec<u64>> = Vec::new();
+    for contig in contigs {
+        let seq = contig.as_ref();
+        let mut hashes: Vec<u64> = if seq.len() < params.k as usize {
+            Vec::new()
+        } else {
+            minimizers(seq, params.k, params.w)?
+                .into_iter()
+                .map(|m| m.hash)
+                .collect()
+        };
+        hashes.sort_unstable();
+        hashes.dedup();
+        sets.push(hashes);
+    }
+
+    let mut postings: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (id, set) in sets.iter().enumerate() {
+        for &h in set {
+            postings.entry(h).or_default().push(id);
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut shared: HashMap<usize, usize> = HashMap::new();
+    for (a, set) in sets.iter().enumerate() {
+        if set.is_empty() {
+            continue;
+        }
+        shared.clear();
+        for h in set {
+            for &b in &postings[h] {
+                if b != a {
+                    *shared.entry(b).or_default() += 1;
+                }
+            }
+        }
+        let start = out.len();
+        for (&b, &n) in &shared {
+            let fraction = n as f64 / set.len() as f64;
+            let larger = sets[b].len() > set.len() || (sets[b].len() == set.len() && b < a);
+            if larger && fraction >= params.min_containment {
+                out.push(Containment {
+                    contained: a,
+                    container: b,
+                    fraction,
+                });
+            }
+        }
+        out[start..].sort_by(|x, y| {
+            y.fraction
+                .total_cmp(&x.fraction)
+                .then(x.container.cmp(&y.container))
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_dna(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                b"ACGT"[(state >> 60) as usize & 3]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn detects_duplicates_and_reverse_complements() {
+        let a = random_dna(5000, 1);
+        let b = random_dna(3000, 2);
+        let part_rc: Vec<u8> = a[1000..2500]
+            .iter()
+            .rev()
+            .map(|&c| match c {
+                b'A' => b'T',
+                b'C' => b'G',
+                b'G' => b'C',
+                _ => b'A',
+            })
+            .collect();
+        let contigs = [
+            a.clone(),
+            b.clone(),
+            part_rc,
+            b.clone(),
+            random_dna(2000, 3),
+        ];
+        let found = find_containments(&contigs, &DedupParams::new(21, 10)).unwrap();
+        let pairs: Vec<(usize, usize)> = found.iter().map(|c| (c.contained, c.container)).collect();
+        assert_eq!(pairs, [(2, 0), (3, 1)]);
+        assert!(found.iter().all(|c| c.fraction > 0.99));
+
+        // half overlap passes only with a relaxed threshold
+        let mut half = a[4000..].to_vec();
+        half.extend(random_dna(1000, 4));
+        let strict = find_containments([&a, &half], &DedupParams::new(21, 10)).unwrap();
+        assert!(strict.is_empty());
+        let relaxed =
+            find_containments([&a, &half], &DedupParams::new(21, 10).min_containment(0.4)).unwrap();
+        assert_eq!((relaxed[0].contained, relaxed[0].container), (1, 0));
+    }
+}
@@ -0,0 +1,259 @@
+//! **Compression dictionaries** from frequent minimizer contexts.
+//!
+//! Repeated content in a corpus of sequences (transposons, rRNA operons,
+//! shared plasmids, adapter read‑through) shows up as the same minimizer
+//! surrounded by the same bases over and over.  [`DictionaryBuilder`]
+//! counts every minimizer *context* — the minimizer k‑mer plus `flank`
+//! bases on each side, taken in canonical orientation — across a corpus
+//! and keeps the most frequent ones.  The resulting [`Dictionary`] can be
+//! written as FASTA (with minimizer hash, context hash, and count in each
+//! header) for homology‑based deduplication, or concatenated into raw
+//! dictionary content for general‑purpose compressors such as zstd.
+//!
+//! ```
+//! use nthash_rs::dictionary::DictionaryBuilder;
+//!
+//! let repeat = b"GATTACAGCTAGCTAGGCATCGATCGATTGCA";
+//! let mut b = DictionaryBuilder::new(11, 5).flank(4);
+//! for filler in [&b"CCGTA"[..], b"TTAGC", b"AGGCT"] {
+//!     let mut read = filler.to_vec();
+//!     read.extend_from_slice(repeat);
+//!     b.add(&read).unwrap();
+//! }
+//! let dict = b.build(4, 3);
+//! assert!(!dict.entries().is_empty());
+//! assert!(dict.entries().iter().all(|e| e.count >= 3 && e.seq.len() == 19));
+//! ```
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+};
+
+use crate::{
+    io::{Record, Writer},
+    kmer::{is_acgt, CanonicalKmer},
+    minimizer::minimizers,
+    Result,
+};
+
+/// One frequent minimizer context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictEntry {
+    /// Canonical hash of the minimizer k‑mer.
+    pub minimizer: u64,
+    /// Canonical hash of the whole context.
+    pub hash: u64,
+    /// Occurrences in the corpus (either strand).
+    pub count: u64,
+    /// Context bases in canonical orientation.
+    pub seq: Vec<u8>,
+}
+
+/// Counts minimizer contexts over a corpus.
+///
+/// Every distinct context is kept in memory until [`build`](Self::build),
+/// so very large corpora should be subsampled first.
+#[derive(Debug, Clone)]
+pub struct DictionaryBuilder {
+    k: u16,
+    w: usize,
+    flank: usize,
+    contexts: HashMap<u64, DictEntry>,
+}
+
+impl DictionaryBuilder {
+    /// Contexts of (w, k)‑minimizers with 8 flanking bases by default.
+    pub fn new(k: u16, w: usize) -> Self {
+        Self {
+            k,
+            w,
+            flank: 8,
+            contexts: HashMap::new(),
+        }
+    }
+
+    /// Bases taken on each side of the minimizer.
+    pub fn flank(mut self, flank: usize) -> Self {
+        self.flank = flank;
+        self
+    }
+
+    /// Count the contexts of every minimizer of `seq`.  Minimizers too
+    /// close to either end, and contexts with non‑ACGT bases, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`minimizers`] errors; sequences shorter than `k` are
+    /// ignored.
+    pub fn add(&mut self, seq: &[u8]) -> Result<()> {
+        if seq.len() < self.k as usize {
+            return Ok(());
+        }
+        let span = self.k as usize + 2 * self.flank;
+        for m in minimizers(seq, self.k, self.w)? {
+            let Some(start) = m.pos.checked_sub(self.flank) else {
+                continue;
+            };
+            let Some(context) = seq.get(start..start + span) else {
+                continue;
+            };
+            if !context.iter().all(|&c| is_acgt(c)) {
+                continue;
+            }
+            let key = CanonicalKmer::new(context);
+            self.contexts
+                .entry(key.nthash())
+                .or_insert_with(|| DictEntry {
+                    minimizer: m.hash,
+                    hash: key.nthash(),
+                    count: 0,
+                    seq: key.canonical_bases().collect(),
+                })
+                .count += 1;
+        }
+        Ok(())
+    }
+
+    /// Number of distinct contexts counted so far.
+    pub fn len(&self) -> usize {
+        self.contexts.len()
+    }
+
+    /// Returns `true` if no context has been counted.
+    pub fn is_empty(&self) -> bool {
+        self.contexts.is_empty()
+    }
+
+    /// Keep at most `max_entries` contexts seen at least `min_count`
+    /// times, most frequent first (ties by hash).
+    pub fn build(self, max_entries: usize, min_count: u64) -> Dictionary {
+        let mut entries: Vec<DictEntry> = self
+            .contexts
+            .into_values()
+            .filter(|e| e.count >= min_count)
+            .collect();
+        entries.sort_unstable_by(|a, b| b.count.cmp(&a.count).then(a.hash.cmp(&b.hash)));
+        entries.truncate(max_entries);
+        Dictionary {
+            k: self.k,
+            w: self.w,
+            entries,
+        }
+    }
+}
+
+/// The most frequent minimizer contexts of a corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    k: u16,
+    w: usize,
+    entries: Vec<DictEntry>,
+}
+
+impl Dictionary {
+    /// Minimizer k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Minimizer window.
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// Entries, most frequent first.
+    pub fn entries(&self) -> &[DictEntry] {
+        &self.entries
+    }
+
+    /// Entry sequences concatenated from least to most frequent, the
+    /// order zstd‑style raw dictionaries expect (most useful content last).
+    pub fn content(&self) -> Vec<u8> {
+        self.entries
+            .iter()
+            .rev()
+            .flat_map(|e| e.seq.iter().copied())
+            .collect()
+    }
+
+    /// Write the entries as FASTA, one record per context, headed
+    /// `ctx<i> minimizer=<hex> hash=<hex> count=<n>`.
+    pub fn write_fasta<W: Write>(&self, out: W) -> io::Result<W> {
+        let mut w = Writer::new(out);
+        for (i, e) in self.entries.iter().enumerate() {
+            let head = format!(
+                "ctx{i} minimizer={:016x} hash={:016x} count={}",
+                e.minimizer, e.hash, e.count
+            );
+            w.write(&Record {
+                head: head.into_bytes(),
+                seq: e.seq.clone(),
+                qual: None,
+            })?;
+        }
+        w.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_dna(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                b"ACGT"[(state >> 60) as usize & 3]
+            })
+            .collect()
+    }
+
+    fn revcomp(seq: &[u8]) -> Vec<u8> {
+        seq.iter()
+            .rev()
+            .map(|&c| match c {
+                b'A' => b'T',
+                b'C' => b'G',
+                b'G' => b'C',
+                _ => b'A',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn repeated_element_dominates_on_both_strands() {
+        let element = random_dna(300, 11);
+        let mut b = DictionaryBuilder::new(15, 10).flank(6);
+        for i in 0..6 {
+            let mut read = random_dna(500, 100 + i);
+            let copy = if i % 2 == 0 {
+                element.clone()
+            } else {
+                revcomp(&element)
+            };
+            read.splice(200..200, copy);
+            b.add(&read).unwrap();
+        }
+        b.add(b"ACGT").unwrap();
+        let dict = b.build(usize::MAX, 6);
+        assert!(!dict.entries().is_empty());
+        for e in dict.entries() {
+            assert_eq!(e.count, 6);
+            assert_eq!(e.seq.len(), 27);
+            let found = element.windows(27).any(|w| w == e.seq)
+                || revcomp(&element).windows(27).any(|w| w == e.seq);
+            assert!(found);
+        }
+        assert_eq!(dict.content().len(), 27 * dict.entries().len());
+
+        let fasta = dict.write_fasta(Vec::new()).unwrap();
+        let recs: Vec<Record> = crate::io::Reader::new(&fasta[..])
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(recs.len(), dict.entries().len());
+        assert!(recs[0].head.ends_with(b"count=6"));
+    }
+}
@@ -0,0 +1,488 @@
+//! **C ABI** mirroring the original ntHash C++ classes.
+//!
+//! Each hasher is exposed as an opaque handle with `*_new` / `*_roll` /
+//! `*_hashes` / `*_free` functions, so C and C++ pipelines written against
+//! `nthash::NtHash`, `nthash::BlindNtHash` and `nthash::SeedNtHash` can link
+//! against this crate instead.  The matching header lives in
+//! `include/nthash_rs.h` and is regenerated with
+//!
+//! ```text
+//! cbindgen --config cbindgen.toml --output include/nthash_rs.h
+//! ```
+//!
+//! The crate builds as an rlib only; produce the C library with
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib     # or staticlib
+//! ```
+//!
+//! As in the C++ API, `NtHash` and `SeedNtHash` **borrow** the sequence: the
+//! caller must keep it alive and unmodified until the handle is freed.
+//! Constructors return `NULL` on invalid arguments instead of throwing.
+
+use std::collections::VecDeque;
+use std::ffi::{c_char, CStr};
+use std::ptr;
+use std::slice;
+
+use crate::{records::PosHash, BlindNtHash, NtHash, SeedNtHash, SpacedSeed};
+
+/// View `(seq, len)` as a slice, rejecting `NULL`.
+///
+/// # Safety
+///
+/// `seq` must be valid for reads of `len` bytes for the chosen lifetime.
+unsafe fn seq_slice<'a>(seq: *const c_char, len: usize) -> Option<&'a [u8]> {
+    if seq.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(seq.cast(), len))
+    }
+}
+
+/// Move `value` to the heap and hand ownership to the caller.
+fn into_handle<T>(value: Option<T>) -> *mut T {
+    value.map_or(ptr::null_mut(), |v| Box::into_raw(Box::new(v)))
+}
+
+// ──────────────────────────────────────────────────────────────
+// NtHash
+// ──────────────────────────────────────────────────────────────
+
+/// Create an `NtHash` over `seq[0..seq_len]` starting at `pos`.
+///
+/// Returns `NULL` if `seq` is `NULL`, `k == 0`, `seq_len < k`, or `pos` is out
+/// of range.
+///
+/// # Safety
+///
+/// `seq` must point to `seq_len` readable bytes that outlive the handle.
+#[no_mangle]
+pub unsafe extern "C" fn nthash_new(
+    seq: *const c_char,
+    seq_len: usize,
+    num_hashes: u8,
+    k: u16,
+    pos: usize,
+) -> *mut NtHash<'static> {
+    let hasher = seq_slice(seq, seq_len).and_then(|s| NtHash::new(s, k, num_hashes, pos).ok());
+    into_handle(hasher)
+}
+
+/// Advance by one base, skipping k‑mers with non‑ACGT bases.
+///
+/// Returns `false` once the end of the sequence is reached.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_roll(h: *mut NtHash<'static>) -> bool {
+    (*h).roll()
+}
+
+/// Step back by one base, skipping k‑mers with non‑ACGT bases.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_roll_back(h: *mut NtHash<'static>) -> bool {
+    (*h).roll_back()
+}
+
+/// Pointer to the current [`nthash_get_hash_num`] hash values.
+///
+/// The pointer is invalidated by the next roll or by [`nthash_free`].
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_hashes(h: *const NtHash<'static>) -> *const u64 {
+    (*h).hashes().as_ptr()
+}
+
+/// Number of hash values per k‑mer.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_get_hash_num(h: *const NtHash<'static>) -> usize {
+    (*h).hashes().len()
+}
+
+/// Start position of the current k‑mer.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_get_pos(h: *const NtHash<'static>) -> usize {
+    (*h).pos()
+}
+
+/// Forward‑strand hash of the current k‑mer.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_get_forward_hash(h: *const NtHash<'static>) -> u64 {
+    (*h).forward_hash()
+}
+
+/// Reverse‑complement hash of the current k‑mer.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn nthash_get_reverse_hash(h: *const NtHash<'static>) -> u64 {
+    (*h).reverse_hash()
+}
+
+/// Release a handle.  `NULL` is ignored.
+///
+/// # Safety
+///
+/// `h` must be `NULL` or a handle returned by [`nthash_new`] that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn nthash_free(h: *mut NtHash<'static>) {
+    if !h.is_null() {
+        drop(Box::from_raw(h));
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// BlindNtHash
+// ──────────────────────────────────────────────────────────────
+
+/// Create a `BlindNtHash` whose first window is `seq[pos..pos + k]`.
+///
+/// The window is copied, so `seq` need not outlive the handle.  Returns
+/// `NULL` if `seq` is `NULL`, `k == 0`, or the window does not fit.
+///
+/// # Safety
+///
+/// `seq` must point to `seq_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_new(
+    seq: *const c_char,
+    seq_len: usize,
+    num_hashes: u8,
+    k: u16,
+    pos: isize,
+) -> *mut BlindNtHash<VecDeque<u8>> {
+    let hasher = seq_slice(seq, seq_len)
+        .filter(|s| s.len() >= k as usize)
+        .and_then(|s| BlindNtHash::new(s, k, num_hashes, pos).ok());
+    into_handle(hasher)
+}
+
+/// Slide the window forward, appending `char_in`.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`blind_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_roll(
+    h: *mut BlindNtHash<VecDeque<u8>>,
+    char_in: c_char,
+) -> bool {
+    (*h).roll(char_in as u8)
+}
+
+/// Slide the window backward, prepending `char_in`.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`blind_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_roll_back(
+    h: *mut BlindNtHash<VecDeque<u8>>,
+    char_in: c_char,
+) -> bool {
+    (*h).roll_back(char_in as u8)
+}
+
+/// Pointer to the current [`blind_nthash_get_hash_num`] hash values.
+///
+/// The pointer is invalidated by the next roll or by [`blind_nthash_free`].
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`blind_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_hashes(h: *const BlindNtHash<VecDeque<u8>>) -> *const u64 {
+    (*h).hashes().as_ptr()
+}
+
+/// Number of hash values per k‑mer.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`blind_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_get_hash_num(h: *const BlindNtHash<VecDeque<u8>>) -> usize {
+    (*h).hashes().len()
+}
+
+/// Start position of the current window.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`blind_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_get_pos(h: *const BlindNtHash<VecDeque<u8>>) -> isize {
+    (*h).pos()
+}
+
+/// Release a handle.  `NULL` is ignored.
+///
+/// # Safety
+///
+/// `h` must be `NULL` or a handle returned by [`blind_nthash_new`] that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn blind_nthash_free(h: *mut BlindNtHash<VecDeque<u8>>) {
+    if !h.is_null() {
+        drop(Box::from_raw(h));
+    }
+}
+
+// ──────────────────────────────────────────────────────────────
+// SeedNtHash
+// ──────────────────────────────────────────────────────────────
+
+/// Create a `SeedNtHash` from `num_seeds` NUL‑terminated '0'/'1' masks of
+/// length `k`.
+///
+/// Hash values are laid out seed by seed, `num_hashes_per_seed` each.
+/// Returns `NULL` on a `NULL` pointer, an invalid mask, or a sequence that is
+/// too short.
+///
+/// # Safety
+///
+/// `seq` must point to `seq_len` readable bytes that outlive the handle, and
+/// `seeds` must point to `num_seeds` valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn seed_nthash_new(
+    seq: *const c_char,
+    seq_len: usize,
+    seeds: *const *const c_char,
+    num_seeds: usize,
+    num_hashes_per_seed: u8,
+    k: u16,
+    pos: usize,
+) -> *mut SeedNtHash<'static> {
+    if seeds.is_null() {
+        return ptr::null_mut();
+    }
+    let mut masks = Vec::with_capacity(num_seeds);
+    for &mask in slice::from_raw_parts(seeds, num_seeds) {
+        if mask.is_null() {
+            return ptr::null_mut();
+        }
+        match CStr::from_ptr(mask).to_str() {
+            Ok(m) => masks.push(m.to_owned()),
+            Err(_) => return ptr::null_mut(),
+        }
+    }
+    let hasher = seq_slice(seq, seq_len)
+        .and_then(|s| SeedNtHash::new(s, &masks, num_hashes_per_seed as usize, k, pos).ok());
+    into_handle(hasher)
+}
+
+/// Advance to the next k‑mer whose care positions are all ACGT.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`seed_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn seed_nthash_roll(h: *mut SeedNtHash<'static>) -> bool {
+    (*h).roll()
+}
+
+/// Pointer to the current [`seed_nthash_get_hash_num`] hash values.
+///
+/// The pointer is invalidated by the next roll or by [`seed_nthash_free`].
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`seed_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn seed_nthash_hashes(h: *const SeedNtHash<'static>) -> *const u64 {
+    (*h).hashes().as_ptr()
+}
+
+/// Total number of hash values (seeds × hashes per seed).
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`seed_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn seed_nthash_get_hash_num(h: *const SeedNtHash<'static>) -> usize {
+    (*h).hashes().len()
+}
+
+/// Start position of the current k‑mer.
+///
+/// # Safety
+///
+/// `h` must be a live handle returned by [`seed_nthash_new`].
+#[no_mangle]
+pub unsafe extern "C" fn seed_nthash_get_pos(h: *const SeedNtHash<'static>) -> usize {
+    (*h).pos()
+}
+
+/// Release a handle.  `NULL` is ignored.
+///
+/// # Safety
+///
+/// `h` must be `NULL` or a handle returned by [`seed_nthash_new`] that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn seed_nthash_free(h: *mut SeedNtHash<'static>) {
+    if !h.is_null() {
+        drop(Box::from_raw(h));
+    }
+}
+
+/// Returns `true` if `mask` is a valid spaced‑seed mask ('0'/'1', non‑empty).
+///
+/// # Safety
+///
+/// `mask` must be `NULL` or a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn nthash_seed_is_valid(mask: *const c_char) -> bool {
+    !mask.is_null()
+        && CStr::from_ptr(mask)
+            .to_str()
+            .is_ok_and(|m| SpacedSeed::parse(m).is_ok())
+}
+
+// ──────────────────────────────────────────────────────────────
+// Bulk records
+// ──────────────────────────────────────────────────────────────
+
+/// Write a [`PosHash`] for each valid k‑mer of `seq[0..seq_len]` into
+/// `out[0..out_len]`.
+///
+/// Returns the total number of valid k‑mers, which may exceed `out_len`;
+/// call with `out_len == 0` (and `out` `NULL`) to size the buffer first.
+/// Returns 0 if `seq` is `NULL`, `k == 0`, or `seq_len < k`.
+///
+/// # Safety
+///
+/// `seq` must point to `seq_len` readable bytes and `out` to `out_len`
+/// writable records.
+#[no_mangle]
+pub unsafe extern "C" fn nthash_pos_hashes(
+    seq: *const c_char,
+    seq_len: usize,
+    k: u16,
+    out: *mut PosHash,
+    out_len: usize,
+) -> usize {
+    let Some(mut h) = seq_slice(seq, seq_len).and_then(|s| NtHash::new(s, k, 1, 0).ok()) else {
+        return 0;
+    };
+    let mut n = 0;
+    while h.roll() {
+        if n < out_len {
+            out.add(n).write(PosHash {
+                pos: h.pos() as u64,
+                hash: h.hashes()[0],
+            });
+        }
+        n += 1;
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEQ: &[u8] = b"ACGTACGTNACGGTACCATG";
+
+    #[test]
+    fn nthash_handle_matches_rust_api() {
+        unsafe {
+            let h = nthash_new(SEQ.as_ptr().cast(), SEQ.len(), 2, 4, 0);
+            assert!(!h.is_null());
+            let mut rust = NtHash::new(SEQ, 4, 2, 0).unwrap();
+            while rust.roll() {
+                assert!(nthash_roll(h));
+                let n = nthash_get_hash_num(h);
+                assert_eq!(slice::from_raw_parts(nthash_hashes(h), n), rust.hashes());
+                assert_eq!(nthash_get_pos(h), rust.pos());
+            }
+            assert!(!nthash_roll(h));
+            nthash_free(h);
+
+            assert!(nthash_new(SEQ.as_ptr().cast(), SEQ.len(), 1, 0, 0).is_null());
+            assert!(nthash_new(ptr::null(), 0, 1, 4, 0).is_null());
+            nthash_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn pos_hashes_fill_caller_buffer() {
+        let expected = PosHash::collect(SEQ, 5).unwrap();
+        unsafe {
+            let n = nthash_pos_hashes(SEQ.as_ptr().cast(), SEQ.len(), 5, ptr::null_mut(), 0);
+            assert_eq!(n, expected.len());
+            let mut buf = vec![PosHash::default(); n];
+            let written = nthash_pos_hashes(SEQ.as_ptr().cast(), SEQ.len(), 5, buf.as_mut_ptr(), n);
+            assert_eq!((written, buf), (n, expected));
+            assert_eq!(nthash_pos_hashes(ptr::null(), 0, 5, ptr::null_mut(), 0), 0);
+        }
+    }
+
+    #[test]
+    fn blind_handle_copies_window() {
+        unsafe {
+            let h = blind_nthash_new(SEQ.as_ptr().cast(), 4, 1, 4, 0);
+            let mut rust = BlindNtHash::new(SEQ, 4, 1, 0).unwrap();
+            for &c in &SEQ[4..8] {
+                blind_nthash_roll(h, c as c_char);
+                rust.roll(c);
+                assert_eq!(*blind_nthash_hashes(h), rust.hashes()[0]);
+            }
+            assert_eq!(blind_nthash_get_pos(h), 4);
+            blind_nthash_free(h);
+
+            assert!(blind_nthash_new(SEQ.as_ptr().cast(), 3, 1, 4, 0).is_null());
+        }
+    }
+
+    #[test]
+    fn seed_handle_parses_c_masks() {
+        let masks = [c"1101011".as_ptr(), c"1000001".as_ptr()];
+        unsafe {
+            let h = seed_nthash_new(SEQ.as_ptr().cast(), SEQ.len(), masks.as_ptr(), 2, 3, 7, 0);
+            assert!(!h.is_null());
+            let masks_rs = ["1101011".to_string(), "1000001".to_string()];
+            let mut rust = SeedNtHash::new(SEQ, &masks_rs, 3, 7, 0).unwrap();
+            while rust.roll() {
+                assert!(seed_nthash_roll(h));
+                assert_eq!(seed_nthash_get_hash_num(h), 6);
+                assert_eq!(
+                    slice::from_raw_parts(seed_nthash_hashes(h), 6),
+                    rust.hashes()
+                );
+            }
+            seed_nthash_free(h);
+
+            let bad = [c"11x".as_ptr()];
+            let h = seed_nthash_new(SEQ.as_ptr().cast(), SEQ.len(), bad.as_ptr(), 1, 1, 3, 0);
+            assert!(h.is_null());
+            assert!(!nthash_seed_is_valid(bad[0]));
+            assert!(nthash_seed_is_valid(masks[0]));
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Entry points for the `cargo fuzz` targets in `fuzz/`.
+//!
+//! Each function decodes arbitrary bytes into constructor arguments — k,
+//! hash count, start position, masks, and a sequence — followed by a
+//! stream of operations, and replays them against one hasher.  Errors are
+//! expected and ignored; only panics (including arithmetic overflow in
+//! debug builds) are failures.  Not part of the stable API.
+
+use crate::{BlindNtHash, NtHash, NtHashState, SeedNtHash};
+
+/// Little cursor over the fuzzer's input.
+struct Input<'a>(&'a [u8]);
+
+impl<'a> Input<'a> {
+    fn byte(&mut self) -> u8 {
+        match self.0.split_first() {
+            Some((&b, rest)) => {
+                self.0 = rest;
+                b
+            }
+            None => 0,
+        }
+    }
+
+    fn u16(&mut self) -> u16 {
+        u16::from_le_bytes([self.byte(), self.byte()])
+    }
+
+    /// The next `len`‑prefixed run of bytes.
+    fn bytes(&mut self) -> &'a [u8] {
+        let n = (self.u16() as usize).min(self.0.len());
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        head
+    }
+}
+
+/// Exercise [`NtHash`]: construction, every roll/peek/seek method, the
+/// graph‑neighbour helpers, and a checkpoint round trip.
+pub fn nthash(data: &[u8]) {
+    let mut input = Input(data);
+    let (k, m, pos) = (input.u16(), input.byte(), input.u16() as usize);
+    let (packed, seq) = (input.byte(), input.bytes());
+    let ops = input.0;
+    let Ok(mut h) = NtHash::new(seq, k, m, pos) else {
+        return;
+    };
+    if packed & 1 == 1 {
+        match h.with_packed_kmers() {
+            Ok(p) => h = p,
+            Err(_) => return,
+        }
+    }
+    h = h.with_skipped_ranges();
+    let mut ops = Input(ops);
+    while !ops.0.is_empty() {
+        let arg = ops.byte();
+        match ops.byte() % 12 {
+            0 => _ = h.roll(),
+            1 => _ = h.roll_back(),
+            2 => _ = h.peek(),
+            3 => _ = h.peek_back(),
+            4 => _ = h.peek_char(arg),
+            5 => _ = h.peek_back_char(arg),
+            6 => _ = h.seek(ops.u16() as usize),
+            7 => _ = (h.successors(), h.predecessors()),
+            8 => _ = h.substitute(ops.u16() as usize, arg),
+            9 => {
+                _ = (
+                    h.prefix_hash(),
+                    h.suffix_hash(),
+                    h.packed_kmer(),
+                    h.kmer_pos(),
+                )
+            }
+            10 => {
+                let state = NtHashState {
+                    pos: ops.u16() as usize,
+                    ..h.state()
+                };
+                if let Ok(r) = NtHash::resume(seq, state) {
+                    h = r;
+                }
+            }
+            _ => _ = h.hashes_array::<4>(),
+        }
+    }
+    while h.roll() {}
+    _ = h.skipped_ranges();
+}
+
+/// Exercise [`BlindNtHash`]: plain and checked construction, then rolls,
+/// peeks, seeks, and substitutions with arbitrary incoming bytes.
+pub fn blind(data: &[u8]) {
+    let mut input = Input(data);
+    let (k, m, pos) = (input.u16(), input.byte(), input.u16() as isize);
+    let (checked, seq) = (input.byte(), input.bytes());
+    let ops = input.0;
+    let made = if checked & 1 == 1 {
+        BlindNtHash::new_checked(seq, k, m, pos)
+    } else {
+        BlindNtHash::new(seq, k, m, pos)
+    };
+    let Ok(mut h) = made else {
+        return;
+    };
+    let mut ops = Input(ops);
+    while !ops.0.is_empty() {
+        let arg = ops.byte();
+        match ops.byte() % 7 {
+            0 => _ = h.roll(arg),
+            1 => _ = h.roll_back(arg),
+            2 => h.peek(arg),
+            3 => h.peek_back(arg),
+            4 => _ = h.seek(seq, ops.u16() as isize),
+            5 => _ = h.substitute(ops.u16() as usize, arg),
+            _ => _ = (h.window_start(), h.window_end(), h.hashes_array::<2>()),
+        }
+    }
+}
+
+/// Exercise [`SeedNtHash`] with arbitrary masks: construction, rolling in
+/// both directions, and the per‑seed accessors.
+pub fn seed(data: &[u8]) {
+    let mut input = Input(data);
+    let (k, m, pos) = (input.u16(), input.byte() as usize, input.u16() as usize);
+    // mostly well‑formed masks of length k, with the odd stray byte
+    let masks: Vec<String> = (0..input.byte() % 4)
+        .map(|_| {
+            (0..k.min(256))
+                .map(|_| match input.byte() {
+                    b if b % 8 < 4 => '1',
+                    b if b % 8 < 7 => '0',
+                    b => b as char,
+                })
+                .collect()
+        })
+        .collect();
+    let seq = input.bytes();
+    let ops = input.0;
+    let Ok(mut h) = SeedNtHash::new(seq, &masks, m, k, pos) else {
+        return;
+    };
+    for op in ops {
+        match op % 4 {
+            0 => _ = h.roll(),
+            1 => _ = h.roll_back(),
+            2 => _ = (h.forward_hash(), h.reverse_hash()),
+            _ => {
+                for (i, hashes) in h.seed_hashes() {
+                    assert_eq!(Some(hashes), h.hashes_for_seed(i));
+                }
+            }
+        }
+    }
+    while h.roll() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Short pseudo‑random inputs biased towards small `k`, short
+    /// sequences, and DNA letters, so the interesting paths are reached.
+    fn inputs() -> impl Iterator<Item = Vec<u8>> {
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..20_000).map(move |_| {
+            let len = (next() % 160) as usize;
+            let mut data: Vec<u8> = (0..len)
+                .map(|_| match next() % 8 {
+                    0 => next() as u8,
+                    r => b"ACGTN10\x03"[r as usize % 8],
+                })
+                .collect();
+            // small k and positions most of the time
+            if data.len() > 8 && next() % 4 != 0 {
+                data[0] %= 12;
+                data[1] = 0;
+                data[3] %= 8;
+                data[4] = 0;
+                data[6] %= 40;
+                data[7] = 0;
+            }
+            data
+        })
+    }
+
+    #[test]
+    fn entry_points_do_not_panic() {
+        for data in inputs() {
+            nthash(&data);
+            blind(&data);
+            seed(&data);
+        }
+    }
+}
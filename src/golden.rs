@@ -0,0 +1,236 @@
+//! **Golden output snapshots** for pinning hash values downstream.
+//!
+//! A [`Snapshot`] records the complete output stream of a hasher — every
+//! position and hash value — for one `(seq, k, num_hashes, masks)`
+//! configuration.  Its JSON and binary encodings are canonical (identical
+//! input always gives identical bytes), so downstream crates can commit a
+//! snapshot next to their tests and fail loudly if an upstream release ever
+//! changes the hash values.
+//!
+//! ```
+//! use nthash_rs::golden::Snapshot;
+//!
+//! let snap = Snapshot::capture(b"ACGTACGTNACGT", 4, 2, &[]).unwrap();
+//! let pinned = snap.to_bytes(); // e.g. stored under tests/data/
+//! assert_eq!(Snapshot::from_bytes(&pinned).unwrap(), snap);
+//! assert!(snap.to_json().starts_with(r#"{"version":1,"k":4,"num_hashes":2,"#));
+//! ```
+
+use std::{
+    fmt::Write as _,
+    io::{self, Read},
+};
+
+use crate::{NtHash, Result, SeedNtHash};
+
+/// Version of the snapshot encodings, bumped if their layout changes.
+pub const FORMAT_VERSION: u8 = 1;
+
+const MAGIC: &[u8; 8] = b"NTHSNAP\0";
+
+/// The full output of one hasher run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// k‑mer length.
+    pub k: u16,
+    /// Hashes per k‑mer (per seed, with masks).
+    pub num_hashes: u8,
+    /// Spaced‑seed masks; empty for contiguous [`NtHash`].
+    pub masks: Vec<String>,
+    /// Length of the hashed sequence.
+    pub seq_len: usize,
+    /// `(pos, hashes)` for every emitted k‑mer, in order.
+    pub windows: Vec<(usize, Vec<u64>)>,
+}
+
+impl Snapshot {
+    /// Run the hasher over `seq` and record its output.
+    ///
+    /// With no `masks` this is [`NtHash`]; otherwise [`SeedNtHash`] with
+    /// `num_hashes` hashes per seed.
+    ///
+    /// # Errors
+    ///
+    /// Propagates the hasher's constructor errors.
+    pub fn capture(seq: &[u8], k: u16, num_hashes: u8, masks: &[String]) -> Result<Self> {
+        let mut windows = Vec::new();
+        if masks.is_empty() {
+            let mut h = NtHash::new(seq, k, num_hashes, 0)?;
+            while h.roll() {
+                windows.push((h.pos(), h.hashes().to_vec()));
+            }
+        } else {
+            let mut h = SeedNtHash::new(seq, masks, num_hashes as usize, k, 0)?;
+            while h.roll() {
+                windows.push((h.pos(), h.hashes().to_vec()));
+            }
+        }
+        Ok(Self {
+            k,
+            num_hashes,
+            masks: masks.to_vec(),
+            seq_len: seq.len(),
+            windows,
+        })
+    }
+
+    /// Index into [`windows`](Self::windows) of the first disagreement with
+    /// `other`, or `None` if the streams are identical.
+    pub fn first_difference(&self, other: &Snapshot) -> Option<usize> {
+        let n = self.windows.len().min(other.windows.len());
+        (0..n)
+            .find(|&i| self.windows[i] != other.windows[i])
+            .or((self.windows.len() != other.windows.len()).then_some(n))
+    }
+
+    /// Canonical single‑line JSON; hashes are 16‑digit hex strings so they
+    /// survive parsers that read numbers as doubles.
+    pub fn to_json(&self) -> String {
+        let mut s = format!(
+            r#"{{"version":{FORMAT_VERSION},"k":{},"num_hashes":{},"masks":["#,
+            self.k, self.num_hashes
+        );
+        for (i, m) in self.masks.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            let _ = write!(s, r#"{sep}"{m}""#);
+        }
+        let _ = write!(s, r#"],"seq_len":{},"windows":["#, self.seq_len);
+        for (i, (pos, hashes)) in self.windows.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            let _ = write!(s, r#"{sep}{{"pos":{pos},"hashes":["#);
+            for (j, h) in hashes.iter().enumerate() {
+                let sep = if j == 0 { "" } else { "," };
+                let _ = write!(s, r#"{sep}"{h:016x}""#);
+            }
+            s.push_str("]}");
+        }
+        s.push_str("]}");
+        s
+    }
+
+    /// Canonical little‑endian binary encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32 + self.windows.len() * 16);
+        out.extend_from_slice(MAGIC);
+        out.push(FORMAT_VERSION);
+        out.extend_from_slice(&self.k.to_le_bytes());
+        out.push(self.num_hashes);
+        out.extend_from_slice(&(self.masks.len() as u32).to_le_bytes());
+        for m in &self.masks {
+            out.extend_from_slice(&(m.len() as u32).to_le_bytes());
+            out.extend_from_slice(m.as_bytes());
+        }
+        out.extend_from_slice(&(self.seq_len as u64).to_le_bytes());
+        out.extend_from_slice(&(self.windows.len() as u64).to_le_bytes());
+        for (pos, hashes) in &self.windows {
+            out.extend_from_slice(&(*pos as u64).to_le_bytes());
+            out.extend_from_slice(&(hashes.len() as u32).to_le_bytes());
+            for h in hashes {
+                out.extend_from_slice(&h.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    /// Decode [`to_bytes`](Self::to_bytes) output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] for a foreign or newer format,
+    /// and [`io::ErrorKind::UnexpectedEof`] if the input is truncated.
+    pub fn from_bytes(mut bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+        let mut magic = [0; 8];
+        bytes.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("not an ntHash snapshot"));
+        }
+        if read_array::<1>(&mut bytes)?[0] != FORMAT_VERSION {
+            return Err(invalid("unsupported snapshot version"));
+        }
+        let k = u16::from_le_bytes(read_array(&mut bytes)?);
+        let num_hashes = read_array::<1>(&mut bytes)?[0];
+        let num_masks = u32::from_le_bytes(read_array(&mut bytes)?);
+        let mut masks = Vec::new();
+        for _ in 0..num_masks {
+            let len = u32::from_le_bytes(read_array(&mut bytes)?) as usize;
+            let mut buf = vec![0; len.min(bytes.len())];
+            bytes.read_exact(&mut buf)?;
+            masks.push(String::from_utf8(buf).map_err(|_| invalid("mask is not UTF-8"))?);
+        }
+        let seq_len = u64::from_le_bytes(read_array(&mut bytes)?) as usize;
+        let num_windows = u64::from_le_bytes(read_array(&mut bytes)?);
+        let mut windows = Vec::new();
+        for _ in 0..num_windows {
+            let pos = u64::from_le_bytes(read_array(&mut bytes)?) as usize;
+            let n = u32::from_le_bytes(read_array(&mut bytes)?);
+            let hashes = (0..n)
+                .map(|_| read_array(&mut bytes).map(u64::from_le_bytes))
+                .collect::<io::Result<_>>()?;
+            windows.push((pos, hashes));
+        }
+        if !bytes.is_empty() {
+            return Err(invalid("trailing bytes after snapshot"));
+        }
+        Ok(Self {
+            k,
+            num_hashes,
+            masks,
+            seq_len,
+            windows,
+        })
+    }
+}
+
+fn read_array<const N: usize>(r: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEQ: &[u8] = b"ATCGTACGATGCATGCATGCTGACG";
+
+    #[test]
+    fn encodings_are_stable() {
+        let snap = Snapshot::capture(b"ACGTAC", 4, 1, &[]).unwrap();
+        let mut h = NtHash::new(b"ACGTAC", 4, 1, 0).unwrap();
+        let mut expected =
+            String::from(r#"{"version":1,"k":4,"num_hashes":1,"masks":[],"seq_len":6,"windows":["#);
+        for pos in 0..3 {
+            assert!(h.roll());
+            let sep = if pos == 0 { "" } else { "," };
+            expected += &format!(
+                r#"{sep}{{"pos":{pos},"hashes":["{:016x}"]}}"#,
+                h.hashes()[0]
+            );
+        }
+        expected += "]}";
+        assert_eq!(snap.to_json(), expected);
+        assert_eq!(
+            snap.to_bytes().len(),
+            8 + 1 + 2 + 1 + 4 + 8 + 8 + 3 * (8 + 4 + 8)
+        );
+    }
+
+    #[test]
+    fn binary_round_trip_and_diff() {
+        let masks = vec!["110011".to_string(), "101101".to_string()];
+        let snap = Snapshot::capture(SEQ, 6, 2, &masks).unwrap();
+        assert_eq!(snap.windows[0].1.len(), 4);
+        let bytes = snap.to_bytes();
+        assert_eq!(Snapshot::from_bytes(&bytes).unwrap(), snap);
+        assert!(Snapshot::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Snapshot::from_bytes(b"NTHSNAP\0\x02").is_err());
+
+        let mut changed = snap.clone();
+        changed.windows[3].1[1] ^= 1;
+        assert_eq!(snap.first_difference(&changed), Some(3));
+        assert_eq!(snap.first_difference(&snap), None);
+        changed.windows.truncate(3);
+        assert_eq!(snap.first_difference(&changed), Some(3));
+    }
+}
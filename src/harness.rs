@@ -0,0 +1,250 @@
+//! **In‑process throughput comparison** of ntHash and other hashers.
+//!
+//! [`Harness`] hashes every k‑mer window of one input with the crate's
+//! rolling hasher, with per‑window recomputation, and with any closures
+//! the caller registers (e.g. xxh3 or a `std::hash::Hasher` over each
+//! window), then reports the throughput of each side by side.  It needs no
+//! benchmark framework, so integrators can measure on their own data
+//! inside their own binaries:
+//!
+//! ```
+//! use nthash_rs::harness::Harness;
+//! use std::hash::{DefaultHasher, Hasher};
+//! use std::time::Duration;
+//!
+//! let seq = b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACG".repeat(50);
+//! let report = Harness::new(&seq, 21)
+//!     .min_time(Duration::from_millis(5))
+//!     .add("siphash", |window: &[u8]| {
+//!         let mut h = DefaultHasher::new();
+//!         h.write(window);
+//!         h.finish()
+//!     })
+//!     .run()
+//!     .unwrap();
+//! assert_eq!(report.measurements().len(), 3);
+//! println!("{report}");
+//! ```
+//!
+//! Build with `--release`; debug builds say little about relative speed.
+
+use std::{
+    fmt,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{kmer::CanonicalKmer, NtHash, Result};
+
+type WindowHasher<'a> = Box<dyn FnMut(&[u8]) -> u64 + 'a>;
+
+/// Throughput of one hasher over the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    /// Hasher label.
+    pub name: String,
+    /// Complete passes over the input.
+    pub passes: u64,
+    /// k‑mers hashed over all passes.
+    pub kmers: u64,
+    /// Wall time of all passes.
+    pub elapsed: Duration,
+    /// XOR of every hash, kept so the work cannot be optimised away.
+    pub checksum: u64,
+}
+
+impl Measurement {
+    /// k‑mers hashed per second.
+    pub fn kmers_per_sec(&self) -> f64 {
+        self.kmers as f64 / self.elapsed.as_secs_f64()
+    }
+
+    /// Average nanoseconds per k‑mer.
+    pub fn ns_per_kmer(&self) -> f64 {
+        self.elapsed.as_nanos() as f64 / self.kmers as f64
+    }
+}
+
+/// Measurements of every hasher, in registration order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    measurements: Vec<Measurement>,
+}
+
+impl Report {
+    /// All measurements; the first is the rolling ntHash.
+    pub fn measurements(&self) -> &[Measurement] {
+        &self.measurements
+    }
+
+    /// The measurement labelled `name`.
+    pub fn get(&self, name: &str) -> Option<&Measurement> {
+        self.measurements.iter().find(|m| m.name == name)
+    }
+}
+
+impl fmt::Display for Report {
+    /// A plain‑text table with speed relative to the rolling ntHash.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .measurements
+            .iter()
+            .map(|m| m.name.len())
+            .max()
+            .unwrap_or(0);
+        let base = self
+            .measurements
+            .first()
+            .map_or(1.0, Measurement::ns_per_kmer);
+        writeln!(
+            f,
+            "{:width$}  {:>12}  {:>10}  {:>8}",
+            "hasher", "Mkmer/s", "ns/kmer", "relative"
+        )?;
+        for m in &self.measurements {
+            writeln!(
+                f,
+                "{:width$}  {:>12.2}  {:>10.2}  {:>7.2}x",
+                m.name,
+                m.kmers_per_sec() / 1e6,
+                m.ns_per_kmer(),
+                base / m.ns_per_kmer(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Side‑by‑side throughput measurement over one sequence.
+pub struct Harness<'a> {
+    seq: &'a [u8],
+    k: u16,
+    min_time: Duration,
+    hashers: Vec<(String, WindowHasher<'a>)>,
+}
+
+impl<'a> Harness<'a> {
+    /// Compare hashers on the `k`‑mers of `seq`, for at least 200 ms each.
+    pub fn new(seq: &'a [u8], k: u16) -> Self {
+        Self {
+            seq,
+            k,
+            min_time: Duration::from_millis(200),
+            hashers: Vec::new(),
+        }
+    }
+
+    /// Minimum time spent on each hasher (whole passes are always run).
+    pub fn min_time(mut self, min_time: Duration) -> Self {
+        self.min_time = min_time;
+        self
+    }
+
+    /// Also measure `hash`, called on every k‑mer window of the input.
+    ///
+    /// Unlike ntHash, a window hasher sees windows containing `N` too, so
+    /// it may hash slightly more k‑mers per pass.
+    pub fn add<F>(mut self, name: impl Into<String>, hash: F) -> Self
+    where
+        F: FnMut(&[u8]) -> u64 + 'a,
+    {
+        self.hashers.push((name.into(), Box::new(hash)));
+        self
+    }
+
+    /// Run every hasher and collect the measurements: `"ntHash"` (rolling,
+    /// one hash per k‑mer), `"ntHash (per window)"` (each k‑mer hashed
+    /// from scratch), then the registered hashers.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn run(self) -> Result<Report> {
+        let (seq, k, min_time) = (self.seq, self.k, self.min_time);
+        NtHash::new(seq, k, 1, 0)?;
+        let mut measurements = vec![
+            measure("ntHash", min_time, || {
+                let mut h = NtHash::new(seq, k, 1, 0).expect("validated above");
+                let (mut n, mut acc) = (0, 0);
+                while h.roll() {
+                    acc ^= h.hashes()[0];
+                    n += 1;
+                }
+                (n, acc)
+            }),
+            measure("ntHash (per window)", min_time, || {
+                hash_windows(seq, k, |w| CanonicalKmer::new(w).nthash())
+            }),
+        ];
+        for (name, mut hash) in self.hashers {
+            measurements.push(measure(&name, min_time, || hash_windows(seq, k, &mut hash)));
+        }
+        Ok(Report { measurements })
+    }
+}
+
+/// Hash every window of `seq` with `hash`: `(windows, xor of hashes)`.
+fn hash_windows(seq: &[u8], k: u16, mut hash: impl FnMut(&[u8]) -> u64) -> (u64, u64) {
+    let (mut n, mut acc) = (0, 0);
+    for w in seq.windows(k as usize) {
+        acc ^= hash(black_box(w));
+        n += 1;
+    }
+    (n, acc)
+}
+
+/// Repeat `pass` until `min_time` has elapsed.
+fn measure(name: &str, min_time: Duration, mut pass: impl FnMut() -> (u64, u64)) -> Measurement {
+    let start = Instant::now();
+    let (mut passes, mut kmers, mut checksum) = (0, 0, 0);
+    loop {
+        let (n, acc) = pass();
+        passes += 1;
+        kmers += n;
+        checksum = black_box(checksum ^ acc);
+        if start.elapsed() >= min_time {
+            break;
+        }
+    }
+    Measurement {
+        name: name.to_owned(),
+        passes,
+        kmers,
+        elapsed: start.elapsed(),
+        checksum,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_hasher_covers_the_input() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACGNNACGTTGCA";
+        let mut calls = 0;
+        let report = Harness::new(seq, 7)
+            .min_time(Duration::ZERO)
+            .add("count", |_: &[u8]| {
+                calls += 1;
+                0
+            })
+            .run()
+            .unwrap();
+        let names: Vec<&str> = report
+            .measurements()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, ["ntHash", "ntHash (per window)", "count"]);
+        let m = report.get("count").unwrap();
+        assert_eq!((m.passes, m.kmers), (1, seq.len() as u64 - 6));
+        assert_eq!(calls, seq.len() - 6);
+        assert_eq!(
+            report.get("ntHash").unwrap().kmers,
+            seq.len() as u64 - 6 - 8
+        );
+        assert_eq!(report.to_string().lines().count(), 4);
+        assert!(Harness::new(b"ACG", 7).run().is_err());
+    }
+}
@@ -0,0 +1,257 @@
+//! **COBS / BIGSI‑style bit‑matrix indexes** queried with ntHash.
+//!
+//! A compact bit‑sliced signature index stores one Bloom filter per document
+//! column‑wise: row `r` holds bit `r` of every document's filter, packed
+//! `ceil(num_docs / 8)` bytes per row (document `d` at byte `d / 8`, bit
+//! `d % 8`).  Querying a k‑mer ANDs the `num_hashes` rows it hashes to,
+//! giving the documents that may contain it in one pass over a few bytes.
+//!
+//! This module uses the same row‑major layout as the classic COBS index
+//! payload, so matrices can be written to disk and queried straight from a
+//! memory map ([`CobsIndex::from_raw`] takes any `AsRef<[u8]>`).  The row
+//! addresses come from the canonical ntHash multi‑hash output, so an index
+//! must be built and queried with this crate (or the same `k` and
+//! `num_hashes` through another ntHash implementation).
+//!
+//! ```
+//! use nthash_rs::interop::{query_index, CobsIndexBuilder};
+//!
+//! let mut builder = CobsIndexBuilder::new(5, 3, 1 << 12);
+//! builder.add_document(b"ACGTACGTTGCA").unwrap();
+//! builder.add_document(b"TTTTGGGGCCCC").unwrap();
+//! let index = builder.build().unwrap();
+//!
+//! let presence = query_index(&index, b"ACGTACG").unwrap();
+//! assert!(presence.iter().all(|(_, docs)| docs[0]));
+//! assert_eq!(index.document_scores(b"ACGTACG").unwrap(), [3, 0]);
+//! ```
+
+use crate::{NtHash, NtHashError, Result};
+
+/// A bit‑sliced signature matrix: `signature_size` rows × `num_docs` columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CobsIndex<S = Vec<u8>> {
+    k: u16,
+    num_hashes: u8,
+    num_docs: usize,
+    data: S,
+}
+
+impl<S: AsRef<[u8]>> CobsIndex<S> {
+    /// View an existing matrix, e.g. a memory‑mapped file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] if `num_docs == 0` or the
+    /// data is empty or not a whole number of rows.
+    pub fn from_raw(data: S, k: u16, num_hashes: u8, num_docs: usize) -> Result<Self> {
+        let len = data.as_ref().len();
+        if num_docs == 0 || len == 0 || len % num_docs.div_ceil(8) != 0 {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        Ok(Self {
+            k,
+            num_hashes,
+            num_docs,
+            data,
+        })
+    }
+
+    /// k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Rows probed per k‑mer.
+    pub fn num_hashes(&self) -> u8 {
+        self.num_hashes
+    }
+
+    /// Number of documents (columns).
+    pub fn num_docs(&self) -> usize {
+        self.num_docs
+    }
+
+    /// Bytes per row.
+    pub fn row_bytes(&self) -> usize {
+        self.num_docs.div_ceil(8)
+    }
+
+    /// Number of rows (filter size in bits).
+    pub fn signature_size(&self) -> usize {
+        self.data.as_ref().len() / self.row_bytes()
+    }
+
+    /// The raw matrix, ready to be written to disk.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+
+    /// Row addressed by one hash value.
+    fn row(&self, hash: u64) -> &[u8] {
+        let rb = self.row_bytes();
+        let r = (hash % self.signature_size() as u64) as usize;
+        &self.data.as_ref()[r * rb..(r + 1) * rb]
+    }
+
+    /// AND of the rows of one k‑mer's hashes into `acc`.
+    fn probe(&self, hashes: &[u64], acc: &mut [u8]) {
+        acc.fill(0xFF);
+        for &h in hashes {
+            for (a, b) in acc.iter_mut().zip(self.row(h)) {
+                *a &= b;
+            }
+        }
+    }
+
+    /// Number of k‑mers of `seq` each document may contain.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn document_scores(&self, seq: &[u8]) -> Result<Vec<usize>> {
+        let mut scores = vec![0; self.num_docs];
+        let mut acc = vec![0; self.row_bytes()];
+        let mut h = NtHash::new(seq, self.k, self.num_hashes, 0)?;
+        while h.roll() {
+            self.probe(h.hashes(), &mut acc);
+            for (d, s) in scores.iter_mut().enumerate() {
+                *s += (acc[d / 8] >> (d % 8) & 1) as usize;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+/// Per‑k‑mer presence of `seq` in every document: `(pos, present[doc])`.
+///
+/// # Errors
+///
+/// Propagates [`NtHash::new`] errors.
+pub fn query_index<S: AsRef<[u8]>>(
+    index: &CobsIndex<S>,
+    seq: &[u8],
+) -> Result<Vec<(usize, Vec<bool>)>> {
+    let mut out = Vec::new();
+    let mut acc = vec![0; index.row_bytes()];
+    let mut h = NtHash::new(seq, index.k, index.num_hashes, 0)?;
+    while h.roll() {
+        index.probe(h.hashes(), &mut acc);
+        let docs = (0..index.num_docs)
+            .map(|d| acc[d / 8] >> (d % 8) & 1 == 1)
+            .collect();
+        out.push((h.pos(), docs));
+    }
+    Ok(out)
+}
+
+/// Builds a [`CobsIndex`] in memory, one document at a time.
+pub struct CobsIndexBuilder {
+    k: u16,
+    num_hashes: u8,
+    signature_size: usize,
+    // one Bloom filter (bit vector) per document
+    docs: Vec<Vec<u64>>,
+}
+
+impl CobsIndexBuilder {
+    /// Documents hashed with `k`‑mers and `num_hashes` rows per k‑mer into
+    /// `signature_size` rows (at least one).
+    pub fn new(k: u16, num_hashes: u8, signature_size: usize) -> Self {
+        Self {
+            k,
+            num_hashes,
+            signature_size: signature_size.max(1),
+            docs: Vec::new(),
+        }
+    }
+
+    /// Add a document, returning its column index.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn add_document(&mut self, seq: &[u8]) -> Result<usize> {
+        let mut bits = vec![0u64; self.signature_size.div_ceil(64)];
+        let mut h = NtHash::new(seq, self.k, self.num_hashes, 0)?;
+        while h.roll() {
+            for &x in h.hashes() {
+                let r = (x % self.signature_size as u64) as usize;
+                bits[r / 64] |= 1 << (r % 64);
+            }
+        }
+        self.docs.push(bits);
+        Ok(self.docs.len() - 1)
+    }
+
+    /// Transpose the document filters into the row‑major matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] if no document was added.
+    pub fn build(self) -> Result<CobsIndex> {
+        let n = self.docs.len();
+        if n == 0 {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        let rb = n.div_ceil(8);
+        let mut data = vec![0u8; self.signature_size * rb];
+        for (d, bits) in self.docs.iter().enumerate() {
+            for r in 0..self.signature_size {
+                if bits[r / 64] >> (r % 64) & 1 == 1 {
+                    data[r * rb + d / 8] |= 1 << (d % 8);
+                }
+            }
+        }
+        Ok(CobsIndex {
+            k: self.k,
+            num_hashes: self.num_hashes,
+            num_docs: n,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_bytes_round_trip_through_borrowed_view() {
+        let docs: [&[u8]; 10] = [
+            b"ACGTACGTACGGATC",
+            b"TTGACCATGACAGTA",
+            b"GGGCCCAAATTTGCA",
+            b"ACGTTTTTTTGGGAC",
+            b"CATCATCATCATCAT",
+            b"GATTACAGATTACAG",
+            b"CCCCGGGGAAAATTT",
+            b"TGCATGCATGCATGC",
+            b"AAAACCCCGGGGTTT",
+            b"ATATATCGCGCGATA",
+        ];
+        let mut b = CobsIndexBuilder::new(7, 2, 4096);
+        for d in docs {
+            b.add_document(d).unwrap();
+        }
+        let owned = b.build().unwrap();
+        assert_eq!((owned.row_bytes(), owned.signature_size()), (2, 4096));
+
+        // e.g. the bytes of a memory‑mapped file
+        let view = CobsIndex::from_raw(owned.as_bytes(), 7, 2, 10).unwrap();
+        for (i, d) in docs.iter().enumerate() {
+            let scores = view.document_scores(d).unwrap();
+            assert_eq!(scores[i], d.len() - 6);
+            let hits = query_index(&view, d).unwrap();
+            assert!(hits.iter().all(|(_, present)| present[i]));
+        }
+        assert!(CobsIndex::from_raw(&owned.as_bytes()[..3], 7, 2, 10).is_err());
+        assert_eq!(
+            CobsIndexBuilder::new(7, 2, 4096).build(),
+            Err(NtHashError::InvalidCapacity)
+        );
+    }
+}
@@ -0,0 +1,640 @@
+//! **FASTA / FASTQ I/O** with hash‑annotated pass‑through.
+//!
+//! A minimal, dependency‑free reader and writer for the two sequence formats
+//! every hashing pipeline starts from, plus an [`AnnotatingWriter`] that
+//! re‑emits records unchanged except for per‑read summary [`Tags`] (sketch
+//! signature, minimizer count, containment score, …).  Tags go either into
+//! the header description (`@read1 sig=1f2e mins=12`) or into a sidecar TSV
+//! keyed by read id, so screening results stay attached to reads downstream.
+//!
+//! ```
+//! use nthash_rs::io::{AnnotatingWriter, Reader, Tags};
+//! use nthash_rs::NtHashBuilder;
+//!
+//! let input = b">r1 sample\nACGTACGT\n>r2\nTTGCA\n";
+//! let mut out = Vec::new();
+//! AnnotatingWriter::in_description(&mut out)
+//!     .annotate(Reader::new(&input[..]), |rec| {
+//!         let kmers = NtHashBuilder::new(&rec.seq).k(4).finish().map_or(0, |it| it.count());
+//!         Tags::new().with("kmers", kmers)
+//!     })
+//!     .unwrap();
+//! assert_eq!(out, b">r1 sample kmers=5\nACGTACGT\n>r2 kmers=2\nTTGCA\n");
+//! ```
+//!
+//! [`SpectrumWriter`] / [`SpectrumReader`] stream raw `(pos, hashes)` or
+//! hash‑only k‑mer records through a compact little‑endian binary file,
+//! so hashing and downstream analysis can run in separate processes.
+//!
+//! ```
+//! use nthash_rs::io::{SpectrumReader, SpectrumWriter};
+//! use nthash_rs::NtHash;
+//!
+//! let mut w = SpectrumWriter::new(Vec::new(), 5, 2, true).unwrap();
+//! NtHash::new(b"ACGTACGTTG", 5, 2, 0).unwrap().drive(&mut w);
+//! let bytes = w.finish().unwrap();
+//!
+//! let r = SpectrumReader::new(&bytes[..]).unwrap();
+//! assert_eq!((r.header().k, r.header().num_hashes), (5, 2));
+//! let positions: Vec<_> = r.map(|rec| rec.unwrap().0).collect();
+//! assert_eq!(positions, [Some(0), Some(1), Some(2), Some(3), Some(4), Some(5)]);
+//! ```
+
+use std::fmt::Display;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::sink::HashSink;
+
+/// One FASTA or FASTQ record.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Record {
+    /// Header line without the leading `>` / `@` (id plus description).
+    pub head: Vec<u8>,
+    /// Sequence, with FASTA line breaks removed.
+    pub seq: Vec<u8>,
+    /// Base qualities; `None` for FASTA.
+    pub qual: Option<Vec<u8>>,
+}
+
+impl Record {
+    /// Record id: the header up to the first whitespace.
+    pub fn id(&self) -> &[u8] {
+        let end = self
+            .head
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(self.head.len());
+        &self.head[..end]
+    }
+
+    /// Returns `true` for FASTQ records.
+    pub fn is_fastq(&self) -> bool {
+        self.qual.is_some()
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Strip a trailing `\n` / `\r\n`.
+fn trim_eol(line: &mut Vec<u8>) {
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+}
+
+/// Streaming FASTA / FASTQ reader; the format is detected per record.
+///
+/// FASTA sequences may span several lines; FASTQ records must use the
+/// four‑line layout.
+pub struct Reader<R> {
+    inner: R,
+    line: Vec<u8>,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Wrap a buffered reader.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            line: Vec::new(),
+        }
+    }
+
+    /// Read the next line into `self.line`; returns `false` at EOF.
+    fn next_line(&mut self) -> io::Result<bool> {
+        self.line.clear();
+        let n = self.inner.read_until(b'\n', &mut self.line)?;
+        trim_eol(&mut self.line);
+        Ok(n > 0)
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        // `self.line` holds a pending header if the previous FASTA record
+        // stopped on one; otherwise skip blank lines up to the next header.
+        while self.line.is_empty() {
+            if !self.next_line()? {
+                return Ok(None);
+            }
+        }
+        let marker = self.line[0];
+        let head = self.line[1..].to_vec();
+        let mut rec = Record {
+            head,
+            ..Record::default()
+        };
+        match marker {
+            b'>' => {
+                while self.next_line()? {
+                    if matches!(self.line.first(), Some(b'>' | b'@')) {
+                        return Ok(Some(rec));
+                    }
+                    rec.seq.extend_from_slice(&self.line);
+                }
+                self.line.clear();
+            }
+            b'@' => {
+                if !self.next_line()? {
+                    return Err(invalid("truncated FASTQ record"));
+                }
+                rec.seq = std::mem::take(&mut self.line);
+                if !self.next_line()? || self.line.first() != Some(&b'+') {
+                    return Err(invalid("missing FASTQ '+' line"));
+                }
+                if !self.next_line()? || self.line.len() != rec.seq.len() {
+                    return Err(invalid("FASTQ quality length differs from sequence"));
+                }
+                rec.qual = Some(std::mem::take(&mut self.line));
+            }
+            _ => return Err(invalid("expected '>' or '@' at start of record")),
+        }
+        Ok(Some(rec))
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+/// Writes records back out, as FASTQ when they carry qualities and as
+/// single‑line FASTA otherwise.
+pub struct Writer<W: Write> {
+    out: W,
+}
+
+impl<W: Write> Writer<W> {
+    /// Wrap a writer.
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    /// Write `rec` unchanged.
+    pub fn write(&mut self, rec: &Record) -> io::Result<()> {
+        self.write_with_suffix(rec, &[])
+    }
+
+    /// Write `rec` with `suffix` appended to its header line.
+    fn write_with_suffix(&mut self, rec: &Record, suffix: &[u8]) -> io::Result<()> {
+        let marker: &[u8] = if rec.is_fastq() { b"@" } else { b">" };
+        self.out.write_all(marker)?;
+        self.out.write_all(&rec.head)?;
+        self.out.write_all(suffix)?;
+        self.out.write_all(b"\n")?;
+        self.out.write_all(&rec.seq)?;
+        self.out.write_all(b"\n")?;
+        if let Some(qual) = &rec.qual {
+            self.out.write_all(b"+\n")?;
+            self.out.write_all(qual)?;
+            self.out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/// Ordered `key=value` summary tags for one read.
+///
+/// Values should not contain whitespace (description mode) or tabs
+/// (sidecar mode).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tags(Vec<(String, String)>);
+
+impl Tags {
+    /// An empty tag set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a tag (builder style).
+    pub fn with(mut self, key: impl Into<String>, value: impl Display) -> Self {
+        self.push(key, value);
+        self
+    }
+
+    /// Append a tag.
+    pub fn push(&mut self, key: impl Into<String>, value: impl Display) {
+        self.0.push((key.into(), value.to_string()));
+    }
+
+    /// The tags in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+/// Pass‑through writer attaching [`Tags`] to each record.
+///
+/// In description mode the tags are appended to the header as
+/// ` key=value` pairs.  In sidecar mode records are written unchanged and a
+/// TSV with one row per read (`id`, then one column per tag) goes to a
+/// second writer; every record must then carry the same tag keys.
+pub struct AnnotatingWriter<W: Write, S: Write = io::Sink> {
+    records: Writer<W>,
+    sidecar: Option<S>,
+    columns: Option<Vec<String>>,
+}
+
+impl<W: Write> AnnotatingWriter<W> {
+    /// Append tags to each record's description.
+    pub fn in_description(out: W) -> Self {
+        Self {
+            records: Writer::new(out),
+            sidecar: None,
+            columns: None,
+        }
+    }
+}
+
+impl<W: Write, S: Write> AnnotatingWriter<W, S> {
+    /// Write records unchanged to `out` and their tags as TSV to `tsv`.
+    pub fn with_sidecar(out: W, tsv: S) -> Self {
+        Self {
+            records: Writer::new(out),
+            sidecar: Some(tsv),
+            columns: None,
+        }
+    }
+
+    /// Write one record with its tags.
+    ///
+    /// # Errors
+    ///
+    /// I/O errors, or `InvalidData` if in sidecar mode the tag keys differ
+    /// from those of the first record.
+    pub fn write(&mut self, rec: &Record, tags: &Tags) -> io::Result<()> {
+        let Some(tsv) = self.sidecar.as_mut() else {
+            let mut suffix = Vec::new();
+            for (k, v) in tags.iter() {
+                write!(suffix, " {k}={v}")?;
+            }
+            return self.records.write_with_suffix(rec, &suffix);
+        };
+
+        match &self.columns {
+            Some(cols) => {
+                if !cols
+                    .iter()
+                    .map(String::as_str)
+                    .eq(tags.iter().map(|(k, _)| k))
+                {
+                    return Err(invalid("tag keys differ from the sidecar header"));
+                }
+            }
+            None => {
+                let cols: Vec<String> = tags.iter().map(|(k, _)| k.to_owned()).collect();
+                write!(tsv, "id")?;
+                for c in &cols {
+                    write!(tsv, "\t{c}")?;
+                }
+                writeln!(tsv)?;
+                self.columns = Some(cols);
+            }
+        }
+        tsv.write_all(rec.id())?;
+        for (_, v) in tags.iter() {
+            write!(tsv, "\t{v}")?;
+        }
+        writeln!(tsv)?;
+        self.records.write(rec)
+    }
+
+    /// Re‑emit every record of `records`, tagged by `summarize`.
+    ///
+    /// Returns the number of records written.
+    pub fn annotate<I, F>(&mut self, records: I, mut summarize: F) -> io::Result<usize>
+    where
+        I: IntoIterator<Item = io::Result<Record>>,
+        F: FnMut(&Record) -> Tags,
+    {
+        let mut n = 0;
+        for rec in records {
+            let rec = rec?;
+            let tags = summarize(&rec);
+            self.write(&rec, &tags)?;
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    /// Flush both outputs and return them.
+    pub fn into_inner(self) -> io::Result<(W, Option<S>)> {
+        let out = self.records.into_inner()?;
+        let sidecar = match self.sidecar {
+            Some(mut s) => {
+                s.flush()?;
+                Some(s)
+            }
+            None => None,
+        };
+        Ok((out, sidecar))
+    }
+}
+
+/// Version of the hash functions (seed tables and multi‑hash extension)
+/// recorded in spectrum files; bumped if hash values ever change.
+pub const SEED_VERSION: u16 = 1;
+
+const SPECTRUM_MAGIC: &[u8; 8] = b"NTHSPEC\0";
+const SPECTRUM_FORMAT: u8 = 1;
+
+/// Parameters stored at the start of a spectrum file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpectrumHeader {
+    /// k‑mer length.
+    pub k: u16,
+    /// Hashes per record.
+    pub num_hashes: u8,
+    /// Whether every record starts with its k‑mer position.
+    pub with_pos: bool,
+    /// [`SEED_VERSION`] of the writer.
+    pub seed_version: u16,
+}
+
+impl SpectrumHeader {
+    /// Bytes per record.
+    pub fn record_size(&self) -> usize {
+        8 * (self.num_hashes as usize + self.with_pos as usize)
+    }
+}
+
+/// Writes k‑mer hash records to a binary spectrum file.
+///
+/// Layout: the 8‑byte magic `NTHSPEC\0`, format version (`u8`), seed
+/// version (`u16`), `k` (`u16`), `num_hashes` (`u8`), flags (`u8`, bit 0 =
+/// positions), one reserved byte; then one record per k‑mer: an optional
+/// `u64` position followed by `num_hashes` `u64` hashes, all
+/// little‑endian.  Wrap slow writers in a [`std::io::BufWriter`].
+pub struct SpectrumWriter<W: Write> {
+    out: W,
+    header: SpectrumHeader,
+    records: u64,
+    // first error met while used as a `HashSink`
+    error: Option<io::Error>,
+}
+
+impl<W: Write> SpectrumWriter<W> {
+    /// Write the header for records of `num_hashes` hashes of `k`‑mers,
+    /// with or without positions.
+    pub fn new(mut out: W, k: u16, num_hashes: u8, with_pos: bool) -> io::Result<Self> {
+        out.write_all(SPECTRUM_MAGIC)?;
+        out.write_all(&[SPECTRUM_FORMAT])?;
+        out.write_all(&SEED_VERSION.to_le_bytes())?;
+        out.write_all(&k.to_le_bytes())?;
+        out.write_all(&[num_hashes, with_pos as u8, 0])?;
+        Ok(Self {
+            out,
+            header: SpectrumHeader {
+                k,
+                num_hashes,
+                with_pos,
+                seed_version: SEED_VERSION,
+            },
+            records: 0,
+            error: None,
+        })
+    }
+
+    /// The header written.
+    pub fn header(&self) -> &SpectrumHeader {
+        &self.header
+    }
+
+    /// Number of records written so far.
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Write one record; only the first `num_hashes` of `hashes` are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidInput`] if fewer than `num_hashes`
+    /// hashes are given, and propagates write errors.
+    pub fn write(&mut self, pos: usize, hashes: &[u64]) -> io::Result<()> {
+        let n = self.header.num_hashes as usize;
+        if hashes.len() < n {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("record has {} hashes, expected {n}", hashes.len()),
+            ));
+        }
+        let mut buf = [0u8; 8 * 256];
+        let mut len = 0;
+        if self.header.with_pos {
+            buf[..8].copy_from_slice(&(pos as u64).to_le_bytes());
+            len = 8;
+        }
+        for h in &hashes[..n] {
+            buf[len..len + 8].copy_from_slice(&h.to_le_bytes());
+            len += 8;
+        }
+        self.out.write_all(&buf[..len])?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer, or the first error met
+    /// while used as a [`HashSink`].
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> HashSink for SpectrumWriter<W> {
+    fn accept(&mut self, pos: usize, hashes: &[u64]) {
+        if self.error.is_none() {
+            if let Err(e) = self.write(pos, hashes) {
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+/// Iterates the records of a spectrum file as `(pos, hashes)`; `pos` is
+/// `None` for hash‑only files.
+pub struct SpectrumReader<R: Read> {
+    input: R,
+    header: SpectrumHeader,
+    buf: Vec<u8>,
+}
+
+impl<R: Read> SpectrumReader<R> {
+    /// Read and check the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] for a foreign or newer
+    /// format, and propagates read errors.
+    pub fn new(mut input: R) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+        let mut raw = [0u8; 16];
+        input.read_exact(&mut raw)?;
+        if &raw[..8] != SPECTRUM_MAGIC {
+            return Err(invalid("not an ntHash spectrum"));
+        }
+        if raw[8] != SPECTRUM_FORMAT {
+            return Err(invalid("unsupported spectrum format version"));
+        }
+        let header = SpectrumHeader {
+            seed_version: u16::from_le_bytes([raw[9], raw[10]]),
+            k: u16::from_le_bytes([raw[11], raw[12]]),
+            num_hashes: raw[13],
+            with_pos: raw[14] & 1 == 1,
+        };
+        Ok(Self {
+            input,
+            buf: vec![0; header.record_size()],
+            header,
+        })
+    }
+
+    /// The file's header.
+    pub fn header(&self) -> &SpectrumHeader {
+        &self.header
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<(Option<usize>, Vec<u64>)>> {
+        // distinguish a clean end of file from a truncated record
+        let mut filled = 0;
+        while filled < self.buf.len() {
+            match self.input.read(&mut self.buf[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let mut words = self
+            .buf
+            .chunks_exact(8)
+            .map(|c| u64::from_le_bytes(c.try_into().expect("8-byte chunk")));
+        let pos = if self.header.with_pos {
+            words.next().map(|p| p as usize)
+        } else {
+            None
+        };
+        Ok(Some((pos, words.collect())))
+    }
+}
+
+impl<R: Read> Iterator for SpectrumReader<R> {
+    type Item = io::Result<(Option<usize>, Vec<u64>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(data: &[u8]) -> Vec<Record> {
+        Reader::new(data).collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn reads_multiline_fasta_and_fastq() {
+        let recs = read_all(b">a desc\r\nACGT\r\nTT\n\n>b\nGG\n@c x\nACG\n+\nIII\n");
+        assert_eq!(recs.len(), 3);
+        assert_eq!(recs[0].id(), b"a");
+        assert_eq!(recs[0].head, b"a desc");
+        assert_eq!(recs[0].seq, b"ACGTTT");
+        assert_eq!(recs[1].seq, b"GG");
+        assert_eq!(recs[2].qual.as_deref(), Some(&b"III"[..]));
+
+        let mut out = Writer::new(Vec::new());
+        for r in &recs {
+            out.write(r).unwrap();
+        }
+        let again = read_all(&out.into_inner().unwrap());
+        assert_eq!(again, recs);
+    }
+
+    #[test]
+    fn rejects_malformed_fastq() {
+        assert!(Reader::new(&b"@r\nACGT\n+\nII\n"[..])
+            .next()
+            .unwrap()
+            .is_err());
+        assert!(Reader::new(&b"ACGT\n"[..]).next().unwrap().is_err());
+    }
+
+    #[test]
+    fn sidecar_tsv_keeps_records_unchanged() {
+        let input = b"@r1 lane=2\nACGT\n+\nIIII\n@r2\nTTTT\n+\nJJJJ\n";
+        let mut w = AnnotatingWriter::with_sidecar(Vec::new(), Vec::new());
+        let n = w
+            .annotate(Reader::new(&input[..]), |r| {
+                let gc = r.seq.iter().filter(|&&b| b == b'C' || b == b'G').count();
+                Tags::new().with("len", r.seq.len()).with("gc", gc)
+            })
+            .unwrap();
+        assert_eq!(n, 2);
+        let (out, tsv) = w.into_inner().unwrap();
+        assert_eq!(out, input);
+        assert_eq!(tsv.unwrap(), b"id\tlen\tgc\nr1\t4\t2\nr2\t4\t0\n");
+    }
+
+    #[test]
+    fn spectrum_round_trips_with_and_without_positions() {
+        let seq = b"ATCGTACGATGCANGCATGCTGACGTTAGC";
+        let mut expected = Vec::new();
+        let mut h = crate::NtHash::new(seq, 6, 3, 0).unwrap();
+        while h.roll() {
+            expected.push((h.pos(), h.hashes().to_vec()));
+        }
+        for with_pos in [true, false] {
+            let mut w = SpectrumWriter::new(Vec::new(), 6, 3, with_pos).unwrap();
+            crate::NtHash::new(seq, 6, 3, 0).unwrap().drive(&mut w);
+            assert_eq!(w.records() as usize, expected.len());
+            let bytes = w.finish().unwrap();
+            let r = SpectrumReader::new(&bytes[..]).unwrap();
+            assert_eq!(bytes.len(), 16 + expected.len() * r.header().record_size());
+            let got: Vec<_> = r.collect::<io::Result<_>>().unwrap();
+            for ((pos, hashes), (p, h)) in got.iter().zip(&expected) {
+                assert_eq!(*pos, with_pos.then_some(*p));
+                assert_eq!(hashes, h);
+            }
+            assert_eq!(got.len(), expected.len());
+
+            let truncated = SpectrumReader::new(&bytes[..bytes.len() - 3]).unwrap();
+            assert!(truncated.last().unwrap().is_err());
+        }
+        assert!(SpectrumReader::new(&b"NTHSNAP\0\x01\x01\0\x05\0\x01\0\0"[..]).is_err());
+
+        let mut w = SpectrumWriter::new(Vec::new(), 6, 3, false).unwrap();
+        w.accept(0, &[1]);
+        assert!(w.finish().is_err());
+    }
+
+    #[test]
+    fn sidecar_rejects_inconsistent_keys() {
+        let rec = Record {
+            head: b"r".to_vec(),
+            seq: b"A".to_vec(),
+            qual: None,
+        };
+        let mut w = AnnotatingWriter::with_sidecar(Vec::new(), Vec::new());
+        w.write(&rec, &Tags::new().with("a", 1)).unwrap();
+        assert!(w.write(&rec, &Tags::new().with("b", 1)).is_err());
+    }
+}
@@ -10,33 +10,156 @@
 //! generating extra hash values per k‑mer.
 //!
 //! Additionally, a Rust‑idiomatic **builder + iterator** facade
-//! (`NtHashBuilder` / `NtHashIter`) is provided.
+//! (`NtHashBuilder` / `NtHashIter`) is provided, along with
+//! `NtHashRevIter` for last‑to‑first iteration and `NtHashMultiIter` for
+//! hashing many records without k‑mers spanning them.
+
+use std::ops::Range;
 
 use crate::{
+    alphabet::{Bisulfite, RollingHash},
     constants::*,
+    packed::PackedHash,
+    rolling::SeekableHasher,
+    sink::HashSink,
+    stats::Tally,
     tables::{srol, srol_n, srol_table, sror},
-    util::extend_hashes,
+    util::{
+        canonical, extend_hashes, extend_hashes_with, same_seq, CanonicalPolicy, HashMixer, Mixer,
+    },
     NtHashError, // unified crate-level error
 };
 
 /// Convenient alias for fallible operations in this module.
 pub type Result<T> = crate::Result<T>;
 
+/// Location of a k‑mer: the half‑open window `start..end` of the hashed
+/// sequence, with `end - start == k`.
+///
+/// Every hasher iterator in this crate yields its positions as a `KmerPos`,
+/// and every hasher exposes the same two coordinates through
+/// `window_start()` / `window_end()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KmerPos {
+    /// Index of the k‑mer's first base.
+    pub start: usize,
+    /// One past the index of its last base.
+    pub end: usize,
+}
+
+impl KmerPos {
+    /// The k‑mer starting at `start`.
+    #[inline]
+    pub const fn new(start: usize, k: usize) -> Self {
+        Self {
+            start,
+            end: start + k,
+        }
+    }
+
+    /// The window as a range, for slicing the sequence.
+    #[inline]
+    pub const fn range(&self) -> std::ops::Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl From<KmerPos> for usize {
+    /// The start of the window.
+    fn from(p: KmerPos) -> usize {
+        p.start
+    }
+}
+
 /// Rolling k‑mer hasher over a contiguous DNA sequence.
 ///
 /// - Initialization is deferred until the first valid k‑mer (skips any
-///   windows containing `N`).
+///   windows containing `N`, or any other base but `A/C/G/T` in either
+///   case).
 /// - `roll()` / `roll_back()` advance by one base, handling skips transparently.
 /// - Each valid k‑mer emits `num_hashes` values: the canonical hash plus
 ///   extra mixes.
+///
+/// Cloning is cheap (the sequence is borrowed), so a search can fork the
+/// hasher at a branch point instead of re‑hashing from scratch.  Two
+/// hashers compare equal when they read the same sequence and their
+/// [`state`](Self::state)s match.
+///
+/// No method panics on a hasher returned by a constructor, whatever the
+/// sequence holds and in whatever order the methods are called: past
+/// either end of the sequence, moves simply return `false`.
+#[derive(Clone)]
 pub struct NtHash<'a> {
     seq: &'a [u8],
     k: u16,
+    /// Start of the last window, `seq.len() - k`; positions past it are
+    /// never indexed.
+    end: usize,
     pos: usize,
     initialized: bool,
+    /// A run of bases known to be valid (the chunk last scanned), so
+    /// rolls inside it skip the per‑base `N` check.
+    valid: Range<usize>,
     fwd_hash: u64,
     rev_hash: u64,
     hashes: Vec<u64>,
+    packed: Option<Packed>,
+    skipped: Option<Vec<Range<usize>>>,
+    peeked: Vec<u64>,
+    mixer: Mixer,
+    policy: CanonicalPolicy,
+    tally: Tally,
+}
+
+impl std::fmt::Debug for NtHash<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NtHash")
+            .field("seq_len", &self.seq.len())
+            .field("k", &self.k)
+            .field("pos", &self.pos)
+            .field("initialized", &self.initialized)
+            .field("hashes", &self.hashes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for NtHash<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state() == other.state() && same_seq(self.seq, other.seq)
+    }
+}
+
+impl Eq for NtHash<'_> {}
+
+/// 2‑bit encodings of the current k‑mer and its reverse complement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Packed {
+    fwd: u128,
+    rev: u128,
+}
+
+/// Snapshot of an [`NtHash`]'s rolling state, taken with
+/// [`NtHash::state`] and restored with [`NtHash::resume`].
+///
+/// It holds no reference to the sequence, so it can be stored (or, with the
+/// `serde` feature, serialized) while the sequence buffer is dropped or
+/// re‑mapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NtHashState {
+    /// k‑mer length.
+    pub k: u16,
+    /// Hash values per k‑mer.
+    pub num_hashes: u8,
+    /// Current position.
+    pub pos: usize,
+    /// Forward‑strand hash of the k‑mer at `pos`.
+    pub fwd_hash: u64,
+    /// Reverse‑complement hash of the k‑mer at `pos`.
+    pub rev_hash: u64,
+    /// Whether `pos` holds a valid k‑mer (i.e. the hashes are meaningful).
+    pub initialized: bool,
 }
 
 impl<'a> NtHash<'a> {
@@ -65,39 +188,203 @@ impl<'a> NtHash<'a> {
             return Err(NtHashError::PositionOutOfRange { pos, seq_len: len });
         }
         Ok(Self {
-            seq: seq,
+            seq,
             k,
+            end: len - k_usz,
             pos,
             initialized: false,
+            valid: 0..0,
             fwd_hash: 0,
             rev_hash: 0,
             hashes: vec![0; num_hashes as usize],
+            packed: None,
+            skipped: None,
+            peeked: Vec::new(),
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
+            tally: Tally::default(),
         })
     }
 
+    /// Also maintain the canonical 2‑bit encoding of each k‑mer while
+    /// rolling, read back through [`packed_kmer`](Self::packed_kmer).
+    ///
+    /// Each roll then costs a few extra shifts; only re‑initialization
+    /// after an `N` re‑encodes the whole window.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::KTooLarge`] if `k > 64`.
+    pub fn with_packed_kmers(mut self) -> Result<Self> {
+        if self.k > 64 {
+            return Err(NtHashError::KTooLarge { k: self.k, max: 64 });
+        }
+        self.packed = Some(Packed::default());
+        if self.initialized {
+            self.pack_window();
+        }
+        Ok(self)
+    }
+
+    /// Also record the runs of invalid bases the hasher skips over, read
+    /// back through [`skipped_ranges`](Self::skipped_ranges) (e.g. for QC
+    /// reports of `N` content).
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"ACGTNNACGTACGRTACN", 4, 1, 0)
+    ///     .unwrap()
+    ///     .with_skipped_ranges();
+    /// while h.roll() {}
+    /// assert_eq!(h.skipped_ranges(), [4..6, 13..14, 17..18]);
+    /// ```
+    pub fn with_skipped_ranges(mut self) -> Self {
+        self.skipped = Some(Vec::new());
+        self
+    }
+
+    /// Derive the extra hashes with `mixer` instead of the reference
+    /// scheme; see [`HashMixer`].
+    pub fn with_mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        if self.initialized {
+            self.update_hashes();
+        }
+        self
+    }
+
+    /// Combine the strand hashes under `policy` instead of summing them,
+    /// here and in the neighbour hashes ([`successors`](Self::successors),
+    /// [`substitute`](Self::substitute), …); see [`CanonicalPolicy`].
+    pub fn with_canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
+        if self.initialized {
+            self.update_hashes();
+        }
+        self
+    }
+
+    /// Sorted, disjoint runs of invalid bases stepped over so far (since
+    /// the last [`reset`](Self::reset)), each extended to the whole run;
+    /// empty unless enabled with
+    /// [`with_skipped_ranges`](Self::with_skipped_ranges).
+    pub fn skipped_ranges(&self) -> &[Range<usize>] {
+        self.skipped.as_deref().unwrap_or_default()
+    }
+
+    /// Bases read, windows skipped, and hash values computed by this
+    /// hasher (a clone starts from zero); see [`stats`](crate::stats).
+    #[cfg(feature = "stats")]
+    pub fn counters(&self) -> crate::stats::Counters {
+        self.tally.get()
+    }
+
     /// Advance forward by one base, skipping over k‑mers with `N`.
     /// Returns `true` if a new valid hash was produced.
+    ///
+    /// Bases are checked ahead a chunk of their [segment](valid_segments)
+    /// at a time rather than one per roll: within a run of valid bases the
+    /// only branch is a bounds check.
     pub fn roll(&mut self) -> bool {
         if !self.initialized {
             return self.init();
         }
         let k_usz = self.k as usize;
-        if self.pos >= self.seq.len() - k_usz {
+        if self.pos >= self.end {
             return false;
         }
-        let incoming = self.seq[self.pos + k_usz];
-        if SEED_TAB[incoming as usize] == SEED_N {
+        let at = self.pos + k_usz;
+        if !self.valid.contains(&at) && !self.scan_valid(at) {
+            // the windows up to the one starting at the invalid base
+            self.tally.skipped((k_usz - 1).min(self.end - self.pos));
             self.pos += k_usz;
             return self.init();
         }
+        let incoming = self.seq[at];
         let outgoing = self.seq[self.pos];
         self.fwd_hash = next_forward_hash(self.fwd_hash, self.k, outgoing, incoming);
         self.rev_hash = next_reverse_hash(self.rev_hash, self.k, outgoing, incoming);
         self.update_hashes();
+        self.tally.bases(1);
+        if let Some(p) = &mut self.packed {
+            let (code, shift) = (
+                (CONVERT_TAB[incoming as usize] & 3) as u128,
+                2 * (k_usz - 1),
+            );
+            p.fwd = (p.fwd << 2 | code) & pack_mask(self.k);
+            p.rev = p.rev >> 2 | (3 - code) << shift;
+        }
         self.pos += 1;
         true
     }
 
+    /// Advance by `n` valid k‑mers, as `n` calls to [`roll`](Self::roll)
+    /// would; returns `false` if fewer than `n` remain (the hasher is then
+    /// on the last valid k‑mer, as after those calls).
+    ///
+    /// Within a run of valid bases the strand hashes are rolled in a tight
+    /// loop and the hash buffer is filled once at the end; a jump of `k` or
+    /// more bases rehashes the target window directly instead.  Runs
+    /// containing `N` are crossed one [`roll`](Self::roll) at a time.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let seq = b"ACGTTGCAAGNCTTAGCGATCGATCG";
+    /// let mut jump = NtHash::new(seq, 5, 2, 0).unwrap();
+    /// let mut step = jump.clone();
+    /// for n in [1, 3, 7, 2] {
+    ///     assert!(jump.roll_n(n));
+    ///     (0..n).for_each(|_| assert!(step.roll()));
+    ///     assert_eq!((jump.pos(), jump.hashes()), (step.pos(), step.hashes()));
+    /// }
+    /// assert!(!jump.roll_n(100));
+    /// ```
+    pub fn roll_n(&mut self, n: usize) -> bool {
+        let mut left = n;
+        if left > 0 && !self.initialized {
+            if !self.init() {
+                return false;
+            }
+            left -= 1;
+        }
+        let k_usz = self.k as usize;
+        while left > 0 {
+            if self.pos >= self.end {
+                return false;
+            }
+            let at = self.pos + k_usz;
+            if !self.valid.contains(&at) && !self.scan_valid(at) {
+                if !self.roll() {
+                    return false;
+                }
+                left -= 1;
+                continue;
+            }
+            // every incoming base up to the end of the valid run
+            let steps = left.min(self.end - self.pos).min(self.valid.end - at);
+            if steps >= k_usz {
+                let window = &self.seq[self.pos + steps..self.pos + steps + k_usz];
+                self.fwd_hash = base_forward_hash(window, self.k);
+                self.rev_hash = base_reverse_hash(window, self.k);
+                self.tally.bases(k_usz);
+            } else {
+                for i in self.pos..self.pos + steps {
+                    let (outgoing, incoming) = (self.seq[i], self.seq[i + k_usz]);
+                    self.fwd_hash = next_forward_hash(self.fwd_hash, self.k, outgoing, incoming);
+                    self.rev_hash = next_reverse_hash(self.rev_hash, self.k, outgoing, incoming);
+                }
+                self.tally.bases(steps);
+            }
+            self.pos += steps;
+            self.update_hashes();
+            self.pack_window();
+            left -= steps;
+        }
+        true
+    }
+
     /// Move backward by one base, skipping over k‑mers with `N`.
     pub fn roll_back(&mut self) -> bool {
         if !self.initialized && !self.init() {
@@ -106,47 +393,73 @@ impl<'a> NtHash<'a> {
         if self.pos == 0 {
             return false;
         }
-        let incoming = self.seq[self.pos - 1];
-        if SEED_TAB[incoming as usize] == SEED_N {
+        let at = self.pos - 1;
+        if !self.valid.contains(&at) && !self.scan_valid_back(at) {
             if self.pos < self.k as usize {
                 return false;
             }
             self.pos -= self.k as usize;
             return self.init();
         }
+        let incoming = self.seq[at];
         let outgoing = self.seq[self.pos + self.k as usize - 1];
         self.fwd_hash = prev_forward_hash(self.fwd_hash, self.k, outgoing, incoming);
         self.rev_hash = prev_reverse_hash(self.rev_hash, self.k, outgoing, incoming);
         self.update_hashes();
+        self.tally.bases(1);
+        if let Some(p) = &mut self.packed {
+            let (code, shift) = (
+                (CONVERT_TAB[incoming as usize] & 3) as u128,
+                2 * (self.k - 1),
+            );
+            p.fwd = p.fwd >> 2 | code << shift;
+            p.rev = (p.rev << 2 | (3 - code)) & pack_mask(self.k);
+        }
         self.pos -= 1;
         true
     }
 
-    /// Peek the next k‑mer without mutating self.
+    /// Hash the next k‑mer without moving to it; on success its hashes are
+    /// in [`peeked_hashes`](Self::peeked_hashes), and [`hashes`](Self::hashes)
+    /// still holds the current k‑mer's.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"ACGTTGCA", 4, 2, 0).unwrap();
+    /// assert!(h.roll());
+    /// let current = h.hashes().to_vec();
+    /// assert!(h.peek());
+    /// assert_eq!(h.hashes(), current);
+    /// let next = h.peeked_hashes().to_vec();
+    /// assert!(h.roll());
+    /// assert_eq!(h.hashes(), next);
+    /// ```
     pub fn peek(&mut self) -> bool {
-        if self.pos >= self.seq.len() - self.k as usize {
+        if self.pos >= self.end {
             return false;
         }
         let incoming = self.seq[self.pos + self.k as usize];
         self.peek_char(incoming)
     }
 
-    /// Peek with an explicit incoming byte.
+    /// [`peek`](Self::peek) with an explicit incoming byte.
     pub fn peek_char(&mut self, incoming: u8) -> bool {
         if !self.initialized && !self.init() {
             return false;
         }
-        if SEED_TAB[incoming as usize] == SEED_N {
+        if !is_acgt(incoming) {
             return false;
         }
         let outgoing = self.seq[self.pos];
         let fwd = next_forward_hash(self.fwd_hash, self.k, outgoing, incoming);
         let rev = next_reverse_hash(self.rev_hash, self.k, outgoing, incoming);
-        self.fill_hash_buffer(fwd, rev);
+        self.fill_peeked(fwd, rev);
         true
     }
 
-    /// Peek the previous k‑mer without mutating self.
+    /// Hash the previous k‑mer into
+    /// [`peeked_hashes`](Self::peeked_hashes) without moving to it.
     pub fn peek_back(&mut self) -> bool {
         if self.pos == 0 {
             return false;
@@ -155,33 +468,407 @@ impl<'a> NtHash<'a> {
         self.peek_back_char(incoming)
     }
 
-    /// Peek backward with explicit incoming byte.
+    /// [`peek_back`](Self::peek_back) with an explicit incoming byte.
     pub fn peek_back_char(&mut self, incoming: u8) -> bool {
         if !self.initialized && !self.init() {
             return false;
         }
-        if SEED_TAB[incoming as usize] == SEED_N {
+        if !is_acgt(incoming) {
             return false;
         }
         let outgoing = self.seq[self.pos + self.k as usize - 1];
         let fwd = prev_forward_hash(self.fwd_hash, self.k, outgoing, incoming);
         let rev = prev_reverse_hash(self.rev_hash, self.k, outgoing, incoming);
-        self.fill_hash_buffer(fwd, rev);
+        self.fill_peeked(fwd, rev);
         true
     }
 
-    /// Returns the most recent hash buffer.
+    /// Point the hasher at a new sequence (typically another subslice of
+    /// the same read or reference), keeping `k` and the hash buffer.
+    ///
+    /// The hasher restarts at position 0 of `seq`, exactly as if freshly
+    /// constructed, but without reallocating — for per‑locus loops that
+    /// rehash many short candidate regions.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let genome = b"ACGTCAGTNACGTTGCAGGA";
+    /// let mut h = NtHash::new(&genome[..8], 4, 2, 0).unwrap();
+    /// h.rebase(&genome[9..16]).unwrap();
+    /// assert!(h.roll());
+    /// let mut fresh = NtHash::new(&genome[9..16], 4, 2, 0).unwrap();
+    /// assert!(fresh.roll());
+    /// assert_eq!((h.pos(), h.hashes()), (fresh.pos(), fresh.hashes()));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::SequenceTooShort`] if `seq` is shorter than
+    /// `k`; the hasher is unchanged on error.
+    pub fn rebase(&mut self, seq: &'a [u8]) -> Result<()> {
+        self.reset(seq, 0)
+    }
+
+    /// Rebind the hasher to `seq`, starting at `pos`: the same as
+    /// `NtHash::new(seq, k, num_hashes, pos)` with this hasher's `k`,
+    /// `num_hashes`, and optional bookkeeping, but reusing its buffers.
+    ///
+    /// Meant for loops over many reads, where one hasher per read would
+    /// allocate and validate its configuration each time.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let reads: [&[u8]; 3] = [b"ACGTTGCA", b"TTGCAGGA", b"GGATCCAT"];
+    /// let mut h = NtHash::new(reads[0], 5, 2, 0).unwrap();
+    /// for read in reads {
+    ///     h.reset(read, 1).unwrap();
+    ///     let mut fresh = NtHash::new(read, 5, 2, 1).unwrap();
+    ///     while fresh.roll() {
+    ///         assert!(h.roll());
+    ///         assert_eq!((h.pos(), h.hashes()), (fresh.pos(), fresh.hashes()));
+    ///     }
+    ///     assert!(!h.roll());
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`NtHash::new`] for a short `seq` or out‑of‑range `pos`; the
+    /// hasher is unchanged on error.
+    pub fn reset(&mut self, seq: &'a [u8], pos: usize) -> Result<()> {
+        let Some(end) = seq.len().checked_sub(self.k as usize) else {
+            return Err(NtHashError::SequenceTooShort {
+                seq_len: seq.len(),
+                k: self.k,
+            });
+        };
+        if pos > end {
+            return Err(NtHashError::PositionOutOfRange {
+                pos,
+                seq_len: seq.len(),
+            });
+        }
+        self.seq = seq;
+        self.end = end;
+        self.pos = pos;
+        self.initialized = false;
+        self.valid = 0..0;
+        if let Some(skipped) = &mut self.skipped {
+            skipped.clear();
+        }
+        Ok(())
+    }
+
+    /// Snapshot of the rolling state, for [`resume`](Self::resume).
+    ///
+    /// Optional bookkeeping ([`with_packed_kmers`](Self::with_packed_kmers),
+    /// [`with_skipped_ranges`](Self::with_skipped_ranges)) is not part of
+    /// the state.
+    pub fn state(&self) -> NtHashState {
+        NtHashState {
+            k: self.k,
+            num_hashes: self.hashes.len() as u8,
+            pos: self.pos,
+            fwd_hash: self.fwd_hash,
+            rev_hash: self.rev_hash,
+            initialized: self.initialized,
+        }
+    }
+
+    /// Continue hashing `seq` from a snapshot taken with
+    /// [`state`](Self::state), in O(num_hashes) rather than the O(k) of a
+    /// fresh start.
+    ///
+    /// `seq` must hold the same bases the snapshot was taken over (e.g. the
+    /// same file mapped again); this is not checked.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let seq = b"ACGTCAGTNACGTTGCAGGA".to_vec();
+    /// let mut h = NtHash::new(&seq, 5, 2, 0).unwrap();
+    /// h.roll();
+    /// h.roll();
+    /// let state = h.state();
+    ///
+    /// let copy = seq.clone();
+    /// let mut resumed = NtHash::resume(&copy, state).unwrap();
+    /// assert_eq!(resumed.hashes(), h.hashes());
+    /// while h.roll() {
+    ///     assert!(resumed.roll());
+    ///     assert_eq!((resumed.pos(), resumed.hashes()), (h.pos(), h.hashes()));
+    /// }
+    /// assert!(!resumed.roll());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new) for the snapshot's `k` and `pos`.
+    pub fn resume(seq: &'a [u8], state: NtHashState) -> Result<Self> {
+        let mut h = Self::new(seq, state.k, state.num_hashes, state.pos)?;
+        h.fwd_hash = state.fwd_hash;
+        h.rev_hash = state.rev_hash;
+        h.initialized = state.initialized;
+        if h.initialized {
+            h.update_hashes();
+        }
+        Ok(h)
+    }
+
+    /// Jump to the k‑mer starting at `pos` in O(k), keeping the hash buffer.
+    ///
+    /// Returns `Ok(true)` if that k‑mer is valid and is now current.  If it
+    /// contains a non‑ACGT base, returns `Ok(false)` and the next
+    /// [`roll`](Self::roll) continues from the first valid k‑mer after `pos`.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let seq = b"ACGTCAGTNACGTTGCA";
+    /// let mut h = NtHash::new(seq, 4, 2, 0).unwrap();
+    /// assert_eq!(h.seek(9), Ok(true));
+    /// let mut fresh = NtHash::new(seq, 4, 2, 9).unwrap();
+    /// assert!(fresh.roll());
+    /// assert_eq!(h.hashes(), fresh.hashes());
+    /// assert_eq!(h.seek(6), Ok(false));
+    /// assert!(h.roll() && h.pos() == 9);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::PositionOutOfRange`] if `pos > seq.len() - k`.
+    pub fn seek(&mut self, pos: usize) -> Result<bool> {
+        let k_usz = self.k as usize;
+        if pos > self.end {
+            return Err(NtHashError::PositionOutOfRange {
+                pos,
+                seq_len: self.seq.len(),
+            });
+        }
+        self.pos = pos;
+        let mut skip = 0;
+        if has_invalid_base(&self.seq[pos..], k_usz, &mut skip) {
+            self.initialized = false;
+            return Ok(false);
+        }
+        self.fwd_hash = base_forward_hash(&self.seq[pos..], self.k);
+        self.rev_hash = base_reverse_hash(&self.seq[pos..], self.k);
+        self.update_hashes();
+        self.pack_window();
+        self.initialized = true;
+        Ok(true)
+    }
+
+    /// Roll to the end of the sequence, passing every valid k‑mer to
+    /// `sink`; returns the number of k‑mers passed.
+    ///
+    /// Unlike the iterators, no memory is allocated per k‑mer.
+    pub fn drive<S: HashSink + ?Sized>(&mut self, sink: &mut S) -> usize {
+        let mut n = 0;
+        while self.roll() {
+            sink.accept(self.pos, &self.hashes);
+            n += 1;
+        }
+        n
+    }
+
+    /// Canonical hashes of the k‑mers reached by appending `A`, `C`, `G`,
+    /// `T` (in that order) to the current k‑mer's last `k − 1` bases.
+    ///
+    /// These are the out‑neighbours in the de Bruijn graph.  The state,
+    /// including the hash buffer, is left untouched.  Returns `None` before
+    /// the first valid k‑mer.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"ACGTG", 4, 1, 0).unwrap();
+    /// assert!(h.roll());
+    /// let next = h.successors().unwrap();
+    /// assert!(h.roll()); // CGTG
+    /// assert_eq!(next[2], h.hashes()[0]);
+    /// ```
+    pub fn successors(&self) -> Option<[u64; 4]> {
+        if !self.initialized {
+            return None;
+        }
+        let outgoing = self.seq[self.pos];
+        Some(b"ACGT".map(|incoming| {
+            self.policy.combine(
+                next_forward_hash(self.fwd_hash, self.k, outgoing, incoming),
+                next_reverse_hash(self.rev_hash, self.k, outgoing, incoming),
+            )
+        }))
+    }
+
+    /// Canonical hashes of the k‑mers reached by prepending `A`, `C`, `G`,
+    /// `T` (in that order) to the current k‑mer's first `k − 1` bases.
+    ///
+    /// The in‑neighbours counterpart of [`successors`](Self::successors).
+    pub fn predecessors(&self) -> Option<[u64; 4]> {
+        if !self.initialized {
+            return None;
+        }
+        let outgoing = self.seq[self.pos + self.k as usize - 1];
+        Some(b"ACGT".map(|incoming| {
+            self.policy.combine(
+                prev_forward_hash(self.fwd_hash, self.k, outgoing, incoming),
+                prev_reverse_hash(self.rev_hash, self.k, outgoing, incoming),
+            )
+        }))
+    }
+
+    /// Canonical hash of the current k‑mer with the base at `offset`
+    /// (counted from the window start) replaced by `new_base`.
+    ///
+    /// Costs two table lookups per strand, however large `k` is, so the
+    /// effect of a SNP on a sketch can be evaluated without rehashing.  The
+    /// state, including the hash buffer, is left untouched.  Returns `None`
+    /// before the first valid k‑mer, if `offset >= k`, or if `new_base` is
+    /// not ACGT.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"ACGTTGCA", 8, 1, 0).unwrap();
+    /// assert!(h.roll());
+    /// let mut snp = NtHash::new(b"ACGATGCA", 8, 1, 0).unwrap();
+    /// assert!(snp.roll());
+    /// assert_eq!(h.substitute(3, b'A'), Some(snp.hashes()[0]));
+    /// ```
+    pub fn substitute(&self, offset: usize, new_base: u8) -> Option<u64> {
+        if !self.initialized || offset >= self.k as usize || !is_acgt(new_base) {
+            return None;
+        }
+        let old = self.seq[self.pos + offset];
+        let (fwd, rev) = sub_hash(self.fwd_hash, self.rev_hash, self.k, offset, old, new_base);
+        Some(self.policy.combine(fwd, rev))
+    }
+
+    /// Canonical hash of the current k‑mer's first `k − 1` bases.
+    ///
+    /// Derived from the current strand hashes with one split‑rotate and one
+    /// table lookup per strand, without rehashing — e.g. for the source node
+    /// of a de Bruijn graph edge.  Returns `None` before the first valid
+    /// k‑mer or when `k == 1`.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"ACGTCA", 6, 1, 0).unwrap();
+    /// assert!(h.roll());
+    /// let mut prefix = NtHash::new(b"ACGTC", 5, 1, 0).unwrap();
+    /// assert!(prefix.roll());
+    /// assert_eq!(h.prefix_hash(), Some(prefix.hashes()[0]));
+    /// ```
+    pub fn prefix_hash(&self) -> Option<u64> {
+        self.prefix_strands()
+            .map(|(fwd, rev)| self.policy.combine(fwd, rev))
+    }
+
+    /// Forward and reverse hashes of the first `k − 1` bases.
+    #[inline]
+    pub(crate) fn prefix_strands(&self) -> Option<(u64, u64)> {
+        if !self.initialized || self.k < 2 {
+            return None;
+        }
+        let last = self.seq[self.pos + self.k as usize - 1];
+        let fwd = sror(self.fwd_hash ^ SEED_TAB[last as usize]);
+        let rev = self.rev_hash ^ srol_table(last & CP_OFF, self.k as u32 - 1);
+        Some((fwd, rev))
+    }
+
+    /// Canonical hash of the current k‑mer's last `k − 1` bases.
+    ///
+    /// The counterpart of [`prefix_hash`](Self::prefix_hash) for the target
+    /// node of an edge.  Returns `None` before the first valid k‑mer or when
+    /// `k == 1`.
+    pub fn suffix_hash(&self) -> Option<u64> {
+        if !self.initialized || self.k < 2 {
+            return None;
+        }
+        let first = self.seq[self.pos];
+        let fwd = self.fwd_hash ^ srol_table(first, self.k as u32 - 1);
+        let rev = sror(self.rev_hash ^ SEED_TAB[(first & CP_OFF) as usize]);
+        Some(self.policy.combine(fwd, rev))
+    }
+
+    /// Returns the current k‑mer's hash buffer.
     #[inline(always)]
     pub fn hashes(&self) -> &[u64] {
         &self.hashes
     }
 
-    /// Returns the current k‑mer start index.
+    /// Hashes of the k‑mer reached by the last successful peek (empty
+    /// before the first).
+    #[inline(always)]
+    pub fn peeked_hashes(&self) -> &[u64] {
+        &self.peeked
+    }
+
+    /// The current k‑mer's first `M` hash values, computed on the stack.
+    ///
+    /// Equal to the first `M` entries [`hashes`](Self::hashes) would hold
+    /// with `num_hashes = M`, whatever this hasher was created with; with a
+    /// compile‑time `M` the extension loop is fully unrolled.  Peeks do not
+    /// affect the result.  Construct the hasher with `num_hashes = 1` to
+    /// keep the heap buffer from doing redundant work.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"ACGTCAGT", 5, 1, 0).unwrap();
+    /// let mut wide = NtHash::new(b"ACGTCAGT", 5, 3, 0).unwrap();
+    /// while h.roll() && wide.roll() {
+    ///     let [a, b, c] = h.hashes_array::<3>();
+    ///     assert_eq!([a, b, c], wide.hashes());
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn hashes_array<const M: usize>(&self) -> [u64; M] {
+        let mut out = [0; M];
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
+            self.fwd_hash,
+            self.rev_hash,
+            self.k as u32,
+            &mut out,
+        );
+        out
+    }
+
+    /// Returns the current k‑mer start index (same as
+    /// [`window_start`](Self::window_start)).
     #[inline(always)]
     pub fn pos(&self) -> usize {
         self.pos
     }
 
+    /// Index of the current k‑mer's first base.
+    ///
+    /// After a successful [`roll`](Self::roll) / [`roll_back`](Self::roll_back)
+    /// this is the start of the k‑mer whose hashes are in
+    /// [`hashes`](Self::hashes).
+    #[inline(always)]
+    pub fn window_start(&self) -> usize {
+        self.pos
+    }
+
+    /// One past the index of the current k‑mer's last base.
+    #[inline(always)]
+    pub fn window_end(&self) -> usize {
+        self.pos + self.k as usize
+    }
+
+    /// The current window as a [`KmerPos`].
+    #[inline(always)]
+    pub fn kmer_pos(&self) -> KmerPos {
+        KmerPos::new(self.pos, self.k as usize)
+    }
+
     /// Returns the forward‑strand hash.
     #[inline(always)]
     pub fn forward_hash(&self) -> u64 {
@@ -194,27 +881,195 @@ impl<'a> NtHash<'a> {
         self.rev_hash
     }
 
+    /// Canonical 2‑bit encoding of the current k‑mer: `A=0, C=1, G=2,
+    /// T=3`, first base in the most significant position, taking the
+    /// lexicographically smaller of the k‑mer and its reverse complement.
+    ///
+    /// Returns `None` unless enabled with
+    /// [`with_packed_kmers`](Self::with_packed_kmers), and before the first
+    /// valid k‑mer.
+    ///
+    /// ```
+    /// use nthash_rs::NtHash;
+    ///
+    /// let mut h = NtHash::new(b"TTGCA", 4, 1, 0).unwrap().with_packed_kmers().unwrap();
+    /// assert!(h.roll()); // TTGC, reverse complement GCAA
+    /// assert_eq!(h.packed_kmer(), Some(0b10_01_00_00));
+    /// assert!(h.roll()); // TGCA is its own reverse complement
+    /// assert_eq!(h.packed_kmer_u64(), Some(0b11_10_01_00));
+    /// ```
+    #[inline]
+    pub fn packed_kmer(&self) -> Option<u128> {
+        self.packed
+            .filter(|_| self.initialized)
+            .map(|p| p.fwd.min(p.rev))
+    }
+
+    /// [`packed_kmer`](Self::packed_kmer) as a `u64`; also `None` if
+    /// `k > 32`.
+    #[inline]
+    pub fn packed_kmer_u64(&self) -> Option<u64> {
+        self.packed_kmer()
+            .filter(|_| self.k <= 32)
+            .map(|p| p as u64)
+    }
+
     /// Initialize on the first valid k‑mer.
     fn init(&mut self) -> bool {
         let k_usz = self.k as usize;
-        while self.pos <= self.seq.len() - k_usz {
+        while self.pos <= self.end {
             let mut skip = 0;
             if has_invalid_base(&self.seq[self.pos..], k_usz, &mut skip) {
+                self.note_skipped(self.pos..self.pos + skip + 1);
+                self.tally.skipped((skip + 1).min(self.end + 1 - self.pos));
                 self.pos += skip + 1;
                 continue;
             }
             self.fwd_hash = base_forward_hash(&self.seq[self.pos..], self.k);
             self.rev_hash = base_reverse_hash(&self.seq[self.pos..], self.k);
             self.update_hashes();
+            self.tally.bases(k_usz);
+            self.pack_window();
             self.initialized = true;
             return true;
         }
+        // the tail too short to hold a k‑mer
+        self.note_skipped(self.pos.min(self.seq.len())..self.seq.len());
+        self.initialized = false;
         false
     }
 
+    /// Initialize on the last valid k‑mer starting at or before `pos`.
+    fn init_back(&mut self) -> bool {
+        let k_usz = self.k as usize;
+        loop {
+            let window = &self.seq[self.pos..self.pos + k_usz];
+            let Some(first_bad) = window.iter().position(|&c| !is_acgt(c)) else {
+                self.fwd_hash = base_forward_hash(window, self.k);
+                self.rev_hash = base_reverse_hash(window, self.k);
+                self.update_hashes();
+                self.tally.bases(k_usz);
+                self.pack_window();
+                self.initialized = true;
+                return true;
+            };
+            // the window must end before the leftmost invalid base
+            let bad = self.pos + first_bad;
+            self.note_skipped(bad..self.pos + k_usz);
+            // the windows from here back to the one ending at `bad`
+            self.tally
+                .skipped((self.pos + k_usz - bad).min(self.pos + 1));
+            if bad < k_usz {
+                // the head too short to hold a k‑mer
+                self.note_skipped(0..bad);
+                self.initialized = false;
+                return false;
+            }
+            self.pos = bad - k_usz;
+        }
+    }
+
+    /// Step to the previous valid k‑mer, jumping backward over invalid
+    /// bases (unlike [`roll_back`](Self::roll_back), which re‑initializes
+    /// forward from there).
+    fn retreat(&mut self) -> bool {
+        if self.pos == 0 {
+            return false;
+        }
+        if !is_acgt(self.seq[self.pos - 1]) {
+            self.note_skipped(self.pos - 1..self.pos);
+            self.tally.skipped(self.pos.min(self.k as usize));
+            if self.pos <= self.k as usize {
+                self.note_skipped(0..self.pos);
+                return false;
+            }
+            self.pos -= self.k as usize + 1;
+            return self.init_back();
+        }
+        self.roll_back()
+    }
+
+    /// Whether the base at `at` is valid; if so, remember how far the run
+    /// of valid bases continues from it, looking at most [`SCAN_CHUNK`]
+    /// bases ahead so a hasher started mid‑sequence pays O(1) per roll.
+    #[cold]
+    fn scan_valid(&mut self, at: usize) -> bool {
+        let len = acgt_run(&self.seq[at..self.seq.len().min(at + SCAN_CHUNK)]);
+        if len == 0 {
+            return false;
+        }
+        self.valid = at..at + len;
+        true
+    }
+
+    /// Whether the base at `at` is valid, growing the known run down to it
+    /// when it sits just before; rolling backward checks one base per step.
+    #[cold]
+    fn scan_valid_back(&mut self, at: usize) -> bool {
+        if !is_acgt(self.seq[at]) {
+            return false;
+        }
+        let end = if self.valid.start == at + 1 {
+            self.valid.end
+        } else {
+            at + 1
+        };
+        self.valid = at..end;
+        true
+    }
+
+    /// Record the runs of invalid bases touching `region`, if enabled.
+    fn note_skipped(&mut self, region: Range<usize>) {
+        let Some(skipped) = &mut self.skipped else {
+            return;
+        };
+        let invalid = |i: usize| !is_acgt(self.seq[i]);
+        let mut i = region.start;
+        while i < region.end {
+            if !invalid(i) {
+                i += 1;
+                continue;
+            }
+            let (mut start, mut end) = (i, i + 1);
+            while start > 0 && invalid(start - 1) {
+                start -= 1;
+            }
+            while end < self.seq.len() && invalid(end) {
+                end += 1;
+            }
+            // merge into the sorted list
+            let lo = skipped.partition_point(|r| r.end < start);
+            let hi = skipped.partition_point(|r| r.start <= end);
+            if lo < hi {
+                start = start.min(skipped[lo].start);
+                end = end.max(skipped[hi - 1].end);
+            }
+            skipped.splice(lo..hi, std::iter::once(start..end));
+            i = end;
+        }
+    }
+
+    /// Encode the current window from scratch, if packing is enabled.
+    fn pack_window(&mut self) {
+        if let Some(p) = &mut self.packed {
+            *p = Packed::default();
+            for (i, &c) in self.seq[self.pos..self.pos + self.k as usize]
+                .iter()
+                .enumerate()
+            {
+                let code = (CONVERT_TAB[c as usize] & 3) as u128;
+                p.fwd = p.fwd << 2 | code;
+                p.rev |= (3 - code) << (2 * i);
+            }
+        }
+    }
+
     #[inline(always)]
     fn update_hashes(&mut self) {
-        extend_hashes(
+        self.tally.hashes(self.hashes.len());
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
             self.fwd_hash,
             self.rev_hash,
             self.k as u32,
@@ -223,30 +1078,382 @@ impl<'a> NtHash<'a> {
     }
 
     #[inline(always)]
-    fn fill_hash_buffer(&mut self, fwd: u64, rev: u64) {
-        extend_hashes(fwd, rev, self.k as u32, &mut self.hashes);
+    fn fill_peeked(&mut self, fwd: u64, rev: u64) {
+        self.peeked.resize(self.hashes.len(), 0);
+        extend_hashes_with(
+            self.mixer,
+            self.policy,
+            fwd,
+            rev,
+            self.k as u32,
+            &mut self.peeked,
+        );
     }
 }
 
-#[inline(always)]
-pub fn has_invalid_base(seq: &[u8], k: usize, pos_n: &mut usize) -> bool {
-    if let Some(idx) = seq[..k]
-        .iter()
-        .rposition(|&c| SEED_TAB[c as usize] == SEED_N)
-    {
-        *pos_n = idx;
-        true
-    } else {
-        false
-    }
+/// Hash a single k‑mer (`k = kmer.len()`) in one call: `num_hashes`
+/// values, the first being the canonical hash, exactly as [`NtHash`]
+/// would report for this window.
+///
+/// ```
+/// use nthash_rs::{hash_kmer, NtHash};
+///
+/// let mut h = NtHash::new(b"TTACGTAC", 6, 3, 2).unwrap();
+/// assert!(h.roll());
+/// assert_eq!(hash_kmer(b"ACGTAC", 3).unwrap(), h.hashes());
+/// ```
+///
+/// # Errors
+///
+/// Returns [`NtHashError::InvalidK`] if `kmer` is empty or longer than
+/// `u16::MAX`, and [`NtHashError::InvalidBase`] for its first non‑ACGT base.
+pub fn hash_kmer(kmer: &[u8], num_hashes: u8) -> Result<Vec<u64>> {
+    let (fwd, rev, k) = kmer_strands(kmer)?;
+    let mut hashes = vec![0; num_hashes as usize];
+    extend_hashes(fwd, rev, k as u32, &mut hashes);
+    Ok(hashes)
 }
 
-#[inline]
-pub fn base_forward_hash(seq: &[u8], k: u16) -> u64 {
-    let k = k as usize;
-    let mut h = 0_u64;
+/// The canonical hash of a single k‑mer (`hash_kmer(kmer, 1)[0]`) without
+/// allocating.
+///
+/// ```
+/// use nthash_rs::canonical_kmer_hash;
+///
+/// assert_eq!(canonical_kmer_hash(b"ACGGT"), canonical_kmer_hash(b"ACCGT"));
+/// assert!(canonical_kmer_hash(b"ACNGT").is_err());
+/// ```
+///
+/// # Errors
+///
+/// As [`hash_kmer`].
+pub fn canonical_kmer_hash(kmer: &[u8]) -> Result<u64> {
+    let (fwd, rev, _) = kmer_strands(kmer)?;
+    Ok(canonical(fwd, rev))
+}
 
-    for chunk in seq[..k - k % 4].chunks_exact(4) {
+/// Canonical hashes of many independent, same‑length k‑mers (e.g. queries
+/// drawn from a database rather than a contiguous sequence), in input
+/// order.
+///
+/// The length is checked once and the output allocated up front; each
+/// k‑mer then costs one validation pass and the table‑driven (with the
+/// `simd` feature, vectorized) initial hash on both strands.
+///
+/// ```
+/// use nthash_rs::{canonical_kmer_hash, hash_kmers};
+///
+/// let kmers: [&[u8]; 3] = [b"ACGTA", b"TTGCA", b"TACGT"];
+/// let hashes = hash_kmers(kmers).unwrap();
+/// assert_eq!(hashes[1], canonical_kmer_hash(b"TTGCA").unwrap());
+/// assert_eq!(hashes[0], hashes[2]); // reverse complements
+/// ```
+///
+/// # Errors
+///
+/// Returns [`NtHashError::KmerLengthMismatch`] if a k‑mer's length differs
+/// from the first's, [`NtHashError::InvalidBase`] for the first non‑ACGT
+/// base (its `pos` counted across the batch as if concatenated), and
+/// [`NtHashError::InvalidK`] for empty or over‑long k‑mers.
+pub fn hash_kmers<'k, I>(kmers: I) -> Result<Vec<u64>>
+where
+    I: IntoIterator<Item = &'k [u8]>,
+{
+    let kmers = kmers.into_iter();
+    let mut out = Vec::with_capacity(kmers.size_hint().0);
+    let mut k = 0;
+    for (index, kmer) in kmers.enumerate() {
+        if index == 0 {
+            k = match u16::try_from(kmer.len()) {
+                Ok(0) | Err(_) => return Err(NtHashError::InvalidK),
+                Ok(k) => k,
+            };
+        } else if kmer.len() != k as usize {
+            return Err(NtHashError::KmerLengthMismatch {
+                index,
+                expected: k as usize,
+                actual: kmer.len(),
+            });
+        }
+        if let Some(i) = kmer.iter().position(|&c| !is_acgt(c)) {
+            return Err(NtHashError::InvalidBase {
+                pos: index * k as usize + i,
+                byte: kmer[i],
+            });
+        }
+        out.push(canonical(
+            base_forward_hash(kmer, k),
+            base_reverse_hash(kmer, k),
+        ));
+    }
+    Ok(out)
+}
+
+/// Value written by [`hash_into`] for windows that contain a non‑ACGT base.
+///
+/// A real canonical hash can take this value too (with probability
+/// 2⁻⁶⁴ per k‑mer); callers that must tell the two apart should keep a
+/// separate validity mask.
+pub const SKIPPED_HASH: u64 = u64::MAX;
+
+/// Write the canonical hash of every window of `seq` into `out`, in
+/// position order: `out[i]` holds the k‑mer starting at `i`, or
+/// [`SKIPPED_HASH`] if that window was skipped.  Returns the number of
+/// valid k‑mers.
+///
+/// `out` must have exactly `seq.len() − k + 1` slots, so it can be a view
+/// of a pre‑sized, memory‑mapped file that is then indexed by position
+/// without any intermediate allocation.
+///
+/// ```
+/// use nthash_rs::{hash_into, NtHash, SKIPPED_HASH};
+///
+/// let seq = b"ACGTNACGTAC";
+/// let mut out = [0u64; 8];
+/// assert_eq!(hash_into(seq, 4, &mut out).unwrap(), 4);
+/// assert_eq!(out[1..5], [SKIPPED_HASH; 4]);
+///
+/// let mut h = NtHash::new(seq, 4, 1, 0).unwrap();
+/// assert!(h.roll());
+/// assert_eq!(out[0], h.hashes()[0]);
+/// ```
+///
+/// # Errors
+///
+/// As [`NtHash::new`], and [`NtHashError::OutputLengthMismatch`] if `out`
+/// does not have one slot per window.
+pub fn hash_into(seq: &[u8], k: u16, out: &mut [u64]) -> Result<usize> {
+    let mut h = NtHash::new(seq, k, 1, 0)?;
+    let expected = seq.len() - k as usize + 1;
+    if out.len() != expected {
+        return Err(NtHashError::OutputLengthMismatch {
+            expected,
+            actual: out.len(),
+        });
+    }
+    out.fill(SKIPPED_HASH);
+    Ok(h.drive(&mut |pos: usize, hashes: &[u64]| out[pos] = hashes[0]))
+}
+
+/// The maximal `A/C/G/T` runs (either case) in `seq` that are long enough
+/// to hold a `k`‑mer, in order: every valid k‑mer lies in exactly one
+/// segment, and a segment of length `n` holds `n - k + 1` of them.
+///
+/// Segments are independent — no k‑mer spans two — so they can be hashed
+/// in parallel, each as its own sequence, with positions offset by the
+/// segment start.  [`NtHash`] walks the same runs internally, which is why
+/// its rolls need no per‑base check.
+///
+/// ```
+/// use nthash_rs::{valid_segments, NtHash};
+///
+/// let seq = b"ACGTNNACNGATTACA";
+/// assert_eq!(valid_segments(seq, 3).collect::<Vec<_>>(), [0..4, 9..16]);
+/// assert_eq!(valid_segments(seq, 2).count(), 3);
+///
+/// let mut starts = Vec::new();
+/// for segment in valid_segments(seq, 3) {
+///     let mut h = NtHash::new(&seq[segment.clone()], 3, 1, 0).unwrap();
+///     while h.roll() {
+///         starts.push(segment.start + h.pos());
+///     }
+/// }
+/// assert_eq!(starts, [0, 1, 9, 10, 11, 12, 13]);
+/// ```
+pub fn valid_segments(seq: &[u8], k: u16) -> ValidSegments<'_> {
+    ValidSegments {
+        seq,
+        k: (k as usize).max(1),
+        pos: 0,
+    }
+}
+
+/// Iterator over the ACGT runs of a sequence; see [`valid_segments`].
+#[derive(Debug, Clone)]
+pub struct ValidSegments<'a> {
+    seq: &'a [u8],
+    k: usize,
+    pos: usize,
+}
+
+impl Iterator for ValidSegments<'_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        while self.pos < self.seq.len() {
+            let rest = &self.seq[self.pos..];
+            let start = self.pos + rest.iter().position(|&c| is_acgt(c))?;
+            let end = start + acgt_run(&self.seq[start..]);
+            self.pos = end;
+            if end - start >= self.k {
+                return Some(start..end);
+            }
+        }
+        None
+    }
+}
+
+impl std::iter::FusedIterator for ValidSegments<'_> {}
+
+/// Bases [`NtHash`] checks ahead in one go when it leaves the run of
+/// valid bases it last checked.
+const SCAN_CHUNK: usize = 256;
+
+/// Windows hashed per block by [`hash_block`]; the block's bytes are
+/// prefetched while the previous block is hashed.
+const HASH_BLOCK: usize = 16 * 1024;
+
+/// Every byte mapped to itself if it is `A/C/G/T`, and to `A` otherwise, so
+/// the rolling recurrence can run through invalid bases without branching.
+const BLOCK_XLAT: [u8; 256] = {
+    let mut t = [b'A'; 256];
+    let mut i = 0;
+    while i < 256 {
+        if is_acgt(i as u8) {
+            t[i] = i as u8;
+        }
+        i += 1;
+    }
+    t
+};
+
+/// [`hash_into`] for throughput: the same output, computed block by block
+/// with software prefetching and no data‑dependent branches per base.
+///
+/// Invalid bases are rolled through as `A` while a running count of
+/// consecutive valid bases decides, with a select rather than a branch,
+/// whether each window's slot gets its hash or [`SKIPPED_HASH`].  Because
+/// the recurrence is exact for any substituted base, hashes are correct
+/// again as soon as the substitute leaves the window.  Prefer this over
+/// [`hash_into`] for long sequences with scattered `N`s; for a handful of
+/// windows the two are equivalent.
+///
+/// ```
+/// use nthash_rs::{hash_block, hash_into};
+///
+/// let seq = b"ACGTNACGTACGGATTACANNCAGT";
+/// let (mut block, mut rolled) = ([0u64; 20], [0u64; 20]);
+/// assert_eq!(hash_block(seq, 6, &mut block).unwrap(), hash_into(seq, 6, &mut rolled).unwrap());
+/// assert_eq!(block, rolled);
+/// ```
+///
+/// # Errors
+///
+/// As [`hash_into`].
+pub fn hash_block(seq: &[u8], k: u16, out: &mut [u64]) -> Result<usize> {
+    NtHash::new(seq, k, 1, 0)?;
+    let k_usz = k as usize;
+    let expected = seq.len() - k_usz + 1;
+    if out.len() != expected {
+        return Err(NtHashError::OutputLengthMismatch {
+            expected,
+            actual: out.len(),
+        });
+    }
+
+    // per‑byte recurrence terms for this k, with invalid bytes as `A`
+    let mut terms = [[0u64; 4]; 256];
+    for (c, t) in terms.iter_mut().enumerate() {
+        let x = BLOCK_XLAT[c];
+        *t = [
+            SEED_TAB[x as usize],
+            srol_table(x, k as u32),
+            srol_table(x & CP_OFF, k as u32),
+            SEED_TAB[(x & CP_OFF) as usize],
+        ];
+    }
+
+    // run: consecutive valid bases ending at the window's last base
+    let mut run = 0usize;
+    for &c in &seq[..k_usz] {
+        run = (run + 1) * usize::from(is_acgt(c));
+    }
+    let first: Vec<u8> = seq[..k_usz]
+        .iter()
+        .map(|&c| BLOCK_XLAT[c as usize])
+        .collect();
+    let (mut fwd, mut rev) = (base_forward_hash(&first, k), base_reverse_hash(&first, k));
+    let mut valid = usize::from(run >= k_usz);
+    out[0] = if run >= k_usz {
+        canonical(fwd, rev)
+    } else {
+        SKIPPED_HASH
+    };
+
+    // window `pos` drops `seq[pos - 1]` and takes in `seq[pos + k - 1]`
+    let (outgoing, incoming) = (&seq[..expected - 1], &seq[k_usz..]);
+    for start in (1..expected).step_by(HASH_BLOCK) {
+        let end = (start + HASH_BLOCK).min(expected);
+        prefetch(&incoming[end - 1..]);
+        let bases = outgoing[start - 1..end - 1]
+            .iter()
+            .zip(&incoming[start - 1..end - 1]);
+        for (slot, (&c_out, &c_in)) in out[start..end].iter_mut().zip(bases) {
+            let (t_out, t_in) = (&terms[c_out as usize], &terms[c_in as usize]);
+            fwd = srol(fwd) ^ t_in[0] ^ t_out[1];
+            rev = sror(rev ^ t_in[2] ^ t_out[3]);
+            run = (run + 1) * usize::from(is_acgt(c_in));
+            let ok = run >= k_usz;
+            *slot = if ok {
+                canonical(fwd, rev)
+            } else {
+                SKIPPED_HASH
+            };
+            valid += usize::from(ok);
+        }
+    }
+    Ok(valid)
+}
+
+/// Hint the next `HASH_BLOCK` bytes of `ahead` into cache.
+#[inline(always)]
+fn prefetch(ahead: &[u8]) {
+    #[cfg(target_arch = "x86_64")]
+    for line in ahead.chunks(64).take(HASH_BLOCK / 64) {
+        // SAFETY: prefetching is a hint and never faults; `line` is a
+        // valid, non‑empty slice
+        unsafe {
+            std::arch::x86_64::_mm_prefetch::<{ std::arch::x86_64::_MM_HINT_T0 }>(
+                line.as_ptr().cast(),
+            );
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ahead;
+}
+
+/// Validate `kmer` and hash both strands.
+fn kmer_strands(kmer: &[u8]) -> Result<(u64, u64, u16)> {
+    let k = match u16::try_from(kmer.len()) {
+        Ok(0) | Err(_) => return Err(NtHashError::InvalidK),
+        Ok(k) => k,
+    };
+    check_bases(kmer, 0..kmer.len(), &[])?;
+    Ok((base_forward_hash(kmer, k), base_reverse_hash(kmer, k), k))
+}
+
+#[inline(always)]
+pub fn has_invalid_base(seq: &[u8], k: usize, pos_n: &mut usize) -> bool {
+    if let Some(idx) = seq[..k].iter().rposition(|&c| !is_acgt(c)) {
+        *pos_n = idx;
+        true
+    } else {
+        false
+    }
+}
+
+#[inline]
+pub fn base_forward_hash(seq: &[u8], k: u16) -> u64 {
+    forward_hash_of(seq, k as usize)
+}
+
+/// [`base_forward_hash`] for a window of any length.
+#[inline]
+fn forward_hash_of(seq: &[u8], k: usize) -> u64 {
+    let mut h = 0_u64;
+
+    #[cfg(not(feature = "simd"))]
+    for chunk in seq[..k - k % 4].chunks_exact(4) {
         h = srol_n(h, 4);
 
         // build 0‑255 index with 8‑bit wrapping
@@ -257,6 +1464,19 @@ pub fn base_forward_hash(seq: &[u8], k: u16) -> u64 {
         h ^= TETRAMER_TAB[idx & 0xFF];
     }
 
+    #[cfg(feature = "simd")]
+    {
+        let mut idx = [0u8; SIMD_BLOCK / 4];
+        for block in seq[..k - k % 4].chunks(SIMD_BLOCK) {
+            let n = block.len() / 4;
+            crate::simd::tetramer_indices(block, false, &mut idx[..n]);
+            for &i in &idx[..n] {
+                h = srol_n(h, 4);
+                h ^= TETRAMER_TAB[i as usize];
+            }
+        }
+    }
+
     h = srol_n(h, (k % 4) as u32);
     match k % 4 {
         3 => {
@@ -278,7 +1498,12 @@ pub fn base_forward_hash(seq: &[u8], k: u16) -> u64 {
 
 #[inline]
 pub fn base_reverse_hash(seq: &[u8], k: u16) -> u64 {
-    let k = k as usize;
+    reverse_hash_of(seq, k as usize)
+}
+
+/// [`base_reverse_hash`] for a window of any length.
+#[inline]
+fn reverse_hash_of(seq: &[u8], k: usize) -> u64 {
     let mut h = 0_u64;
 
     // Handle the ‘tail’ (k % 4 = 1,2,3)
@@ -302,23 +1527,65 @@ pub fn base_reverse_hash(seq: &[u8], k: u16) -> u64 {
     }
 
     // Process full 4‑mer chunks in reverse order
-    let mut i = k - k % 4;
-    while i >= 4 {
-        // split‑rotate the accumulator by 4
-        h = srol_n(h, 4);
+    #[cfg(not(feature = "simd"))]
+    {
+        let mut i = k - k % 4;
+        while i >= 4 {
+            // split‑rotate the accumulator by 4
+            h = srol_n(h, 4);
 
-        // build 4‑mer index, mask to 8 bits
-        let idx = (RC_CONVERT_TAB[seq[i - 1] as usize] as usize) * 64
-            + (RC_CONVERT_TAB[seq[i - 2] as usize] as usize) * 16
-            + (RC_CONVERT_TAB[seq[i - 3] as usize] as usize) * 4
-            + RC_CONVERT_TAB[seq[i - 4] as usize] as usize;
-        h ^= TETRAMER_TAB[idx & 0xFF];
+            // build 4‑mer index, mask to 8 bits
+            let idx = (RC_CONVERT_TAB[seq[i - 1] as usize] as usize) * 64
+                + (RC_CONVERT_TAB[seq[i - 2] as usize] as usize) * 16
+                + (RC_CONVERT_TAB[seq[i - 3] as usize] as usize) * 4
+                + RC_CONVERT_TAB[seq[i - 4] as usize] as usize;
+            h ^= TETRAMER_TAB[idx & 0xFF];
 
-        i -= 4;
+            i -= 4;
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    {
+        let mut idx = [0u8; SIMD_BLOCK / 4];
+        for block in seq[..k - k % 4].rchunks(SIMD_BLOCK) {
+            let n = block.len() / 4;
+            crate::simd::tetramer_indices(block, true, &mut idx[..n]);
+            for &i in idx[..n].iter().rev() {
+                h = srol_n(h, 4);
+                h ^= TETRAMER_TAB[i as usize];
+            }
+        }
     }
     h
 }
 
+/// Bases converted to tetramer indices per vectorised batch.
+#[cfg(feature = "simd")]
+const SIMD_BLOCK: usize = 256;
+
+/// Mask of the low `2k` bits.
+#[inline(always)]
+fn pack_mask(k: u16) -> u128 {
+    u128::MAX >> (128 - 2 * k as u32)
+}
+
+/// Strand hashes of a k‑mer after replacing the base at `offset` (counted
+/// from the window start) `old` → `new`.
+///
+/// The base at offset `i` contributes `srol^(k-1-i)` of its seed to the
+/// forward hash and `srol^i` of its complement's seed to the reverse hash,
+/// so swapping it costs two table lookups per strand.
+#[inline(always)]
+pub(crate) fn sub_hash(fwd: u64, rev: u64, k: u16, offset: usize, old: u8, new: u8) -> (u64, u64) {
+    let d_fwd = k as u32 - 1 - offset as u32;
+    let d_rev = offset as u32;
+    (
+        fwd ^ srol_table(old, d_fwd) ^ srol_table(new, d_fwd),
+        rev ^ srol_table(old & CP_OFF, d_rev) ^ srol_table(new & CP_OFF, d_rev),
+    )
+}
+
 #[inline(always)]
 fn next_forward_hash(prev: u64, k: u16, char_out: u8, char_in: u8) -> u64 {
     let mut h = srol(prev);
@@ -353,12 +1620,29 @@ fn prev_reverse_hash(prev: u64, k: u16, char_out: u8, char_in: u8) -> u64 {
 // Builder + Iterator facade
 // -------------------------------------------------------------------------
 
+/// Order in which an iterator visits k‑mers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// First k‑mer to last, rolling forward.
+    #[default]
+    Forward,
+    /// Last k‑mer to first, rolling backward.
+    Reverse,
+}
+
 /// Configure and consume a rolling‐hash computation as an iterator.
 pub struct NtHashBuilder<'a> {
     seq: &'a [u8],
     k: u16,
     num_hashes: u8,
-    pos: usize,
+    pos: Option<usize>,
+    direction: Direction,
+    bisulfite: bool,
+    small_k: bool,
+    strict: bool,
+    mask: Vec<Range<usize>>,
+    mixer: Mixer,
+    policy: CanonicalPolicy,
 }
 
 impl<'a> NtHashBuilder<'a> {
@@ -368,7 +1652,14 @@ impl<'a> NtHashBuilder<'a> {
             seq,
             k: 0,
             num_hashes: 1,
-            pos: 0,
+            pos: None,
+            direction: Direction::Forward,
+            bisulfite: false,
+            small_k: false,
+            strict: false,
+            mask: Vec::new(),
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
         }
     }
 
@@ -384,49 +1675,1575 @@ impl<'a> NtHashBuilder<'a> {
         self
     }
 
-    /// Set the starting position.
+    /// Set the starting position: the first k‑mer considered going
+    /// forward (default `0`), or the last going in reverse (default the
+    /// final window).
     pub fn pos(mut self, pos: usize) -> Self {
-        self.pos = pos;
+        self.pos = Some(pos);
+        self
+    }
+
+    /// Set the iteration order (default [`Direction::Forward`]).
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Hash under bisulfite conversion (default `false`): `C` collapses
+    /// into `T`, and `G` into `A` on the reverse strand, so converted reads
+    /// hash like the reference they came from.  See [`Bisulfite`].
+    ///
+    /// ```
+    /// use nthash_rs::NtHashBuilder;
+    ///
+    /// let reference = b"ACGTTGCAAGCTCGGA";
+    /// let read = b"ATGTTGTAAGTTCGGA"; // unmethylated Cs read as T
+    /// let hashes = |seq: &[u8]| -> Vec<u64> {
+    ///     NtHashBuilder::new(seq)
+    ///         .k(7)
+    ///         .bisulfite(true)
+    ///         .finish()
+    ///         .unwrap()
+    ///         .map(|(_, h)| h[0])
+    ///         .collect()
+    /// };
+    /// assert_eq!(hashes(reference), hashes(read));
+    /// ```
+    pub fn bisulfite(mut self, bisulfite: bool) -> Self {
+        self.bisulfite = bisulfite;
+        self
+    }
+
+    /// Hash with the table‑free [`PackedHash`] (default `false`), faster
+    /// for `k ≤ 32`.  Its values are a different hash family from ntHash,
+    /// so only compare them with other `small_k` output.  Ignored under
+    /// [`bisulfite`](Self::bisulfite).
+    ///
+    /// ```
+    /// use nthash_rs::{packed::PackedHash, NtHashBuilder};
+    ///
+    /// let seq = b"ACGTTGCANAGCTCGGA";
+    /// let mut direct = PackedHash::new(seq, 7, 1, 0).unwrap();
+    /// for (pos, hashes) in NtHashBuilder::new(seq).k(7).small_k(true).finish().unwrap() {
+    ///     assert!(direct.roll());
+    ///     assert_eq!((pos.start, hashes[0]), (direct.pos(), direct.hashes()[0]));
+    /// }
+    /// ```
+    pub fn small_k(mut self, small_k: bool) -> Self {
+        self.small_k = small_k;
+        self
+    }
+
+    /// Skip k‑mers overlapping any of the `intervals` (e.g. repeat
+    /// annotations), jumping past each as over a run of `N`s.  Intervals
+    /// are half‑open base ranges, in any order, and may overlap; repeated
+    /// calls add to the mask.
+    ///
+    /// ```
+    /// use nthash_rs::NtHashBuilder;
+    ///
+    /// let seq = b"ACGTACGTACGTACGT";
+    /// let starts: Vec<usize> = NtHashBuilder::new(seq)
+    ///     .k(4)
+    ///     .mask([5..8])
+    ///     .finish()
+    ///     .unwrap()
+    ///     .map(|(pos, _)| pos.start)
+    ///     .collect();
+    /// assert_eq!(starts, [0, 1, 8, 9, 10, 11, 12]);
+    /// ```
+    pub fn mask<I: IntoIterator<Item = Range<usize>>>(mut self, intervals: I) -> Self {
+        self.mask
+            .extend(intervals.into_iter().filter(|r| !r.is_empty()));
+        self
+    }
+
+    /// Reject non‑ACGT bases instead of skipping them (default `false`),
+    /// for pipelines where an `N` means corrupted data rather than
+    /// ambiguity.  Bases inside [`mask`](Self::mask)ed intervals are not
+    /// checked.
+    ///
+    /// ```
+    /// use nthash_rs::{NtHashBuilder, NtHashError};
+    ///
+    /// let err = NtHashBuilder::new(b"ACGTNACGT").k(3).strict(true).finish().err();
+    /// assert_eq!(err, Some(NtHashError::InvalidBase { pos: 4, byte: b'N' }));
+    /// ```
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Derive the extra hashes with `mixer` (default the reference
+    /// scheme); see [`HashMixer`].
+    pub fn mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        self
+    }
+
+    /// Combine the strand hashes under `policy` (default
+    /// [`CanonicalPolicy::Sum`], the ntHash definition).  Ignored under
+    /// [`small_k`](Self::small_k), whose canonical value is the smaller
+    /// packed strand.
+    pub fn canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
         self
     }
 
     /// Finalize into an iterator.
+    ///
+    /// # Errors
+    ///
+    /// As [`NtHash::new`], plus [`NtHashError::InvalidNumHashes`] for zero
+    /// hashes and [`NtHashError::KTooLarge`] for [`small_k`](Self::small_k)
+    /// with `k > 32`; in [`strict`](Self::strict) mode also
+    /// [`NtHashError::InvalidBase`] for the first non‑ACGT base the
+    /// iterator would visit.
     pub fn finish(self) -> Result<NtHashIter<'a>> {
-        let hasher = NtHash::new(self.seq, self.k, self.num_hashes, self.pos)?;
-        Ok(NtHashIter {
+        if self.k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if self.num_hashes == 0 {
+            return Err(NtHashError::InvalidNumHashes);
+        }
+        let mask = merge_intervals(self.mask);
+        if self.strict && self.k as usize <= self.seq.len() {
+            let visited = match (self.direction, self.pos) {
+                (Direction::Forward, pos) => pos.unwrap_or(0)..self.seq.len(),
+                (Direction::Reverse, Some(pos)) => 0..(pos + self.k as usize).min(self.seq.len()),
+                (Direction::Reverse, None) => 0..self.seq.len(),
+            };
+            check_bases(self.seq, visited, &mask)?;
+        }
+        if self.bisulfite || self.small_k {
+            let reverse = self.direction == Direction::Reverse;
+            let start = if reverse { 0 } else { self.pos.unwrap_or(0) };
+            let inner = if self.bisulfite {
+                let mut hasher = RollingHash::with_alphabet(
+                    Bisulfite,
+                    self.seq,
+                    self.k,
+                    self.num_hashes,
+                    start,
+                )?;
+                if let Some(mixer) = self.mixer {
+                    hasher = hasher.with_mixer(mixer);
+                }
+                hasher = hasher.with_canonical_policy(self.policy);
+                IterInner::Bisulfite(AltScan::new(hasher, reverse, self.pos)?)
+            } else {
+                let mut hasher = PackedHash::new(self.seq, self.k, self.num_hashes, start)?;
+                if let Some(mixer) = self.mixer {
+                    hasher = hasher.with_mixer(mixer);
+                }
+                IterInner::Packed(AltScan::new(hasher, reverse, self.pos)?)
+            };
+            return Ok(NtHashIter { inner, mask });
+        }
+        let inner = match self.direction {
+            Direction::Forward => {
+                let mut hasher =
+                    NtHash::new(self.seq, self.k, self.num_hashes, self.pos.unwrap_or(0))?;
+                hasher.mixer = self.mixer;
+                hasher.policy = self.policy;
+                IterInner::Forward {
+                    hasher,
+                    done: false,
+                }
+            }
+            Direction::Reverse => {
+                let mut rev = NtHashRevIter::new(self.seq, self.k, self.num_hashes)?;
+                rev.hasher.mixer = self.mixer;
+                rev.hasher.policy = self.policy;
+                if let Some(pos) = self.pos {
+                    rev.seek(pos)?;
+                }
+                IterInner::Reverse(rev)
+            }
+        };
+        Ok(NtHashIter { inner, mask })
+    }
+}
+
+/// The first non‑ACGT base in `range` of `seq` outside `mask`.
+fn check_bases(seq: &[u8], range: Range<usize>, mask: &[Range<usize>]) -> Result<()> {
+    let mut masked = mask.iter().peekable();
+    for pos in range {
+        while masked.next_if(|m| m.end <= pos).is_some() {}
+        if masked.peek().is_some_and(|m| m.start <= pos) {
+            continue;
+        }
+        let byte = seq[pos];
+        if !is_acgt(byte) {
+            return Err(NtHashError::InvalidBase { pos, byte });
+        }
+    }
+    Ok(())
+}
+
+/// `A/C/G/T` in either case: the bases [`NtHash`] and [`valid_segments`]
+/// accept.
+#[inline(always)]
+pub(crate) const fn is_acgt(c: u8) -> bool {
+    matches!(c.to_ascii_uppercase(), b'A' | b'C' | b'G' | b'T')
+}
+
+/// Length of the run of `A/C/G/T` bases that `seq` starts with.
+#[inline]
+fn acgt_run(seq: &[u8]) -> usize {
+    seq.iter().position(|&c| !is_acgt(c)).unwrap_or(seq.len())
+}
+
+/// Sort `intervals` and merge those that overlap or touch.
+fn merge_intervals(mut intervals: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    intervals.sort_unstable_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(intervals.len());
+    for r in intervals {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// Iterator yielding `(KmerPos, Vec<u64>)` for each valid k‑mer, in the
+/// builder's [`Direction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NtHashIter<'a> {
+    inner: IterInner<'a>,
+    /// Sorted, disjoint masked intervals.
+    mask: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum IterInner<'a> {
+    Forward { hasher: NtHash<'a>, done: bool },
+    Reverse(NtHashRevIter<'a>),
+    Bisulfite(AltScan<RollingHash<'a, Bisulfite>>),
+    Packed(AltScan<PackedHash<'a>>),
+}
+
+/// A scan in either direction over a hasher other than [`NtHash`].
+///
+/// When reversing, `back_from` holds the start position until the first
+/// k‑mer is found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AltScan<H> {
+    hasher: H,
+    reverse: bool,
+    back_from: Option<usize>,
+    done: bool,
+}
+
+impl<'a, H: SeekableHasher<'a>> AltScan<H> {
+    /// Scan with `hasher`, starting in reverse from `pos` (default the
+    /// last window); a forward `hasher` is already placed at `pos`.
+    fn new(hasher: H, reverse: bool, pos: Option<usize>) -> Result<Self> {
+        let (seq_len, last) = (hasher.seq().len(), hasher.end());
+        let back_from = match pos {
+            Some(pos) if reverse && pos > last => {
+                return Err(NtHashError::PositionOutOfRange { pos, seq_len });
+            }
+            pos => reverse.then(|| pos.unwrap_or(last)),
+        };
+        Ok(Self {
             hasher,
+            reverse,
+            back_from,
             done: false,
         })
     }
+
+    fn advance(&mut self) -> Option<(KmerPos, Vec<u64>)> {
+        if self.done {
+            return None;
+        }
+        let found = match (self.reverse, self.back_from.take()) {
+            (false, _) => self.hasher.roll(),
+            (true, Some(pos)) => self.hasher.init_back_from(pos),
+            (true, None) => self.hasher.roll_back(),
+        };
+        if !found {
+            self.done = true;
+            return None;
+        }
+        Some((self.hasher.kmer_pos(), self.hasher.hashes().to_owned()))
+    }
+
+    fn skip_masked(&mut self, k: usize, masked: Range<usize>) {
+        if self.reverse {
+            match masked.start.checked_sub(k) {
+                Some(pos) => self.back_from = Some(pos),
+                None => self.done = true,
+            }
+        } else if !self.hasher.restart_at(masked.end) {
+            self.done = true;
+        }
+    }
 }
 
-/// Iterator yielding `(pos, Vec<u64>)` for each valid k‑mer.
-pub struct NtHashIter<'a> {
+impl<'a> Iterator for NtHashIter<'a> {
+    type Item = (KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.advance()?;
+            let i = self.mask.partition_point(|m| m.end <= item.0.start);
+            match self.mask.get(i) {
+                Some(m) if m.start < item.0.end => {
+                    let masked = m.clone();
+                    self.skip_masked(item.0, masked);
+                }
+                _ => return Some(item),
+            }
+        }
+    }
+}
+
+impl NtHashIter<'_> {
+    /// The next valid k‑mer, masked or not.
+    fn advance(&mut self) -> Option<(KmerPos, Vec<u64>)> {
+        match &mut self.inner {
+            IterInner::Forward { hasher, done } => {
+                if *done {
+                    return None;
+                }
+                if !hasher.roll() {
+                    *done = true;
+                    return None;
+                }
+                Some((hasher.kmer_pos(), hasher.hashes().to_owned()))
+            }
+            IterInner::Reverse(rev) => rev.next(),
+            IterInner::Bisulfite(scan) => scan.advance(),
+            IterInner::Packed(scan) => scan.advance(),
+        }
+    }
+
+    /// Move past `masked`, which overlaps the k‑mer at `at`, so that the
+    /// next k‑mer reached lies entirely before or after it.
+    fn skip_masked(&mut self, at: KmerPos, masked: Range<usize>) {
+        let k = at.end - at.start;
+        match &mut self.inner {
+            IterInner::Forward { hasher, done } => {
+                if masked.end > hasher.end {
+                    *done = true;
+                } else {
+                    hasher.pos = masked.end;
+                    hasher.initialized = false;
+                }
+            }
+            IterInner::Reverse(rev) => match masked.start.checked_sub(k) {
+                Some(pos) => {
+                    rev.hasher.pos = pos;
+                    rev.pending = true;
+                }
+                None => rev.done = true,
+            },
+            IterInner::Bisulfite(scan) => scan.skip_masked(k, masked),
+            IterInner::Packed(scan) => scan.skip_masked(k, masked),
+        }
+    }
+}
+
+/// Iterator over the valid k‑mers of a sequence from last to first,
+/// yielding `(KmerPos, Vec<u64>)`.
+///
+/// Consecutive k‑mers are reached with [`NtHash::roll_back`]; runs of
+/// invalid bases are jumped over backward, so the output is exactly the
+/// forward iteration reversed.  [`seek`](Self::seek) restarts the scan
+/// from any position, e.g. for right‑to‑left seeding.
+///
+/// ```
+/// use nthash_rs::{kmer::Direction, NtHashBuilder};
+///
+/// let seq = b"ACGTNCGTAC";
+/// let starts: Vec<usize> = NtHashBuilder::new(seq)
+///     .k(3)
+///     .direction(Direction::Reverse)
+///     .finish()
+///     .unwrap()
+///     .map(|(pos, _)| pos.start)
+///     .collect();
+/// assert_eq!(starts, [7, 6, 5, 1, 0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NtHashRevIter<'a> {
     hasher: NtHash<'a>,
+    // the next call re‑initializes backward from `hasher.pos`
+    pending: bool,
     done: bool,
 }
 
-impl<'a> Iterator for NtHashIter<'a> {
-    type Item = (usize, Vec<u64>);
+impl<'a> NtHashRevIter<'a> {
+    /// Iterate `seq` backward from its last k‑mer.
+    ///
+    /// # Errors
+    ///
+    /// As [`NtHash::new`].
+    pub fn new(seq: &'a [u8], k: u16, num_hashes: u8) -> Result<Self> {
+        let mut hasher = NtHash::new(seq, k, num_hashes, 0)?;
+        hasher.pos = hasher.end;
+        Ok(Self {
+            hasher,
+            pending: true,
+            done: false,
+        })
+    }
+
+    /// Continue from the last valid k‑mer starting at or before `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::PositionOutOfRange`] if `pos > seq.len() - k`.
+    pub fn seek(&mut self, pos: usize) -> Result<()> {
+        let seq_len = self.hasher.seq.len();
+        if pos > seq_len - self.hasher.k as usize {
+            return Err(NtHashError::PositionOutOfRange { pos, seq_len });
+        }
+        self.hasher.pos = pos;
+        self.pending = true;
+        self.done = false;
+        Ok(())
+    }
+
+    /// The underlying hasher, positioned on the last k‑mer yielded.
+    pub fn hasher(&self) -> &NtHash<'a> {
+        &self.hasher
+    }
+}
+
+impl Iterator for NtHashRevIter<'_> {
+    type Item = (KmerPos, Vec<u64>);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
             return None;
         }
-        if !self.hasher.roll() {
+        let found = if std::mem::take(&mut self.pending) {
+            self.hasher.init_back()
+        } else {
+            self.hasher.retreat()
+        };
+        if !found {
             self.done = true;
             return None;
         }
-        let out = (self.hasher.pos(), self.hasher.hashes().to_owned());
-        Some(out)
+        Some((self.hasher.kmer_pos(), self.hasher.hashes().to_owned()))
+    }
+}
+
+impl<'a> NtHashIter<'a> {
+    /// Also yield each k‑mer's bases: `(pos, window, hashes)` with
+    /// `window == &seq[pos.range()]`.
+    ///
+    /// ```
+    /// use nthash_rs::NtHashBuilder;
+    ///
+    /// let seq = b"ACGTNCGTA";
+    /// let kmers: Vec<&[u8]> = NtHashBuilder::new(seq)
+    ///     .k(3)
+    ///     .finish()
+    ///     .unwrap()
+    ///     .with_kmers()
+    ///     .map(|(_, window, _)| window)
+    ///     .collect();
+    /// assert_eq!(kmers, [&b"ACG"[..], b"CGT", b"CGT", b"GTA"]);
+    /// ```
+    pub fn with_kmers(self) -> WithKmers<'a> {
+        WithKmers { inner: self }
+    }
+}
+
+/// Iterator yielding `(KmerPos, window, Vec<u64>)`; see
+/// [`NtHashIter::with_kmers`].
+pub struct WithKmers<'a> {
+    inner: NtHashIter<'a>,
+}
+
+impl<'a> Iterator for WithKmers<'a> {
+    type Item = (KmerPos, &'a [u8], Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (pos, hashes) = self.inner.next()?;
+        let seq = match &self.inner.inner {
+            IterInner::Forward { hasher, .. } => hasher.seq,
+            IterInner::Reverse(rev) => rev.hasher.seq,
+            IterInner::Bisulfite(scan) => scan.hasher.seq(),
+            IterInner::Packed(scan) => scan.hasher.seq(),
+        };
+        Some((pos, &seq[pos.range()], hashes))
     }
 }
 
 impl<'a> IntoIterator for NtHashBuilder<'a> {
-    type Item = (usize, Vec<u64>);
+    type Item = (KmerPos, Vec<u64>);
     type IntoIter = NtHashIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.finish().expect("invalid NtHashBuilder configuration")
     }
 }
+
+/// Iterator over the k‑mers of several records, yielding
+/// `(record_idx, KmerPos, Vec<u64>)`.
+///
+/// Each record is hashed independently, so no k‑mer ever spans two
+/// records; records shorter than `k` yield nothing.
+///
+/// ```
+/// use nthash_rs::NtHashMultiIter;
+///
+/// let records: [&[u8]; 3] = [b"ACGTA", b"AC", b"TTGCA"];
+/// let hits: Vec<(usize, usize)> = NtHashMultiIter::new(records, 4, 1)
+///     .unwrap()
+///     .map(|(rec, pos, _)| (rec, pos.start))
+///     .collect();
+/// assert_eq!(hits, [(0, 0), (0, 1), (2, 0), (2, 1)]);
+/// ```
+pub struct NtHashMultiIter<'a, I> {
+    records: I,
+    k: u16,
+    num_hashes: u8,
+    next_idx: usize,
+    current: Option<(usize, NtHash<'a>)>,
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> NtHashMultiIter<'a, I> {
+    /// Hash every record of `records` with `k`‑mers and `num_hashes` hashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns if `k == 0`.
+    pub fn new<R>(records: R, k: u16, num_hashes: u8) -> Result<Self>
+    where
+        R: IntoIterator<IntoIter = I>,
+    {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        Ok(Self {
+            records: records.into_iter(),
+            k,
+            num_hashes,
+            next_idx: 0,
+            current: None,
+        })
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> Iterator for NtHashMultiIter<'a, I> {
+    type Item = (usize, KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((idx, hasher)) = &mut self.current {
+                if hasher.roll() {
+                    return Some((*idx, hasher.kmer_pos(), hasher.hashes().to_owned()));
+                }
+                self.current = None;
+            }
+            let seq = self.records.next()?;
+            let idx = self.next_idx;
+            self.next_idx += 1;
+            // too‑short records simply contribute no k‑mers
+            self.current = NtHash::new(seq, self.k, self.num_hashes, 0)
+                .ok()
+                .map(|h| (idx, h));
+        }
+    }
+}
+
+// -------------------------------------------------------------------------
+// Hashable k‑mer keys
+// -------------------------------------------------------------------------
+
+/// Base‑wise complement used by [`CanonicalKmer`]; other bytes map to
+/// themselves.
+#[inline]
+fn complement(c: u8) -> u8 {
+    match c {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        b'a' => b't',
+        b'c' => b'g',
+        b'g' => b'c',
+        b't' => b'a',
+        other => other,
+    }
+}
+
+/// A strand‑specific k‑mer usable as a map key.
+///
+/// Equality and ordering are those of the bytes; [`Hash`](std::hash::Hash)
+/// feeds the forward ntHash of the window to the hasher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Kmer<'a>(&'a [u8]);
+
+impl<'a> Kmer<'a> {
+    /// Wrap a window of any length.
+    pub fn new(seq: &'a [u8]) -> Self {
+        Self(seq)
+    }
+
+    /// The wrapped bases.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Forward‑strand ntHash of the k‑mer.
+    pub fn nthash(&self) -> u64 {
+        forward_hash_of(self.0, self.0.len())
+    }
+}
+
+impl std::hash::Hash for Kmer<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.nthash());
+    }
+}
+
+/// A strand‑neutral k‑mer usable as a map key.
+///
+/// A k‑mer and its reverse complement compare equal, hash identically
+/// (canonical ntHash), and order by the lexicographically smaller of the
+/// two orientations.  A window holding any base other than ACGT is equal
+/// only to itself: ntHash does not hash such bases symmetrically across
+/// strands, so its reverse complement may hash differently.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use nthash_rs::kmer::CanonicalKmer;
+///
+/// let mut counts = HashMap::new();
+/// for kmer in [&b"ACGGT"[..], b"ACCGT", b"TTTTT"] {
+///     *counts.entry(CanonicalKmer::new(kmer)).or_insert(0) += 1;
+/// }
+/// assert_eq!(counts[&CanonicalKmer::new(b"ACCGT")], 2);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CanonicalKmer<'a>(&'a [u8]);
+
+impl<'a> CanonicalKmer<'a> {
+    /// Wrap a window of any length.
+    pub fn new(seq: &'a [u8]) -> Self {
+        Self(seq)
+    }
+
+    /// The wrapped bases, in the orientation they were given.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Canonical ntHash of the k‑mer.
+    pub fn nthash(&self) -> u64 {
+        let k = self.0.len();
+        crate::util::canonical(forward_hash_of(self.0, k), reverse_hash_of(self.0, k))
+    }
+
+    /// Returns `true` if the given orientation is the canonical one.
+    pub fn is_forward(&self) -> bool {
+        self.strand(true).le(self.strand(false))
+    }
+
+    fn strand(&self, forward: bool) -> impl Iterator<Item = u8> + 'a {
+        let seq = self.0;
+        let n = seq.len();
+        (0..n).map(move |i| {
+            if forward {
+                seq[i]
+            } else {
+                complement(seq[n - 1 - i])
+            }
+        })
+    }
+
+    /// Bases of the canonical orientation.
+    pub fn canonical_bases(&self) -> impl Iterator<Item = u8> + 'a {
+        self.strand(self.is_forward())
+    }
+
+    /// The bases compared and ordered by: the canonical orientation, or
+    /// the given one if a base is not ACGT.
+    fn key(&self) -> impl Iterator<Item = u8> + 'a {
+        let acgt = self.0.iter().all(|&c| is_acgt(c));
+        self.strand(!acgt || self.is_forward())
+    }
+}
+
+impl PartialEq for CanonicalKmer<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.key().eq(other.key())
+    }
+}
+
+impl Eq for CanonicalKmer<'_> {}
+
+impl PartialOrd for CanonicalKmer<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CanonicalKmer<'_> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(other.key())
+    }
+}
+
+impl std::hash::Hash for CanonicalKmer<'_> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.nthash());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashSet};
+
+    #[test]
+    fn bisulfite_collapses_c_to_t_in_both_directions() {
+        let reference = b"GATTAGACGTTGCAAGNCTCGGACCTAGCATCGACTACGN";
+        let read: Vec<u8> = reference
+            .iter()
+            .map(|&b| if b == b'C' { b'T' } else { b })
+            .collect();
+        let run = |seq: &[u8], direction, bisulfite| -> Vec<(KmerPos, Vec<u64>)> {
+            NtHashBuilder::new(seq)
+                .k(5)
+                .num_hashes(2)
+                .direction(direction)
+                .bisulfite(bisulfite)
+                .finish()
+                .unwrap()
+                .collect()
+        };
+        let fwd = run(reference, Direction::Forward, true);
+        assert_eq!(fwd, run(&read, Direction::Forward, true));
+        assert_ne!(fwd, run(reference, Direction::Forward, false));
+        // same windows as plain ntHash, and a C‑free k‑mer hashes the same
+        let plain = run(reference, Direction::Forward, false);
+        assert!(fwd.iter().zip(&plain).all(|(b, p)| b.0 == p.0));
+        assert_eq!((fwd.len(), &fwd[0]), (plain.len(), &plain[0]));
+        let mut back = run(reference, Direction::Reverse, true);
+        back.reverse();
+        assert_eq!(back, fwd);
+        let from_12: Vec<_> = NtHashBuilder::new(reference)
+            .k(5)
+            .direction(Direction::Reverse)
+            .pos(12)
+            .bisulfite(true)
+            .finish()
+            .unwrap()
+            .map(|(pos, _)| pos.start)
+            .collect();
+        assert_eq!(from_12, (0..12).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn masked_intervals_skip_overlapping_windows() {
+        let seq = b"GATTAGACGTTGCAAGNCTCGGACCTAGCATCGACTACGNACGTTAGC";
+        let mask = [30..33, 3..5, 9..12, 4..6, 44..48, 0..0];
+        let k = 4;
+        for direction in [Direction::Forward, Direction::Reverse] {
+            for bisulfite in [false, true] {
+                let build = || {
+                    NtHashBuilder::new(seq)
+                        .k(k)
+                        .num_hashes(2)
+                        .direction(direction)
+                        .bisulfite(bisulfite)
+                };
+                let expected: Vec<_> = build()
+                    .finish()
+                    .unwrap()
+                    .filter(|(pos, _)| {
+                        let clear = |m: &Range<usize>| m.end <= pos.start || m.start >= pos.end;
+                        mask.iter().all(|m| m.is_empty() || clear(m))
+                    })
+                    .collect();
+                let got: Vec<_> = build().mask(mask.clone()).finish().unwrap().collect();
+                assert_eq!(got, expected, "{direction:?} bisulfite={bisulfite}");
+            }
+        }
+        let everything = NtHashBuilder::new(seq)
+            .k(k)
+            .mask(std::iter::once(0..seq.len()));
+        assert_eq!(everything.finish().unwrap().count(), 0);
+        assert_eq!(merge_intervals(vec![5..7, 1..3, 3..4, 6..9]), [1..4, 5..9]);
+    }
+
+    #[test]
+    fn skipped_ranges_cover_every_invalid_run() {
+        let seq = b"NNACGTNACGTACGTRYACGTNNNNNNNACGTAANCGTAC-";
+        let expected = [0..2, 6..7, 15..17, 21..28, 34..35, 40..41];
+        for k in [1u16, 3, 5, 9] {
+            let mut fwd = NtHash::new(seq, k, 1, 0).unwrap().with_skipped_ranges();
+            while fwd.roll() {}
+            assert_eq!(fwd.skipped_ranges(), expected, "k={k}");
+
+            let mut rev = NtHashRevIter::new(seq, k, 1).unwrap();
+            rev.hasher.skipped = Some(Vec::new());
+            rev.by_ref().for_each(drop);
+            assert_eq!(rev.hasher.skipped_ranges(), expected, "k={k}");
+        }
+        let mut off = NtHash::new(seq, 3, 1, 0).unwrap();
+        while off.roll() {}
+        assert!(off.skipped_ranges().is_empty());
+    }
+
+    #[test]
+    fn strict_mode_reports_the_first_visited_invalid_base() {
+        let seq = b"ACGTACnTACGTRCGTAC";
+        let strict = || NtHashBuilder::new(seq).k(4).strict(true);
+        let invalid = |pos, byte| Err(NtHashError::InvalidBase { pos, byte });
+        assert_eq!(strict().finish().map(|_| ()), invalid(6, b'n'));
+        assert_eq!(strict().pos(7).finish().map(|_| ()), invalid(12, b'R'));
+        let back = strict().direction(Direction::Reverse).pos(1);
+        assert_eq!(back.finish().unwrap().count(), 2);
+        let masked = strict().mask([6..7, 12..13]).finish().unwrap();
+        assert_eq!(masked.count(), 15 - 8);
+        assert!(strict().strict(false).finish().is_ok());
+        assert_eq!(
+            NtHashError::InvalidBase { pos: 6, byte: b'n' }.to_string(),
+            "invalid base 'n' at position 6"
+        );
+    }
+
+    #[test]
+    fn one_call_kmer_hashes_match_the_rolling_hasher() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACGTTAGCatcgga";
+        for k in [1u16, 4, 7, 31, 33] {
+            let mut h = NtHash::new(seq, k, 4, 0).unwrap();
+            while h.roll() {
+                let window = &seq[h.kmer_pos().range()];
+                assert_eq!(hash_kmer(window, 4).unwrap(), h.hashes(), "k={k}");
+                assert_eq!(canonical_kmer_hash(window), Ok(h.hashes()[0]));
+            }
+        }
+        assert_eq!(hash_kmer(b"ACGT", 0), Ok(vec![]));
+        assert_eq!(canonical_kmer_hash(b""), Err(NtHashError::InvalidK));
+        assert_eq!(
+            hash_kmer(b"ACGTRA", 2),
+            Err(NtHashError::InvalidBase { pos: 4, byte: b'R' })
+        );
+        assert_eq!(
+            canonical_kmer_hash(&vec![b'A'; 70_000]),
+            Err(NtHashError::InvalidK)
+        );
+    }
+
+    #[test]
+    fn batch_hashes_match_single_kmer_hashes() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACGTTAGCATCGGA";
+        let kmers: Vec<&[u8]> = seq.windows(9).collect();
+        let expected: Vec<u64> = kmers
+            .iter()
+            .map(|w| canonical_kmer_hash(w).unwrap())
+            .collect();
+        assert_eq!(hash_kmers(kmers.iter().copied()), Ok(expected));
+        assert_eq!(hash_kmers(std::iter::empty()), Ok(vec![]));
+        assert_eq!(
+            hash_kmers([&b"ACGT"[..], b"ACG"]),
+            Err(NtHashError::KmerLengthMismatch {
+                index: 1,
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            hash_kmers([&b"ACGT"[..], b"ACNT"]),
+            Err(NtHashError::InvalidBase { pos: 6, byte: b'N' })
+        );
+    }
+
+    #[test]
+    fn hash_into_fills_every_window_in_order() {
+        let seq = b"ATCGTACGATGCNTGCATGCTGACGTTAGCATCGGANNAT";
+        let k = 9;
+        let mut out = vec![0; seq.len() - 8];
+        let n = hash_into(seq, k, &mut out).unwrap();
+        for (i, w) in seq.windows(9).enumerate() {
+            let expected = canonical_kmer_hash(w).unwrap_or(SKIPPED_HASH);
+            assert_eq!(out[i], expected, "window {i}");
+        }
+        assert_eq!(
+            n,
+            seq.windows(9)
+                .filter(|w| w.iter().all(|&c| is_acgt(c)))
+                .count()
+        );
+        assert_eq!(
+            hash_into(seq, k, &mut out[1..]),
+            Err(NtHashError::OutputLengthMismatch {
+                expected: out.len(),
+                actual: out.len() - 1
+            })
+        );
+        assert!(hash_into(b"ACG", 4, &mut []).is_err());
+    }
+
+    #[test]
+    fn segments_hold_every_valid_kmer() {
+        // the seeded control codes are invalid for both
+        let seqs: [&[u8]; 2] = [
+            b"NNACGTACNGTTNNNNACGATTAGCANGTN",
+            b"ACgT\x01ACGTT\x03ACG\x04TTACA\x07Gc",
+        ];
+        for (seq, k) in seqs.iter().flat_map(|&s| (1u16..=8).map(move |k| (s, k))) {
+            let from_segments: Vec<usize> = valid_segments(seq, k)
+                .flat_map(|s| s.start..=s.end - k as usize)
+                .collect();
+            let mut h = NtHash::new(seq, k, 1, 0).unwrap();
+            let mut rolled = Vec::new();
+            while h.roll() {
+                rolled.push(h.pos());
+            }
+            assert_eq!(from_segments, rolled, "k={k}");
+        }
+        assert_eq!(valid_segments(b"NNN", 1).next(), None);
+        assert_eq!(valid_segments(b"ACGT", 4).next(), Some(0..4));
+        // U and the seeded control codes are not ACGT
+        assert_eq!(
+            valid_segments(b"ACUGT\x03AC", 2).collect::<Vec<_>>(),
+            [0..2, 3..5, 6..8]
+        );
+    }
+
+    #[test]
+    fn cached_segment_does_not_outlive_the_sequence() {
+        let (clean, dirty) = (b"ACGTACGTACGT", b"ACGTANGTACGT");
+        let mut h = NtHash::new(clean, 4, 1, 0).unwrap();
+        while h.roll() {}
+        h.reset(dirty, 0).unwrap();
+        let mut starts = Vec::new();
+        while h.roll() {
+            starts.push(h.pos());
+        }
+        assert_eq!(starts, [0, 1, 6, 7, 8]);
+        assert!(h.seek(1).unwrap());
+        assert!(h.roll());
+        assert_eq!(h.pos(), 6);
+    }
+
+    #[test]
+    fn mid_sequence_rolls_match_a_full_scan() {
+        // valid runs longer than a scan chunk, entered from the middle
+        let seq: Vec<u8> = (0..3 * SCAN_CHUNK + 100)
+            .map(|i| {
+                if i % (2 * SCAN_CHUNK + 7) == SCAN_CHUNK + 5 {
+                    b'N'
+                } else {
+                    b"ACGT"[i * 7 % 13 % 4]
+                }
+            })
+            .collect();
+        let k = 11u16;
+        let mut full = NtHash::new(&seq, k, 1, 0).unwrap();
+        let mut all = Vec::new();
+        while full.roll() {
+            all.push((full.pos(), full.hashes()[0]));
+        }
+        for start in [1, SCAN_CHUNK - 3, SCAN_CHUNK + 40, 2 * SCAN_CHUNK] {
+            let expected: Vec<_> = all.iter().copied().filter(|&(p, _)| p >= start).collect();
+            let mut h = NtHash::new(&seq, k, 1, start).unwrap();
+            let mut rolled = Vec::new();
+            while h.roll() {
+                rolled.push((h.pos(), h.hashes()[0]));
+            }
+            assert_eq!(rolled, expected, "start={start}");
+
+            let mut h = NtHash::new(&seq, k, 1, start).unwrap();
+            assert!(h.roll_n(SCAN_CHUNK + 50));
+            assert_eq!((h.pos(), h.hashes()[0]), expected[SCAN_CHUNK + 49]);
+            for _ in 0..SCAN_CHUNK + 50 {
+                if !h.roll_back() {
+                    break;
+                }
+                let at = all.partition_point(|&(p, _)| p < h.pos());
+                assert_eq!((h.pos(), h.hashes()[0]), all[at], "start={start}");
+            }
+        }
+    }
+
+    #[test]
+    fn hash_block_matches_hash_into_across_blocks() {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let seq: Vec<u8> = (0..2 * HASH_BLOCK + 777)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                match state % 200 {
+                    0 => b'N',
+                    1 => b'-',
+                    2 => b'x',
+                    r => b"ACGTacgt"[r as usize % 8],
+                }
+            })
+            .collect();
+        for k in [1u16, 2, 7, 31, 64, 100] {
+            let len = seq.len() - k as usize + 1;
+            let (mut block, mut rolled) = (vec![0; len], vec![0; len]);
+            let n = hash_block(&seq, k, &mut block).unwrap();
+            assert_eq!(n, hash_into(&seq, k, &mut rolled).unwrap(), "k={k}");
+            assert_eq!(block, rolled, "k={k}");
+        }
+        assert_eq!(hash_block(b"NNNN", 4, &mut [0]), Ok(0));
+        assert!(hash_block(b"ACGT", 4, &mut []).is_err());
+        assert!(hash_block(b"ACG", 4, &mut []).is_err());
+    }
+
+    #[test]
+    fn resumed_hasher_matches_uninterrupted_run() {
+        let seq = b"ATCGTACGATGCNTGCATGCTGACGTTAGCATCGGANNATCGTTAGC";
+        let mut full = Vec::new();
+        let mut h = NtHash::new(seq, 7, 3, 0).unwrap();
+        while h.roll() {
+            full.push((h.pos(), h.hashes().to_vec()));
+        }
+
+        for stop in 0..full.len() {
+            let mut h = NtHash::new(seq, 7, 3, 0).unwrap();
+            let mut got = Vec::new();
+            for _ in 0..stop {
+                h.roll();
+                got.push((h.pos(), h.hashes().to_vec()));
+            }
+            let state = h.state();
+            drop(h);
+            let copy = seq.to_vec();
+            let mut h = NtHash::resume(&copy, state).unwrap();
+            while h.roll() {
+                got.push((h.pos(), h.hashes().to_vec()));
+            }
+            assert_eq!(got, full, "interrupted after {stop} k-mers");
+        }
+
+        let mut state = NtHash::new(seq, 7, 1, 0).unwrap().state();
+        state.pos = seq.len();
+        assert!(matches!(
+            NtHash::resume(seq, state),
+            Err(NtHashError::PositionOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn reset_matches_a_fresh_hasher() {
+        let reads: [&[u8]; 4] = [b"ACGTNNACGTACG", b"TTGCA", b"GGNATCCATNA", b"CATGCATGCA"];
+        let mut h = NtHash::new(reads[0], 4, 3, 0)
+            .unwrap()
+            .with_packed_kmers()
+            .unwrap()
+            .with_skipped_ranges();
+        while h.roll() {}
+        for read in reads {
+            for pos in 0..=read.len() - 4 {
+                h.reset(read, pos).unwrap();
+                let mut fresh = NtHash::new(read, 4, 3, pos)
+                    .unwrap()
+                    .with_packed_kmers()
+                    .unwrap()
+                    .with_skipped_ranges();
+                while fresh.roll() {
+                    assert!(h.roll());
+                    assert_eq!(h.packed_kmer(), fresh.packed_kmer());
+                    assert_eq!(h, fresh);
+                }
+                assert!(!h.roll());
+                assert_eq!(h.skipped_ranges(), fresh.skipped_ranges());
+            }
+        }
+
+        let before = h.clone();
+        assert_eq!(
+            h.reset(b"ACG", 0),
+            Err(NtHashError::SequenceTooShort { seq_len: 3, k: 4 })
+        );
+        assert_eq!(
+            h.reset(b"ACGTA", 2),
+            Err(NtHashError::PositionOutOfRange { pos: 2, seq_len: 5 })
+        );
+        assert_eq!(h, before);
+    }
+
+    #[test]
+    fn forked_hashers_compare_by_state() {
+        let seq = b"ATCGTACGATGCNTGCATGCTGACGTTAGCATCGGA";
+        let mut h = NtHash::new(seq, 7, 2, 0).unwrap();
+        for _ in 0..4 {
+            h.roll();
+        }
+        let mut fork = h.clone();
+        assert_eq!(fork, h);
+        assert!(fork.roll());
+        assert_ne!(fork, h);
+        assert!(h.roll());
+        assert_eq!(fork, h);
+
+        // same contents in another buffer compare equal; other contents don't
+        let copy = seq.to_vec();
+        let mut other = NtHash::new(&copy, 7, 2, 0).unwrap();
+        while other.pos() < h.pos() {
+            assert!(other.roll());
+        }
+        assert_eq!(other, h);
+        let mut mutated = copy.clone();
+        mutated[30] = b'G';
+        let mut diverged = NtHash::resume(&mutated, h.state()).unwrap();
+        assert_ne!(diverged, h);
+        assert!(diverged.roll());
+        assert!(format!("{h:?}").contains("seq_len: 36"));
+    }
+
+    #[test]
+    fn canonical_keys_are_strand_neutral() {
+        let a = CanonicalKmer::new(b"AACGTG");
+        let b = CanonicalKmer::new(b"CACGTT");
+        assert_eq!(a, b);
+        assert_eq!(a.nthash(), b.nthash());
+        assert!(a.is_forward() && !b.is_forward());
+        assert_ne!(Kmer::new(b"AACGTG"), Kmer::new(b"CACGTT"));
+
+        let set: HashSet<_> = [a, b, CanonicalKmer::new(b"AACGTA")].into_iter().collect();
+        assert_eq!(set.len(), 2);
+
+        // equal keys hash equally even when a base has no complement
+        let (n, n_rc) = (CanonicalKmer::new(b"ACGNA"), CanonicalKmer::new(b"TNCGT"));
+        assert_ne!(n.nthash(), n_rc.nthash());
+        assert_ne!(n, n_rc);
+        assert_ne!(n.cmp(&n_rc), std::cmp::Ordering::Equal);
+        assert_eq!(n, CanonicalKmer::new(b"ACGNA"));
+        let set: HashSet<_> = [n, n_rc, CanonicalKmer::new(b"ACGNA")]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&CanonicalKmer::new(b"TNCGT")));
+
+        let mut map = BTreeMap::new();
+        for s in [&b"TTTT"[..], b"AAAA", b"CCCC", b"GGGG", b"ACGT"] {
+            *map.entry(CanonicalKmer::new(s)).or_insert(0) += 1;
+        }
+        let keys: Vec<Vec<u8>> = map.keys().map(|k| k.canonical_bases().collect()).collect();
+        assert_eq!(keys, [b"AAAA".to_vec(), b"ACGT".to_vec(), b"CCCC".to_vec()]);
+        assert_eq!(map[&CanonicalKmer::new(b"AAAA")], 2);
+
+        // keys longer than u16::MAX bases hash without narrowing k
+        let long = b"ACGT".repeat(20_000);
+        let set: HashSet<_> = [CanonicalKmer::new(&long)].into_iter().collect();
+        assert!(set.contains(&CanonicalKmer::new(&long)));
+        assert_ne!(
+            Kmer::new(&long).nthash(),
+            Kmer::new(&long[..long.len() - 4]).nthash()
+        );
+    }
+
+    #[test]
+    fn seek_then_roll_matches_fresh_hasher() {
+        let seq = b"ATCGTACGATNGCATGCATGCTGACG";
+        let mut h = NtHash::new(seq, 5, 3, 0).unwrap();
+        for target in [15, 2, 20, 0] {
+            assert_eq!(h.seek(target), Ok(true));
+            assert!(h.roll() && h.roll_back());
+            let mut fresh = NtHash::new(seq, 5, 3, target).unwrap();
+            assert!(fresh.roll());
+            assert_eq!((h.pos(), h.hashes()), (fresh.pos(), fresh.hashes()));
+        }
+        assert!(h.seek(seq.len() - 4).is_err());
+    }
+
+    #[test]
+    fn prefix_and_suffix_match_shorter_kmers() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACGNACGTTGCATTAGC";
+        for k in [2u16, 3, 5, 31, 32, 33] {
+            let mut h = NtHash::new(seq, k, 1, 0).unwrap();
+            let mut short = NtHash::new(seq, k - 1, 1, 0).unwrap();
+            assert_eq!(h.prefix_hash(), None);
+            while h.roll() {
+                assert_eq!(short.seek(h.pos()), Ok(true));
+                assert_eq!(h.prefix_hash(), Some(short.hashes()[0]), "k={k}");
+                assert_eq!(short.seek(h.pos() + 1), Ok(true));
+                assert_eq!(h.suffix_hash(), Some(short.hashes()[0]), "k={k}");
+            }
+        }
+        let mut one = NtHash::new(b"ACGT", 1, 1, 0).unwrap();
+        assert!(one.roll() && one.prefix_hash().is_none());
+    }
+
+    #[test]
+    fn neighbours_match_hashed_sequences() {
+        let seq = b"GATTACAGATC";
+        let k = 6;
+        let mut h = NtHash::new(seq, k, 2, 0).unwrap();
+        assert_eq!(h.successors(), None);
+        while h.roll() {
+            let before = h.hashes().to_vec();
+            let (succ, pred) = (h.successors().unwrap(), h.predecessors().unwrap());
+            assert_eq!(h.hashes(), &before[..]);
+            let window = &seq[h.pos()..h.pos() + k as usize];
+            for (i, &c) in b"ACGT".iter().enumerate() {
+                let mut next = window[1..].to_vec();
+                next.push(c);
+                let mut prev = vec![c];
+                prev.extend_from_slice(&window[..k as usize - 1]);
+                assert_eq!(succ[i], CanonicalKmer::new(&next).nthash());
+                assert_eq!(pred[i], CanonicalKmer::new(&prev).nthash());
+            }
+        }
+    }
+
+    #[test]
+    fn builder_reports_bad_parameters() {
+        let seq = b"ACGTNACG";
+        assert_eq!(
+            NtHashBuilder::new(seq).strict(true).finish().err(),
+            Some(NtHashError::InvalidK)
+        );
+        assert_eq!(
+            NtHashBuilder::new(seq).k(3).num_hashes(0).finish().err(),
+            Some(NtHashError::InvalidNumHashes)
+        );
+        for bisulfite in [false, true] {
+            for direction in [Direction::Forward, Direction::Reverse] {
+                let built = NtHashBuilder::new(seq)
+                    .k(9)
+                    .bisulfite(bisulfite)
+                    .direction(direction)
+                    .finish();
+                assert_eq!(
+                    built.err(),
+                    Some(NtHashError::SequenceTooShort { seq_len: 8, k: 9 })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn with_kmers_windows_match_hashes() {
+        let seq = b"ATCGTACGATNGCATGCATGCTGACG";
+        let iter = NtHashBuilder::new(seq).k(6).num_hashes(2).finish().unwrap();
+        let mut n = 0;
+        for (pos, window, hashes) in iter.with_kmers() {
+            assert_eq!(window, &seq[pos.start..pos.start + 6]);
+            assert_eq!(pos.end - pos.start, 6);
+            assert_eq!(CanonicalKmer::new(window).nthash(), hashes[0]);
+            n += 1;
+        }
+        assert_eq!(n, seq.len() - 5 - 6);
+    }
+
+    #[test]
+    fn reverse_iteration_mirrors_forward() {
+        let seq = b"NATCGTACGATNGCATGCANNNNNNNATGCTGACGNNAC";
+        for k in [1u16, 3, 6] {
+            let mut fwd: Vec<_> = NtHashBuilder::new(seq)
+                .k(k)
+                .num_hashes(2)
+                .into_iter()
+                .collect();
+            fwd.reverse();
+            let rev: Vec<_> = NtHashBuilder::new(seq)
+                .k(k)
+                .num_hashes(2)
+                .direction(Direction::Reverse)
+                .into_iter()
+                .collect();
+            assert_eq!(rev, fwd, "k={k}");
+        }
+
+        let mut rev = NtHashRevIter::new(seq, 6, 1).unwrap();
+        rev.seek(20).unwrap(); // windows from 14 on hold an N: back to 13..19
+        assert_eq!(rev.next().unwrap().0, KmerPos::new(13, 6));
+        let rest: Vec<usize> = rev.by_ref().map(|(pos, _)| pos.start).collect();
+        assert_eq!(rest, [12, 5, 4, 3, 2, 1]);
+        rev.seek(2).unwrap();
+        assert_eq!(rev.next().unwrap().0, KmerPos::new(2, 6));
+        assert_eq!(rev.hasher().pos(), 2);
+        assert_eq!(rev.next().unwrap().0, KmerPos::new(1, 6));
+        assert!(rev.next().is_none());
+        assert!(rev.seek(seq.len()).is_err());
+        assert_eq!(NtHashRevIter::new(b"NNNN", 2, 1).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn small_k_iteration_visits_the_same_kmers() {
+        let seq = b"NATCGTACGATNGCATGCANNNNNNNATGCTGACGNNAC";
+        for k in [1u16, 3, 6] {
+            let starts = |small_k, direction| -> Vec<(KmerPos, usize)> {
+                NtHashBuilder::new(seq)
+                    .k(k)
+                    .num_hashes(2)
+                    .small_k(small_k)
+                    .direction(direction)
+                    .mask([14..16, 30..31])
+                    .into_iter()
+                    .map(|(pos, hashes)| (pos, hashes.len()))
+                    .collect()
+            };
+            let fwd = starts(true, Direction::Forward);
+            assert_eq!(fwd, starts(false, Direction::Forward), "k={k}");
+            let mut rev = starts(true, Direction::Reverse);
+            rev.reverse();
+            assert_eq!(rev, fwd, "k={k}");
+        }
+        let long = NtHashBuilder::new(&[b'A'; 40]).k(33).small_k(true).finish();
+        assert_eq!(long.err(), Some(NtHashError::KTooLarge { k: 33, max: 32 }));
+    }
+
+    #[test]
+    fn packed_kmers_track_rolls_and_skips() {
+        fn pack(window: &[u8]) -> u128 {
+            window
+                .iter()
+                .fold(0, |acc, &c| acc << 2 | CONVERT_TAB[c as usize] as u128)
+        }
+        fn pack_rc(window: &[u8]) -> u128 {
+            window
+                .iter()
+                .rev()
+                .fold(0, |acc, &c| acc << 2 | RC_CONVERT_TAB[c as usize] as u128)
+        }
+        let mut seq = b"ATCGTACGATNGCATGCATGCTGACGTACGGATCCATGCAAGT".repeat(3);
+        seq.extend_from_slice(&b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACG".repeat(2));
+        seq.extend_from_slice(b"acgtTGCA");
+        for k in [1u16, 5, 32, 33, 64] {
+            let mut h = NtHash::new(&seq, k, 1, 0)
+                .unwrap()
+                .with_packed_kmers()
+                .unwrap();
+            assert_eq!(h.packed_kmer(), None);
+            // lower case packs like upper case
+            let expected = |h: &NtHash| {
+                let w = &seq[h.pos()..h.pos() + k as usize];
+                pack(w).min(pack_rc(w))
+            };
+            let mut n = 0;
+            while h.roll() {
+                assert_eq!(h.packed_kmer(), Some(expected(&h)), "k={k} pos={}", h.pos());
+                assert_eq!(h.packed_kmer_u64().is_some(), k <= 32);
+                n += 1;
+            }
+            while h.retreat() {
+                assert_eq!(h.packed_kmer(), Some(expected(&h)), "k={k} pos={}", h.pos());
+            }
+            assert!(n > 0);
+        }
+        assert!(NtHash::new(b"ACGT", 2, 1, 0)
+            .unwrap()
+            .packed_kmer()
+            .is_none());
+        let long = [b'A'; 65];
+        assert_eq!(
+            NtHash::new(&long, 65, 1, 0)
+                .unwrap()
+                .with_packed_kmers()
+                .err(),
+            Some(NtHashError::KTooLarge { k: 65, max: 64 })
+        );
+    }
+
+    #[test]
+    fn key_hash_matches_rolling_hash() {
+        let seq = b"ATCGTACGATGCATGC";
+        let mut h = NtHash::new(seq, 7, 1, 0).unwrap();
+        while h.roll() {
+            let w = &seq[h.pos()..h.pos() + 7];
+            assert_eq!(CanonicalKmer::new(w).nthash(), h.hashes()[0]);
+            assert_eq!(Kmer::new(w).nthash(), h.forward_hash());
+        }
+    }
+
+    #[test]
+    fn edge_inputs_never_panic() {
+        let seqs: [&[u8]; 5] = [b"ACG", b"NNN", b"NAC", b"ACN", b"A"];
+        for seq in seqs {
+            for k in 1..=seq.len() as u16 {
+                let mut h = NtHash::new(seq, k, 2, 0).unwrap();
+                for _ in 0..3 {
+                    while h.roll() {}
+                    _ = (
+                        h.peek(),
+                        h.peek_back(),
+                        h.peek_char(b'A'),
+                        h.peek_back_char(b'T'),
+                    );
+                    _ = (h.successors(), h.predecessors(), h.seek(seq.len()));
+                    // roll_back re‑initializes forward past an `N`, so bound it
+                    for _ in 0..seq.len() {
+                        h.roll_back();
+                    }
+                    _ = (h.peek_back(), h.peek_back_char(b'C'), h.seek(0));
+                }
+                if let Ok(mut late) = NtHash::new(seq, k, 1, seq.len() - 1) {
+                    _ = (late.roll(), late.peek_back_char(b'A'), late.roll_back());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_hasher_extends_with_its_mixer() {
+        use crate::util::XxhMixer;
+        use crate::{BlindNtHashBuilder, SeedNtHashBuilder};
+
+        let seq = b"ACGTTGCANAGCTTAGCGATCGA";
+        let mixed = |fwd, rev| {
+            let mut out = [0; 5];
+            XxhMixer.extend(fwd, rev, 6, &mut out);
+            out
+        };
+        let mut h = NtHash::new(seq, 6, 5, 0).unwrap().with_mixer(&XxhMixer);
+        let mut n = 0;
+        while h.roll() {
+            assert_eq!(h.hashes(), mixed(h.forward_hash(), h.reverse_hash()));
+            assert_eq!(h.hashes_array::<5>(), h.hashes());
+            n += 1;
+        }
+        for direction in [Direction::Forward, Direction::Reverse] {
+            let it = || {
+                NtHashBuilder::new(seq)
+                    .k(6)
+                    .num_hashes(5)
+                    .direction(direction)
+            };
+            let plain: Vec<_> = it().into_iter().collect();
+            let xxh: Vec<_> = it().mixer(&XxhMixer).into_iter().collect();
+            assert_eq!(xxh.len(), n);
+            for ((p, a), (q, b)) in plain.iter().zip(&xxh) {
+                assert_eq!((p, a[0]), (q, b[0]));
+                assert_ne!(a[1..], b[1..]);
+            }
+        }
+        let blind: Vec<_> = BlindNtHashBuilder::new(b"ACGTTGCA")
+            .k(6)
+            .num_hashes(5)
+            .mixer(&XxhMixer)
+            .into_iter()
+            .collect();
+        let plain: Vec<_> = NtHashBuilder::new(b"ACGTTGCA")
+            .k(6)
+            .num_hashes(5)
+            .mixer(&XxhMixer)
+            .into_iter()
+            .collect();
+        assert_eq!(blind, plain);
+        // a spaced‑seed scan stops at the first `N`
+        let spaced: Vec<_> = SeedNtHashBuilder::new(&seq[9..])
+            .k(6)
+            .masks(vec!["111111"])
+            .num_hashes(5)
+            .mixer(&XxhMixer)
+            .into_iter()
+            .collect();
+        assert_eq!(spaced.len(), 9);
+        assert!(spaced.iter().all(|(_, h)| h[1..] == mixed(h[0], 0)[1..]));
+    }
+
+    #[test]
+    fn canonical_policy_reaches_every_output() {
+        use crate::{BlindNtHashBuilder, SeedNtHash};
+
+        let seq = b"ACGTTGCAAGCTTAGCGATCGA";
+        for policy in [
+            CanonicalPolicy::Sum,
+            CanonicalPolicy::Min,
+            CanonicalPolicy::Xor,
+        ] {
+            let mut h = NtHash::new(seq, 7, 3, 0)
+                .unwrap()
+                .with_canonical_policy(policy);
+            let (mut expected, mut successors) = (Vec::new(), None::<[u64; 4]>);
+            while h.roll() {
+                let base = policy.combine(h.forward_hash(), h.reverse_hash());
+                assert_eq!(h.hashes()[0], base);
+                if let Some(next) = successors {
+                    let last = b"ACGT".iter().position(|&c| c == seq[h.pos() + 6]);
+                    assert_eq!(next[last.unwrap()], base);
+                }
+                successors = h.successors();
+                let mut all = vec![0; 3];
+                extend_hashes(base, 0, 7, &mut all);
+                expected.push(all);
+            }
+            for direction in [Direction::Forward, Direction::Reverse] {
+                let mut got: Vec<_> = NtHashBuilder::new(seq)
+                    .k(7)
+                    .num_hashes(3)
+                    .direction(direction)
+                    .canonical_policy(policy)
+                    .into_iter()
+                    .map(|(_, h)| h)
+                    .collect();
+                if direction == Direction::Reverse {
+                    got.reverse();
+                }
+                assert_eq!(got, expected);
+            }
+            let blind: Vec<_> = BlindNtHashBuilder::new(seq)
+                .k(7)
+                .num_hashes(3)
+                .canonical_policy(policy)
+                .into_iter()
+                .map(|(_, h)| h)
+                .collect();
+            assert_eq!(blind, expected);
+            let mut spaced = SeedNtHash::new(seq, &["1101011".into()], 1, 7, 0)
+                .unwrap()
+                .with_canonical_policy(policy);
+            while spaced.roll() {
+                let (fwd, rev) = (spaced.forward_hash(), spaced.reverse_hash());
+                assert_eq!(spaced.hashes()[0], policy.combine(fwd, rev));
+            }
+        }
+    }
+
+    #[test]
+    fn roll_n_matches_single_rolls() {
+        let seq: Vec<u8> = (0..400u32)
+            .map(|i| b"ACGTACGTacgtACGTN"[(i * i / 7 % 17) as usize])
+            .collect();
+        for k in [1, 4, 11] {
+            for jumps in [&[1usize, 1, 2][..], &[3, 10, 25, 1], &[50, 13, 200]] {
+                let mut jump = NtHash::new(&seq, k, 3, 0)
+                    .unwrap()
+                    .with_packed_kmers()
+                    .unwrap();
+                let mut step = jump.clone();
+                for &n in jumps.iter().cycle().take(40) {
+                    let ok = jump.roll_n(n);
+                    let stepped = (0..n).all(|_| step.roll());
+                    assert_eq!(ok, stepped, "k {k}, n {n}");
+                    assert_eq!(jump.pos(), step.pos());
+                    assert_eq!(jump.hashes(), step.hashes());
+                    assert_eq!(jump.packed_kmer(), step.packed_kmer());
+                    if !ok {
+                        break;
+                    }
+                }
+            }
+        }
+        let mut h = NtHash::new(b"ACGT", 2, 1, 0).unwrap();
+        assert!(h.roll_n(0) && h.roll_n(3) && !h.roll_n(1));
+    }
+
+    #[test]
+    fn multi_iter_matches_per_record_hashing() {
+        let records: [&[u8]; 4] = [b"ATCGTACGATGC", b"", b"GCATNGCTGACGTT", b"ACG"];
+        let got: Vec<_> = NtHashMultiIter::new(records, 5, 2).unwrap().collect();
+
+        let mut expected = Vec::new();
+        for (idx, rec) in records.iter().enumerate() {
+            if let Ok(mut h) = NtHash::new(rec, 5, 2, 0) {
+                while h.roll() {
+                    expected.push((idx, h.kmer_pos(), h.hashes().to_vec()));
+                }
+            }
+        }
+        assert_eq!(got, expected);
+        assert!(NtHashMultiIter::new(records, 0, 1).is_err());
+    }
+}
@@ -11,6 +11,38 @@
 //! `constants`), which mirror the original C++ reference implementation, and
 //! helper functionality in `util` for canonicalization and hash extension.
 //!
+//! ## Cargo features
+//!
+//! - `serde` — `Serialize` / `Deserialize` for resumable hasher state
+//!   ([`BlindNtHash`]), seed definitions ([`SpacedSeed`]), filters, and sketches.
+//! - `ffi` — `extern "C"` API mirroring the C++ `NtHash` / `BlindNtHash` /
+//!   `SeedNtHash` classes; header in `include/nthash_rs.h`.
+//! - `python` — PyO3 extension module with iterator and numpy batch output;
+//!   build with `maturin` (see `pyproject.toml`).
+//! - `simd` — AVX2 (runtime‑detected) / NEON tetramer table lookups when
+//!   computing a k‑mer's initial hash; outputs are identical.
+//! - `wasm-bindgen` — JS‑facing `hash_sequence(seq, k)` for
+//!   `wasm32-unknown-unknown` builds.
+//! - `bench-harness` — `harness` module timing ntHash against caller‑supplied
+//!   hashers on the caller's own data.
+//! - `bio` — `from_record` / `from_checked_record` on the builders for
+//!   `bio::io::fasta::Record`s, optionally checked against a
+//!   `bio::alphabets` alphabet.
+//! - `needletail` — [`NtHashRecord`] extension trait hashing
+//!   `needletail::parser::SequenceRecord`s directly.
+//! - `async` — [`AsyncNtHash`], a `futures` `Stream` of hashes over a tokio
+//!   `AsyncRead` or a stream of `Bytes` chunks.
+//! - `htslib` — [`alignment::hash_record`] over `rust_htslib` BAM records
+//!   (needs libclang and a C toolchain to build htslib).
+//! - `cli` — the `nthash` binary, hashing FASTA/FASTQ to per‑k‑mer hashes,
+//!   minimizers, or MinHash sketches as TSV or spectrum files.
+//! - `stats` — per‑hasher and process‑wide counts of bases read, windows
+//!   skipped, and hashes computed ([`stats`]).
+//!
+//! Without `python` the crate has no platform‑specific dependencies and
+//! builds for `wasm32-unknown-unknown`; the [`io`] module only needs the
+//! `std::io` traits, and [`cancel`] uses plain atomics.
+//!
 //! ## Example
 //!
 //! ```rust
@@ -39,44 +71,190 @@
 // #![cfg_attr(not(feature = "std"), no_std)]
 
 /// Low‑level random seeds, split‑rotate tables, and numeric constants.
-// Not re‑exported directly.
-mod constants;
-mod tables;
+pub mod constants;
+#[cfg(feature = "simd")]
+mod simd;
+/// Split‑rotate primitives and table generation for custom seeds.
+pub mod tables;
 
-pub mod util;
+/// Rolling hash over peptide (amino‑acid) k‑mers.
+pub mod aa;
+/// Per‑window min / max / xor folding of k‑mer hash streams.
+pub mod aggregate;
+/// Hashing aligned reads around their clips, in read or reference
+/// coordinates.
+pub mod alignment;
+/// The rolling recurrence generic over DNA, RNA, protein, and custom
+/// alphabets.
+pub mod alphabet;
+/// Streams of hashes over sequences arriving asynchronously.
+#[cfg(feature = "async")]
+mod async_io;
+/// `from_record` builders over `bio` crate FASTA records.
+#[cfg(feature = "bio")]
+mod bio_io;
+pub mod blind;
+/// Bloom and counting Bloom filters over k‑mer hashes.
+pub mod bloom;
+/// Cooperative cancellation of long scans.
+pub mod cancel;
+/// Splitting a shared reference into per‑thread hashing chunks.
+pub mod chunked;
+/// Argument parsing and output formats of the `nthash` binary.
+#[cfg(feature = "cli")]
+pub mod cli;
+/// In‑memory and disk‑backed k‑mer counting keyed by canonical hashes.
+pub mod count;
+/// Redundant contig detection by shared minimizers.
+pub mod dedup;
+/// Compression dictionaries of frequent minimizer contexts.
+pub mod dictionary;
+/// C ABI mirroring the reference C++ classes.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+/// Entry points for the `cargo fuzz` targets.
+#[doc(hidden)]
+pub mod fuzzing;
+/// Canonical snapshots of hasher output for downstream regression tests.
+pub mod golden;
+/// Side‑by‑side throughput of ntHash and other hashers.
+#[cfg(feature = "bench-harness")]
+pub mod harness;
+/// COBS/BIGSI‑style bit‑sliced signature indexes queried with ntHash.
+pub mod interop;
+/// FASTA / FASTQ reading, writing, hash‑annotated re‑emission, and binary
+/// k‑mer spectrum files.
+pub mod io;
 /// High‑level contiguous k‑mer rolling hasher.
 /// Skips over non‑ACGT bases exactly as the original reference.
 pub mod kmer;
-pub mod blind;
+/// (w, k)‑minimizers and a minimizer index with sequence search.
+pub mod minimizer;
+/// Hashing at several k‑mer lengths in a single pass.
+pub mod multik;
+/// Hashing `needletail` sequence records in place.
+#[cfg(feature = "needletail")]
+mod needletail_io;
+/// Sequence buffers owned alongside a borrowing hasher.
+#[cfg(any(feature = "python", feature = "needletail", feature = "async"))]
+mod owned;
+/// Table‑free hashing of small k‑mers from a packed 2‑bit window.
+pub mod packed;
+/// Memory‑budgeted choice of exact, Elias–Fano, or Bloom k‑mer sets.
+pub mod plan;
+/// Python bindings (`nthash_rs` extension module).
+#[cfg(feature = "python")]
+pub mod python;
+/// ntHash over FASTQ reads, skipping low‑quality k‑mers.
+pub mod qual;
+/// `#[repr(C)]` result records shared with the C and JavaScript bindings.
+pub mod records;
+/// Per‑bin k‑mer fingerprints for localizing differences between assemblies.
+pub mod region;
+/// A trait unifying the rolling hashers.
+pub mod rolling;
 pub mod seed;
+/// Reproducible content‑hash ordering of sequence collections.
+pub mod shuffle;
+/// Sinks fed directly from the hashing loop, serially or in parallel.
+pub mod sink;
+/// MinHash, FracMinHash, and HyperLogLog sketches with incremental updates.
+pub mod sketch;
+/// Sliding windows of the last w canonical hashes with O(1) min / max
+/// queries.
+pub mod sliding;
+/// ntHits‑style filters of k‑mers within a count range.
+pub mod solid;
+/// Throughput counters (`stats` feature) and a benchmark‑safe bulk digest.
+pub mod stats;
+/// Pluggable bit / bucket storage for filters and indexes.
+pub mod storage;
+/// BED / bedGraph / wiggle export of per‑position hash signals.
+pub mod track;
+pub mod util;
+/// JavaScript bindings for WebAssembly builds.
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
 
 // ──────────────────────────────────────────────────────────────
 // Re‑exports: public API surface
 // --------------------------------------------------------------------------
 
+/// Seed of an ASCII base.
+pub use constants::seed_for;
 /// One‑bit split‑rotate left (33 + 31 halves).
 pub use tables::srol;
+/// Multi‑bit split‑rotate left, as in the reference implementation.
+pub use tables::srol_n;
 /// Arbitrary split‑rotate via lookup tables.
 pub use tables::srol_table;
 /// One‑bit split‑rotate right (33 + 31 halves).
 pub use tables::sror;
+/// Multi‑bit split‑rotate right; inverse of [`srol_n`] for `d ≤ 31`.
+pub use tables::sror_n;
+/// Runtime self‑test of the lookup tables.
+pub use tables::verify_tables;
+/// Split‑rotate tables generated from custom seeds.
+pub use tables::{build_ms_tabs, MsTabs};
 
 /// Combine forward and reverse hashes into a strand‑independent value.
-pub use util::canonical;
+pub use util::{canonical, CanonicalPolicy};
 /// Derive multiple hash values from a single canonical hash.
-pub use util::extend_hashes;
+pub use util::{extend_hashes, extend_hashes_array, HashMixer};
 
 /// Primary rolling k‑mer hasher.
 ///
 /// See [`kmer::NtHash`] for full documentation.
 pub use kmer::NtHash;
 pub use kmer::NtHashBuilder;
+pub use kmer::NtHashMultiIter;
+pub use kmer::NtHashRevIter;
+pub use kmer::NtHashState;
+/// One‑call hashing of a single k‑mer or a batch of them.
+pub use kmer::{canonical_kmer_hash, hash_kmer, hash_kmers};
+/// Position‑ordered hashing into a caller‑provided array.
+pub use kmer::{hash_block, hash_into, SKIPPED_HASH};
+/// Runs of valid bases between `N`s.
+pub use kmer::{valid_segments, ValidSegments};
+pub use kmer::{CanonicalKmer, Direction, Kmer, KmerPos};
+
+pub use chunked::ChunkedNtHash;
+
+pub use alphabet::{Alphabet, RollingHash};
 
 pub use blind::BlindNtHash;
 pub use blind::BlindNtHashBuilder;
 
 pub use seed::SeedNtHash;
 pub use seed::SeedNtHashBuilder;
+pub use seed::SpacedSeed;
+
+pub use rolling::RollingHasher;
+
+/// Hashing chunked `AsyncRead` / `Bytes` stream input.
+#[cfg(feature = "async")]
+pub use async_io::{AsyncNtHash, ReaderChunks};
+/// `record.nthash(k, m)` on `needletail` records.
+#[cfg(feature = "needletail")]
+pub use needletail_io::{NtHashRecord, RecordNtHash};
+
+// Every hasher and iterator can be moved to, and shared between, threads.
+const fn assert_send_sync<T: Send + Sync>() {}
+const _: () = {
+    assert_send_sync::<NtHash<'static>>();
+    assert_send_sync::<kmer::NtHashIter<'static>>();
+    assert_send_sync::<NtHashRevIter<'static>>();
+    assert_send_sync::<BlindNtHash>();
+    assert_send_sync::<SeedNtHash<'static>>();
+    assert_send_sync::<qual::QualNtHash<'static>>();
+    assert_send_sync::<minimizer::SuperKmers<'static>>();
+    assert_send_sync::<ChunkedNtHash>();
+    assert_send_sync::<chunked::Chunk>();
+    assert_send_sync::<multik::DualKNtHash<'static>>();
+    assert_send_sync::<multik::MultiKNtHash<'static>>();
+    assert_send_sync::<RollingHash<'static, alphabet::Dna>>();
+    assert_send_sync::<aa::AaHash<'static>>();
+};
 
 // ──────────────────────────────────────────────────────────────
 // Crate‑wide result and error types
@@ -92,6 +270,10 @@ pub enum NtHashError {
     #[error("k must be > 0")]
     InvalidK,
 
+    /// A builder was asked for zero hashes per k‑mer.
+    #[error("num_hashes must be > 0")]
+    InvalidNumHashes,
+
     /// Provided sequence length is shorter than `k`.
     #[error("sequence length ({seq_len}) < k ({k})")]
     SequenceTooShort { seq_len: usize, k: u16 },
@@ -100,11 +282,88 @@ pub enum NtHashError {
     #[error("position ({pos}) exceeds sequence length ({seq_len})")]
     PositionOutOfRange { pos: usize, seq_len: usize },
 
-    #[error("invalid sequence")]
-    InvalidSequence,
+    /// A spaced‑seed mask contains a character other than `0` or `1`.
+    #[error(
+        "invalid character {:?} at index {index} of spaced-seed mask {mask:?}",
+        char::from(*.byte)
+    )]
+    InvalidMaskChar {
+        mask: String,
+        index: usize,
+        byte: u8,
+    },
+
+    /// A spaced‑seed mask's length differs from `k`.
+    #[error("spaced-seed mask has length {actual}, expected {expected}")]
+    MaskLengthMismatch { expected: usize, actual: usize },
+
+    /// A care index of spaced seed `seed` lies outside the k‑mer.
+    #[error("care index {index} of spaced seed {seed} is outside a k-mer of length {k}")]
+    CareIndexOutOfRange { seed: usize, index: usize, k: usize },
+
+    /// Spaced seed `seed` has no care positions, so it would hash every
+    /// k‑mer to the same value.
+    #[error("spaced seed {seed} has no care positions")]
+    EmptySeed { seed: usize },
+
+    /// A spaced‑seed hasher was given no seeds at all.
+    #[error("at least one spaced seed is required")]
+    NoSeeds,
+
+    /// Paired per‑base inputs (e.g. bases and qualities) differ in length.
+    #[error("sequence length ({seq_len}) differs from quality length ({qual_len})")]
+    QualityLengthMismatch { seq_len: usize, qual_len: usize },
+
+    /// Canonical hashing was requested with a seed whose mask is not
+    /// symmetric, so its hashes would depend on the strand.
+    #[error("spaced seed {seed} is not symmetric; canonical hashes would be strand-dependent")]
+    AsymmetricSeed { seed: usize },
+
+    /// A lookup table disagrees with the tables it is derived from.
+    #[error("lookup table {table} is inconsistent at index {index}")]
+    TableMismatch { table: &'static str, index: usize },
+
+    /// A scan was stopped through a [`cancel::CancellationToken`].
+    #[error("scan cancelled after {processed} k-mers")]
+    Cancelled { processed: usize },
+
+    /// A filter or index was given storage too small to be usable, or
+    /// more hashes per k‑mer than the hashers can produce.
+    #[error("invalid filter capacity")]
+    InvalidCapacity,
+
+    /// A window size of zero k‑mers was requested.
+    #[error("window size must be > 0")]
+    InvalidWindow,
+
+    /// `k` is larger than a fixed‑width representation can hold.
+    #[error("k ({k}) exceeds the maximum of {max}")]
+    KTooLarge { k: u16, max: u16 },
+
+    /// A batch of k‑mers hashed together were not all of the same length.
+    #[error("k-mer {index} has length {actual}, expected {expected}")]
+    KmerLengthMismatch {
+        index: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    /// Strict validation found a byte other than `A/C/G/T` (either case).
+    #[error("invalid base {:?} at position {pos}", char::from(*.byte))]
+    InvalidBase { pos: usize, byte: u8 },
+
+    /// An output buffer does not have one slot per k‑mer window.
+    #[error("output buffer has {actual} slots, expected {expected}")]
+    OutputLengthMismatch { expected: usize, actual: usize },
+
+    /// A k‑mer count range was empty or admitted k‑mers never seen.
+    #[error("count range {min}..={max} must satisfy 1 <= min <= max")]
+    InvalidCountRange { min: u8, max: u8 },
 
-    #[error("invalid window offsets")]
-    InvalidWindowOffsets,
+    /// Window storage cannot hold a k‑mer of length `k` (e.g. an
+    /// `ArrayWindow<K>` with `K != k`).
+    #[error("window storage of capacity {capacity} cannot hold a k-mer of length {k}")]
+    WindowCapacityMismatch { k: usize, capacity: usize },
 }
 
 // ──────────────────────────────────────────────────────────────
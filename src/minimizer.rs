@@ -0,0 +1,1388 @@
+//! **(w, k)‑minimizers** and a minimizer index with sequence search.
+//!
+//! A minimizer is the k‑mer with the smallest canonical ntHash among `w`
+//! consecutive k‑mer positions.  Consecutive windows usually share their
+//! minimizer, so a sequence of length *n* is summarised by roughly
+//! `2n / (w + 1)` sampled k‑mers, which makes them the standard seeds for
+//! read mapping and sequence search.  [`mod_minimizers`] and
+//! [`miniception`] sample the same rolling‑hash stream at lower density.
+//!
+//! [`MinimizerIndex`] stores the minimizers of a set of reference sequences
+//! in three flat arrays (sorted keys, offsets, packed hits) held in
+//! [`Buckets`] storage, so large indexes can live in memory‑mapped files.
+//! [`MinimizerIndex::find`] turns the shared minimizers of a query into
+//! approximate matching reference intervals — a BLAST‑lite `locate`.
+//! [`paired_minimizers`] and [`minimizer_chains`] anchor two assemblies
+//! against each other on their unique shared minimizers, as in ntJoin.
+//!
+//! ```
+//! use nthash_rs::minimizer::MinimizerIndexBuilder;
+//!
+//! let reference = b"TTGACCGATAGCTAGGCTTACCGATCGATCGGCATCGACTAGCTAGCATCGTACGATCGAT";
+//! let mut builder = MinimizerIndexBuilder::new(11, 4);
+//! let id = builder.add("chr1", reference).unwrap();
+//! let index = builder.build();
+//!
+//! let hits = index.find(&reference[10..50]).unwrap();
+//! assert_eq!(hits[0].ref_id, id);
+//! assert!(hits[0].ref_interval.start >= 10 && hits[0].ref_interval.end <= 50);
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Range;
+
+use crate::{records::Anchor, storage::Buckets, util::salt_hash, NtHash, NtHashError, Result};
+
+/// One sampled k‑mer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Minimizer {
+    /// Start position of the k‑mer.
+    pub pos: usize,
+    /// Canonical hash of the k‑mer.
+    pub hash: u64,
+    /// `true` if the forward strand hash is the smaller one.
+    pub forward: bool,
+}
+
+/// How a window with several equally small hashes picks its minimizer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// The leftmost of the tied k‑mers.
+    #[default]
+    Leftmost,
+    /// The rightmost of the tied k‑mers.
+    Rightmost,
+    /// Every tied k‑mer is a minimizer of the window.
+    All,
+    /// Robust winnowing (Schleimer et al. 2003): keep the previously chosen
+    /// minimizer while it is still in the window and tied for the minimum,
+    /// otherwise take the rightmost tied k‑mer.  In low‑complexity regions
+    /// this samples far fewer positions than [`Leftmost`](Self::Leftmost).
+    Robust,
+}
+
+/// Which windows at the ends of a sequence are sampled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WindowEnds {
+    /// Only full windows of `w` k‑mer positions (a sequence with fewer
+    /// than `w` positions is one partial window).
+    #[default]
+    Closed,
+    /// Also the partial windows of 1 to `w − 1` positions anchored at
+    /// either end, so the first and last k‑mers are always sampled
+    /// ("end minimizers").
+    Open,
+}
+
+/// Which hash of a k‑mer windows are minimized over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MinimizerHash {
+    /// The canonical hash; a sequence and its reverse complement share
+    /// their minimizers.
+    #[default]
+    Canonical,
+    /// The forward‑strand hash only.
+    Forward,
+}
+
+/// Selection policies for [`minimizers_with_options`].
+///
+/// The defaults reproduce [`minimizers`]; other tools differ in each of
+/// these choices, and matching them exactly is needed to share indexes or
+/// compare sampled positions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MinimizerOptions {
+    /// Rule for windows with tied minima.
+    pub ties: TieBreak,
+    /// Whether partial windows at the sequence ends are sampled.
+    pub ends: WindowEnds,
+    /// Hash compared, and reported in [`Minimizer::hash`].
+    pub hash: MinimizerHash,
+    /// Windows are minimized over [`salt_hash`]`(hash, salt)`, giving an
+    /// independent ordering per salt; reported hashes stay unsalted.  `0`
+    /// keeps the plain hash order.
+    pub salt: u64,
+}
+
+/// Overrepresented k‑mers to avoid when selecting minimizers.
+///
+/// In [`weighted_minimizers`] a listed k‑mer only wins a window made up
+/// entirely of listed k‑mers, so repeats no longer dominate the sample —
+/// the selection‑time analogue of minimap2's `-f` frequency masking.
+/// Hashes are those compared under [`MinimizerOptions::hash`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KmerWeights {
+    frequent: HashSet<u64>,
+}
+
+impl KmerWeights {
+    /// No down‑weighted k‑mers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Down‑weight every hash counted more than `max_count` times, e.g.
+    /// from [`KmerCounter::iter`](crate::count::KmerCounter::iter).
+    pub fn from_counts(counts: impl IntoIterator<Item = (u64, u64)>, max_count: u64) -> Self {
+        Self {
+            frequent: counts
+                .into_iter()
+                .filter(|&(_, n)| n > max_count)
+                .map(|(hash, _)| hash)
+                .collect(),
+        }
+    }
+
+    /// Down‑weight one more hash.
+    pub fn insert(&mut self, hash: u64) {
+        self.frequent.insert(hash);
+    }
+
+    /// Returns `true` if `hash` is down‑weighted.
+    pub fn contains(&self, hash: u64) -> bool {
+        self.frequent.contains(&hash)
+    }
+
+    /// Number of down‑weighted hashes.
+    pub fn len(&self) -> usize {
+        self.frequent.len()
+    }
+
+    /// Returns `true` if no hash is down‑weighted.
+    pub fn is_empty(&self) -> bool {
+        self.frequent.is_empty()
+    }
+}
+
+/// The (w, k)‑minimizers of `seq`, in position order.
+///
+/// Windows span `w` consecutive k‑mer *positions*; k‑mers containing
+/// non‑ACGT bases never become minimizers, and windows without any valid
+/// k‑mer contribute nothing.  Ties are broken towards the leftmost k‑mer.
+///
+/// # Errors
+///
+/// Returns [`NtHashError::InvalidWindow`] if `w == 0`, and
+/// [`NtHash::new`] errors for `k`.
+pub fn minimizers(seq: &[u8], k: u16, w: usize) -> Result<Vec<Minimizer>> {
+    minimizers_with(seq, k, w, TieBreak::Leftmost)
+}
+
+/// [`minimizers`] with an explicit [`TieBreak`] rule.
+///
+/// ```
+/// use nthash_rs::minimizer::{minimizers_with, TieBreak};
+///
+/// // a homopolymer ties every window
+/// let seq = [b'A'; 40];
+/// let robust = minimizers_with(&seq, 5, 8, TieBreak::Robust).unwrap();
+/// let leftmost = minimizers_with(&seq, 5, 8, TieBreak::Leftmost).unwrap();
+/// assert!(robust.len() < leftmost.len());
+/// ```
+///
+/// # Errors
+///
+/// As for [`minimizers`].
+pub fn minimizers_with(seq: &[u8], k: u16, w: usize, ties: TieBreak) -> Result<Vec<Minimizer>> {
+    let opts = MinimizerOptions {
+        ties,
+        ..MinimizerOptions::default()
+    };
+    minimizers_with_options(seq, k, w, &opts)
+}
+
+/// [`minimizers`] under explicit tie, end‑window, and hash policies.
+///
+/// ```
+/// use nthash_rs::minimizer::{minimizers_with_options, MinimizerOptions, TieBreak, WindowEnds};
+///
+/// let seq = b"ACGTTGCAAGCTTAGCGATCGATCGG";
+/// let opts = MinimizerOptions { ends: WindowEnds::Open, ..Default::default() };
+/// let open = minimizers_with_options(seq, 5, 6, &opts).unwrap();
+/// assert_eq!(open[0].pos, 0);
+/// assert_eq!(open.last().unwrap().pos, seq.len() - 5);
+///
+/// let homopolymer = [b'A'; 20];
+/// let all = MinimizerOptions { ties: TieBreak::All, ..Default::default() };
+/// assert_eq!(minimizers_with_options(&homopolymer, 5, 4, &all).unwrap().len(), 16);
+/// ```
+///
+/// # Errors
+///
+/// As for [`minimizers`].
+pub fn minimizers_with_options(
+    seq: &[u8],
+    k: u16,
+    w: usize,
+    opts: &MinimizerOptions,
+) -> Result<Vec<Minimizer>> {
+    select(seq, k, w, opts, None)
+}
+
+/// [`minimizers_with_options`], preferring any k‑mer over those
+/// down‑weighted in `weights`.
+///
+/// ```
+/// use nthash_rs::minimizer::{minimizers, weighted_minimizers, KmerWeights};
+///
+/// let seq = b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACGATTACA";
+/// let plain = minimizers(seq, 7, 5).unwrap();
+/// let mut weights = KmerWeights::new();
+/// for m in &plain {
+///     weights.insert(m.hash);
+/// }
+/// let weighted = weighted_minimizers(seq, 7, 5, &Default::default(), &weights).unwrap();
+/// assert!(weighted.iter().all(|m| !weights.contains(m.hash)));
+/// ```
+///
+/// # Errors
+///
+/// As for [`minimizers`].
+pub fn weighted_minimizers(
+    seq: &[u8],
+    k: u16,
+    w: usize,
+    opts: &MinimizerOptions,
+    weights: &KmerWeights,
+) -> Result<Vec<Minimizer>> {
+    select(seq, k, w, opts, Some(weights))
+}
+
+fn select(
+    seq: &[u8],
+    k: u16,
+    w: usize,
+    opts: &MinimizerOptions,
+    weights: Option<&KmerWeights>,
+) -> Result<Vec<Minimizer>> {
+    if w == 0 {
+        return Err(NtHashError::InvalidWindow);
+    }
+    let mut h = NtHash::new(seq, k, 1, 0)?;
+    let last = seq.len() - k as usize;
+    let next_kmer = |h: &mut NtHash| {
+        h.roll().then(|| Minimizer {
+            pos: h.pos(),
+            hash: match opts.hash {
+                MinimizerHash::Canonical => h.hashes()[0],
+                MinimizerHash::Forward => h.forward_hash(),
+            },
+            forward: h.forward_hash() <= h.reverse_hash(),
+        })
+    };
+    // down‑weighted k‑mers after all others, then by salted hash
+    let rank = |m: &Minimizer| {
+        let frequent = weights.is_some_and(|f| f.contains(m.hash));
+        (frequent, salt_hash(m.hash, opts.salt))
+    };
+    let mut next = next_kmer(&mut h);
+    // candidates in increasing rank order, ties in position order; the
+    // leading run of equal ranks are the window's minima
+    let mut window: VecDeque<((bool, u64), Minimizer)> = VecDeque::with_capacity(w);
+    let mut out: Vec<Minimizer> = Vec::new();
+    let (open, tail) = match opts.ends {
+        WindowEnds::Closed => (false, 0),
+        WindowEnds::Open => (true, w - 1),
+    };
+
+    for end in 0..=last + tail {
+        if let Some(m) = next.filter(|m| m.pos == end) {
+            let r = rank(&m);
+            while window.back().is_some_and(|b| b.0 > r) {
+                window.pop_back();
+            }
+            window.push_back((r, m));
+            next = next_kmer(&mut h);
+        }
+        while window.front().is_some_and(|f| f.1.pos + w <= end) {
+            window.pop_front();
+        }
+        // the first full window, or the single partial one of a short sequence
+        if !open && end + 1 < w && end != last {
+            continue;
+        }
+        let Some(&(min_rank, min)) = window.front() else {
+            continue;
+        };
+        let tied = window.iter().take_while(|m| m.0 == min_rank).map(|m| m.1);
+        let last_out = out.last().map(|l| l.pos);
+        let chosen = match (opts.ties, out.last()) {
+            (TieBreak::Leftmost, _) => min,
+            (TieBreak::All, _) => {
+                out.extend(tied.filter(|m| last_out.is_none_or(|p| m.pos > p)));
+                continue;
+            }
+            (TieBreak::Robust, Some(&prev)) if prev.pos + w > end && rank(&prev) == min_rank => {
+                prev
+            }
+            (TieBreak::Rightmost | TieBreak::Robust, _) => tied.last().expect("front is tied"),
+        };
+        if last_out != Some(chosen.pos) {
+            out.push(chosen);
+        }
+    }
+    Ok(out)
+}
+
+/// Mod‑minimizers (Groot Koerkamp & Pibiri 2024): a lower‑density
+/// sampling than [`minimizers`] when `k` is large relative to `w`.
+///
+/// With `t = r + (k − r) mod w`, each window of `w` k‑mers finds the
+/// position `x` of its smallest canonical `t`‑mer and samples the k‑mer at
+/// `x mod w` within the window.  `r` is a small lower bound on `t` (4 is
+/// typical) that keeps random `t`‑mer collisions rare.
+///
+/// Only windows of `w` k‑mers free of non‑ACGT bases are sampled; unlike
+/// [`minimizers`], a sequence with fewer than `w` k‑mers yields nothing.
+///
+/// ```
+/// use nthash_rs::minimizer::{minimizers, mod_minimizers};
+///
+/// let seq: Vec<u8> = (0..2000u32).map(|i| b"ACGT"[(i * i / 7 % 4) as usize]).collect();
+/// let sampled = mod_minimizers(&seq, 31, 8, 4).unwrap();
+/// assert!(!sampled.is_empty());
+/// assert!(sampled.windows(2).all(|p| p[1].pos - p[0].pos <= 8));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`NtHashError::InvalidWindow`] if `w == 0`,
+/// [`NtHashError::InvalidK`] unless `0 < r ≤ k`, and [`NtHash::new`]
+/// errors for `k`.
+pub fn mod_minimizers(seq: &[u8], k: u16, w: usize, r: u16) -> Result<Vec<Minimizer>> {
+    if w == 0 {
+        return Err(NtHashError::InvalidWindow);
+    }
+    if r == 0 || r > k {
+        return Err(NtHashError::InvalidK);
+    }
+    let t = r as usize + (k - r) as usize % w;
+    let kmers = kmer_stream(seq, k)?;
+    let tmers = kmer_stream(seq, t as u16)?;
+    let tmer_hashes: Vec<Option<u64>> = tmers.iter().map(|m| m.map(|m| m.hash)).collect();
+    // each window of w k‑mers holds w + k − t t‑mers
+    let smallest = sliding_argmin(&tmer_hashes, w + k as usize - t);
+    let mut out: Vec<Minimizer> = Vec::new();
+    for start in full_windows(&kmers, w) {
+        let x = smallest[start].expect("t-mers of valid k-mers are valid") - start;
+        let chosen = kmers[start + x % w].expect("window is valid");
+        if out.last().map(|l| l.pos) != Some(chosen.pos) {
+            out.push(chosen);
+        }
+    }
+    Ok(out)
+}
+
+/// Miniception (Zheng, Kingsford & Marçais 2020): minimizers restricted to
+/// "charged" k‑mers, lowering density below random minimizers.
+///
+/// A k‑mer is charged when its smallest canonical `k0`‑mer is its first or
+/// last one.  Each window of `w` k‑mers samples its smallest charged k‑mer
+/// by canonical hash, or its smallest k‑mer if none is charged.  Windows
+/// are handled as in [`mod_minimizers`].
+///
+/// ```
+/// use nthash_rs::minimizer::miniception;
+///
+/// let seq: Vec<u8> = (0..2000u32).map(|i| b"ACGT"[(i * i / 7 % 4) as usize]).collect();
+/// let sampled = miniception(&seq, 15, 10, 5).unwrap();
+/// assert!(sampled.windows(2).all(|p| p[1].pos - p[0].pos <= 10));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`NtHashError::InvalidWindow`] if `w == 0`,
+/// [`NtHashError::InvalidK`] unless `0 < k0 ≤ k`, and [`NtHash::new`]
+/// errors for `k`.
+pub fn miniception(seq: &[u8], k: u16, w: usize, k0: u16) -> Result<Vec<Minimizer>> {
+    if w == 0 {
+        return Err(NtHashError::InvalidWindow);
+    }
+    if k0 == 0 || k0 > k {
+        return Err(NtHashError::InvalidK);
+    }
+    let inner = (k - k0) as usize + 1;
+    let kmers = kmer_stream(seq, k)?;
+    let small: Vec<Option<u64>> = kmer_stream(seq, k0)?
+        .iter()
+        .map(|m| m.map(|m| m.hash))
+        .collect();
+    let smallest = sliding_argmin(&small, inner);
+    // charged k‑mers rank before the rest, then by hash
+    let ranks: Vec<Option<(bool, u64)>> = kmers
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            m.map(|m| {
+                let x = smallest[i].expect("k0-mers of a valid k-mer are valid");
+                (x != i && x != i + inner - 1, m.hash)
+            })
+        })
+        .collect();
+    let mut out: Vec<Minimizer> = Vec::new();
+    let mut window: VecDeque<usize> = VecDeque::with_capacity(w);
+    let mut next = 0;
+    for start in full_windows(&kmers, w) {
+        while window.front().is_some_and(|&i| i < start) {
+            window.pop_front();
+        }
+        for i in next.max(start)..start + w {
+            while window.back().is_some_and(|&b| ranks[b] > ranks[i]) {
+                window.pop_back();
+            }
+            window.push_back(i);
+        }
+        next = start + w;
+        let chosen = kmers[window[0]].expect("window is valid");
+        if out.last().map(|l| l.pos) != Some(chosen.pos) {
+            out.push(chosen);
+        }
+    }
+    Ok(out)
+}
+
+/// Every k‑mer position of `seq`, `None` where the k‑mer is not valid.
+fn kmer_stream(seq: &[u8], k: u16) -> Result<Vec<Option<Minimizer>>> {
+    let mut out = vec![None; (seq.len() + 1).saturating_sub(k as usize)];
+    let mut h = NtHash::new(seq, k, 1, 0)?;
+    while h.roll() {
+        out[h.pos()] = Some(Minimizer {
+            pos: h.pos(),
+            hash: h.hashes()[0],
+            forward: h.forward_hash() <= h.reverse_hash(),
+        });
+    }
+    Ok(out)
+}
+
+/// Starts of the windows of `w` consecutive valid k‑mers.
+fn full_windows(kmers: &[Option<Minimizer>], w: usize) -> impl Iterator<Item = usize> + '_ {
+    let mut run = 0;
+    kmers.iter().enumerate().filter_map(move |(i, m)| {
+        run = if m.is_some() { run + 1 } else { 0 };
+        (run >= w).then(|| i + 1 - w)
+    })
+}
+
+/// Leftmost position of the smallest value in each window of `width`
+/// entries, indexed by window start; `None` entries are ignored.
+fn sliding_argmin(values: &[Option<u64>], width: usize) -> Vec<Option<usize>> {
+    let mut out = Vec::with_capacity(values.len());
+    let mut window: VecDeque<(u64, usize)> = VecDeque::with_capacity(width);
+    for end in 0..values.len() + width - 1 {
+        if let Some(v) = values.get(end).copied().flatten() {
+            while window.back().is_some_and(|&(b, _)| b > v) {
+                window.pop_back();
+            }
+            window.push_back((v, end));
+        }
+        if end + 1 >= width {
+            let start = end + 1 - width;
+            while window.front().is_some_and(|&(_, i)| i < start) {
+                window.pop_front();
+            }
+            if start < values.len() {
+                out.push(window.front().map(|&(_, i)| i));
+            }
+        }
+    }
+    out
+}
+
+/// A run of consecutive windows sharing one minimizer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuperKmer {
+    /// First base of the first window.
+    pub start: usize,
+    /// One past the last base of the last window.
+    pub end: usize,
+    /// Canonical hash of the shared minimizer.
+    pub minimizer: u64,
+}
+
+impl SuperKmer {
+    /// Sequence coordinates of the span.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Iterator over the super‑k‑mers of a sequence; see [`superkmers`].
+pub struct SuperKmers<'a> {
+    h: NtHash<'a>,
+    k: usize,
+    w: usize,
+    window: VecDeque<(usize, u64)>,
+    run_start: usize,
+    last_pos: Option<usize>,
+    current: Option<SuperKmer>,
+}
+
+/// Partition `seq` into super‑k‑mers for minimizer‑bucketed counting
+/// (KMC‑style).
+///
+/// A *window* is `w + k − 1` bases, i.e. `w` consecutive `k`‑mers, and is
+/// keyed by its smallest canonical ntHash (leftmost on ties, as in
+/// [`minimizers`]).  Consecutive windows with the same minimizer hash are
+/// merged into one [`SuperKmer`]; neighbouring super‑k‑mers overlap by
+/// `w + k − 2` bases, so every window lies entirely inside exactly one of
+/// them.  Windows containing non‑ACGT bases belong to no super‑k‑mer, so
+/// runs of valid bases shorter than `w + k − 1` produce nothing.
+///
+/// ```
+/// use nthash_rs::minimizer::superkmers;
+///
+/// let seq = b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACG";
+/// let spans: Vec<_> = superkmers(seq, 7, 6).unwrap().collect();
+/// assert_eq!(spans[0].start, 0);
+/// assert_eq!(spans.last().unwrap().end, seq.len());
+/// for pair in spans.windows(2) {
+///     assert_eq!(pair[0].end - pair[1].start, 6 + 7 - 2);
+/// }
+/// ```
+///
+/// # Errors
+///
+/// As for [`minimizers`].
+pub fn superkmers(seq: &[u8], k: u16, w: usize) -> Result<SuperKmers<'_>> {
+    if w == 0 {
+        return Err(NtHashError::InvalidWindow);
+    }
+    Ok(SuperKmers {
+        h: NtHash::new(seq, k, 1, 0)?,
+        k: k as usize,
+        w,
+        window: VecDeque::with_capacity(w),
+        run_start: 0,
+        last_pos: None,
+        current: None,
+    })
+}
+
+impl Iterator for SuperKmers<'_> {
+    type Item = SuperKmer;
+
+    fn next(&mut self) -> Option<SuperKmer> {
+        while self.h.roll() {
+            let (pos, hash) = (self.h.pos(), self.h.hashes()[0]);
+            let mut done = None;
+            if self.last_pos.is_some_and(|p| p + 1 != pos) {
+                // an invalid base ended the run of windows
+                self.window.clear();
+                done = self.current.take();
+            }
+            if self.window.is_empty() {
+                self.run_start = pos;
+            }
+            self.last_pos = Some(pos);
+            while self.window.back().is_some_and(|&(_, b)| b > hash) {
+                self.window.pop_back();
+            }
+            self.window.push_back((pos, hash));
+            while self.window.front().is_some_and(|&(p, _)| p + self.w <= pos) {
+                self.window.pop_front();
+            }
+            if pos + 1 < self.run_start + self.w {
+                if done.is_some() {
+                    return done;
+                }
+                continue;
+            }
+            let min = self.window[0].1;
+            match &mut self.current {
+                Some(cur) if cur.minimizer == min => cur.end = pos + self.k,
+                cur => {
+                    let prev = cur.replace(SuperKmer {
+                        start: pos + 1 - self.w,
+                        end: pos + self.k,
+                        minimizer: min,
+                    });
+                    done = done.or(prev);
+                }
+            }
+            if done.is_some() {
+                return done;
+            }
+        }
+        self.current.take()
+    }
+}
+
+/// A minimizer occurring exactly once in each of two sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PairedMinimizer {
+    /// k‑mer start in the first sequence.
+    pub pos_a: usize,
+    /// k‑mer start in the second sequence.
+    pub pos_b: usize,
+    /// Canonical hash of the k‑mer.
+    pub hash: u64,
+    /// `true` if the k‑mer has the same orientation in both sequences.
+    pub same_strand: bool,
+}
+
+/// Anchors between two sequences (e.g. two assemblies), ntJoin‑style:
+/// the (w, k)‑minimizers found exactly once in `a` and exactly once in
+/// `b`, ordered by position in `a`.
+///
+/// Repeated minimizers are dropped because they cannot place a region
+/// unambiguously; [`minimizer_chains`] groups the remaining anchors into
+/// collinear blocks for synteny or scaffolding.
+///
+/// ```
+/// use nthash_rs::minimizer::{minimizer_chains, paired_minimizers};
+///
+/// let a = b"ATCGGCTAGCTAGGCTAACGTTGACGATCGATGCATCGACTAGCATCGAGCTAGCGCATT";
+/// let b = [&a[30..], &a[..30]].concat(); // the two halves swapped
+/// let pairs = paired_minimizers(a, &b, 9, 4).unwrap();
+/// assert!(pairs.iter().all(|p| p.same_strand));
+/// assert_eq!(minimizer_chains(&pairs).len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// As for [`minimizers`], for either sequence.
+pub fn paired_minimizers(a: &[u8], b: &[u8], k: u16, w: usize) -> Result<Vec<PairedMinimizer>> {
+    fn unique(seq: &[u8], k: u16, w: usize) -> Result<HashMap<u64, Option<Minimizer>>> {
+        let mut seen: HashMap<u64, Option<Minimizer>> = HashMap::new();
+        for m in minimizers(seq, k, w)? {
+            seen.entry(m.hash)
+                .and_modify(|e| *e = None)
+                .or_insert(Some(m));
+        }
+        Ok(seen)
+    }
+    let in_b = unique(b, k, w)?;
+    let mut pairs: Vec<PairedMinimizer> = unique(a, k, w)?
+        .into_values()
+        .flatten()
+        .filter_map(|ma| {
+            let mb = in_b.get(&ma.hash).copied().flatten()?;
+            Some(PairedMinimizer {
+                pos_a: ma.pos,
+                pos_b: mb.pos,
+                hash: ma.hash,
+                same_strand: ma.forward == mb.forward,
+            })
+        })
+        .collect();
+    pairs.sort_unstable_by_key(|p| p.pos_a);
+    Ok(pairs)
+}
+
+/// Split anchors from [`paired_minimizers`] into collinear chains.
+///
+/// Two anchors adjacent in `a` stay in one chain when they are also
+/// adjacent among the anchors of `b` — in the same order for same‑strand
+/// anchors, in reverse order for opposite‑strand ones — i.e. when the
+/// edge between them in the ntJoin minimizer graph is supported by both
+/// sequences.  Chains are returned in order of position in `a`.
+pub fn minimizer_chains(pairs: &[PairedMinimizer]) -> Vec<&[PairedMinimizer]> {
+    let mut by_b: Vec<usize> = (0..pairs.len()).collect();
+    by_b.sort_unstable_by_key(|&i| pairs[i].pos_b);
+    let mut rank_b = vec![0; pairs.len()];
+    for (rank, &i) in by_b.iter().enumerate() {
+        rank_b[i] = rank;
+    }
+    let mut chains = Vec::new();
+    let mut start = 0;
+    for i in 1..=pairs.len() {
+        let linked = i < pairs.len() && {
+            let (prev, cur) = (&pairs[i - 1], &pairs[i]);
+            prev.same_strand == cur.same_strand
+                && if cur.same_strand {
+                    rank_b[i] == rank_b[i - 1] + 1
+                } else {
+                    rank_b[i] + 1 == rank_b[i - 1]
+                }
+        };
+        if !linked && i > start {
+            chains.push(&pairs[start..i]);
+            start = i;
+        }
+    }
+    chains
+}
+
+// Packed hit layout: ref id (31 bits) | strand (1 bit) | position (32 bits).
+const POS_BITS: u32 = 32;
+const MAX_REFS: usize = 1 << 31;
+
+#[inline]
+fn pack(ref_id: usize, m: &Minimizer) -> u64 {
+    ((ref_id as u64) << (POS_BITS + 1)) | ((m.forward as u64) << POS_BITS) | m.pos as u64
+}
+
+#[inline]
+fn unpack(e: u64) -> (usize, usize, bool) {
+    (
+        (e >> (POS_BITS + 1)) as usize,
+        (e & u32::MAX as u64) as usize,
+        (e >> POS_BITS) & 1 == 1,
+    )
+}
+
+/// Collects reference minimizers and lays out a [`MinimizerIndex`].
+pub struct MinimizerIndexBuilder {
+    k: u16,
+    w: usize,
+    options: MinimizerOptions,
+    names: Vec<String>,
+    // (hash, packed hit)
+    pairs: Vec<(u64, u64)>,
+}
+
+impl MinimizerIndexBuilder {
+    /// Start an index of (w, k)‑minimizers.
+    pub fn new(k: u16, w: usize) -> Self {
+        Self {
+            k,
+            w,
+            options: MinimizerOptions::default(),
+            names: Vec::new(),
+            pairs: Vec::new(),
+        }
+    }
+
+    /// Tie‑breaking rule for references and queries alike; set it before
+    /// adding references.
+    pub fn ties(mut self, ties: TieBreak) -> Self {
+        self.options.ties = ties;
+        self
+    }
+
+    /// All selection policies for references and queries alike; set them
+    /// before adding references.
+    pub fn options(mut self, options: MinimizerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add a reference sequence, returning its id.
+    ///
+    /// # Errors
+    ///
+    /// Errors from [`minimizers`], or [`NtHashError::InvalidCapacity`] if the
+    /// sequence is longer than `u32::MAX` or there are more than 2³¹
+    /// references.
+    pub fn add(&mut self, name: impl Into<String>, seq: &[u8]) -> Result<usize> {
+        if seq.len() > u32::MAX as usize || self.names.len() >= MAX_REFS {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        let id = self.names.len();
+        let mins = minimizers_with_options(seq, self.k, self.w, &self.options)?;
+        self.pairs
+            .extend(mins.iter().map(|m| (m.hash, pack(id, m))));
+        self.names.push(name.into());
+        Ok(id)
+    }
+
+    fn sorted(&mut self) -> usize {
+        self.pairs.sort_unstable();
+        let mut keys = 0;
+        let mut prev = None;
+        for &(h, _) in &self.pairs {
+            if prev != Some(h) {
+                keys += 1;
+                prev = Some(h);
+            }
+        }
+        keys
+    }
+
+    /// Slots needed for `(keys, offsets, hits)` by
+    /// [`build_into`](Self::build_into).
+    pub fn sizes(&mut self) -> (usize, usize, usize) {
+        let keys = self.sorted();
+        (keys, keys + 1, self.pairs.len())
+    }
+
+    /// Lay the index out in memory.
+    pub fn build(mut self) -> MinimizerIndex {
+        let (nk, no, ne) = self.sizes();
+        self.build_into(vec![0; nk], vec![0; no], vec![0; ne])
+            .expect("buffers sized by sizes()")
+    }
+
+    /// Lay the index out in caller‑provided storage, sized at least as
+    /// reported by [`sizes`](Self::sizes).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] if a buffer is too small.
+    pub fn build_into<B: Buckets<u64>>(
+        mut self,
+        mut keys: B,
+        mut offsets: B,
+        mut hits: B,
+    ) -> Result<MinimizerIndex<B>> {
+        let (nk, no, ne) = self.sizes();
+        if keys.num_buckets() < nk || offsets.num_buckets() < no || hits.num_buckets() < ne {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        let mut key = 0;
+        for (i, &(h, e)) in self.pairs.iter().enumerate() {
+            if i == 0 || self.pairs[i - 1].0 != h {
+                keys.set(key, h);
+                offsets.set(key, i as u64);
+                key += 1;
+            }
+            hits.set(i, e);
+        }
+        offsets.set(nk, ne as u64);
+        Ok(MinimizerIndex {
+            k: self.k,
+            w: self.w,
+            options: self.options,
+            names: self.names,
+            num_keys: nk,
+            keys,
+            offsets,
+            hits,
+        })
+    }
+}
+
+/// Tuning knobs for [`MinimizerIndex::find_with`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FindOptions {
+    /// Largest reference gap between consecutive anchors of one match.
+    pub max_gap: usize,
+    /// Largest diagonal drift (indels) tolerated within one match.
+    pub band: usize,
+    /// Matches with fewer anchors are dropped.
+    pub min_anchors: usize,
+    /// Minimizers occurring more often than this in the index are ignored.
+    pub max_occurrences: usize,
+}
+
+impl Default for FindOptions {
+    fn default() -> Self {
+        Self {
+            max_gap: 1000,
+            band: 50,
+            min_anchors: 2,
+            max_occurrences: 500,
+        }
+    }
+}
+
+/// An approximate match of a query against one reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// Id returned by [`MinimizerIndexBuilder::add`].
+    pub ref_id: usize,
+    /// Reference interval spanned by the anchors (k‑mer ends included).
+    pub ref_interval: Range<usize>,
+    /// `true` if the query matches the reference's forward strand.
+    pub forward: bool,
+    /// Number of shared minimizers supporting the match.
+    pub score: usize,
+}
+
+/// Minimizer → reference positions index.
+pub struct MinimizerIndex<B = Vec<u64>> {
+    k: u16,
+    w: usize,
+    options: MinimizerOptions,
+    names: Vec<String>,
+    num_keys: usize,
+    keys: B,
+    offsets: B,
+    hits: B,
+}
+
+impl<B: Buckets<u64>> MinimizerIndex<B> {
+    /// k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Window size in k‑mers.
+    pub fn w(&self) -> usize {
+        self.w
+    }
+
+    /// Tie‑breaking rule used for references and queries.
+    pub fn ties(&self) -> TieBreak {
+        self.options.ties
+    }
+
+    /// Selection policies used for references and queries.
+    pub fn options(&self) -> &MinimizerOptions {
+        &self.options
+    }
+
+    /// Number of indexed references.
+    pub fn num_refs(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Name of reference `ref_id`.
+    pub fn ref_name(&self, ref_id: usize) -> Option<&str> {
+        self.names.get(ref_id).map(String::as_str)
+    }
+
+    /// Slot range of `hash` in the hit array.
+    fn slots(&self, hash: u64) -> Range<usize> {
+        let (mut lo, mut hi) = (0, self.num_keys);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.keys.get(mid) < hash {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        if lo < self.num_keys && self.keys.get(lo) == hash {
+            self.offsets.get(lo) as usize..self.offsets.get(lo + 1) as usize
+        } else {
+            0..0
+        }
+    }
+
+    /// Every `(ref_id, pos, forward)` occurrence of a minimizer hash.
+    pub fn lookup(&self, hash: u64) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+        self.slots(hash).map(move |i| unpack(self.hits.get(i)))
+    }
+
+    /// [`find_with`](Self::find_with) using [`FindOptions::default`].
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from hashing the query.
+    pub fn find(&self, query: &[u8]) -> Result<Vec<Match>> {
+        self.find_with(query, &FindOptions::default())
+    }
+
+    /// Every occurrence in the index of every minimizer of `query`, in
+    /// query order; minimizers with more than `opts.max_occurrences`
+    /// occurrences are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from hashing the query.
+    pub fn anchors(&self, query: &[u8], opts: &FindOptions) -> Result<Vec<Anchor>> {
+        if query.len() < self.k as usize {
+            return Ok(Vec::new());
+        }
+        let mut anchors = Vec::new();
+        for q in minimizers_with_options(query, self.k, self.w, &self.options)? {
+            let slots = self.slots(q.hash);
+            if slots.len() > opts.max_occurrences {
+                continue;
+            }
+            for i in slots {
+                let (r, rpos, rfwd) = unpack(self.hits.get(i));
+                anchors.push(Anchor {
+                    ref_id: r as u32,
+                    ref_pos: rpos as u32,
+                    query_pos: q.pos as u32,
+                    forward: (rfwd == q.forward) as u32,
+                });
+            }
+        }
+        Ok(anchors)
+    }
+
+    /// Reference intervals approximately matching `query`, best first.
+    ///
+    /// Shared minimizers ("anchors") are grouped by reference, relative
+    /// strand, and diagonal band, then split wherever consecutive anchors
+    /// are more than `max_gap` apart on the reference.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from hashing the query.
+    pub fn find_with(&self, query: &[u8], opts: &FindOptions) -> Result<Vec<Match>> {
+        // (ref_id, forward, diagonal, ref_pos, query_pos)
+        let mut anchors: Vec<(usize, bool, i64, usize, usize)> = self
+            .anchors(query, opts)?
+            .into_iter()
+            .map(|a| {
+                let (rpos, qpos) = (a.ref_pos as usize, a.query_pos as usize);
+                let forward = a.forward == 1;
+                let diag = if forward {
+                    rpos as i64 - qpos as i64
+                } else {
+                    (rpos + qpos) as i64
+                };
+                (a.ref_id as usize, forward, diag, rpos, qpos)
+            })
+            .collect();
+        anchors.sort_unstable();
+
+        let mut matches = Vec::new();
+        let mut band: Vec<(usize, usize)> = Vec::new();
+        let mut i = 0;
+        while i < anchors.len() {
+            let (r, fwd, d0, _, _) = anchors[i];
+            band.clear();
+            while i < anchors.len()
+                && anchors[i].0 == r
+                && anchors[i].1 == fwd
+                && anchors[i].2 - d0 <= opts.band as i64
+            {
+                band.push((anchors[i].3, anchors[i].4));
+                i += 1;
+            }
+            band.sort_unstable();
+            let mut start = 0;
+            for j in 1..=band.len() {
+                if j == band.len() || band[j].0 - band[j - 1].0 > opts.max_gap {
+                    let run = &band[start..j];
+                    let mut qs: Vec<usize> = run.iter().map(|a| a.1).collect();
+                    qs.sort_unstable();
+                    qs.dedup();
+                    if qs.len() >= opts.min_anchors {
+                        matches.push(Match {
+                            ref_id: r,
+                            ref_interval: run[0].0..run[run.len() - 1].0 + self.k as usize,
+                            forward: fwd,
+                            score: qs.len(),
+                        });
+                    }
+                    start = j;
+                }
+            }
+        }
+        matches.sort_by(|a, b| b.score.cmp(&a.score).then(a.ref_id.cmp(&b.ref_id)));
+        Ok(matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_dna(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                b"ACGT"[(state >> 60) as usize & 3]
+            })
+            .collect()
+    }
+
+    fn revcomp(seq: &[u8]) -> Vec<u8> {
+        seq.iter()
+            .rev()
+            .map(|&c| match c {
+                b'A' => b'T',
+                b'C' => b'G',
+                b'G' => b'C',
+                _ => b'A',
+            })
+            .collect()
+    }
+
+    /// Brute force: minimum of every window of `w` positions.
+    fn naive(seq: &[u8], k: u16, w: usize) -> Vec<Minimizer> {
+        let n = seq.len() - k as usize + 1;
+        let mut all = vec![None; n];
+        let mut h = NtHash::new(seq, k, 1, 0).unwrap();
+        while h.roll() {
+            all[h.pos()] = Some(Minimizer {
+                pos: h.pos(),
+                hash: h.hashes()[0],
+                forward: h.forward_hash() <= h.reverse_hash(),
+            });
+        }
+        let mut out: Vec<Minimizer> = Vec::new();
+        for s in 0..n.saturating_sub(w - 1).max(1) {
+            let best = all[s..(s + w).min(n)]
+                .iter()
+                .flatten()
+                .fold(None::<Minimizer>, |b, m| match b {
+                    Some(b) if b.hash <= m.hash => Some(b),
+                    _ => Some(*m),
+                });
+            if let Some(m) = best {
+                if out.last().map(|l| l.pos) != Some(m.pos) {
+                    out.push(m);
+                }
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn matches_brute_force_with_gaps() {
+        let mut seq = random_dna(400, 7);
+        seq[100..112].fill(b'N');
+        seq[300] = b'N';
+        for (k, w) in [(5, 1), (7, 4), (11, 10), (15, 30), (9, 500)] {
+            assert_eq!(
+                minimizers(&seq, k, w).unwrap(),
+                naive(&seq, k, w),
+                "k={k} w={w}"
+            );
+        }
+        assert_eq!(minimizers(&seq, 5, 0), Err(NtHashError::InvalidWindow));
+    }
+
+    #[test]
+    fn selection_policies_match_brute_force() {
+        let mut seq = random_dna(300, 21);
+        seq[150..153].fill(b'N');
+        seq.extend(b"ACACACACACACACACACACAAAAAAAAAAAAAAAA");
+        let (k, w) = (5, 7);
+        let n = seq.len() - k + 1;
+        let mut kmers = vec![None; n];
+        let mut h = NtHash::new(&seq, k as u16, 1, 0).unwrap();
+        while h.roll() {
+            kmers[h.pos()] = Some((h.pos(), [h.hashes()[0], h.forward_hash()]));
+        }
+        let counts = crate::count::KmerCounter::count_seq(&seq, k as u16).unwrap();
+        let frequent = KmerWeights::from_counts(counts.iter(), 2);
+        assert!(!frequent.is_empty());
+        let variants = [
+            (MinimizerHash::Canonical, 0, None),
+            (MinimizerHash::Forward, 0, None),
+            (MinimizerHash::Canonical, 0x5eed, None),
+            (MinimizerHash::Canonical, 0, Some(&frequent)),
+        ];
+        for ties in [TieBreak::Leftmost, TieBreak::Rightmost, TieBreak::All] {
+            for ends in [WindowEnds::Closed, WindowEnds::Open] {
+                for (hash, salt, weights) in variants {
+                    let opts = MinimizerOptions {
+                        ties,
+                        ends,
+                        hash,
+                        salt,
+                    };
+                    let mut windows: Vec<Range<usize>> = (0..=n - w).map(|s| s..s + w).collect();
+                    if ends == WindowEnds::Open {
+                        windows.extend((1..w).map(|e| 0..e));
+                        windows.extend((n - w + 1..n).map(|s| s..n));
+                    }
+                    let key = |h: u64| (weights.is_some_and(|f| f.contains(h)), salt_hash(h, salt));
+                    let mut expected: Vec<(usize, u64)> = Vec::new();
+                    for win in windows {
+                        let valid: Vec<(usize, u64)> = kmers[win]
+                            .iter()
+                            .flatten()
+                            .map(|&(p, hashes)| (p, hashes[hash as usize]))
+                            .collect();
+                        let Some(min) = valid.iter().map(|v| key(v.1)).min() else {
+                            continue;
+                        };
+                        let tied = valid.iter().filter(|v| key(v.1) == min).copied();
+                        match ties {
+                            TieBreak::Leftmost => expected.extend(tied.take(1)),
+                            TieBreak::Rightmost => expected.extend(tied.last()),
+                            _ => expected.extend(tied),
+                        }
+                    }
+                    expected.sort_unstable();
+                    expected.dedup();
+                    let got: Vec<(usize, u64)> = select(&seq, k as u16, w, &opts, weights)
+                        .unwrap()
+                        .iter()
+                        .map(|m| (m.pos, m.hash))
+                        .collect();
+                    assert_eq!(got, expected, "{opts:?} weighted={}", weights.is_some());
+                }
+            }
+        }
+    }
+
+    fn canonical(kmer: &[u8]) -> u64 {
+        let mut h = NtHash::new(kmer, kmer.len() as u16, 1, 0).unwrap();
+        assert!(h.roll());
+        h.hashes()[0]
+    }
+
+    /// Starts of the windows of `w` k‑mers without `N`.
+    fn valid_windows(seq: &[u8], k: usize, w: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..=seq.len() - (w + k - 1)).filter(move |&s| !seq[s..s + w + k - 1].contains(&b'N'))
+    }
+
+    #[test]
+    fn low_density_schemes_match_brute_force() {
+        let mut seq = random_dna(3000, 31);
+        seq[1000..1004].fill(b'N');
+        seq.extend(b"ACACACACACACACACACACACACACACACACACACACAC");
+        let leftmost_min = |keys: &mut dyn Iterator<Item = (u64, usize)>| keys.min().unwrap().1;
+
+        let (k, w, r) = (21usize, 6usize, 4u16);
+        let t = r as usize + (k - r as usize) % w;
+        let mut expected: Vec<usize> = valid_windows(&seq, k, w)
+            .map(|s| {
+                let mut tmers = (s..s + w + k - t).map(|i| (canonical(&seq[i..i + t]), i));
+                s + (leftmost_min(&mut tmers) - s) % w
+            })
+            .collect();
+        expected.dedup();
+        let got = mod_minimizers(&seq, k as u16, w, r).unwrap();
+        assert_eq!(got.iter().map(|m| m.pos).collect::<Vec<_>>(), expected);
+        for m in &got {
+            assert_eq!(m.hash, canonical(&seq[m.pos..m.pos + k]));
+        }
+
+        let (k, w, k0) = (15usize, 8usize, 5usize);
+        let charged = |i: usize| {
+            let mut inner = (i..=i + k - k0).map(|j| (canonical(&seq[j..j + k0]), j));
+            let x = leftmost_min(&mut inner);
+            x == i || x == i + k - k0
+        };
+        let mut expected: Vec<usize> = valid_windows(&seq, k, w)
+            .map(|s| {
+                let keys = (s..s + w).map(|i| ((!charged(i), canonical(&seq[i..i + k])), i));
+                keys.min().unwrap().1
+            })
+            .collect();
+        expected.dedup();
+        let got = miniception(&seq, k as u16, w, k0 as u16).unwrap();
+        assert_eq!(got.iter().map(|m| m.pos).collect::<Vec<_>>(), expected);
+
+        // both sample more sparsely than random minimizers
+        let random = random_dna(20_000, 8);
+        let classic = minimizers(&random, 31, 10).unwrap().len();
+        assert!(mod_minimizers(&random, 31, 10, 4).unwrap().len() < classic);
+        let classic = minimizers(&random, 15, 10).unwrap().len();
+        assert!(miniception(&random, 15, 10, 6).unwrap().len() < classic);
+
+        assert!(mod_minimizers(&seq, 21, 0, 4).is_err());
+        assert!(mod_minimizers(&seq, 21, 6, 22).is_err());
+        assert!(miniception(&seq, 15, 8, 0).is_err());
+        assert!(mod_minimizers(&seq[..25], 21, 6, 4).unwrap().is_empty());
+    }
+
+    #[test]
+    fn robust_winnowing_keeps_previous_choice() {
+        // low‑complexity stretch with many tied windows
+        let mut seq = random_dna(120, 3);
+        seq.extend(b"ACACACACACACACACACACACACACACACACACAC");
+        seq.extend(random_dna(120, 4));
+        let (k, w) = (7, 12);
+        let robust = minimizers_with(&seq, k, w, TieBreak::Robust).unwrap();
+        let leftmost = minimizers(&seq, k, w).unwrap();
+        assert!(robust.len() < leftmost.len());
+
+        // every window's choice is a minimum of that window
+        let n = seq.len() - k as usize + 1;
+        for s in 0..=n - w {
+            let min = naive(&seq[s..s + w + k as usize - 1], k, w)[0].hash;
+            assert!(robust
+                .iter()
+                .any(|m| (s..s + w).contains(&m.pos) && m.hash == min));
+        }
+    }
+
+    #[test]
+    fn superkmers_group_windows_by_minimizer() {
+        let mut seq = random_dna(600, 9);
+        seq[200] = b'N';
+        seq[210] = b'N';
+        let (k, w) = (9, 8);
+        let span = w + k as usize - 1;
+        let got: Vec<SuperKmer> = superkmers(&seq, k, w).unwrap().collect();
+
+        // brute force: merge consecutive valid windows with equal minima
+        let mut expected: Vec<SuperKmer> = Vec::new();
+        for s in 0..=seq.len() - span {
+            if seq[s..s + span].contains(&b'N') {
+                continue;
+            }
+            let min = naive(&seq[s..s + span], k, w)[0].hash;
+            match expected.last_mut() {
+                Some(l) if l.minimizer == min && l.end == s + span - 1 => l.end += 1,
+                _ => expected.push(SuperKmer {
+                    start: s,
+                    end: s + span,
+                    minimizer: min,
+                }),
+            }
+        }
+        assert_eq!(got, expected);
+        assert!(got.len() < seq.len() / 2);
+        assert!(got.iter().all(|s| !seq[s.range()].contains(&b'N')));
+
+        assert_eq!(superkmers(&seq[..span - 1], k, w).unwrap().count(), 0);
+        assert!(superkmers(&seq, k, 0).is_err());
+    }
+
+    #[test]
+    fn paired_minimizers_chain_rearranged_blocks() {
+        let a = random_dna(3000, 11);
+        // b: a's second third reverse‑complemented, then its first third;
+        // the last third is absent
+        let mut b = revcomp(&a[1000..2000]);
+        b.extend_from_slice(&a[..1000]);
+        let (k, w) = (15, 10);
+        let pairs = paired_minimizers(&a, &b, k, w).unwrap();
+        assert!(pairs.windows(2).all(|p| p[0].pos_a < p[1].pos_a));
+        for p in &pairs {
+            let kmer = &a[p.pos_a..p.pos_a + k as usize];
+            if p.same_strand {
+                assert!(p.pos_a < 1000 && p.pos_b == p.pos_a + 1000);
+                assert_eq!(&b[p.pos_b..p.pos_b + k as usize], kmer);
+            } else {
+                assert!(p.pos_a >= 1000 && p.pos_a < 2000);
+                assert_eq!(revcomp(&b[p.pos_b..p.pos_b + k as usize]), kmer);
+            }
+        }
+
+        let chains = minimizer_chains(&pairs);
+        assert_eq!(chains.len(), 2);
+        assert!(chains[0].iter().all(|p| p.same_strand));
+        assert!(chains[1].iter().all(|p| !p.same_strand));
+        assert_eq!(chains[0].len() + chains[1].len(), pairs.len());
+        assert!(minimizer_chains(&[]).is_empty());
+
+        // a minimizer repeated in either sequence is not an anchor
+        let doubled = [&a[..1000], &a[..1000]].concat();
+        let in_doubled = minimizers(&doubled, k, w).unwrap();
+        let repeated = paired_minimizers(&a, &doubled, k, w).unwrap();
+        assert!(repeated.len() * 4 < paired_minimizers(&a, &a[..1000], k, w).unwrap().len());
+        for p in repeated {
+            assert_eq!(in_doubled.iter().filter(|m| m.hash == p.hash).count(), 1);
+        }
+    }
+
+    #[test]
+    fn find_locates_both_strands() {
+        let refs = [
+            random_dna(5000, 1),
+            random_dna(5000, 2),
+            random_dna(5000, 3),
+        ];
+        let mut b = MinimizerIndexBuilder::new(15, 10);
+        for (i, r) in refs.iter().enumerate() {
+            b.add(format!("r{i}"), r).unwrap();
+        }
+        let index = b.build();
+        assert_eq!(index.ref_name(1), Some("r1"));
+
+        let fwd = index.find(&refs[1][1200..1800]).unwrap();
+        assert_eq!((fwd[0].ref_id, fwd[0].forward), (1, true));
+        assert!(fwd[0].ref_interval.start < 1230 && fwd[0].ref_interval.end > 1770);
+
+        let rev = index.find(&revcomp(&refs[2][3000..3400])).unwrap();
+        assert_eq!((rev[0].ref_id, rev[0].forward), (2, false));
+        assert!(rev[0].ref_interval.start < 3030 && rev[0].ref_interval.end > 3370);
+
+        assert!(index.find(&random_dna(500, 99)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn build_into_borrowed_storage() {
+        let reference = random_dna(2000, 5);
+        let mut b = MinimizerIndexBuilder::new(13, 8);
+        b.add("x", &reference).unwrap();
+        let (nk, no, ne) = b.sizes();
+        let mut buf = vec![0u64; nk + no + ne];
+        let (keys, rest) = buf.split_at_mut(nk);
+        let (offsets, hits) = rest.split_at_mut(no);
+        let index = b.build_into(keys, offsets, hits).unwrap();
+        let m = index.find(&reference[500..900]).unwrap();
+        assert_eq!(m[0].ref_id, 0);
+        let first = minimizers(&reference, 13, 8).unwrap()[0];
+        assert!(index
+            .lookup(first.hash)
+            .any(|(r, p, _)| r == 0 && p == first.pos));
+    }
+}
@@ -0,0 +1,282 @@
+//! **Several k‑mer lengths in one pass** over a sequence.
+//!
+//! Assemblers working on a de Bruijn graph need hashes of the k‑mers (edges)
+//! and of the (k − 1)‑mers (nodes) together.  [`DualKNtHash`] rolls a single
+//! k‑mer state and derives the hash of each k‑mer's (k − 1)‑base prefix from
+//! it with one split‑rotate and one table lookup per strand, instead of
+//! rolling a second hasher over the same bases.  [`MultiKNtHash`] rolls
+//! one state per requested k over a single left‑to‑right traversal, for
+//! multi‑k graph construction (e.g. k ∈ {21, 33, 55, 77}).
+
+use crate::{
+    kmer::{KmerPos, NtHash},
+    util::extend_hashes,
+    NtHashError, Result,
+};
+
+/// Rolling hashes of every k‑mer and its (k − 1)‑base prefix.
+///
+/// As an iterator it yields `((pos, hash_k), (pos, hash_k_minus_1))` for
+/// every valid k‑mer, both canonical.  The last (k − 1)‑mer of each run of
+/// valid bases is not the prefix of any k‑mer; read it with
+/// [`suffix_hash`](Self::suffix_hash) before rolling past the run.
+///
+/// ```
+/// use nthash_rs::{multik::DualKNtHash, NtHash};
+///
+/// let seq = b"ACGTTGCAAGCT";
+/// let pairs: Vec<_> = DualKNtHash::new(seq, 5, 1).unwrap().collect();
+///
+/// let mut k5 = NtHash::new(seq, 5, 1, 0).unwrap();
+/// let mut k4 = NtHash::new(seq, 4, 1, 0).unwrap();
+/// for ((pos, h5), (pos4, h4)) in pairs {
+///     assert!(k5.roll() && k4.roll());
+///     assert_eq!((pos, pos4), (k5.pos(), k4.pos()));
+///     assert_eq!((h5, h4), (k5.hashes()[0], k4.hashes()[0]));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DualKNtHash<'a> {
+    inner: NtHash<'a>,
+    k: u16,
+    prefix: Vec<u64>,
+}
+
+impl<'a> DualKNtHash<'a> {
+    /// Hash the `k`‑mers and (`k` − 1)‑mers of `seq`, `num_hashes` values
+    /// each.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k < 2`, and [`NtHash::new`]
+    /// errors otherwise.
+    pub fn new(seq: &'a [u8], k: u16, num_hashes: u8) -> Result<Self> {
+        if k < 2 {
+            return Err(NtHashError::InvalidK);
+        }
+        Ok(Self {
+            inner: NtHash::new(seq, k, num_hashes, 0)?,
+            k,
+            prefix: vec![0; num_hashes as usize],
+        })
+    }
+
+    /// Advance to the next valid k‑mer; returns `false` at the end.
+    pub fn roll(&mut self) -> bool {
+        if !self.inner.roll() {
+            return false;
+        }
+        let (fwd, rev) = self
+            .inner
+            .prefix_strands()
+            .expect("initialized with k >= 2");
+        extend_hashes(fwd, rev, self.k as u32 - 1, &mut self.prefix);
+        true
+    }
+
+    /// The longer k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Start of the current k‑mer and of its prefix.
+    pub fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    /// Hashes of the current k‑mer.
+    pub fn hashes(&self) -> &[u64] {
+        self.inner.hashes()
+    }
+
+    /// Hashes of the current k‑mer's first `k − 1` bases.
+    pub fn prefix_hashes(&self) -> &[u64] {
+        &self.prefix
+    }
+
+    /// Canonical hash of the current k‑mer's last `k − 1` bases (the
+    /// (k − 1)‑mer starting at `pos() + 1`).
+    pub fn suffix_hash(&self) -> Option<u64> {
+        self.inner.suffix_hash()
+    }
+
+    /// The underlying k‑mer hasher.
+    pub fn hasher(&self) -> &NtHash<'a> {
+        &self.inner
+    }
+}
+
+impl Iterator for DualKNtHash<'_> {
+    type Item = ((usize, u64), (usize, u64));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.roll().then(|| {
+            let pos = self.pos();
+            ((pos, self.hashes()[0]), (pos, self.prefix[0]))
+        })
+    }
+}
+
+/// Rolling hashes at several k over one traversal of a sequence.
+///
+/// k‑mers are reported in order of start position, and for equal starts
+/// in the order of `ks`.  As an iterator it yields
+/// `(KmerPos, Vec<u64>)`, the k being `pos.end - pos.start`;
+/// [`drive`](Self::drive) avoids the per‑k‑mer allocation.
+///
+/// ```
+/// use nthash_rs::{multik::MultiKNtHash, NtHash};
+///
+/// let seq = b"ACGTTGCAAGCTTAGCGATCGATCGG";
+/// let mut per_k = vec![Vec::new(); 3];
+/// MultiKNtHash::new(seq, &[5, 9, 13], 1)
+///     .unwrap()
+///     .drive(|i, pos, hashes| per_k[i].push((pos, hashes[0])));
+///
+/// let mut k9 = Vec::new();
+/// NtHash::new(seq, 9, 1, 0)
+///     .unwrap()
+///     .drive(&mut |pos, hashes: &[u64]| k9.push((pos, hashes[0])));
+/// assert_eq!(per_k[1], k9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiKNtHash<'a> {
+    states: Vec<NtHash<'a>>,
+    ks: Vec<u16>,
+    live: Vec<bool>,
+    started: bool,
+}
+
+impl<'a> MultiKNtHash<'a> {
+    /// One rolling state per k in `ks`, each producing `num_hashes` values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `ks` is empty, and
+    /// [`NtHash::new`] errors for any k (including one longer than `seq`).
+    pub fn new(seq: &'a [u8], ks: &[u16], num_hashes: u8) -> Result<Self> {
+        if ks.is_empty() {
+            return Err(NtHashError::InvalidK);
+        }
+        let states = ks
+            .iter()
+            .map(|&k| NtHash::new(seq, k, num_hashes, 0))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            states,
+            ks: ks.to_vec(),
+            live: vec![true; ks.len()],
+            started: false,
+        })
+    }
+
+    /// The k‑mer lengths, in the order given.
+    pub fn ks(&self) -> &[u16] {
+        &self.ks
+    }
+
+    /// Advance to the next k‑mer of any length; returns the index into
+    /// [`ks`](Self::ks) of the state that moved, or `None` at the end.
+    /// That state's [`hasher`](Self::hasher) holds the k‑mer.
+    pub fn roll(&mut self) -> Option<usize> {
+        if !self.started {
+            self.started = true;
+            for (h, live) in self.states.iter_mut().zip(&mut self.live) {
+                *live = h.roll();
+            }
+        } else if let Some(i) = self.current() {
+            self.live[i] = self.states[i].roll();
+        }
+        self.current()
+    }
+
+    /// The state for `ks()[i]`.
+    pub fn hasher(&self, i: usize) -> &NtHash<'a> {
+        &self.states[i]
+    }
+
+    /// Roll to the end, calling `f(i, pos, hashes)` for every k‑mer, where
+    /// `i` indexes [`ks`](Self::ks); returns the number of k‑mers visited.
+    pub fn drive<F: FnMut(usize, usize, &[u64])>(&mut self, mut f: F) -> usize {
+        let mut n = 0;
+        while let Some(i) = self.roll() {
+            let h = &self.states[i];
+            f(i, h.pos(), h.hashes());
+            n += 1;
+        }
+        n
+    }
+
+    /// The live state with the leftmost k‑mer (first in `ks` on ties).
+    fn current(&self) -> Option<usize> {
+        (0..self.states.len())
+            .filter(|&i| self.live[i])
+            .min_by_key(|&i| self.states[i].pos())
+    }
+}
+
+impl Iterator for MultiKNtHash<'_> {
+    type Item = (KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.roll().map(|i| {
+            let h = &self.states[i];
+            (h.kmer_pos(), h.hashes().to_vec())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_hashes_match_a_k_minus_one_hasher() {
+        let seq = b"NATCGTACGATNGCATGCATGCTGACGTTNNAGCTAGCATCGGA";
+        for k in [2u16, 3, 8, 13] {
+            let mut dual = DualKNtHash::new(seq, k, 3).unwrap();
+            let mut short = NtHash::new(seq, k - 1, 3, 0).unwrap();
+            let mut n = 0;
+            while dual.roll() {
+                // a valid k‑mer's prefix is a valid (k − 1)‑mer
+                while short.roll() && short.pos() < dual.pos() {}
+                assert_eq!(short.pos(), dual.pos(), "k={k}");
+                assert_eq!(dual.prefix_hashes(), short.hashes(), "k={k}");
+                n += 1;
+            }
+            assert!(n > 0);
+        }
+        assert_eq!(
+            DualKNtHash::new(seq, 1, 1).err(),
+            Some(NtHashError::InvalidK)
+        );
+    }
+
+    #[test]
+    fn multi_k_interleaves_independent_hashers() {
+        let seq = b"NATCGTACGATNGCATGCATGCTGACGTTNNAGCTAGCATCGGATTACAGATTACA";
+        let ks = [7u16, 3, 11, 7];
+        let mut got: Vec<(KmerPos, Vec<u64>)> = MultiKNtHash::new(seq, &ks, 2).unwrap().collect();
+        assert!(got.windows(2).all(|w| w[0].0.start <= w[1].0.start));
+
+        let mut expected = Vec::new();
+        for &k in &ks {
+            expected.extend(
+                crate::NtHashBuilder::new(seq)
+                    .k(k)
+                    .num_hashes(2)
+                    .finish()
+                    .unwrap(),
+            );
+        }
+        got.sort();
+        expected.sort();
+        assert_eq!(got, expected);
+
+        let mut m = MultiKNtHash::new(seq, &ks, 1).unwrap();
+        assert_eq!(m.drive(|_, _, _| {}), expected.len());
+        assert_eq!(m.roll(), None);
+        assert!(MultiKNtHash::new(seq, &[], 1).is_err());
+        assert!(MultiKNtHash::new(seq, &[5, 500], 1).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! **`needletail` integration** (`needletail` feature).
+//!
+//! [`NtHashRecord::nthash`] hashes a `SequenceRecord` without the caller
+//! handling the sequence forms needletail hands out: FASTQ and single‑line
+//! FASTA records are hashed straight from the parser's buffer, while
+//! multi‑line FASTA records are hashed from the line‑joined copy that
+//! `SequenceRecord::seq` returns.  Positions are always offsets into that
+//! joined sequence.
+//!
+//! Soft‑masked (lower‑case) bases hash like upper‑case ones and any other
+//! symbol is skipped like `N`, so `Sequence::normalize` is not needed
+//! first; an already normalized sequence can be hashed with
+//! [`NtHashBuilder`] directly.
+//!
+//! ```
+//! use needletail::parse_fastx_reader;
+//! use nthash_rs::NtHashRecord;
+//!
+//! let fasta = &b">wrapped\nACGTCA\ngtnACG\n>flat\nACGTCAGTNACG\n"[..];
+//! let mut reader = parse_fastx_reader(fasta).unwrap();
+//! let wrapped = reader.next().unwrap().unwrap();
+//! let a: Vec<_> = wrapped.nthash(4, 2).unwrap().collect();
+//! let flat = reader.next().unwrap().unwrap();
+//! let b: Vec<_> = flat.nthash(4, 2).unwrap().collect();
+//! assert_eq!(a, b);
+//! assert_eq!(a.iter().map(|(p, _)| p.start).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+//! ```
+
+use std::borrow::Cow;
+
+use needletail::parser::SequenceRecord;
+
+use crate::{kmer::NtHashIter, owned::OwnedSeq, KmerPos, NtHashBuilder, Result};
+
+/// Rolling ntHash directly over a sequence record.
+pub trait NtHashRecord {
+    /// Iterate over the `num_hashes` hashes of every valid `k`‑mer.
+    ///
+    /// # Errors
+    ///
+    /// As [`NtHashBuilder::finish`]; in particular
+    /// [`NtHashError::SequenceTooShort`](crate::NtHashError::SequenceTooShort)
+    /// for records shorter than `k`.
+    fn nthash(&self, k: u16, num_hashes: u8) -> Result<RecordNtHash<'_>>;
+}
+
+impl NtHashRecord for SequenceRecord<'_> {
+    fn nthash(&self, k: u16, num_hashes: u8) -> Result<RecordNtHash<'_>> {
+        match self.seq() {
+            Cow::Borrowed(seq) => Ok(RecordNtHash {
+                inner: NtHashBuilder::new(seq)
+                    .k(k)
+                    .num_hashes(num_hashes)
+                    .finish()?,
+                _seq: None,
+            }),
+            Cow::Owned(seq) => {
+                let owned = OwnedSeq::new(seq);
+                // SAFETY: `inner` is dropped before `_seq`.
+                let seq = unsafe { owned.as_static() };
+                Ok(RecordNtHash {
+                    inner: NtHashBuilder::new(seq)
+                        .k(k)
+                        .num_hashes(num_hashes)
+                        .finish()?,
+                    _seq: Some(owned),
+                })
+            }
+        }
+    }
+}
+
+/// Iterator returned by [`NtHashRecord::nthash`], yielding the same items
+/// as [`NtHashBuilder`]'s iterator.
+#[derive(Debug)]
+pub struct RecordNtHash<'a> {
+    inner: NtHashIter<'a>,
+    _seq: Option<OwnedSeq>,
+}
+
+impl Iterator for RecordNtHash<'_> {
+    type Item = (KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use needletail::parse_fastx_reader;
+
+    use super::*;
+    use crate::NtHashError;
+
+    #[test]
+    fn every_record_form_hashes_like_its_sequence() {
+        let seq = b"ACGTTGCAAGNNCTTAGCGATCgatcgGGCTAGCAT";
+        let expected: Vec<_> = NtHashBuilder::new(seq)
+            .k(7)
+            .num_hashes(3)
+            .into_iter()
+            .collect();
+        let wrapped: Vec<u8> = seq
+            .chunks(10)
+            .flat_map(|line| [line, b"\r\n"].concat())
+            .collect();
+        let files = [
+            [&b">r\n"[..], seq, b"\n"].concat(),
+            [&b">r\r\n"[..], &wrapped].concat(),
+            [&b"@r\n"[..], seq, b"\n+\n", &[b'I'; 36], b"\n"].concat(),
+        ];
+        for file in files {
+            let mut reader = parse_fastx_reader(&file[..]).unwrap();
+            let record = reader.next().unwrap().unwrap();
+            let got: Vec<_> = record.nthash(7, 3).unwrap().collect();
+            assert_eq!(got, expected);
+            assert_eq!(
+                record.nthash(40, 1).err(),
+                Some(NtHashError::SequenceTooShort { seq_len: 36, k: 40 })
+            );
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Heap‑owned sequences for hashers that must outlive their input.
+
+/// Heap copy of a sequence, borrowed for `'static` by a hasher stored next
+/// to it.
+///
+/// The owning struct must declare the hasher *before* this field so that it
+/// is dropped first.
+#[derive(Debug)]
+pub(crate) struct OwnedSeq(*mut [u8]);
+
+impl OwnedSeq {
+    pub(crate) fn new(seq: Vec<u8>) -> Self {
+        Self(Box::into_raw(seq.into_boxed_slice()))
+    }
+
+    /// # Safety
+    ///
+    /// The returned slice must not outlive `self`.
+    pub(crate) unsafe fn as_static(&self) -> &'static [u8] {
+        &*self.0
+    }
+}
+
+impl Drop for OwnedSeq {
+    fn drop(&mut self) {
+        // SAFETY: created by `Box::into_raw` in `new` and never freed elsewhere.
+        drop(unsafe { Box::from_raw(self.0) });
+    }
+}
+
+// SAFETY: the buffer is immutable after construction.
+unsafe impl Send for OwnedSeq {}
+unsafe impl Sync for OwnedSeq {}
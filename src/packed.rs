@@ -0,0 +1,413 @@
+//! **Small‑k hashing from a packed 2‑bit window.**
+//!
+//! For `k ≤ 32` the whole k‑mer fits in one `u64` at two bits per base, so
+//! [`PackedHash`] keeps the forward and reverse‑complement encodings in
+//! registers, shifts one base in per roll, and mixes the smaller of the two
+//! with the SplitMix64 finalizer.  No seed tables are read, which makes it
+//! the cheaper choice for minimizer‑style workloads dominated by small `k`.
+//!
+//! The values are **not ntHash values**: they are a different (equally
+//! strand‑neutral) hash family, so they must not be mixed with sketches or
+//! indexes built with [`NtHash`](crate::NtHash).  For that reason the
+//! specialization is never chosen automatically; opt in with
+//! [`NtHashBuilder::small_k`](crate::NtHashBuilder::small_k) or construct a
+//! [`PackedHash`] directly.
+//!
+//! ```
+//! use nthash_rs::packed::PackedHash;
+//!
+//! let seq = b"ACGTNTTGCAAC";
+//! let mut h = PackedHash::new(seq, 4, 2, 0).unwrap();
+//! let mut starts = Vec::new();
+//! while h.roll() {
+//!     starts.push(h.pos());
+//! }
+//! assert_eq!(starts, [0, 5, 6, 7, 8]);
+//!
+//! // the reverse complement hashes the same k‑mers to the same values
+//! let mut rc = PackedHash::new(b"GCAA", 4, 2, 0).unwrap();
+//! assert!(rc.roll());
+//! let mut fwd = PackedHash::new(b"TTGC", 4, 2, 0).unwrap();
+//! assert!(fwd.roll());
+//! assert_eq!(rc.hashes(), fwd.hashes());
+//! ```
+
+use crate::{
+    constants::{CONVERT_TAB, MULTISEED},
+    kmer::KmerPos,
+    util::{extend_hashes_with, salt_hash, same_seq, CanonicalPolicy, HashMixer, Mixer},
+    NtHashError, Result,
+};
+
+/// Largest `k` a [`PackedHash`] supports.
+pub const MAX_K: u16 = 32;
+
+/// Rolling hasher over packed 2‑bit k‑mers, for `k ≤ 32`; see the
+/// [module docs](self).
+///
+/// `A/C/G/T/U` in either case are bases; windows holding anything else are
+/// skipped.  A failed [`roll`](Self::roll) or [`roll_back`](Self::roll_back)
+/// leaves the hasher on its current k‑mer, and no method panics once
+/// constructed.
+#[derive(Clone)]
+pub struct PackedHash<'a> {
+    seq: &'a [u8],
+    k: u16,
+    /// Start of the last window, `seq.len() - k`.
+    pub(crate) end: usize,
+    pos: usize,
+    initialized: bool,
+    fwd: u64,
+    rev: u64,
+    hashes: Vec<u64>,
+    mixer: Mixer,
+}
+
+impl std::fmt::Debug for PackedHash<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PackedHash")
+            .field("seq_len", &self.seq.len())
+            .field("k", &self.k)
+            .field("pos", &self.pos)
+            .field("initialized", &self.initialized)
+            .field("hashes", &self.hashes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for PackedHash<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.k, self.pos, self.initialized) == (other.k, other.pos, other.initialized)
+            && (!self.initialized || (self.fwd, self.rev) == (other.fwd, other.rev))
+            && self.hashes.len() == other.hashes.len()
+            && same_seq(self.seq, other.seq)
+    }
+}
+
+impl Eq for PackedHash<'_> {}
+
+impl<'a> PackedHash<'a> {
+    /// Create a hasher whose first [`roll`](Self::roll) lands on the first
+    /// valid k‑mer at or after `pos`.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidK`] for `k == 0`, [`NtHashError::KTooLarge`]
+    /// for `k > 32`, and otherwise as [`NtHash::new`](crate::NtHash::new).
+    pub fn new(seq: &'a [u8], k: u16, num_hashes: u8, pos: usize) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if k > MAX_K {
+            return Err(NtHashError::KTooLarge { k, max: MAX_K });
+        }
+        let Some(end) = seq.len().checked_sub(k as usize) else {
+            return Err(NtHashError::SequenceTooShort {
+                seq_len: seq.len(),
+                k,
+            });
+        };
+        if pos > end {
+            return Err(NtHashError::PositionOutOfRange {
+                pos,
+                seq_len: seq.len(),
+            });
+        }
+        Ok(Self {
+            seq,
+            k,
+            end,
+            pos,
+            initialized: false,
+            fwd: 0,
+            rev: 0,
+            hashes: vec![0; num_hashes as usize],
+            mixer: None,
+        })
+    }
+
+    /// Derive the extra hashes with `mixer` instead of the reference
+    /// scheme; see [`HashMixer`].
+    pub fn with_mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        if self.initialized {
+            self.update_hashes();
+        }
+        self
+    }
+
+    /// Move to the next valid k‑mer, jumping over windows with invalid
+    /// bases.  Returns `false`, staying put, when there is none.
+    pub fn roll(&mut self) -> bool {
+        if !self.initialized {
+            return self.settle(self.scan_forward(self.pos));
+        }
+        if self.pos >= self.end {
+            return false;
+        }
+        let code = CONVERT_TAB[self.seq[self.pos + self.k as usize] as usize];
+        if code > 3 {
+            let past = self.pos + self.k as usize + 1;
+            return past <= self.end && self.settle(self.scan_forward(past));
+        }
+        let code = code as u64;
+        self.fwd = (self.fwd << 2 | code) & self.mask();
+        self.rev = self.rev >> 2 | (3 - code) << (2 * (self.k - 1));
+        self.pos += 1;
+        self.update_hashes();
+        true
+    }
+
+    /// Move to the previous valid k‑mer, jumping backward over windows
+    /// with invalid bases.  Returns `false`, staying put, when there is
+    /// none.
+    pub fn roll_back(&mut self) -> bool {
+        if !self.initialized {
+            return self.settle(self.scan_forward(self.pos));
+        }
+        if self.pos == 0 {
+            return false;
+        }
+        let code = CONVERT_TAB[self.seq[self.pos - 1] as usize];
+        if code > 3 {
+            // the window must end before the invalid base
+            return match (self.pos - 1).checked_sub(self.k as usize) {
+                Some(last) => self.settle(self.scan_back(last)),
+                None => false,
+            };
+        }
+        let code = code as u64;
+        self.fwd = self.fwd >> 2 | code << (2 * (self.k - 1));
+        self.rev = (self.rev << 2 | (3 - code)) & self.mask();
+        self.pos -= 1;
+        self.update_hashes();
+        true
+    }
+
+    /// Restart so that the next [`roll`](Self::roll) scans forward from
+    /// `pos`; `false` (and no change) if `pos` is past the last window.
+    pub(crate) fn restart_at(&mut self, pos: usize) -> bool {
+        if pos > self.end {
+            return false;
+        }
+        self.pos = pos;
+        self.initialized = false;
+        true
+    }
+
+    /// Move to the last valid k‑mer starting at or before `pos`.
+    pub(crate) fn init_back_from(&mut self, pos: usize) -> bool {
+        pos <= self.end && self.settle(self.scan_back(pos))
+    }
+
+    /// The sequence being hashed.
+    pub(crate) fn seq(&self) -> &'a [u8] {
+        self.seq
+    }
+
+    /// Hash values of the current k‑mer: the canonical hash, then
+    /// `num_hashes - 1` extra hashes derived from it.
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// Start of the current k‑mer.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The current k‑mer's range in the sequence.
+    pub fn kmer_pos(&self) -> KmerPos {
+        KmerPos::new(self.pos, self.k as usize)
+    }
+
+    /// k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// 2‑bit encoding of the current k‑mer (`A=0, C=1, G=2, T=3`, first
+    /// base most significant).
+    pub fn forward_kmer(&self) -> u64 {
+        self.fwd
+    }
+
+    /// 2‑bit encoding of the current k‑mer's reverse complement.
+    pub fn reverse_kmer(&self) -> u64 {
+        self.rev
+    }
+
+    /// Hash of the forward strand alone.
+    pub fn forward_hash(&self) -> u64 {
+        self.mix(self.fwd)
+    }
+
+    /// Hash of the reverse‑complement strand alone.
+    pub fn reverse_hash(&self) -> u64 {
+        self.mix(self.rev)
+    }
+
+    #[inline(always)]
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - 2 * self.k as u32)
+    }
+
+    /// SplitMix64 finalizer, keyed by `k` so that equal encodings of
+    /// different lengths (e.g. `A` and `AA`) hash apart.
+    #[inline(always)]
+    fn mix(&self, packed: u64) -> u64 {
+        salt_hash(packed, (self.k as u64).wrapping_mul(MULTISEED))
+    }
+
+    #[inline(always)]
+    fn update_hashes(&mut self) {
+        let base = self.mix(self.fwd.min(self.rev));
+        // extend from the single canonical value
+        extend_hashes_with(
+            self.mixer,
+            CanonicalPolicy::Sum,
+            base,
+            0,
+            self.k as u32,
+            &mut self.hashes,
+        );
+    }
+
+    /// Take up a window found by a scan, if any.
+    fn settle(&mut self, found: Option<(usize, u64, u64)>) -> bool {
+        let Some((pos, fwd, rev)) = found else {
+            return false;
+        };
+        (self.pos, self.fwd, self.rev, self.initialized) = (pos, fwd, rev, true);
+        self.update_hashes();
+        true
+    }
+
+    /// The first valid window starting at or after `from`.
+    fn scan_forward(&self, from: usize) -> Option<(usize, u64, u64)> {
+        let (k, mask) = (self.k as usize, self.mask());
+        let (mut fwd, mut rev, mut run) = (0u64, 0u64, 0);
+        for (i, &c) in self.seq.iter().enumerate().skip(from) {
+            let code = CONVERT_TAB[c as usize];
+            if code > 3 {
+                run = 0;
+                continue;
+            }
+            let code = code as u64;
+            fwd = (fwd << 2 | code) & mask;
+            rev = rev >> 2 | (3 - code) << (2 * (k - 1));
+            run += 1;
+            if run == k {
+                return Some((i + 1 - k, fwd, rev));
+            }
+        }
+        None
+    }
+
+    /// The last valid window starting at or before `last`.
+    fn scan_back(&self, last: usize) -> Option<(usize, u64, u64)> {
+        let (k, mask) = (self.k as usize, self.mask());
+        let (mut fwd, mut rev, mut run) = (0u64, 0u64, 0);
+        for i in (0..last + k).rev() {
+            let code = CONVERT_TAB[self.seq[i] as usize];
+            if code > 3 {
+                run = 0;
+                continue;
+            }
+            let code = code as u64;
+            fwd = fwd >> 2 | code << (2 * (k - 1));
+            rev = (rev << 2 | (3 - code)) & mask;
+            run += 1;
+            if run == k {
+                return Some((i, fwd, rev));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(window: &[u8]) -> u64 {
+        window
+            .iter()
+            .fold(0, |acc, &c| acc << 2 | CONVERT_TAB[c as usize] as u64)
+    }
+
+    fn revcomp(window: &[u8]) -> Vec<u8> {
+        window
+            .iter()
+            .rev()
+            .map(|&c| match c.to_ascii_uppercase() {
+                b'A' => b'T',
+                b'C' => b'G',
+                b'G' => b'C',
+                _ => b'A',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rolls_match_direct_encoding_both_ways() {
+        let seq = b"ACGTNacgtTTGCANNGATCCAGGTACCTTAGGCATGCAAGTU";
+        for k in 1..=MAX_K.min(16) {
+            let valid: Vec<usize> = seq
+                .windows(k as usize)
+                .enumerate()
+                .filter(|(_, w)| w.iter().all(|&c| CONVERT_TAB[c as usize] < 4))
+                .map(|(i, _)| i)
+                .collect();
+            let mut h = PackedHash::new(seq, k, 3, 0).unwrap();
+            let mut forward = Vec::new();
+            while h.roll() {
+                let w = &seq[h.pos()..h.pos() + k as usize];
+                assert_eq!(
+                    (h.forward_kmer(), h.reverse_kmer()),
+                    (encode(w), encode(&revcomp(w)))
+                );
+                forward.push(h.pos());
+            }
+            assert_eq!(forward, valid, "k={k}");
+            let mut backward = vec![h.pos()];
+            while h.roll_back() {
+                let w = &seq[h.pos()..h.pos() + k as usize];
+                assert_eq!(h.forward_kmer(), encode(w));
+                backward.push(h.pos());
+            }
+            backward.reverse();
+            assert_eq!(backward, valid, "k={k}");
+        }
+    }
+
+    #[test]
+    fn hashes_are_canonical_and_keyed_by_k() {
+        fn first(seq: &[u8], k: u16) -> PackedHash<'_> {
+            let mut h = PackedHash::new(seq, k, 2, 0).unwrap();
+            assert!(h.roll());
+            h
+        }
+        let (fwd, rc) = (first(b"ACCGTTAG", 8), first(b"CTAACGGT", 8));
+        assert_eq!(fwd.hashes(), rc.hashes());
+        assert_eq!(fwd.forward_hash(), rc.reverse_hash());
+        assert_ne!(first(b"A", 1).hashes(), first(b"AA", 2).hashes());
+        assert_eq!(
+            PackedHash::new(&[b'A'; 40], 33, 1, 0).err(),
+            Some(NtHashError::KTooLarge { k: 33, max: 32 })
+        );
+        assert_eq!(first(&[b'T'; 32], 32).forward_kmer(), u64::MAX);
+    }
+
+    #[test]
+    fn failed_moves_keep_the_current_kmer() {
+        let seq = b"NNACGTNNN";
+        let mut h = PackedHash::new(seq, 4, 1, 0).unwrap();
+        assert!(h.roll());
+        let at = h.clone();
+        assert!(!h.roll());
+        assert!(!h.roll_back());
+        assert_eq!(h, at);
+        assert!(!PackedHash::new(b"NNNN", 4, 1, 0).unwrap().roll_back());
+    }
+}
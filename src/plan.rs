@@ -0,0 +1,509 @@
+//! **Memory‑budgeted k‑mer sets.**
+//!
+//! Given an estimate of the number of distinct k‑mers (e.g. from a
+//! [`HyperLogLog`] pass) and a memory budget, [`MemoryPlan::new`] picks the
+//! most precise structure that fits:
+//!
+//! 1. [`Structure::Exact`] — a hash set of canonical hashes (fast, ~9–18
+//!    bytes per k‑mer);
+//! 2. [`Structure::EliasFano`] — the same hashes as a compressed sorted
+//!    list ([`EliasFano`], ~`66 − log₂ n` bits per k‑mer, slower lookups);
+//! 3. [`Structure::Bloom`] — a [`BloomFilter`] using the whole budget with
+//!    the optimal number of hashes, trading exactness for false positives.
+//!
+//! [`MemoryPlan::builder`] then constructs the chosen structure straight
+//! from the hash stream.  The Elias–Fano list is built from the sorted
+//! hashes, so construction briefly needs 8 bytes per k‑mer on top of the
+//! budget.
+//!
+//! The planner answers membership only.  A counting quotient filter is
+//! deliberately not among the choices: its advantage over these is
+//! storing counts, which a membership set discards, and for plain
+//! membership at a fixed budget it sits between the Elias–Fano list and
+//! the Bloom filter without beating either.  Budgeted counting is covered
+//! by [`CountingBloomFilter`](crate::bloom::CountingBloomFilter) and
+//! [`KmerCounter`](crate::count::KmerCounter).
+//!
+//! ```
+//! use nthash_rs::plan::{MemoryPlan, Structure};
+//!
+//! // ten million k‑mers in 16 MiB: too many for an exact set
+//! let plan = MemoryPlan::new(10_000_000, 16 << 20).unwrap();
+//! assert!(matches!(plan.structure(), Structure::Bloom { .. }));
+//!
+//! let plan = MemoryPlan::new(1_000, 1 << 20).unwrap();
+//! assert_eq!(plan.structure(), Structure::Exact);
+//! let mut set = plan.builder();
+//! set.insert_seq(b"ACGTACGTTGCA", 5).unwrap();
+//! let set = set.finish();
+//! assert_eq!(set.query_seq(b"ACGTACGT", 5).unwrap(), 4);
+//! ```
+
+use std::collections::HashSet;
+
+use crate::{bloom::BloomFilter, sketch::HyperLogLog, NtHash, NtHashError, Result};
+
+/// Zeros between samples of the Elias–Fano upper‑bits index.
+const SELECT_SAMPLE: usize = 256;
+
+/// The structure chosen by a [`MemoryPlan`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Structure {
+    /// Exact hash set of canonical hashes.
+    Exact,
+    /// Elias–Fano compressed sorted list of canonical hashes.
+    EliasFano,
+    /// Bloom filter with `num_bits` bits and `num_hashes` hashes per k‑mer.
+    Bloom {
+        /// Filter size in bits.
+        num_bits: usize,
+        /// Hashes per k‑mer.
+        num_hashes: u8,
+    },
+}
+
+/// A structure choice sized for an expected number of distinct k‑mers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryPlan {
+    structure: Structure,
+    distinct: u64,
+    bytes: usize,
+}
+
+impl MemoryPlan {
+    /// Choose the most precise structure holding `distinct` k‑mers in at
+    /// most `budget_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] if the budget is smaller
+    /// than one 64‑bit word.
+    pub fn new(distinct: u64, budget_bytes: usize) -> Result<Self> {
+        if budget_bytes < 8 {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        let n = distinct.max(1);
+        let exact = exact_bytes(n);
+        if exact <= budget_bytes {
+            return Ok(Self {
+                structure: Structure::Exact,
+                distinct,
+                bytes: exact,
+            });
+        }
+        let ef = elias_fano_bytes(n);
+        if ef <= budget_bytes {
+            return Ok(Self {
+                structure: Structure::EliasFano,
+                distinct,
+                bytes: ef,
+            });
+        }
+        let num_bits = budget_bytes / 8 * 64;
+        let optimal = (num_bits as f64 / n as f64 * std::f64::consts::LN_2).round();
+        Ok(Self {
+            structure: Structure::Bloom {
+                num_bits,
+                num_hashes: optimal.clamp(1.0, u8::MAX as f64) as u8,
+            },
+            distinct,
+            bytes: num_bits / 8,
+        })
+    }
+
+    /// Plan for the cardinality estimated by `hll`.
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new).
+    pub fn from_estimate(hll: &HyperLogLog, budget_bytes: usize) -> Result<Self> {
+        Self::new(hll.estimate().ceil() as u64, budget_bytes)
+    }
+
+    /// The chosen structure.
+    pub fn structure(&self) -> Structure {
+        self.structure
+    }
+
+    /// Approximate memory footprint of the structure when full.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Hashes per k‑mer the structure consumes (1 unless Bloom).
+    pub fn num_hashes(&self) -> u8 {
+        match self.structure {
+            Structure::Bloom { num_hashes, .. } => num_hashes,
+            _ => 1,
+        }
+    }
+
+    /// Expected false‑positive rate once `distinct` k‑mers are inserted;
+    /// zero for the exact structures (up to 64‑bit hash collisions).
+    pub fn expected_fpr(&self) -> f64 {
+        match self.structure {
+            Structure::Bloom {
+                num_bits,
+                num_hashes,
+            } => {
+                let k = num_hashes as f64;
+                let fill = 1.0 - (-k * self.distinct as f64 / num_bits as f64).exp();
+                fill.powf(k)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// An empty builder for the chosen structure.
+    pub fn builder(&self) -> KmerSetBuilder {
+        let inner = match self.structure {
+            Structure::Exact => BuilderInner::Exact(HashSet::with_capacity(self.distinct as usize)),
+            Structure::EliasFano => {
+                BuilderInner::Sorted(Vec::with_capacity(self.distinct as usize))
+            }
+            Structure::Bloom {
+                num_bits,
+                num_hashes,
+            } => BuilderInner::Bloom(BloomFilter::new(num_bits, num_hashes as usize)),
+        };
+        KmerSetBuilder {
+            inner,
+            num_hashes: self.num_hashes(),
+        }
+    }
+}
+
+/// Approximate size of a `HashSet<u64>` of `n` entries: power‑of‑two
+/// buckets at 7/8 load, 8 bytes plus one control byte each.
+fn exact_bytes(n: u64) -> usize {
+    let buckets = (n.saturating_mul(8) / 7).max(4).next_power_of_two();
+    buckets.saturating_mul(9) as usize
+}
+
+/// Size of an [`EliasFano`] list of `n` distinct 64‑bit values.
+fn elias_fano_bytes(n: u64) -> usize {
+    let l = n.leading_zeros() as u64;
+    let lows = (n * l).div_ceil(64);
+    let highs = (n + (n << 1)).div_ceil(64);
+    let samples = n.div_ceil(SELECT_SAMPLE as u64) * 2 + 1;
+    ((lows + highs + samples) * 8) as usize
+}
+
+enum BuilderInner {
+    Exact(HashSet<u64>),
+    Sorted(Vec<u64>),
+    Bloom(BloomFilter),
+}
+
+/// Accumulates k‑mer hashes into the structure chosen by a [`MemoryPlan`].
+pub struct KmerSetBuilder {
+    inner: BuilderInner,
+    num_hashes: u8,
+}
+
+impl KmerSetBuilder {
+    /// Insert one k‑mer by its hash values (at least
+    /// [`MemoryPlan::num_hashes`] of them; only the first, canonical, hash
+    /// is used by the exact structures).  An empty slice is ignored.
+    pub fn insert(&mut self, hashes: &[u64]) {
+        let Some(&canonical) = hashes.first() else {
+            return;
+        };
+        match &mut self.inner {
+            BuilderInner::Exact(set) => {
+                set.insert(canonical);
+            }
+            BuilderInner::Sorted(list) => list.push(canonical),
+            BuilderInner::Bloom(bf) => {
+                bf.insert(hashes);
+            }
+        }
+    }
+
+    /// Insert every valid k‑mer of `seq`.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn insert_seq(&mut self, seq: &[u8], k: u16) -> Result<()> {
+        let mut h = NtHash::new(seq, k, self.num_hashes, 0)?;
+        while h.roll() {
+            self.insert(h.hashes());
+        }
+        Ok(())
+    }
+
+    /// Finalise the structure.
+    pub fn finish(self) -> KmerSet {
+        let inner = match self.inner {
+            BuilderInner::Exact(set) => SetInner::Exact(set),
+            BuilderInner::Sorted(mut list) => {
+                list.sort_unstable();
+                list.dedup();
+                SetInner::EliasFano(EliasFano::from_sorted(&list))
+            }
+            BuilderInner::Bloom(bf) => SetInner::Bloom(bf),
+        };
+        KmerSet {
+            inner,
+            num_hashes: self.num_hashes,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum SetInner {
+    Exact(HashSet<u64>),
+    EliasFano(EliasFano),
+    Bloom(BloomFilter),
+}
+
+/// A k‑mer membership structure built from a [`MemoryPlan`].
+#[derive(Debug, Clone)]
+pub struct KmerSet {
+    inner: SetInner,
+    num_hashes: u8,
+}
+
+impl KmerSet {
+    /// Returns `true` if the k‑mer with these hash values may be present
+    /// (exactly, unless the set is a Bloom filter); `false` for an empty
+    /// slice.
+    pub fn contains(&self, hashes: &[u64]) -> bool {
+        let Some(&canonical) = hashes.first() else {
+            return false;
+        };
+        match &self.inner {
+            SetInner::Exact(set) => set.contains(&canonical),
+            SetInner::EliasFano(ef) => ef.contains(canonical),
+            SetInner::Bloom(bf) => bf.contains(hashes),
+        }
+    }
+
+    /// Number of valid k‑mers of `seq` the set may contain.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn query_seq(&self, seq: &[u8], k: u16) -> Result<usize> {
+        let mut h = NtHash::new(seq, k, self.num_hashes, 0)?;
+        let mut hits = 0;
+        while h.roll() {
+            hits += self.contains(h.hashes()) as usize;
+        }
+        Ok(hits)
+    }
+
+    /// Hashes per k‑mer that [`contains`](Self::contains) expects.
+    pub fn num_hashes(&self) -> u8 {
+        self.num_hashes
+    }
+
+    /// Returns `true` if lookups can give false positives.
+    pub fn is_approximate(&self) -> bool {
+        matches!(self.inner, SetInner::Bloom(_))
+    }
+}
+
+/// Elias–Fano encoding of a sorted set of 64‑bit values.
+///
+/// Each value is split into `l` low bits, stored packed, and high bits,
+/// stored in unary in a bit vector of about `2n` bits; membership takes
+/// one sampled select plus a short scan of the value's bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EliasFano {
+    len: usize,
+    low_bits: u32,
+    lows: Vec<u64>,
+    highs: Vec<u64>,
+    // bit position of every SELECT_SAMPLE‑th zero in `highs`
+    zero_samples: Vec<usize>,
+    num_buckets: u64,
+}
+
+impl EliasFano {
+    /// Encode `values`, which must be strictly increasing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is not strictly increasing.
+    pub fn from_sorted(values: &[u64]) -> Self {
+        assert!(
+            values.windows(2).all(|w| w[0] < w[1]),
+            "Elias–Fano input must be strictly increasing"
+        );
+        let n = values.len();
+        let low_bits = (n.max(1) as u64).leading_zeros();
+        let num_buckets = values.last().map_or(0, |&v| (v >> low_bits) + 1);
+        let upper_len = n + num_buckets as usize;
+        let mut lows = vec![0u64; (n * low_bits as usize).div_ceil(64) + 1];
+        let mut highs = vec![0u64; upper_len.div_ceil(64).max(1)];
+        let mask = (1u64 << low_bits) - 1;
+        for (i, &v) in values.iter().enumerate() {
+            let bit = i * low_bits as usize;
+            let low = v & mask;
+            lows[bit / 64] |= low << (bit % 64);
+            if bit % 64 + low_bits as usize > 64 {
+                lows[bit / 64 + 1] |= low >> (64 - bit % 64);
+            }
+            let p = (v >> low_bits) as usize + i;
+            highs[p / 64] |= 1 << (p % 64);
+        }
+        let mut zero_samples = Vec::new();
+        let mut zeros = 0;
+        for p in 0..upper_len {
+            if highs[p / 64] >> (p % 64) & 1 == 0 {
+                if zeros % SELECT_SAMPLE == 0 {
+                    zero_samples.push(p);
+                }
+                zeros += 1;
+            }
+        }
+        Self {
+            len: n,
+            low_bits,
+            lows,
+            highs,
+            zero_samples,
+            num_buckets,
+        }
+    }
+
+    /// Number of values stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Heap bytes used by the encoding.
+    pub fn size_in_bytes(&self) -> usize {
+        (self.lows.len() + self.highs.len()) * 8
+            + self.zero_samples.len() * std::mem::size_of::<usize>()
+    }
+
+    fn low(&self, i: usize) -> u64 {
+        let bit = i * self.low_bits as usize;
+        let pair = self.lows[bit / 64] as u128 | (self.lows[bit / 64 + 1] as u128) << 64;
+        (pair >> (bit % 64)) as u64 & ((1u64 << self.low_bits) - 1)
+    }
+
+    fn bit(&self, p: usize) -> bool {
+        self.highs[p / 64] >> (p % 64) & 1 == 1
+    }
+
+    /// Position of the `j`‑th zero of the upper bits.
+    fn select0(&self, j: usize) -> usize {
+        let mut p = self.zero_samples[j / SELECT_SAMPLE];
+        let mut left = j % SELECT_SAMPLE;
+        loop {
+            if !self.bit(p) {
+                if left == 0 {
+                    return p;
+                }
+                left -= 1;
+            }
+            p += 1;
+        }
+    }
+
+    /// Returns `true` if `value` is stored.
+    pub fn contains(&self, value: u64) -> bool {
+        let high = value >> self.low_bits;
+        if high >= self.num_buckets {
+            return false;
+        }
+        let low = value & ((1u64 << self.low_bits) - 1);
+        // the ones of bucket `high` follow its preceding zero
+        let mut p = if high == 0 {
+            0
+        } else {
+            self.select0(high as usize - 1) + 1
+        };
+        let mut i = p - high as usize;
+        while self.bit(p) {
+            match self.low(i).cmp(&low) {
+                std::cmp::Ordering::Less => {}
+                std::cmp::Ordering::Equal => return true,
+                std::cmp::Ordering::Greater => return false,
+            }
+            p += 1;
+            i += 1;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn splitmix(mut x: u64) -> u64 {
+        x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        x ^ (x >> 31)
+    }
+
+    #[test]
+    fn elias_fano_membership_is_exact() {
+        for n in [0, 1, 2, 3, 100, 5000] {
+            let mut values: Vec<u64> = (0..n).map(splitmix).collect();
+            values.sort_unstable();
+            let ef = EliasFano::from_sorted(&values);
+            assert_eq!(ef.len(), n as usize);
+            assert!(values.iter().all(|&v| ef.contains(v)));
+            assert!((n..n + 2000).all(|x| !ef.contains(splitmix(x))));
+            if n >= 100 {
+                assert!(ef.size_in_bytes() <= elias_fano_bytes(n));
+                assert!(ef.size_in_bytes() < n as usize * 8);
+            }
+        }
+        let dense: Vec<u64> = (0..300).chain([u64::MAX - 1, u64::MAX]).collect();
+        let ef = EliasFano::from_sorted(&dense);
+        assert!(dense.iter().all(|&v| ef.contains(v)));
+        assert!(!ef.contains(300) && !ef.contains(u64::MAX - 2));
+    }
+
+    #[test]
+    fn plan_degrades_with_the_budget() {
+        let n = 100_000;
+        let exact = MemoryPlan::new(n, 64 << 20).unwrap();
+        assert_eq!(exact.structure(), Structure::Exact);
+        let ef = MemoryPlan::new(n, 1 << 20).unwrap();
+        assert_eq!(ef.structure(), Structure::EliasFano);
+        let bloom = MemoryPlan::new(n, 64 << 10).unwrap();
+        let Structure::Bloom {
+            num_bits,
+            num_hashes,
+        } = bloom.structure()
+        else {
+            panic!("expected a Bloom filter, got {:?}", bloom.structure());
+        };
+        assert_eq!((num_bits, num_hashes), (64 << 13, 4));
+        assert!(bloom.expected_fpr() > 0.0 && bloom.expected_fpr() < 0.2);
+        assert!(MemoryPlan::new(n, 4).is_err());
+
+        let seq: Vec<u8> = (0..3000)
+            .map(|i| b"ACGT"[(splitmix(i) & 3) as usize])
+            .collect();
+        let other: Vec<u8> = (0..3000)
+            .map(|i| b"ACGT"[(splitmix(i + 9999) & 3) as usize])
+            .collect();
+        for plan in [exact, ef, bloom] {
+            let mut b = plan.builder();
+            b.insert_seq(&seq, 21).unwrap();
+            b.insert(&[]);
+            let set = b.finish();
+            assert!(!set.contains(&[]));
+            assert_eq!(set.query_seq(&seq, 21).unwrap(), seq.len() - 20);
+            let false_hits = set.query_seq(&other, 21).unwrap();
+            if !set.is_approximate() {
+                assert_eq!(false_hits, 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! **Python bindings** (PyO3) for the three rolling hashers.
+//!
+//! Built as the `nthash_rs` extension module with
+//! [maturin](https://www.maturin.rs/) (`maturin develop` / `maturin build`,
+//! configured in `pyproject.toml`):
+//!
+//! ```python
+//! import nthash_rs
+//!
+//! for pos, hashes in nthash_rs.NtHash("ACGTCAGTNNNNACGTACGT", k=4, num_hashes=2):
+//!     print(pos, hashes)
+//!
+//! positions, hashes = nthash_rs.NtHash(seq, k=21).arrays()   # numpy uint64
+//! ```
+//!
+//! Sequences may be passed as `str` or `bytes`; invalid arguments raise
+//! `ValueError`.
+
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyArrayMethods};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+
+use crate::{owned::OwnedSeq, BlindNtHash, NtHash, NtHashError, SeedNtHash};
+
+impl From<NtHashError> for PyErr {
+    fn from(e: NtHashError) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// Bytes of a `str` or `bytes` argument.
+fn seq_bytes(obj: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(s) = obj.cast::<PyString>() {
+        Ok(s.to_str()?.as_bytes().to_vec())
+    } else if let Ok(b) = obj.cast::<PyBytes>() {
+        Ok(b.as_bytes().to_vec())
+    } else {
+        Err(PyTypeError::new_err("expected str or bytes"))
+    }
+}
+
+/// A single base given as a one‑character `str` / `bytes` or an `int`.
+fn base_byte(obj: &Bound<'_, PyAny>) -> PyResult<u8> {
+    if let Ok(c) = obj.extract::<u8>() {
+        return Ok(c);
+    }
+    match seq_bytes(obj)?.as_slice() {
+        [c] => Ok(*c),
+        _ => Err(PyValueError::new_err("expected a single base")),
+    }
+}
+
+/// `(positions, hashes)` numpy arrays returned by the batch methods.
+type HashArrays<'py> = (Bound<'py, PyArray1<usize>>, Bound<'py, PyArray2<u64>>);
+
+/// Flatten `(pos, hashes)` rows into numpy arrays of shape `(n,)` and `(n, m)`.
+fn to_arrays(
+    py: Python<'_>,
+    positions: Vec<usize>,
+    flat: Vec<u64>,
+    m: usize,
+) -> PyResult<HashArrays<'_>> {
+    let n = positions.len();
+    let hashes = PyArray1::from_vec(py, flat).reshape([n, m])?;
+    Ok((positions.into_pyarray(py), hashes))
+}
+
+/// `NtHash(seq, k, num_hashes=1, pos=0)` — iterable over `(pos, hashes)`.
+#[pyclass(name = "NtHash", module = "nthash_rs")]
+struct PyNtHash {
+    inner: NtHash<'static>,
+    _seq: OwnedSeq,
+}
+
+#[pymethods]
+impl PyNtHash {
+    #[new]
+    #[pyo3(signature = (seq, k, num_hashes = 1, pos = 0))]
+    fn new(seq: &Bound<'_, PyAny>, k: u16, num_hashes: u8, pos: usize) -> PyResult<Self> {
+        let owned = OwnedSeq::new(seq_bytes(seq)?);
+        // SAFETY: `inner` is dropped before `_seq`.
+        let inner = NtHash::new(unsafe { owned.as_static() }, k, num_hashes, pos)?;
+        Ok(Self { inner, _seq: owned })
+    }
+
+    /// Advance to the next valid k‑mer; `False` at the end of the sequence.
+    fn roll(&mut self) -> bool {
+        self.inner.roll()
+    }
+
+    /// Step back to the previous valid k‑mer.
+    fn roll_back(&mut self) -> bool {
+        self.inner.roll_back()
+    }
+
+    #[getter]
+    fn hashes(&self) -> Vec<u64> {
+        self.inner.hashes().to_vec()
+    }
+
+    #[getter]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    #[getter]
+    fn forward_hash(&self) -> u64 {
+        self.inner.forward_hash()
+    }
+
+    #[getter]
+    fn reverse_hash(&self) -> u64 {
+        self.inner.reverse_hash()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<(usize, Vec<u64>)> {
+        self.roll().then(|| (self.pos(), self.hashes()))
+    }
+
+    /// Roll to the end, returning `(positions, hashes)` numpy arrays.
+    fn arrays<'py>(&mut self, py: Python<'py>) -> PyResult<HashArrays<'py>> {
+        let m = self.inner.hashes().len();
+        let (mut positions, mut flat) = (Vec::new(), Vec::new());
+        while self.inner.roll() {
+            positions.push(self.inner.pos());
+            flat.extend_from_slice(self.inner.hashes());
+        }
+        to_arrays(py, positions, flat, m)
+    }
+}
+
+/// `BlindNtHash(seq, k, num_hashes=1, pos=0)` — rolled with explicit bases.
+#[pyclass(name = "BlindNtHash", module = "nthash_rs")]
+struct PyBlindNtHash {
+    inner: BlindNtHash,
+}
+
+#[pymethods]
+impl PyBlindNtHash {
+    #[new]
+    #[pyo3(signature = (seq, k, num_hashes = 1, pos = 0))]
+    fn new(seq: &Bound<'_, PyAny>, k: u16, num_hashes: u8, pos: isize) -> PyResult<Self> {
+        let seq = seq_bytes(seq)?;
+        if seq.len() < k as usize {
+            return Err(NtHashError::SequenceTooShort {
+                seq_len: seq.len(),
+                k,
+            }
+            .into());
+        }
+        Ok(Self {
+            inner: BlindNtHash::new(&seq, k, num_hashes, pos)?,
+        })
+    }
+
+    /// Slide forward, appending `base`.
+    fn roll(&mut self, base: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.inner.roll(base_byte(base)?))
+    }
+
+    /// Slide backward, prepending `base`.
+    fn roll_back(&mut self, base: &Bound<'_, PyAny>) -> PyResult<bool> {
+        Ok(self.inner.roll_back(base_byte(base)?))
+    }
+
+    /// Roll in every base of `seq`, returning the hashes after each step as
+    /// a `(len(seq), num_hashes)` numpy array.
+    fn roll_all<'py>(
+        &mut self,
+        py: Python<'py>,
+        seq: &Bound<'_, PyAny>,
+    ) -> PyResult<Bound<'py, PyArray2<u64>>> {
+        let seq = seq_bytes(seq)?;
+        let m = self.inner.hashes().len();
+        let mut flat = Vec::with_capacity(seq.len() * m);
+        for &c in &seq {
+            self.inner.roll(c);
+            flat.extend_from_slice(self.inner.hashes());
+        }
+        PyArray1::from_vec(py, flat).reshape([seq.len(), m])
+    }
+
+    #[getter]
+    fn hashes(&self) -> Vec<u64> {
+        self.inner.hashes().to_vec()
+    }
+
+    #[getter]
+    fn pos(&self) -> isize {
+        self.inner.pos()
+    }
+
+    #[getter]
+    fn forward_hash(&self) -> u64 {
+        self.inner.forward_hash()
+    }
+
+    #[getter]
+    fn reverse_hash(&self) -> u64 {
+        self.inner.reverse_hash()
+    }
+}
+
+/// `SeedNtHash(seq, masks, k, num_hashes=1, pos=0)` — iterable over
+/// `(pos, hashes)` with `len(masks) * num_hashes` values per k‑mer.
+#[pyclass(name = "SeedNtHash", module = "nthash_rs")]
+struct PySeedNtHash {
+    inner: SeedNtHash<'static>,
+    _seq: OwnedSeq,
+}
+
+#[pymethods]
+impl PySeedNtHash {
+    #[new]
+    #[pyo3(signature = (seq, masks, k, num_hashes = 1, pos = 0))]
+    fn new(
+        seq: &Bound<'_, PyAny>,
+        masks: Vec<String>,
+        k: u16,
+        num_hashes: usize,
+        pos: usize,
+    ) -> PyResult<Self> {
+        let owned = OwnedSeq::new(seq_bytes(seq)?);
+        // SAFETY: `inner` is dropped before `_seq`.
+        let inner = SeedNtHash::new(unsafe { owned.as_static() }, &masks, num_hashes, k, pos)?;
+        Ok(Self { inner, _seq: owned })
+    }
+
+    /// Advance to the next k‑mer whose care positions are all ACGT.
+    fn roll(&mut self) -> bool {
+        self.inner.roll()
+    }
+
+    #[getter]
+    fn hashes(&self) -> Vec<u64> {
+        self.inner.hashes().to_vec()
+    }
+
+    #[getter]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<(usize, Vec<u64>)> {
+        self.roll().then(|| (self.pos(), self.hashes()))
+    }
+
+    /// Roll to the end, returning `(positions, hashes)` numpy arrays.
+    fn arrays<'py>(&mut self, py: Python<'py>) -> PyResult<HashArrays<'py>> {
+        let m = self.inner.hashes().len();
+        let (mut positions, mut flat) = (Vec::new(), Vec::new());
+        while self.inner.roll() {
+            positions.push(self.inner.pos());
+            flat.extend_from_slice(self.inner.hashes());
+        }
+        to_arrays(py, positions, flat, m)
+    }
+}
+
+/// The `nthash_rs` Python module.
+#[pymodule]
+fn nthash_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyNtHash>()?;
+    m.add_class::<PyBlindNtHash>()?;
+    m.add_class::<PySeedNtHash>()?;
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! **Quality‑aware ntHash** for FASTQ reads.
+//!
+//! [`QualNtHash`] behaves like [`NtHash`] but also skips every k‑mer that
+//! covers a base whose Phred quality is below a threshold, so sketches and
+//! filters built from raw reads only see high‑confidence k‑mers.  Qualities
+//! are read as Phred+33 ASCII, the encoding of every modern FASTQ file.
+
+use crate::{kmer::KmerPos, sink::HashSink, NtHash, NtHashError, Result};
+
+/// ASCII offset of Phred+33 quality strings.
+const PHRED_OFFSET: u8 = 33;
+
+/// Rolling hasher over `seq` that skips k‑mers with low‑quality bases.
+///
+/// ```
+/// use nthash_rs::qual::QualNtHash;
+///
+/// let seq  = b"ACGTACGTAC";
+/// let qual = b"IIIII#IIII"; // '#' = Q2 at position 5
+/// let mut h = QualNtHash::new(seq, qual, 4, 1, 20).unwrap();
+/// let mut positions = Vec::new();
+/// while h.roll() {
+///     positions.push(h.pos());
+/// }
+/// assert_eq!(positions, [0, 1, 6]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct QualNtHash<'a> {
+    inner: NtHash<'a>,
+    qual: &'a [u8],
+    k: usize,
+    min_phred: u8,
+    // qualities before `scanned` have been checked; `last_bad` is the last
+    // low‑quality position among them
+    scanned: usize,
+    last_bad: Option<usize>,
+}
+
+impl std::fmt::Debug for QualNtHash<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QualNtHash")
+            .field("inner", &self.inner)
+            .field("min_phred", &self.min_phred)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> QualNtHash<'a> {
+    /// Hash `seq` with qualities `qual`, dropping k‑mers that contain a
+    /// base with Phred quality below `min_phred`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::QualityLengthMismatch`] if `qual` and `seq`
+    /// differ in length, and [`NtHash::new`] errors otherwise.
+    pub fn new(
+        seq: &'a [u8],
+        qual: &'a [u8],
+        k: u16,
+        num_hashes: u8,
+        min_phred: u8,
+    ) -> Result<Self> {
+        if qual.len() != seq.len() {
+            return Err(NtHashError::QualityLengthMismatch {
+                seq_len: seq.len(),
+                qual_len: qual.len(),
+            });
+        }
+        Ok(Self {
+            inner: NtHash::new(seq, k, num_hashes, 0)?,
+            qual,
+            k: k as usize,
+            min_phred,
+            scanned: 0,
+            last_bad: None,
+        })
+    }
+
+    /// Advance to the next k‑mer free of ambiguous and low‑quality bases.
+    /// Returns `false` at the end of the sequence.
+    pub fn roll(&mut self) -> bool {
+        while self.inner.roll() {
+            let pos = self.inner.pos();
+            let end = pos + self.k;
+            for i in self.scanned.max(pos)..end {
+                if self.qual[i].saturating_sub(PHRED_OFFSET) < self.min_phred {
+                    self.last_bad = Some(i);
+                }
+            }
+            self.scanned = self.scanned.max(end);
+            if self.last_bad.is_none_or(|b| b < pos) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Roll to the end of the sequence, passing every k‑mer that passes
+    /// the quality filter to `sink`; returns the number passed.
+    pub fn drive<S: HashSink + ?Sized>(&mut self, sink: &mut S) -> usize {
+        let mut n = 0;
+        while self.roll() {
+            sink.accept(self.pos(), self.hashes());
+            n += 1;
+        }
+        n
+    }
+
+    /// Start position of the current k‑mer.
+    #[inline(always)]
+    pub fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+
+    /// Index of the current k‑mer's first base.
+    #[inline(always)]
+    pub fn window_start(&self) -> usize {
+        self.inner.window_start()
+    }
+
+    /// One past the index of the current k‑mer's last base.
+    #[inline(always)]
+    pub fn window_end(&self) -> usize {
+        self.inner.window_end()
+    }
+
+    /// Hash values of the current k‑mer.
+    #[inline(always)]
+    pub fn hashes(&self) -> &[u64] {
+        self.inner.hashes()
+    }
+
+    /// Forward‑strand hash of the current k‑mer.
+    #[inline(always)]
+    pub fn forward_hash(&self) -> u64 {
+        self.inner.forward_hash()
+    }
+
+    /// Reverse‑complement hash of the current k‑mer.
+    #[inline(always)]
+    pub fn reverse_hash(&self) -> u64 {
+        self.inner.reverse_hash()
+    }
+}
+
+impl Iterator for QualNtHash<'_> {
+    type Item = (KmerPos, Vec<u64>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.roll()
+            .then(|| (self.inner.kmer_pos(), self.hashes().to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_filtering_by_hand() {
+        let seq = b"ATCGTACGATGCANGCATGCTGACGTTAGCATCGG";
+        let qual: Vec<u8> = (0..seq.len())
+            .map(|i| if i % 11 == 4 { b'+' } else { b'F' }) // Q10 / Q37
+            .collect();
+        let k = 5;
+        let got: Vec<_> = QualNtHash::new(seq, &qual, k, 2, 20).unwrap().collect();
+
+        let mut expected = Vec::new();
+        let mut h = NtHash::new(seq, k, 2, 0).unwrap();
+        while h.roll() {
+            let p = h.pos();
+            if qual[p..p + k as usize].iter().all(|&q| q - 33 >= 20) {
+                expected.push((h.kmer_pos(), h.hashes().to_vec()));
+            }
+        }
+        assert_eq!(got, expected);
+        assert!(!got.is_empty());
+
+        // threshold 0 keeps every valid k‑mer
+        assert_eq!(
+            QualNtHash::new(seq, &qual, k, 1, 0).unwrap().count(),
+            seq.len() - 4 - 5
+        );
+        assert!(QualNtHash::new(seq, &qual[1..], k, 1, 20).is_err());
+    }
+}
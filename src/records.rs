@@ -0,0 +1,166 @@
+//! **Plain `#[repr(C)]` records** for bulk results.
+//!
+//! These types have a fixed, padding‑free C layout, identical on every
+//! platform, so arrays of them can be handed across the C ABI (`ffi`
+//! feature), to JavaScript (as a `BigUint64Array` /
+//! `Uint32Array`), or to numpy structured arrays without converting each
+//! element.  [`as_u64s`] / [`as_u32s`] view a record slice as the flat
+//! word array those consumers expect.
+//!
+//! ```
+//! use nthash_rs::records::{as_u64s, PosHash};
+//!
+//! let recs = PosHash::collect(b"ACGTNACGTA", 4).unwrap();
+//! assert_eq!(recs.len(), 3);
+//! let words = as_u64s(&recs); // pos, hash, pos, hash, ...
+//! assert_eq!(words[2], 5);
+//! ```
+
+use std::{mem, slice};
+
+use crate::{
+    sketch::{MinHash, ScaledSketch},
+    NtHash, Result,
+};
+
+/// Start position and canonical hash of one k‑mer (16 bytes).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PosHash {
+    /// Start of the k‑mer in the hashed sequence.
+    pub pos: u64,
+    /// Canonical ntHash value.
+    pub hash: u64,
+}
+
+impl PosHash {
+    /// One record per valid k‑mer of `seq`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Propagates [`NtHash::new`] errors.
+    pub fn collect(seq: &[u8], k: u16) -> Result<Vec<PosHash>> {
+        let mut h = NtHash::new(seq, k, 1, 0)?;
+        let mut out = Vec::with_capacity(seq.len() + 1 - k as usize);
+        while h.roll() {
+            out.push(PosHash {
+                pos: h.pos() as u64,
+                hash: h.hashes()[0],
+            });
+        }
+        Ok(out)
+    }
+}
+
+/// A minimizer shared by a query and a reference (16 bytes).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Anchor {
+    /// Reference index.
+    pub ref_id: u32,
+    /// k‑mer start on the reference.
+    pub ref_pos: u32,
+    /// k‑mer start on the query.
+    pub query_pos: u32,
+    /// `1` if the k‑mer has the same orientation in both, `0` otherwise.
+    pub forward: u32,
+}
+
+/// [`SketchHeader::kind`] of a bottom‑s [`MinHash`].
+pub const SKETCH_MINHASH: u16 = 1;
+/// [`SketchHeader::kind`] of a FracMinHash [`ScaledSketch`].
+pub const SKETCH_SCALED: u16 = 2;
+
+/// Fixed‑size header preceding a sketch's `len` hash values (24 bytes).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SketchHeader {
+    /// [`SKETCH_MINHASH`] (1) or [`SKETCH_SCALED`] (2).
+    pub kind: u16,
+    /// k‑mer length the hashes were computed with.
+    pub k: u16,
+    /// Number of `u64` hashes that follow.
+    pub len: u32,
+    /// Sketch size for MinHash; largest retained hash for FracMinHash.
+    pub param: u64,
+    /// Reserved, zero.
+    pub reserved: u64,
+}
+
+impl SketchHeader {
+    /// Header describing `sketch`, built from `k`‑mers.
+    pub fn minhash(sketch: &MinHash, k: u16) -> Self {
+        Self {
+            kind: SKETCH_MINHASH,
+            k,
+            len: sketch.hashes().count() as u32,
+            param: sketch.size() as u64,
+            reserved: 0,
+        }
+    }
+
+    /// Header describing `sketch`, built from `k`‑mers.
+    pub fn scaled(sketch: &ScaledSketch, k: u16) -> Self {
+        Self {
+            kind: SKETCH_SCALED,
+            k,
+            len: sketch.len() as u32,
+            param: sketch.max_hash(),
+            reserved: 0,
+        }
+    }
+}
+
+const _: () = assert!(mem::size_of::<PosHash>() == 16);
+const _: () = assert!(mem::size_of::<Anchor>() == 16);
+const _: () = assert!(mem::size_of::<SketchHeader>() == 24);
+
+/// View records as their `pos, hash` word pairs.
+pub fn as_u64s(records: &[PosHash]) -> &[u64] {
+    // SAFETY: `PosHash` is `repr(C)` with two `u64` fields and no padding.
+    unsafe { slice::from_raw_parts(records.as_ptr().cast(), records.len() * 2) }
+}
+
+/// View anchors as their `ref_id, ref_pos, query_pos, forward` words.
+pub fn as_u32s(anchors: &[Anchor]) -> &[u32] {
+    // SAFETY: `Anchor` is `repr(C)` with four `u32` fields and no padding.
+    unsafe { slice::from_raw_parts(anchors.as_ptr().cast(), anchors.len() * 4) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sketch::Sketch;
+
+    #[test]
+    fn layouts_are_flat() {
+        let recs = PosHash::collect(b"ACGTACGTNNACGT", 5).unwrap();
+        let words = as_u64s(&recs);
+        assert_eq!(words.len(), 2 * recs.len());
+        for (r, w) in recs.iter().zip(words.chunks(2)) {
+            assert_eq!([r.pos, r.hash], w);
+        }
+
+        let anchors = [Anchor {
+            ref_id: 1,
+            ref_pos: 2,
+            query_pos: 3,
+            forward: 1,
+        }];
+        assert_eq!(as_u32s(&anchors), [1, 2, 3, 1]);
+
+        let mut mh = MinHash::new(8);
+        for h in [5, 1, 9] {
+            mh.insert(h);
+        }
+        let header = SketchHeader::minhash(&mh, 21);
+        assert_eq!(
+            (header.kind, header.k, header.len, header.param),
+            (SKETCH_MINHASH, 21, 3, 8)
+        );
+        assert_eq!(mem::align_of::<SketchHeader>(), 8);
+    }
+}
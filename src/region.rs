@@ -0,0 +1,240 @@
+//! **Per‑bin fingerprints** for comparing two versions of a genome.
+//!
+//! A reference is cut into fixed‑size bins and every bin is summarised by
+//! the XOR, the minimum, and the count of the canonical hashes of the
+//! k‑mers starting in it.  Comparing two [`RegionFingerprints`] costs a few
+//! words per bin, so large‑scale differences between assemblies (edits,
+//! insertions shifting the coordinates, relocated blocks) are localized
+//! without aligning anything; only the reported bins need a closer look.
+//!
+//! ```
+//! use nthash_rs::region::RegionFingerprints;
+//!
+//! let old = b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACGTTAGCATCGGATCGACTAG";
+//! let mut new = old.to_vec();
+//! new[35] = b'A'; // G -> A
+//!
+//! let a = RegionFingerprints::new(old, 11, 16).unwrap();
+//! let b = RegionFingerprints::new(&new, 11, 16).unwrap();
+//! assert_eq!(a.changed_regions(&b), [16..48]);
+//! ```
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::{NtHash, NtHashError, Result};
+
+/// Summary of the k‑mers starting in one bin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinFingerprint {
+    /// XOR of the canonical hashes.
+    pub xor: u64,
+    /// Smallest canonical hash (`u64::MAX` if the bin has no k‑mer).
+    pub min: u64,
+    /// Number of valid k‑mers.
+    pub kmers: u32,
+}
+
+impl Default for BinFingerprint {
+    fn default() -> Self {
+        Self {
+            xor: 0,
+            min: u64::MAX,
+            kmers: 0,
+        }
+    }
+}
+
+/// How a bin of one assembly relates to the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinStatus {
+    /// The bin at the same index differs and no identical bin was found.
+    Changed,
+    /// The bin's content appears unchanged at another bin index.
+    Moved {
+        /// Bin index in the other assembly.
+        to: usize,
+    },
+    /// The other assembly is too short to have this bin.
+    Missing,
+}
+
+/// A bin whose fingerprint differs between two assemblies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinDiff {
+    /// Bin index.
+    pub bin: usize,
+    /// Sequence coordinates of the k‑mer starts in the bin.
+    pub range: Range<usize>,
+    /// Where (if anywhere) the content went.
+    pub status: BinStatus,
+}
+
+/// Fingerprints of every `bin_size` bases of a sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionFingerprints {
+    k: u16,
+    bin_size: usize,
+    seq_len: usize,
+    bins: Vec<BinFingerprint>,
+}
+
+impl RegionFingerprints {
+    /// Fingerprint `seq` in bins of `bin_size` bases; a k‑mer belongs to
+    /// the bin containing its start.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidWindow`] if `bin_size == 0`, and
+    /// propagates [`NtHash::new`] errors.
+    pub fn new(seq: &[u8], k: u16, bin_size: usize) -> Result<Self> {
+        if bin_size == 0 {
+            return Err(NtHashError::InvalidWindow);
+        }
+        let mut bins = vec![BinFingerprint::default(); seq.len().div_ceil(bin_size)];
+        NtHash::new(seq, k, 1, 0)?.drive(&mut |pos, hashes: &[u64]| {
+            let bin: &mut BinFingerprint = &mut bins[pos / bin_size];
+            bin.xor ^= hashes[0];
+            bin.min = bin.min.min(hashes[0]);
+            bin.kmers += 1;
+        });
+        Ok(Self {
+            k,
+            bin_size,
+            seq_len: seq.len(),
+            bins,
+        })
+    }
+
+    /// k‑mer length.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Bases per bin.
+    pub fn bin_size(&self) -> usize {
+        self.bin_size
+    }
+
+    /// Per‑bin fingerprints in sequence order.
+    pub fn bins(&self) -> &[BinFingerprint] {
+        &self.bins
+    }
+
+    /// Sequence coordinates covered by bin `bin`.
+    pub fn bin_range(&self, bin: usize) -> Range<usize> {
+        let start = bin * self.bin_size;
+        start..(start + self.bin_size).min(self.seq_len)
+    }
+
+    /// Every bin of `self` whose fingerprint differs from the bin at the
+    /// same index in `other`, in bin order.  Differing bins whose content
+    /// occurs elsewhere in `other` are reported as [`BinStatus::Moved`]
+    /// (bins without k‑mers are never matched this way).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two were built with different `k` or `bin_size`.
+    pub fn compare(&self, other: &RegionFingerprints) -> Vec<BinDiff> {
+        assert!(
+            self.k == other.k && self.bin_size == other.bin_size,
+            "fingerprints built with different k or bin size"
+        );
+        let mut by_content: HashMap<BinFingerprint, usize> = HashMap::new();
+        for (j, fp) in other.bins.iter().enumerate() {
+            if fp.kmers > 0 {
+                by_content.entry(*fp).or_insert(j);
+            }
+        }
+        let mut out = Vec::new();
+        for (i, fp) in self.bins.iter().enumerate() {
+            let status = match other.bins.get(i) {
+                Some(o) if o == fp => continue,
+                _ if fp.kmers > 0 && by_content.contains_key(fp) => {
+                    BinStatus::Moved { to: by_content[fp] }
+                }
+                Some(_) => BinStatus::Changed,
+                None => BinStatus::Missing,
+            };
+            out.push(BinDiff {
+                bin: i,
+                range: self.bin_range(i),
+                status,
+            });
+        }
+        out
+    }
+
+    /// Coordinates in `self` of the runs of consecutive bins that are
+    /// [`Changed`](BinStatus::Changed) or [`Missing`](BinStatus::Missing)
+    /// relative to `other`.
+    ///
+    /// # Panics
+    ///
+    /// As [`compare`](Self::compare).
+    pub fn changed_regions(&self, other: &RegionFingerprints) -> Vec<Range<usize>> {
+        let mut out: Vec<Range<usize>> = Vec::new();
+        for d in self.compare(other) {
+            if matches!(d.status, BinStatus::Moved { .. }) {
+                continue;
+            }
+            match out.last_mut() {
+                Some(last) if last.end == d.range.start => last.end = d.range.end,
+                _ => out.push(d.range),
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_dna(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                b"ACGT"[(state >> 60) as usize & 3]
+            })
+            .collect()
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn localizes_edits_and_shifted_bins() {
+        let old = random_dna(10_000, 7);
+        let bin = 1000;
+        let a = RegionFingerprints::new(&old, 21, bin).unwrap();
+        assert_eq!(a.bins().len(), 10);
+        assert!(a.compare(&a).is_empty());
+
+        // a substitution disturbs the k‑mers of its own bin and, within
+        // k − 1 bases of a boundary, of the previous one
+        let mut edited = old.clone();
+        edited[5010] = if old[5010] == b'A' { b'C' } else { b'A' };
+        let b = RegionFingerprints::new(&edited, 21, bin).unwrap();
+        assert_eq!(a.changed_regions(&b), [4000..6000]);
+
+        // a bin‑sized insertion shifts everything downstream by one bin
+        let mut inserted = old[..5000].to_vec();
+        inserted.extend(random_dna(bin, 8));
+        inserted.extend_from_slice(&old[5000..]);
+        let c = RegionFingerprints::new(&inserted, 21, bin).unwrap();
+        let diffs = a.compare(&c);
+        assert_eq!(diffs[0].status, BinStatus::Changed);
+        assert_eq!(diffs[0].range, 4000..5000);
+        for (i, d) in (5..10).zip(&diffs[1..]) {
+            assert_eq!((d.bin, d.status), (i, BinStatus::Moved { to: i + 1 }));
+        }
+        assert_eq!(a.changed_regions(&c), [4000..5000]);
+
+        // truncation
+        let d = RegionFingerprints::new(&old[..7500], 21, bin).unwrap();
+        assert_eq!(a.changed_regions(&d), [7000..10_000]);
+        assert!(RegionFingerprints::new(&old, 21, 0).is_err());
+    }
+}
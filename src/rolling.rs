@@ -0,0 +1,349 @@
+//! **One trait over every rolling hasher**.
+//!
+//! [`RollingHasher`] is the common surface of the sequence‑bound hashers —
+//! [`NtHash`], [`SeedNtHash`], the alphabet‑generic [`RollingHash`], and
+//! [`BlindNtHashIter`] — so minimizer selectors, filter inserters, and other
+//! consumers can be written once over any of them.
+//!
+//! A bare [`BlindNtHash`](crate::BlindNtHash) is fed one incoming base per
+//! roll and has no sequence to read from; the iterator returned by
+//! [`BlindNtHashBuilder::finish`](crate::BlindNtHashBuilder::finish) binds
+//! it to one and implements the trait.
+//!
+//! ```
+//! use nthash_rs::{BlindNtHashBuilder, NtHash, RollingHasher, SeedNtHash};
+//!
+//! fn first_hashes<H: RollingHasher>(mut h: H) -> Vec<(usize, u64)> {
+//!     let mut out = Vec::new();
+//!     while h.roll() {
+//!         out.push((h.pos(), h.hashes()[0]));
+//!     }
+//!     out
+//! }
+//!
+//! let seq = b"ACGTTGCAAGCT";
+//! let contiguous = first_hashes(NtHash::new(seq, 5, 1, 0).unwrap());
+//! let blind = first_hashes(BlindNtHashBuilder::new(seq).k(5).finish().unwrap());
+//! assert_eq!(contiguous, blind);
+//!
+//! let masks = ["11111".to_string()];
+//! let seeded = first_hashes(SeedNtHash::new(seq, &masks, 1, 5, 0).unwrap());
+//! assert_eq!(seeded.len(), contiguous.len());
+//! ```
+
+use crate::{
+    alphabet::Alphabet, blind::BlindNtHashIter, packed::PackedHash, KmerPos, NtHash, RollingHash,
+    SeedNtHash,
+};
+
+/// A hasher moving a k‑mer window along a sequence.
+pub trait RollingHasher {
+    /// Move to the next window; the first call positions the hasher on
+    /// its first window.  Returns `false` when no window was reached.
+    fn roll(&mut self) -> bool;
+
+    /// Move to the previous window, as [`roll`](Self::roll) in reverse.
+    fn roll_back(&mut self) -> bool;
+
+    /// Hash values of the current window.
+    fn hashes(&self) -> &[u64];
+
+    /// Start of the current window in the sequence.
+    fn pos(&self) -> usize;
+
+    /// Forward‑strand hash of the current window (of the first seed for
+    /// [`SeedNtHash`]).
+    fn forward_hash(&self) -> u64;
+
+    /// Reverse‑complement hash of the current window (of the first seed
+    /// for [`SeedNtHash`]).
+    fn reverse_hash(&self) -> u64;
+}
+
+/// A [`RollingHasher`] that can be re‑seated anywhere on its sequence, as
+/// [`NtHashIter`](crate::NtHashIter) needs to scan backward and to jump
+/// over masked intervals.
+pub(crate) trait SeekableHasher<'a>: RollingHasher {
+    /// The sequence being hashed.
+    fn seq(&self) -> &'a [u8];
+
+    /// The current window's range in the sequence.
+    fn kmer_pos(&self) -> KmerPos;
+
+    /// Start of the last window in the sequence.
+    fn end(&self) -> usize;
+
+    /// Move to the last valid window starting at or before `pos`.
+    fn init_back_from(&mut self, pos: usize) -> bool;
+
+    /// Scan forward from `pos` at the next roll; `false` if no window
+    /// starts there.
+    fn restart_at(&mut self, pos: usize) -> bool;
+}
+
+impl RollingHasher for NtHash<'_> {
+    #[inline]
+    fn roll(&mut self) -> bool {
+        NtHash::roll(self)
+    }
+
+    #[inline]
+    fn roll_back(&mut self) -> bool {
+        NtHash::roll_back(self)
+    }
+
+    #[inline]
+    fn hashes(&self) -> &[u64] {
+        NtHash::hashes(self)
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        NtHash::pos(self)
+    }
+
+    #[inline]
+    fn forward_hash(&self) -> u64 {
+        NtHash::forward_hash(self)
+    }
+
+    #[inline]
+    fn reverse_hash(&self) -> u64 {
+        NtHash::reverse_hash(self)
+    }
+}
+
+impl RollingHasher for SeedNtHash<'_> {
+    #[inline]
+    fn roll(&mut self) -> bool {
+        SeedNtHash::roll(self)
+    }
+
+    #[inline]
+    fn roll_back(&mut self) -> bool {
+        SeedNtHash::roll_back(self)
+    }
+
+    #[inline]
+    fn hashes(&self) -> &[u64] {
+        SeedNtHash::hashes(self)
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        SeedNtHash::pos(self)
+    }
+
+    #[inline]
+    fn forward_hash(&self) -> u64 {
+        SeedNtHash::forward_hash(self)
+    }
+
+    #[inline]
+    fn reverse_hash(&self) -> u64 {
+        SeedNtHash::reverse_hash(self)
+    }
+}
+
+impl<A: Alphabet> RollingHasher for RollingHash<'_, A> {
+    #[inline]
+    fn roll(&mut self) -> bool {
+        RollingHash::roll(self)
+    }
+
+    #[inline]
+    fn roll_back(&mut self) -> bool {
+        RollingHash::roll_back(self)
+    }
+
+    #[inline]
+    fn hashes(&self) -> &[u64] {
+        RollingHash::hashes(self)
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        RollingHash::pos(self)
+    }
+
+    #[inline]
+    fn forward_hash(&self) -> u64 {
+        RollingHash::forward_hash(self)
+    }
+
+    #[inline]
+    fn reverse_hash(&self) -> u64 {
+        RollingHash::reverse_hash(self)
+    }
+}
+
+impl RollingHasher for BlindNtHashIter<'_> {
+    #[inline]
+    fn roll(&mut self) -> bool {
+        BlindNtHashIter::roll(self)
+    }
+
+    #[inline]
+    fn roll_back(&mut self) -> bool {
+        BlindNtHashIter::roll_back(self)
+    }
+
+    #[inline]
+    fn hashes(&self) -> &[u64] {
+        self.hasher().hashes()
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        BlindNtHashIter::pos(self)
+    }
+
+    #[inline]
+    fn forward_hash(&self) -> u64 {
+        self.hasher().forward_hash()
+    }
+
+    #[inline]
+    fn reverse_hash(&self) -> u64 {
+        self.hasher().reverse_hash()
+    }
+}
+
+impl RollingHasher for PackedHash<'_> {
+    #[inline]
+    fn roll(&mut self) -> bool {
+        PackedHash::roll(self)
+    }
+
+    #[inline]
+    fn roll_back(&mut self) -> bool {
+        PackedHash::roll_back(self)
+    }
+
+    #[inline]
+    fn hashes(&self) -> &[u64] {
+        PackedHash::hashes(self)
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        PackedHash::pos(self)
+    }
+
+    #[inline]
+    fn forward_hash(&self) -> u64 {
+        PackedHash::forward_hash(self)
+    }
+
+    #[inline]
+    fn reverse_hash(&self) -> u64 {
+        PackedHash::reverse_hash(self)
+    }
+}
+
+impl<'a, A: Alphabet> SeekableHasher<'a> for RollingHash<'a, A> {
+    #[inline]
+    fn seq(&self) -> &'a [u8] {
+        RollingHash::seq(self)
+    }
+
+    #[inline]
+    fn kmer_pos(&self) -> KmerPos {
+        RollingHash::kmer_pos(self)
+    }
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    #[inline]
+    fn init_back_from(&mut self, pos: usize) -> bool {
+        RollingHash::init_back_from(self, pos)
+    }
+
+    #[inline]
+    fn restart_at(&mut self, pos: usize) -> bool {
+        RollingHash::restart_at(self, pos)
+    }
+}
+
+impl<'a> SeekableHasher<'a> for PackedHash<'a> {
+    #[inline]
+    fn seq(&self) -> &'a [u8] {
+        PackedHash::seq(self)
+    }
+
+    #[inline]
+    fn kmer_pos(&self) -> KmerPos {
+        PackedHash::kmer_pos(self)
+    }
+
+    #[inline]
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    #[inline]
+    fn init_back_from(&mut self, pos: usize) -> bool {
+        PackedHash::init_back_from(self, pos)
+    }
+
+    #[inline]
+    fn restart_at(&mut self, pos: usize) -> bool {
+        PackedHash::restart_at(self, pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{alphabet::Dna, BlindNtHashBuilder};
+
+    /// `(pos, hash, forward, reverse)` of a window.
+    type Window = (usize, u64, u64, u64);
+
+    /// Every window forward, then the positions rolling back to the first.
+    fn walk<H: RollingHasher>(mut h: H) -> (Vec<Window>, Vec<usize>) {
+        let mut fwd = Vec::new();
+        while h.roll() {
+            fwd.push((h.pos(), h.hashes()[0], h.forward_hash(), h.reverse_hash()));
+        }
+        let mut back = Vec::new();
+        while h.roll_back() {
+            back.push(h.pos());
+        }
+        (fwd, back)
+    }
+
+    #[test]
+    fn hashers_agree_through_the_trait() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACG";
+        let k = 7;
+        let expected = walk(NtHash::new(seq, k, 2, 0).unwrap());
+        assert_eq!(expected.0.len(), seq.len() - k as usize + 1);
+        assert_eq!(
+            expected.1,
+            (0..seq.len() - k as usize).rev().collect::<Vec<_>>()
+        );
+
+        let blind = BlindNtHashBuilder::new(seq)
+            .k(k)
+            .num_hashes(2)
+            .finish()
+            .unwrap();
+        assert_eq!(walk(blind), expected);
+        assert_eq!(
+            walk(RollingHash::<Dna>::new(seq, k, 2, 0).unwrap()),
+            expected
+        );
+
+        // an all‑care seed hashes like a contiguous k‑mer before extension
+        let masks = ["1".repeat(k as usize)];
+        let (seeded, back) = walk(SeedNtHash::new(seq, &masks, 2, k, 0).unwrap());
+        assert_eq!(back, expected.1);
+        for (s, e) in seeded.iter().zip(&expected.0) {
+            assert_eq!((s.0, s.2, s.3), (e.0, e.2, e.3));
+        }
+    }
+}
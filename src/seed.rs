@@ -9,75 +9,421 @@
 //! Bit-level operations are delegated to `tables`, `constants`, and
 //! `util::extend_hashes` for efficient hash computation.
 //!
+//! Masks can be parsed once into a [`SpacedSeed`], which records the care
+//! positions, their decomposition into contiguous blocks, whether the mask
+//! is symmetric, and an optional salt.  Parsed seeds are borrowed by the hasher, so the same
+//! set can be reused across many reads without re‑parsing.
+//!
 //! A Rust‑idiomatic **builder + iterator** (`SeedNtHashBuilder` / `SeedNtHashIter`)
 //! provides ergonomic traversal over valid k‑mers.
+//!
+//! The [`design`] submodule parses seed notations from the literature and
+//! searches for seeds of a given weight and span.
+
+use std::borrow::Cow;
+use std::ops::Range;
+use std::str::FromStr;
+
+pub mod design;
 
 use crate::{
-    constants::{CP_OFF, SEED_N, SEED_TAB},
-    tables::srol_table,
-    util::extend_hashes,
+    kmer::{base_forward_hash, base_reverse_hash, is_acgt, KmerPos},
+    sink::HashSink,
+    tables::srol_n,
+    util::{extend_hashes_with, salt_hash, same_seq, CanonicalPolicy, HashMixer, Mixer},
     NtHashError, Result,
 };
 
 /// Parses a spaced-seed mask string composed of '0' and '1' characters
-/// into a list of indices indicating which positions should be used ("care positions").
-/// 
+/// for a k‑mer of length `k`.
+///
 /// # Errors
 /// Returns an error if the mask length does not match `k`, or contains characters other than '0' or '1'.
-fn parse_seed_string(mask: &str, k: usize) -> Result<Vec<usize>> {
+fn parse_seed_string(mask: &str, k: usize) -> Result<SpacedSeed> {
     if mask.len() != k {
+        return Err(NtHashError::MaskLengthMismatch {
+            expected: k,
+            actual: mask.len(),
+        });
+    }
+    SpacedSeed::parse(mask)
+}
+
+/// The k‑mer size shared by seeds with the given spans.
+///
+/// # Errors
+/// Returns [`NtHashError::NoSeeds`] if there are no spans,
+/// [`NtHashError::MaskLengthMismatch`] for the first span that differs from
+/// the first one, and [`NtHashError::InvalidK`] if the span exceeds
+/// `u16::MAX`.
+fn common_span(mut spans: impl Iterator<Item = usize>) -> Result<usize> {
+    let k = spans.next().ok_or(NtHashError::NoSeeds)?;
+    if let Some(actual) = spans.find(|&s| s != k) {
+        return Err(NtHashError::MaskLengthMismatch {
+            expected: k,
+            actual,
+        });
+    }
+    if k > u16::MAX as usize {
         return Err(NtHashError::InvalidK);
     }
-    if !mask.bytes().all(|b| b == b'0' || b == b'1') {
-        return Err(NtHashError::InvalidSequence);
+    Ok(k)
+}
+
+/// A spaced-seed mask, parsed and decomposed once for reuse.
+///
+/// Besides the care positions, the seed stores the maximal runs of
+/// consecutive care positions ("blocks").  Each block is hashed with the
+/// contiguous ntHash kernel and rotated into place, which is considerably
+/// cheaper than rotating every care position individually.
+///
+/// With the `serde` feature a seed is (de)serialized as its mask string and
+/// salt, and re‑validated when loaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "SeedDef", try_from = "SeedDef")
+)]
+pub struct SpacedSeed {
+    span: usize,
+    care: Vec<usize>,
+    blocks: Vec<Range<usize>>,
+    symmetric: bool,
+    salt: u64,
+}
+
+impl SpacedSeed {
+    /// Parses a mask string of '0' / '1' characters.
+    ///
+    /// # Errors
+    /// Returns [`NtHashError::InvalidK`] if the mask is empty and
+    /// [`NtHashError::InvalidMaskChar`] for the first character other than
+    /// '0' or '1'.
+    pub fn parse(mask: &str) -> Result<Self> {
+        if mask.is_empty() {
+            return Err(NtHashError::InvalidK);
+        }
+        let bad = mask
+            .bytes()
+            .enumerate()
+            .find(|&(_, b)| b != b'0' && b != b'1');
+        if let Some((index, byte)) = bad {
+            return Err(NtHashError::InvalidMaskChar {
+                mask: mask.to_owned(),
+                index,
+                byte,
+            });
+        }
+        let care = mask
+            .bytes()
+            .enumerate()
+            .filter_map(|(i, b)| if b == b'1' { Some(i) } else { None })
+            .collect();
+        Ok(Self::from_sorted_care(mask.len(), care))
+    }
+
+    /// Builds a seed of length `span` from sorted, de-duplicated care indices.
+    pub(crate) fn from_sorted_care(span: usize, care: Vec<usize>) -> Self {
+        let mut blocks: Vec<Range<usize>> = Vec::new();
+        for &p in &care {
+            match blocks.last_mut() {
+                Some(b) if b.end == p => b.end += 1,
+                _ => blocks.push(p..p + 1),
+            }
+        }
+        let symmetric = care
+            .iter()
+            .all(|&p| care.binary_search(&(span - 1 - p)).is_ok());
+        Self {
+            span,
+            care,
+            blocks,
+            symmetric,
+            salt: 0,
+        }
+    }
+
+    /// Assigns a salt to this seed.
+    ///
+    /// Seeds sharing a care pattern but carrying different salts produce
+    /// independent hash streams (see [`salt_hash`]), e.g. to feed several
+    /// independent filters from one pattern.  The default salt `0` leaves
+    /// hashes unchanged.
+    pub fn with_salt(mut self, salt: u64) -> Self {
+        self.salt = salt;
+        self
+    }
+
+    /// Salt applied to this seed's hashes.
+    #[inline(always)]
+    pub fn salt(&self) -> u64 {
+        self.salt
+    }
+
+    /// Total length of the seed (the k‑mer size it applies to).
+    #[inline(always)]
+    pub fn span(&self) -> usize {
+        self.span
+    }
+
+    /// Number of care positions.
+    #[inline(always)]
+    pub fn weight(&self) -> usize {
+        self.care.len()
     }
-    Ok(mask
-        .bytes()
-        .enumerate()
-        .filter_map(|(i, b)| if b == b'1' { Some(i) } else { None })
-        .collect())
+
+    /// Care positions in increasing order.
+    #[inline(always)]
+    pub fn care_positions(&self) -> &[usize] {
+        &self.care
+    }
+
+    /// Maximal runs of consecutive care positions, in increasing order.
+    #[inline(always)]
+    pub fn blocks(&self) -> &[Range<usize>] {
+        &self.blocks
+    }
+
+    /// `true` if the mask reads the same in both directions.
+    ///
+    /// Only symmetric seeds yield strand‑independent canonical hashes.
+    #[inline(always)]
+    pub fn is_symmetric(&self) -> bool {
+        self.symmetric
+    }
+}
+
+/// Care positions of one spaced seed, validated against its span.
+///
+/// A typed alternative to mask strings for seeds computed in code.
+/// Positions are sorted and de‑duplicated; each carries a weight (`1`
+/// unless given), kept for weighted seed schemes and ignored by the
+/// hashers.
+///
+/// ```
+/// use nthash_rs::seed::CareIndices;
+/// use nthash_rs::SpacedSeed;
+///
+/// let care = CareIndices::new(7, [6, 0, 1, 3, 5, 1]).unwrap();
+/// assert_eq!(care.positions(), &[0, 1, 3, 5, 6]);
+/// assert_eq!(SpacedSeed::from(&care), "1101011".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CareIndices {
+    span: usize,
+    care: Vec<usize>,
+    weights: Vec<u32>,
+}
+
+impl CareIndices {
+    /// Care `positions` within a seed of length `span`, each of weight 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `span == 0`,
+    /// [`NtHashError::CareIndexOutOfRange`] for the first position
+    /// `>= span`, and [`NtHashError::EmptySeed`] if there are no positions.
+    /// Errors name seed `0`; [`SeedNtHash::from_care_indices`] reports the
+    /// seed's index instead.
+    pub fn new(span: usize, positions: impl IntoIterator<Item = usize>) -> Result<Self> {
+        Self::weighted(span, positions.into_iter().map(|p| (p, 1)))
+    }
+
+    /// Care positions with per‑position weights; a repeated position keeps
+    /// its first weight.
+    ///
+    /// # Errors
+    ///
+    /// As [`new`](Self::new).
+    pub fn weighted(
+        span: usize,
+        positions: impl IntoIterator<Item = (usize, u32)>,
+    ) -> Result<Self> {
+        if span == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        let mut pairs: Vec<(usize, u32)> = positions.into_iter().collect();
+        if let Some(&(index, _)) = pairs.iter().find(|&&(p, _)| p >= span) {
+            return Err(NtHashError::CareIndexOutOfRange {
+                seed: 0,
+                index,
+                k: span,
+            });
+        }
+        if pairs.is_empty() {
+            return Err(NtHashError::EmptySeed { seed: 0 });
+        }
+        pairs.sort_by_key(|&(p, _)| p);
+        pairs.dedup_by_key(|&mut (p, _)| p);
+        Ok(Self {
+            span,
+            care: pairs.iter().map(|&(p, _)| p).collect(),
+            weights: pairs.iter().map(|&(_, w)| w).collect(),
+        })
+    }
+
+    /// Length of the seed.
+    #[inline(always)]
+    pub fn span(&self) -> usize {
+        self.span
+    }
+
+    /// Care positions in increasing order.
+    #[inline(always)]
+    pub fn positions(&self) -> &[usize] {
+        &self.care
+    }
+
+    /// Weight of each position in [`positions`](Self::positions).
+    #[inline(always)]
+    pub fn weights(&self) -> &[u32] {
+        &self.weights
+    }
+}
+
+impl From<&CareIndices> for SpacedSeed {
+    fn from(care: &CareIndices) -> Self {
+        SpacedSeed::from_sorted_care(care.span, care.care.clone())
+    }
+}
+
+impl std::fmt::Display for SpacedSeed {
+    /// Formats the seed back into its '0' / '1' mask.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut mask = vec![b'0'; self.span];
+        for &p in &self.care {
+            mask[p] = b'1';
+        }
+        f.write_str(std::str::from_utf8(&mask).expect("mask is ASCII"))
+    }
+}
+
+/// Serialized form of a [`SpacedSeed`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SeedDef {
+    mask: String,
+    #[serde(default)]
+    salt: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<SpacedSeed> for SeedDef {
+    fn from(seed: SpacedSeed) -> Self {
+        SeedDef {
+            mask: seed.to_string(),
+            salt: seed.salt,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SeedDef> for SpacedSeed {
+    type Error = NtHashError;
+
+    fn try_from(def: SeedDef) -> Result<Self> {
+        Ok(SpacedSeed::parse(&def.mask)?.with_salt(def.salt))
+    }
+}
+
+impl FromStr for SpacedSeed {
+    type Err = NtHashError;
+
+    fn from_str(mask: &str) -> Result<Self> {
+        Self::parse(mask)
+    }
+}
+
+/// Split-rotate left by any distance.
+///
+/// `srol_n` only matches repeated one‑bit split‑rotates for `d <= 31`, so
+/// longer distances are applied in steps.
+#[inline(always)]
+fn srol_by(mut x: u64, d: usize) -> u64 {
+    // Both halves return to their start after lcm(31, 33) = 1023 rotations.
+    let mut d = (d % 1023) as u32;
+    while d > 31 {
+        x = srol_n(x, 31);
+        d -= 31;
+    }
+    srol_n(x, d)
 }
 
 /// Computes the forward and reverse hash values for a given k-mer using a spaced seed.
-/// 
-/// # Arguments
-/// - `window`: The current k-mer slice from the sequence.
-/// - `care`: The positions to include in hashing (as defined by the spaced seed).
-/// - `k`: Length of the k-mer.
-/// 
+///
+/// Each block is hashed as a contiguous k‑mer and then split‑rotated by its
+/// distance to the window end (forward) or start (reverse).
+///
 /// # Returns
 /// A tuple of (forward_hash, reverse_hash).
 #[inline]
-fn compute_pair(window: &[u8], care: &[usize], k: usize) -> (u64, u64) {
+fn compute_pair(window: &[u8], seed: &SpacedSeed) -> (u64, u64) {
     let mut fwd = 0u64;
     let mut rev = 0u64;
-    for &p in care {
-        let c_f = window[p];
-        let c_r = c_f & CP_OFF; // Apply complement transformation
-
-        fwd ^= srol_table(c_f, (k - 1 - p) as u32); // Position-dependent rotation
-        rev ^= srol_table(c_r, p as u32);
+    for b in &seed.blocks {
+        let block = &window[b.clone()];
+        let len = block.len() as u16;
+        fwd ^= srol_by(base_forward_hash(block, len), seed.span - b.end);
+        rev ^= srol_by(base_reverse_hash(block, len), b.start);
     }
     (fwd, rev)
 }
 
 /// Struct for computing spaced-seed ntHash values in a re-computational manner.
 /// Can handle multiple seeds and generates multiple hashes per k-mer.
+///
+/// Clones share the sequence (and borrowed seeds), so forking is cheap;
+/// equality compares the sequence, seeds, position, and hashes.
+///
+/// As with [`NtHash`](crate::NtHash), no method panics on a constructed
+/// hasher; after a scan finds no valid k‑mer, the position rests on the
+/// last window.
+#[derive(Clone)]
 pub struct SeedNtHash<'a> {
-    seq:      &'a [u8],        // Input nucleotide sequence
-    k:        usize,           // k-mer size
-    num_hashes: usize,         // Number of hashes per seed
-    seeds:    Vec<Vec<usize>>, // Care indices for each seed
-    pos:      usize,           // Current position in the sequence
-    hashes:   Vec<u64>,        // Hash results (flattened)
-    initialised: bool,         // Whether the hasher has found the first valid k-mer
+    seq: &'a [u8],                // Input nucleotide sequence
+    k: usize,                     // k-mer size
+    end: usize,                   // Start of the last window, `seq.len() - k`
+    num_hashes: usize,            // Number of hashes per seed
+    seeds: Cow<'a, [SpacedSeed]>, // Parsed seeds (owned or shared)
+    pos: usize,                   // Current position in the sequence
+    hashes: Vec<u64>,             // Hash results (flattened)
+    initialised: bool,            // Whether the hasher has found the first valid k-mer
+    mixer: Mixer,                 // Extra-hash scheme (`None` for the reference)
+    policy: CanonicalPolicy,      // Strand-hash combination
 }
 
+impl std::fmt::Debug for SeedNtHash<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SeedNtHash")
+            .field("seq_len", &self.seq.len())
+            .field("k", &self.k)
+            .field("seeds", &self.seeds)
+            .field("pos", &self.pos)
+            .field("initialised", &self.initialised)
+            .field("hashes", &self.hashes)
+            .finish()
+    }
+}
+
+impl PartialEq for SeedNtHash<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k
+            && self.pos == other.pos
+            && self.initialised == other.initialised
+            && self.hashes == other.hashes
+            && self.seeds == other.seeds
+            && same_seq(self.seq, other.seq)
+    }
+}
+
+impl Eq for SeedNtHash<'_> {}
+
 impl<'a> SeedNtHash<'a> {
     /// Creates a new hasher from a sequence and spaced-seed masks.
-    /// 
+    ///
     /// # Errors
-    /// Returns an error if `k` is zero, the sequence is too short, or a mask is invalid.
+    /// Returns an error if `k` is zero, there are no masks, the sequence is too short, or a mask is invalid.
     pub fn new(
         seq: &'a [u8],
         seed_masks: &[String],
@@ -89,36 +435,122 @@ impl<'a> SeedNtHash<'a> {
             return Err(NtHashError::InvalidK);
         }
         let k_usz = k as usize;
-        if seq.len() < k_usz {
+        let mut seeds = Vec::with_capacity(seed_masks.len());
+        for m in seed_masks {
+            seeds.push(parse_seed_string(m, k_usz)?);
+        }
+        Self::with_seeds(
+            seq,
+            Cow::Owned(seeds),
+            num_hashes_per_seed,
+            k_usz,
+            start_pos,
+        )
+    }
+
+    /// Creates a new hasher from pre-parsed seeds, borrowing them.
+    ///
+    /// The k‑mer size is the seeds' common span.
+    ///
+    /// # Errors
+    /// Returns [`NtHashError::NoSeeds`] if `seeds` is empty,
+    /// [`NtHashError::MaskLengthMismatch`] for the first seed whose span
+    /// differs from the first seed's, and the sequence and position errors
+    /// of [`SeedNtHash::new`].
+    pub fn from_seeds(
+        seq: &'a [u8],
+        seeds: &'a [SpacedSeed],
+        num_hashes_per_seed: usize,
+        start_pos: usize,
+    ) -> Result<Self> {
+        let k = common_span(seeds.iter().map(SpacedSeed::span))?;
+        Self::with_seeds(seq, Cow::Borrowed(seeds), num_hashes_per_seed, k, start_pos)
+    }
+
+    fn with_seeds(
+        seq: &'a [u8],
+        seeds: Cow<'a, [SpacedSeed]>,
+        num_hashes_per_seed: usize,
+        k: usize,
+        start_pos: usize,
+    ) -> Result<Self> {
+        if seeds.is_empty() {
+            return Err(NtHashError::NoSeeds);
+        }
+        if seq.len() < k {
             return Err(NtHashError::SequenceTooShort {
                 seq_len: seq.len(),
-                k,
+                k: k as u16,
             });
         }
-        if start_pos > seq.len() - k_usz {
+        let end = seq.len() - k;
+        if start_pos > end {
             return Err(NtHashError::PositionOutOfRange {
                 pos: start_pos,
                 seq_len: seq.len(),
             });
         }
-
-        let mut seeds = Vec::with_capacity(seed_masks.len());
-        for m in seed_masks {
-            seeds.push(parse_seed_string(m, k_usz)?);
-        }
-
+        let num_hashes = num_hashes_per_seed.max(1);
         Ok(Self {
             seq,
-            k: k_usz,
-            num_hashes: num_hashes_per_seed.max(1),
+            k,
+            end,
+            num_hashes,
+            hashes: vec![0; seeds.len() * num_hashes],
             seeds,
             pos: start_pos,
-            hashes: vec![0; seed_masks.len() * num_hashes_per_seed.max(1)],
             initialised: false,
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
         })
     }
 
-    /// Alternative constructor using pre-parsed care indices (skips mask parsing).
+    /// Derive each seed's extra hashes with `mixer` instead of the
+    /// reference scheme; see [`HashMixer`].
+    pub fn with_mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        if self.initialised {
+            self.compute_current();
+        }
+        self
+    }
+
+    /// Combine each seed's strand hashes under `policy` instead of summing
+    /// them; see [`CanonicalPolicy`].
+    pub fn with_canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
+        if self.initialised {
+            self.compute_current();
+        }
+        self
+    }
+
+    /// Enforces strand‑independent canonical hashing.
+    ///
+    /// The canonical hash of a spaced seed only equals that of the reverse
+    /// complement k‑mer when the mask is symmetric; for any other mask it
+    /// silently depends on the strand.  This checks every seed and consumes
+    /// the hasher back on success.
+    ///
+    /// # Errors
+    /// Returns [`NtHashError::AsymmetricSeed`] naming the first offending seed.
+    pub fn canonical(self) -> Result<Self> {
+        match self.seeds.iter().position(|s| !s.is_symmetric()) {
+            Some(seed) => Err(NtHashError::AsymmetricSeed { seed }),
+            None => Ok(self),
+        }
+    }
+
+    /// Alternative constructor using care indices instead of masks.
+    ///
+    /// Each seed's indices are sorted and de‑duplicated (see
+    /// [`CareIndices`]).
+    ///
+    /// # Errors
+    /// Returns [`NtHashError::InvalidK`] if `k` is zero,
+    /// [`NtHashError::CareIndexOutOfRange`] for the first care index `>= k`,
+    /// [`NtHashError::EmptySeed`] for the first seed without care indices,
+    /// and the sequence and position errors of [`SeedNtHash::new`].
     pub fn from_care_indices(
         seq: &'a [u8],
         seeds: Vec<Vec<usize>>,
@@ -126,35 +558,115 @@ impl<'a> SeedNtHash<'a> {
         k: u16,
         start_pos: usize,
     ) -> Result<Self> {
-        let k_usz = k as usize;
-        if seeds.iter().any(|v| v.iter().any(|&i| i >= k_usz)) {
-            return Err(NtHashError::InvalidWindowOffsets);
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
         }
-        Self::new(
-            seq,
-            &vec![String::from_utf8(vec![b'0'; k_usz]).unwrap(); seeds.len()], // dummy masks
-            num_hashes_per_seed,
-            k,
-            start_pos,
-        )
-        .map(|mut s| {
-            s.seeds = seeds;
-            s
-        })
+        let seeds = seeds
+            .into_iter()
+            .enumerate()
+            .map(|(i, care)| {
+                CareIndices::new(k as usize, care).map_err(|e| match e {
+                    NtHashError::CareIndexOutOfRange { index, k, .. } => {
+                        NtHashError::CareIndexOutOfRange { seed: i, index, k }
+                    }
+                    NtHashError::EmptySeed { .. } => NtHashError::EmptySeed { seed: i },
+                    e => e,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_care(seq, &seeds, num_hashes_per_seed, start_pos)
+    }
+
+    /// Creates a new hasher from typed care indices.
+    ///
+    /// The k‑mer size is the seeds' common span.
+    ///
+    /// # Errors
+    /// As [`SeedNtHash::from_seeds`].
+    pub fn from_care(
+        seq: &'a [u8],
+        seeds: &[CareIndices],
+        num_hashes_per_seed: usize,
+        start_pos: usize,
+    ) -> Result<Self> {
+        let k = common_span(seeds.iter().map(CareIndices::span))?;
+        let seeds: Vec<SpacedSeed> = seeds.iter().map(SpacedSeed::from).collect();
+        Self::with_seeds(seq, Cow::Owned(seeds), num_hashes_per_seed, k, start_pos)
     }
 
-    /// Returns the current position in the sequence.
+    /// Returns the current position in the sequence (same as
+    /// [`window_start`](Self::window_start)).
     #[inline(always)]
     pub fn pos(&self) -> usize {
         self.pos
     }
 
-    /// Returns the current set of hash values.
+    /// Index of the first base spanned by the current seed window.
+    #[inline(always)]
+    pub fn window_start(&self) -> usize {
+        self.pos
+    }
+
+    /// One past the last base spanned by the current seed window.
+    #[inline(always)]
+    pub fn window_end(&self) -> usize {
+        self.pos + self.k
+    }
+
+    /// The current window as a [`KmerPos`].
+    #[inline(always)]
+    pub fn kmer_pos(&self) -> KmerPos {
+        KmerPos::new(self.pos, self.k)
+    }
+
+    /// Returns the current set of hash values, grouped by seed.
+    ///
+    /// The slice holds [`num_seeds`](Self::num_seeds) consecutive groups
+    /// of [`hashes_per_seed`](Self::hashes_per_seed) values: the `j`‑th
+    /// hash of seed `i` is at index `i * hashes_per_seed() + j`, and each
+    /// group starts with the seed's canonical hash.
     #[inline(always)]
     pub fn hashes(&self) -> &[u64] {
         &self.hashes
     }
 
+    /// Number of spaced seeds.
+    #[inline(always)]
+    pub fn num_seeds(&self) -> usize {
+        self.seeds.len()
+    }
+
+    /// Number of hash values per seed (at least one).
+    #[inline(always)]
+    pub fn hashes_per_seed(&self) -> usize {
+        self.num_hashes
+    }
+
+    /// The current hash values of seed `i`, or `None` if
+    /// `i >= num_seeds()`.
+    #[inline]
+    pub fn hashes_for_seed(&self, i: usize) -> Option<&[u64]> {
+        self.hashes.chunks_exact(self.num_hashes).nth(i)
+    }
+
+    /// `(seed index, hash values)` of every seed, in seed order.
+    ///
+    /// ```
+    /// use nthash_rs::SeedNtHash;
+    ///
+    /// let masks = ["110011".to_string(), "101101".to_string()];
+    /// let mut h = SeedNtHash::new(b"ATCGTACGATGC", &masks, 3, 6, 0).unwrap();
+    /// assert!(h.roll());
+    /// for (i, hashes) in h.seed_hashes() {
+    ///     assert_eq!(Some(hashes), h.hashes_for_seed(i));
+    ///     assert_eq!(hashes.len(), 3);
+    /// }
+    /// assert_eq!(h.hashes_for_seed(2), None);
+    /// ```
+    pub fn seed_hashes(&self) -> impl ExactSizeIterator<Item = (usize, &[u64])> + '_ {
+        self.hashes.chunks_exact(self.num_hashes).enumerate()
+    }
+
     /// Advances the iterator by one position.
     /// On first call, searches for the first valid k-mer (initialization).
     pub fn roll(&mut self) -> bool {
@@ -162,7 +674,7 @@ impl<'a> SeedNtHash<'a> {
             return self.init();
         }
 
-        if self.pos >= self.seq.len() - self.k {
+        if self.pos >= self.end {
             return false; // End of sequence
         }
 
@@ -170,34 +682,80 @@ impl<'a> SeedNtHash<'a> {
         self.compute_current()
     }
 
+    /// Moves back by one position, mirroring [`roll`](Self::roll); on first
+    /// call, the window at the start position is computed instead.
+    pub fn roll_back(&mut self) -> bool {
+        if !self.initialised {
+            self.initialised = self.compute_current();
+            return self.initialised;
+        }
+        if self.pos == 0 {
+            return false;
+        }
+        self.pos -= 1;
+        self.compute_current()
+    }
+
+    /// Forward‑strand hash of the first seed over the current window,
+    /// before salting and extension.
+    pub fn forward_hash(&self) -> u64 {
+        compute_pair(&self.seq[self.pos..self.pos + self.k], &self.seeds[0]).0
+    }
+
+    /// Reverse‑complement hash of the first seed over the current window,
+    /// before salting and extension.
+    pub fn reverse_hash(&self) -> u64 {
+        compute_pair(&self.seq[self.pos..self.pos + self.k], &self.seeds[0]).1
+    }
+
+    /// Keeps rolling while [`roll`](Self::roll) succeeds, passing each
+    /// k-mer's hashes to `sink`; returns the number of k-mers passed.
+    pub fn drive<S: HashSink + ?Sized>(&mut self, sink: &mut S) -> usize {
+        let mut n = 0;
+        while self.roll() {
+            sink.accept(self.pos, &self.hashes);
+            n += 1;
+        }
+        n
+    }
+
     /// Computes hashes for the k-mer at the current position.
     /// Returns false if any ambiguous base is found.
     fn compute_current(&mut self) -> bool {
         let win = &self.seq[self.pos..self.pos + self.k];
-        for care in &self.seeds {
-            if care.iter().any(|&p| SEED_TAB[win[p] as usize] == SEED_N) {
+        for seed in self.seeds.iter() {
+            if !seed.care.iter().all(|&p| is_acgt(win[p])) {
                 return false;
             }
         }
 
-        for (i_seed, care) in self.seeds.iter().enumerate() {
-            let (fwd, rev) = compute_pair(win, care, self.k);
-            let slice = &mut self.hashes[i_seed * self.num_hashes
-                ..(i_seed + 1) * self.num_hashes];
-            extend_hashes(fwd, rev, self.k as u32, slice);
+        for (i_seed, seed) in self.seeds.iter().enumerate() {
+            let (fwd, rev) = compute_pair(win, seed);
+            let base = salt_hash(self.policy.combine(fwd, rev), seed.salt);
+            let slice = &mut self.hashes[i_seed * self.num_hashes..(i_seed + 1) * self.num_hashes];
+            extend_hashes_with(
+                self.mixer,
+                CanonicalPolicy::Sum,
+                base,
+                0,
+                self.k as u32,
+                slice,
+            );
         }
         true
     }
 
     /// Initializes by finding the first valid k-mer in the sequence.
     fn init(&mut self) -> bool {
-        while self.pos <= self.seq.len() - self.k {
+        while self.pos <= self.end {
             if self.compute_current() {
                 self.initialised = true;
                 return true;
             }
             self.pos += 1;
         }
+        // stay on the last window, so later calls index within `seq`
+        self.pos = self.end;
         false
     }
 }
@@ -221,16 +779,20 @@ impl<'a> SeedNtHash<'a> {
 ///                        .masks(masks)
 ///                        .num_hashes(2)
 ///                        .finish()? {
-///     println!("{pos:2}  {:016x}", hashes[0]);
+///     println!("{:2}  {:016x}", pos.start, hashes[0]);
 /// }
 /// # Ok(()) }
 /// ```
 pub struct SeedNtHashBuilder<'a> {
-    seq:        &'a [u8],
-    masks:      Vec<String>,
-    k:          u16,
+    seq: &'a [u8],
+    masks: Vec<String>,
+    seeds: Option<&'a [SpacedSeed]>,
+    canonical: bool,
+    k: u16,
     num_hashes: usize,
-    start_pos:  usize,
+    start_pos: usize,
+    mixer: Mixer,
+    policy: CanonicalPolicy,
 }
 
 impl<'a> SeedNtHashBuilder<'a> {
@@ -239,9 +801,13 @@ impl<'a> SeedNtHashBuilder<'a> {
         Self {
             seq,
             masks: Vec::new(),
+            seeds: None,
+            canonical: false,
             k: 0,
             num_hashes: 1,
             start_pos: 0,
+            mixer: None,
+            policy: CanonicalPolicy::Sum,
         }
     }
 
@@ -257,6 +823,24 @@ impl<'a> SeedNtHashBuilder<'a> {
         self
     }
 
+    /// Uses pre-parsed seeds instead of mask strings.
+    ///
+    /// The seeds are borrowed, so one parsed set can be shared by every
+    /// builder in a read‑mapping loop.  Takes precedence over [`masks`](Self::masks);
+    /// if `k` is left unset it is taken from the seeds' span.
+    pub fn seeds(mut self, seeds: &'a [SpacedSeed]) -> Self {
+        self.seeds = Some(seeds);
+        self
+    }
+
+    /// Requires strand‑independent hashes; see [`SeedNtHash::canonical`].
+    ///
+    /// When enabled, [`finish`](Self::finish) fails for asymmetric masks.
+    pub fn canonical(mut self, on: bool) -> Self {
+        self.canonical = on;
+        self
+    }
+
     /// Specifies number of hashes per spaced seed.
     pub fn num_hashes(mut self, n: usize) -> Self {
         self.num_hashes = n;
@@ -269,27 +853,74 @@ impl<'a> SeedNtHashBuilder<'a> {
         self
     }
 
+    /// Derives the extra hashes with `mixer` (default the reference
+    /// scheme); see [`HashMixer`].
+    pub fn mixer(mut self, mixer: &'static dyn HashMixer) -> Self {
+        self.mixer = Some(mixer);
+        self
+    }
+
+    /// Combines the strand hashes under `policy` (default
+    /// [`CanonicalPolicy::Sum`]).  Independent of
+    /// [`canonical`](Self::canonical), which only checks the seeds.
+    pub fn canonical_policy(mut self, policy: CanonicalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     /// Finalizes the builder and returns an iterator over the hashes.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidNumHashes`] for zero hashes per seed, and
+    /// otherwise as [`SeedNtHash::new`] / [`SeedNtHash::from_seeds`].
     pub fn finish(self) -> Result<SeedNtHashIter<'a>> {
-        let hasher = SeedNtHash::new(
-            self.seq,
-            &self.masks,
-            self.num_hashes,
-            self.k,
-            self.start_pos,
-        )?;
-        Ok(SeedNtHashIter { hasher, done: false })
+        if self.num_hashes == 0 {
+            return Err(NtHashError::InvalidNumHashes);
+        }
+        let hasher = match self.seeds {
+            Some(seeds) => {
+                let hasher =
+                    SeedNtHash::from_seeds(self.seq, seeds, self.num_hashes, self.start_pos)?;
+                if self.k != 0 && self.k as usize != hasher.k {
+                    return Err(NtHashError::MaskLengthMismatch {
+                        expected: self.k as usize,
+                        actual: hasher.k,
+                    });
+                }
+                hasher
+            }
+            None => SeedNtHash::new(
+                self.seq,
+                &self.masks,
+                self.num_hashes,
+                self.k,
+                self.start_pos,
+            )?,
+        };
+        let mut hasher = if self.canonical {
+            hasher.canonical()?
+        } else {
+            hasher
+        };
+        hasher.mixer = self.mixer;
+        hasher.policy = self.policy;
+        Ok(SeedNtHashIter {
+            hasher,
+            done: false,
+        })
     }
 }
 
 /// Iterator for traversing valid k-mers and yielding spaced-seed hashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SeedNtHashIter<'a> {
     hasher: SeedNtHash<'a>,
-    done:   bool,
+    done: bool,
 }
 
 impl<'a> Iterator for SeedNtHashIter<'a> {
-    type Item = (usize, Vec<u64>);
+    type Item = (KmerPos, Vec<u64>);
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.done {
@@ -299,12 +930,12 @@ impl<'a> Iterator for SeedNtHashIter<'a> {
             self.done = true;
             return None;
         }
-        Some((self.hasher.pos(), self.hasher.hashes().to_vec()))
+        Some((self.hasher.kmer_pos(), self.hasher.hashes().to_vec()))
     }
 }
 
 impl<'a> IntoIterator for SeedNtHashBuilder<'a> {
-    type Item = (usize, Vec<u64>);
+    type Item = (KmerPos, Vec<u64>);
     type IntoIter = SeedNtHashIter<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -313,6 +944,112 @@ impl<'a> IntoIterator for SeedNtHashBuilder<'a> {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Multi-seed co-selection
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Whether [`SeedSelector`] needs one or every seed to pass the threshold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CoSelection {
+    /// Select windows where at least one seed's hash passes.
+    #[default]
+    Any,
+    /// Select windows where every seed's hash passes.
+    All,
+}
+
+/// A window chosen by [`SeedSelector`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeedHit {
+    /// Start of the window.
+    pub pos: usize,
+    /// Indices of the seeds whose hash passed, in increasing order.
+    pub seeds: Vec<usize>,
+}
+
+/// FracMinHash‑style sampling of windows across several spaced seeds;
+/// see [`SeedNtHash::select`].
+pub struct SeedSelector<'a> {
+    hasher: SeedNtHash<'a>,
+    max_hash: u64,
+    mode: CoSelection,
+    done: bool,
+}
+
+impl<'a> SeedNtHash<'a> {
+    /// Keep the windows where any (or all) seeds' canonical hashes are at
+    /// or below `u64::MAX / scale`, as a FracMinHash sketch would, reporting
+    /// which seeds passed.  Windows the hasher skips (ambiguous bases under
+    /// a care position) are passed over.
+    ///
+    /// ```
+    /// use nthash_rs::seed::{CoSelection, SeedNtHash};
+    ///
+    /// let seq = b"ATCGTACGATGCATGCATGCTGACGTTAGCATCGGATCCATGCATGCATGCATCGATCG";
+    /// let masks = ["1101011".to_string(), "1110111".to_string()];
+    /// let any: Vec<_> = SeedNtHash::new(seq, &masks, 1, 7, 0)
+    ///     .unwrap()
+    ///     .select(4, CoSelection::Any)
+    ///     .collect();
+    /// assert!(!any.is_empty());
+    /// for hit in &any {
+    ///     assert!(!hit.seeds.is_empty());
+    /// }
+    /// ```
+    pub fn select(self, scale: u64, mode: CoSelection) -> SeedSelector<'a> {
+        SeedSelector {
+            hasher: self,
+            max_hash: u64::MAX / scale.max(1),
+            mode,
+            done: false,
+        }
+    }
+}
+
+impl SeedSelector<'_> {
+    /// The hasher, positioned on the last window examined.
+    pub fn hasher(&self) -> &SeedNtHash<'_> {
+        &self.hasher
+    }
+
+    /// Largest hash value that passes.
+    pub fn max_hash(&self) -> u64 {
+        self.max_hash
+    }
+}
+
+impl Iterator for SeedSelector<'_> {
+    type Item = SeedHit;
+
+    fn next(&mut self) -> Option<SeedHit> {
+        while !self.done {
+            if !self.hasher.roll() {
+                // a failed roll past the first window has still moved on
+                let h = &self.hasher;
+                self.done = !h.initialised || h.pos >= h.end;
+                continue;
+            }
+            let seeds: Vec<usize> = self
+                .hasher
+                .seed_hashes()
+                .filter(|(_, hashes)| hashes[0] <= self.max_hash)
+                .map(|(i, _)| i)
+                .collect();
+            let selected = match self.mode {
+                CoSelection::Any => !seeds.is_empty(),
+                CoSelection::All => seeds.len() == self.hasher.num_seeds(),
+            };
+            if selected {
+                return Some(SeedHit {
+                    pos: self.hasher.pos(),
+                    seeds,
+                });
+            }
+        }
+        None
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Basic Unit Test
 // ─────────────────────────────────────────────────────────────────────────────
@@ -330,4 +1067,356 @@ mod tests {
         assert!(h.roll()); // next valid
         assert_ne!(first, h.hashes()[0]); // hashes should differ
     }
+
+    #[test]
+    fn per_seed_slices_match_single_seed_hashers() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACG";
+        let masks = [
+            "1100111".to_string(),
+            "1011101".to_string(),
+            "1111111".to_string(),
+        ];
+        let mut all = SeedNtHash::new(seq, &masks, 3, 7, 0).unwrap();
+        let mut single: Vec<SeedNtHash> = masks
+            .iter()
+            .map(|m| SeedNtHash::new(seq, std::slice::from_ref(m), 3, 7, 0).unwrap())
+            .collect();
+        assert_eq!((all.num_seeds(), all.hashes_per_seed()), (3, 3));
+        while all.roll() {
+            assert_eq!(all.seed_hashes().len(), 3);
+            for (i, hashes) in all.seed_hashes() {
+                assert!(single[i].roll());
+                assert_eq!(hashes, single[i].hashes());
+                assert_eq!(all.hashes_for_seed(i), Some(hashes));
+                assert_eq!(&all.hashes()[i * 3..i * 3 + 3], hashes);
+            }
+        }
+        let h = SeedNtHash::new(seq, &masks, 0, 7, 0).unwrap();
+        assert_eq!(h.hashes_per_seed(), 1);
+        assert_eq!(h.hashes().len(), 3);
+    }
+
+    #[test]
+    fn spaced_seed_blocks_and_symmetry() {
+        let s: SpacedSeed = "1101011".parse().unwrap();
+        assert_eq!(s.span(), 7);
+        assert_eq!(s.weight(), 5);
+        assert_eq!(s.care_positions(), &[0, 1, 3, 5, 6]);
+        assert_eq!(s.blocks(), &[0..2, 3..4, 5..7]);
+        assert!(s.is_symmetric());
+        assert!(!SpacedSeed::parse("000111").unwrap().is_symmetric());
+        assert_eq!(
+            SpacedSeed::parse("01x1"),
+            Err(NtHashError::InvalidMaskChar {
+                mask: "01x1".into(),
+                index: 2,
+                byte: b'x'
+            })
+        );
+        assert!(SpacedSeed::parse("").is_err());
+        assert_eq!(
+            SeedNtHash::new(b"ACGTACGT", &["1011".to_string()], 1, 5, 0).err(),
+            Some(NtHashError::MaskLengthMismatch {
+                expected: 5,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            SeedNtHash::from_care_indices(b"ACGTACGT", vec![vec![0, 1], vec![0, 5]], 1, 5, 0).err(),
+            Some(NtHashError::CareIndexOutOfRange {
+                seed: 1,
+                index: 5,
+                k: 5
+            })
+        );
+        assert_eq!(
+            SeedNtHash::from_care_indices(b"ACGTACGT", vec![vec![0, 1], vec![]], 1, 5, 0).err(),
+            Some(NtHashError::EmptySeed { seed: 1 })
+        );
+    }
+
+    #[test]
+    fn co_selection_matches_per_seed_thresholds() {
+        let mut seq = b"ATCGTACGATGCATGCATGCTGACGTTAGCATCGGATCCATGCATGCATGCATCGATCGAT".to_vec();
+        seq[20] = b'N';
+        let masks = [
+            "1101011".to_string(),
+            "1110111".to_string(),
+            "1011101".to_string(),
+        ];
+        let scale = 3;
+        let max = u64::MAX / scale;
+
+        // per‑window passing seeds, from the plain hasher
+        let mut expected = Vec::new();
+        for pos in 0..=seq.len() - 7 {
+            let Ok(mut h) = SeedNtHash::new(&seq, &masks, 1, 7, pos) else {
+                continue;
+            };
+            if !h.roll() || h.pos() != pos {
+                continue;
+            }
+            let seeds: Vec<usize> = (0..3)
+                .filter(|&i| h.hashes_for_seed(i).unwrap()[0] <= max)
+                .collect();
+            expected.push(SeedHit { pos, seeds });
+        }
+        assert!(expected.iter().any(|h| h.pos > 20));
+
+        let select = |mode| -> Vec<SeedHit> {
+            SeedNtHash::new(&seq, &masks, 2, 7, 0)
+                .unwrap()
+                .select(scale, mode)
+                .collect()
+        };
+        let any: Vec<SeedHit> = expected
+            .iter()
+            .filter(|h| !h.seeds.is_empty())
+            .cloned()
+            .collect();
+        let all: Vec<SeedHit> = expected
+            .iter()
+            .filter(|h| h.seeds.len() == 3)
+            .cloned()
+            .collect();
+        assert!(all.len() < any.len());
+        assert_eq!(select(CoSelection::Any), any);
+        assert_eq!(select(CoSelection::All), all);
+    }
+
+    #[test]
+    fn care_indices_match_masks() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACG";
+        let masks = ["1100111".to_string(), "1011101".to_string()];
+        let by_mask: Vec<_> = SeedNtHash::new(seq, &masks, 2, 7, 0)
+            .map(|h| {
+                SeedNtHashIter {
+                    hasher: h,
+                    done: false,
+                }
+                .collect()
+            })
+            .unwrap();
+        let care = vec![vec![6, 5, 4, 1, 0], vec![0, 2, 3, 4, 6, 2]];
+        let by_care: Vec<_> = SeedNtHash::from_care_indices(seq, care, 2, 7, 0)
+            .map(|h| {
+                SeedNtHashIter {
+                    hasher: h,
+                    done: false,
+                }
+                .collect()
+            })
+            .unwrap();
+        assert_eq!(by_care, by_mask);
+
+        let typed = [
+            CareIndices::new(7, [0, 1, 4, 5, 6]).unwrap(),
+            CareIndices::weighted(7, [(4, 2), (0, 1), (2, 3), (3, 1), (6, 1), (2, 9)]).unwrap(),
+        ];
+        assert_eq!(typed[1].positions(), &[0, 2, 3, 4, 6]);
+        assert_eq!(typed[1].weights(), &[1, 3, 1, 2, 1]);
+        let by_typed: Vec<_> = SeedNtHash::from_care(seq, &typed, 2, 0)
+            .map(|h| {
+                SeedNtHashIter {
+                    hasher: h,
+                    done: false,
+                }
+                .collect()
+            })
+            .unwrap();
+        assert_eq!(by_typed, by_mask);
+
+        assert_eq!(CareIndices::new(0, [0]).err(), Some(NtHashError::InvalidK));
+        assert_eq!(
+            CareIndices::new(4, []).err(),
+            Some(NtHashError::EmptySeed { seed: 0 })
+        );
+        let mismatched = [typed[0].clone(), CareIndices::new(6, [0]).unwrap()];
+        assert!(SeedNtHash::from_care(seq, &mismatched, 1, 0).is_err());
+        assert!(SeedNtHash::from_care(seq, &[], 1, 0).is_err());
+    }
+
+    #[test]
+    fn block_hashing_matches_per_position() {
+        use crate::{constants::CP_OFF, tables::srol_table};
+
+        let seq =
+            b"ATCGTACGATGCATGCATGCTGACGTTAGCATCGGATCCATGCATGCATGCATCGATCGACTAGCTAGCTAGCATCGATCGAT";
+        let wide = format!("{}{}{}", "1".repeat(5), "0".repeat(60), "1".repeat(5));
+        for mask in [
+            "1110111",
+            "1000001",
+            "1",
+            "0110110110",
+            &"1".repeat(70),
+            &wide,
+        ] {
+            let seed = SpacedSeed::parse(mask).unwrap();
+            let k = seed.span();
+            for win in seq.windows(k) {
+                let (mut fwd, mut rev) = (0u64, 0u64);
+                for &p in seed.care_positions() {
+                    fwd ^= srol_table(win[p], (k - 1 - p) as u32);
+                    rev ^= srol_table(win[p] & CP_OFF, p as u32);
+                }
+                assert_eq!(compute_pair(win, &seed), (fwd, rev), "mask {mask}");
+            }
+        }
+    }
+
+    #[test]
+    fn builder_with_shared_seeds_matches_masks() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACG";
+        let masks = ["000111", "010101"];
+        let seeds: Vec<SpacedSeed> = masks.iter().map(|m| m.parse().unwrap()).collect();
+
+        let from_masks: Vec<_> = SeedNtHashBuilder::new(seq)
+            .k(6)
+            .masks(masks)
+            .num_hashes(2)
+            .finish()
+            .unwrap()
+            .collect();
+        for _ in 0..2 {
+            let from_seeds: Vec<_> = SeedNtHashBuilder::new(seq)
+                .seeds(&seeds)
+                .num_hashes(2)
+                .finish()
+                .unwrap()
+                .collect();
+            assert_eq!(from_masks, from_seeds);
+        }
+
+        assert!(SeedNtHashBuilder::new(seq)
+            .k(7)
+            .seeds(&seeds)
+            .finish()
+            .is_err());
+        let mixed = [seeds[0].clone(), SpacedSeed::parse("0101").unwrap()];
+        assert_eq!(
+            SeedNtHash::from_seeds(seq, &mixed, 1, 0).err(),
+            Some(NtHashError::MaskLengthMismatch {
+                expected: 6,
+                actual: 4
+            })
+        );
+        assert_eq!(
+            SeedNtHash::from_seeds(seq, &[], 1, 0).err(),
+            Some(NtHashError::NoSeeds)
+        );
+    }
+
+    #[test]
+    fn builder_rejects_zero_hashes() {
+        let built = SeedNtHashBuilder::new(b"ACGTACGT")
+            .k(3)
+            .masks(["101"])
+            .num_hashes(0)
+            .finish();
+        assert_eq!(built.err(), Some(NtHashError::InvalidNumHashes));
+    }
+
+    #[test]
+    fn canonical_policy_rejects_asymmetric_masks() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACG";
+        let err = SeedNtHashBuilder::new(seq)
+            .k(6)
+            .masks(["110011", "000111"])
+            .canonical(true)
+            .finish()
+            .err();
+        assert_eq!(err, Some(NtHashError::AsymmetricSeed { seed: 1 }));
+
+        // Without the policy, asymmetric masks are still accepted.
+        assert!(SeedNtHashBuilder::new(seq)
+            .k(6)
+            .masks(["000111"])
+            .finish()
+            .is_ok());
+    }
+
+    #[test]
+    fn symmetric_masks_are_strand_independent() {
+        let fwd = b"ATCGTACGATGCATGCATGCTGACG";
+        let rc: Vec<u8> = fwd
+            .iter()
+            .rev()
+            .map(|&b| match b {
+                b'A' => b'T',
+                b'C' => b'G',
+                b'G' => b'C',
+                _ => b'A',
+            })
+            .collect();
+        let hash = |seq: &[u8]| -> Vec<u64> {
+            SeedNtHashBuilder::new(seq)
+                .k(7)
+                .masks(["1101011"])
+                .canonical(true)
+                .finish()
+                .unwrap()
+                .map(|(_, h)| h[0])
+                .collect()
+        };
+        let mut rev_hashes = hash(&rc);
+        rev_hashes.reverse();
+        assert_eq!(hash(fwd), rev_hashes);
+    }
+
+    #[test]
+    fn salted_seeds_produce_independent_streams() {
+        let seq = b"ATCGTACGATGCATGCATGCTGACG";
+        let seed = SpacedSeed::parse("1101011").unwrap();
+        let seeds = [
+            seed.clone(),
+            seed.clone().with_salt(0),
+            seed.clone().with_salt(11),
+            seed.with_salt(12),
+        ];
+        for (_, h) in SeedNtHashBuilder::new(seq)
+            .seeds(&seeds)
+            .num_hashes(2)
+            .finish()
+            .unwrap()
+        {
+            assert_eq!(h[0..2], h[2..4]);
+            assert_ne!(h[0], h[4]);
+            assert_ne!(h[4], h[6]);
+            assert_ne!(h[5], h[7]);
+        }
+    }
+
+    #[test]
+    fn display_round_trips_mask() {
+        for mask in ["1101011", "000111", "0"] {
+            assert_eq!(SpacedSeed::parse(mask).unwrap().to_string(), mask);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_validates() {
+        let seed = SpacedSeed::parse("1100011").unwrap().with_salt(9);
+        let json = serde_json::to_string(&seed).unwrap();
+        assert_eq!(json, r#"{"mask":"1100011","salt":9}"#);
+        assert_eq!(serde_json::from_str::<SpacedSeed>(&json).unwrap(), seed);
+        assert!(serde_json::from_str::<SpacedSeed>(r#"{"mask":"10x"}"#).is_err());
+    }
+
+    #[test]
+    fn failed_scan_leaves_a_usable_hasher() {
+        let masks = ["101".to_string()];
+        for seq in [&b"NNNN"[..], b"ACN", b"NAC", b"ACG"] {
+            let mut h = SeedNtHash::new(seq, &masks, 2, 3, 0).unwrap();
+            for _ in 0..3 {
+                while h.roll() {}
+                assert!(h.pos() <= seq.len() - 3);
+                for _ in 0..seq.len() {
+                    h.roll_back();
+                }
+                _ = (h.forward_hash(), h.reverse_hash(), h.hashes_for_seed(0));
+                assert!(h.hashes_for_seed(h.num_seeds()).is_none());
+            }
+        }
+    }
 }
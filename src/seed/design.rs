@@ -0,0 +1,203 @@
+//! **Spaced‑seed design**: seed notation parsing and seed search.
+//!
+//! [`parse_notation`] reads masks written as `1`/`0` or in the
+//! `#`/`-` notation of iedera and most seed papers.  [`design_seed`]
+//! searches for a seed of a given weight and span by hill‑climbing on
+//! [`overlap_complexity`] (Ilie & Ilie 2007): the fewer care positions a
+//! seed shares with shifted copies of itself, the more independent its
+//! hits on overlapping windows, and the more sensitive it is.
+//!
+//! ```
+//! use nthash_rs::seed::design::{design_seed, overlap_complexity, parse_notation};
+//!
+//! let patternhunter = parse_notation("###-#--#-#--##-###").unwrap();
+//! assert_eq!((patternhunter.weight(), patternhunter.span()), (11, 18));
+//!
+//! let designed = design_seed(11, 18).unwrap();
+//! assert_eq!((designed.weight(), designed.span()), (11, 18));
+//! let contiguous = parse_notation("11111111111").unwrap();
+//! assert!(overlap_complexity(&designed) < overlap_complexity(&contiguous));
+//! ```
+
+use crate::{seed::SpacedSeed, NtHashError, Result};
+
+/// Deterministic starting points tried by [`design_seed`].
+const RESTARTS: usize = 16;
+
+/// Parses a seed written with `1` or `#` for care positions and `0`, `-`,
+/// or `_` for don't‑care positions.
+///
+/// # Errors
+/// Returns [`NtHashError::InvalidK`] for an empty string and
+/// [`NtHashError::InvalidMaskChar`] for the first unrecognised character.
+pub fn parse_notation(notation: &str) -> Result<SpacedSeed> {
+    let mask: String = notation
+        .chars()
+        .map(|c| match c {
+            '#' => '1',
+            '-' | '_' => '0',
+            c => c,
+        })
+        .collect();
+    SpacedSeed::parse(&mask).map_err(|e| match e {
+        NtHashError::InvalidMaskChar { index, byte, .. } => NtHashError::InvalidMaskChar {
+            mask: notation.to_owned(),
+            index,
+            byte,
+        },
+        e => e,
+    })
+}
+
+/// Overlap complexity of a seed: `Σ 2^σ(s)` over the shifts
+/// `s = 1 .. span − 1`, where `σ(s)` counts the care positions the seed
+/// shares with itself shifted by `s`.  Lower is better; saturates at
+/// `u128::MAX`.
+pub fn overlap_complexity(seed: &SpacedSeed) -> u128 {
+    let mut mask = vec![false; seed.span()];
+    for &p in seed.care_positions() {
+        mask[p] = true;
+    }
+    complexity(&mask)
+}
+
+fn complexity(mask: &[bool]) -> u128 {
+    (1..mask.len()).fold(0u128, |acc, shift| {
+        let shared = (shift..mask.len())
+            .filter(|&i| mask[i] && mask[i - shift])
+            .count() as u32;
+        acc.saturating_add(1u128.checked_shl(shared).unwrap_or(u128::MAX))
+    })
+}
+
+/// A seed of `weight` care positions spanning `span` bases with low
+/// overlap complexity.
+///
+/// The first and last positions are always care positions.  From several
+/// deterministic starting masks, the search repeatedly applies the best
+/// swap of a care and a don't‑care position until no swap lowers the
+/// overlap complexity, and keeps the best local optimum; the result is
+/// good, not necessarily optimal, and is the same on every call.
+///
+/// # Errors
+/// Returns [`NtHashError::InvalidK`] unless `1 ≤ weight ≤ span`, and a
+/// weight of 1 is only possible with a span of 1.
+pub fn design_seed(weight: usize, span: usize) -> Result<SpacedSeed> {
+    if weight == 0 || weight > span || (weight == 1 && span > 1) {
+        return Err(NtHashError::InvalidK);
+    }
+    if weight == span {
+        return Ok(SpacedSeed::from_sorted_care(span, (0..span).collect()));
+    }
+    let mut best: Option<(u128, Vec<bool>)> = None;
+    let mut state = (weight as u64) << 32 | span as u64;
+    for restart in 0..RESTARTS {
+        let mut mask = if restart == 0 {
+            spread(weight, span)
+        } else {
+            shuffled(weight, span, &mut state)
+        };
+        let score = climb(&mut mask);
+        if best.as_ref().is_none_or(|(b, _)| score < *b) {
+            best = Some((score, mask));
+        }
+    }
+    let (_, mask) = best.expect("at least one restart");
+    let care = (0..span).filter(|&i| mask[i]).collect();
+    Ok(SpacedSeed::from_sorted_care(span, care))
+}
+
+/// Care positions spread as evenly as possible, both ends included.
+fn spread(weight: usize, span: usize) -> Vec<bool> {
+    let mut mask = vec![false; span];
+    for i in 0..weight {
+        mask[i * (span - 1) / (weight - 1)] = true;
+    }
+    mask
+}
+
+/// Both ends plus `weight − 2` pseudo‑random interior care positions.
+fn shuffled(weight: usize, span: usize, state: &mut u64) -> Vec<bool> {
+    let mut interior: Vec<usize> = (1..span - 1).collect();
+    for i in (1..interior.len()).rev() {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        interior.swap(i, ((z ^ (z >> 31)) % (i as u64 + 1)) as usize);
+    }
+    let mut mask = vec![false; span];
+    mask[0] = true;
+    mask[span - 1] = true;
+    for &p in &interior[..weight - 2] {
+        mask[p] = true;
+    }
+    mask
+}
+
+/// Steepest descent over interior care/don't‑care swaps; returns the
+/// final complexity.
+fn climb(mask: &mut [bool]) -> u128 {
+    let mut score = complexity(mask);
+    let span = mask.len();
+    loop {
+        let interior = 1..span.saturating_sub(1);
+        let on: Vec<usize> = interior.clone().filter(|&i| mask[i]).collect();
+        let off: Vec<usize> = interior.filter(|&i| !mask[i]).collect();
+        let mut best: Option<(u128, usize, usize)> = None;
+        for &on in &on {
+            for &off in &off {
+                mask.swap(on, off);
+                let s = complexity(mask);
+                mask.swap(on, off);
+                if s < best.map_or(score, |b| b.0) {
+                    best = Some((s, on, off));
+                }
+            }
+        }
+        match best {
+            Some((s, on, off)) => {
+                mask.swap(on, off);
+                score = s;
+            }
+            None => return score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn designed_seeds_beat_known_seeds() {
+        let ph = parse_notation("###-#--#-#--##-###").unwrap();
+        assert_eq!(ph, "111010010100110111".parse().unwrap());
+        assert_eq!(parse_notation("1_1").unwrap(), "101".parse().unwrap());
+        assert_eq!(
+            parse_notation("##x#"),
+            Err(NtHashError::InvalidMaskChar {
+                mask: "##x#".into(),
+                index: 2,
+                byte: b'x'
+            })
+        );
+
+        // σ(s) = span − s for a contiguous seed
+        let contiguous = parse_notation("1111").unwrap();
+        assert_eq!(overlap_complexity(&contiguous), 8 + 4 + 2);
+
+        let designed = design_seed(11, 18).unwrap();
+        assert_eq!((designed.weight(), designed.span()), (11, 18));
+        assert_eq!(designed.care_positions().first(), Some(&0));
+        assert_eq!(designed.care_positions().last(), Some(&17));
+        assert!(overlap_complexity(&designed) <= overlap_complexity(&ph));
+        assert_eq!(design_seed(11, 18).unwrap(), designed);
+
+        assert_eq!(design_seed(4, 4).unwrap(), contiguous);
+        assert_eq!(design_seed(1, 1).unwrap(), "1".parse().unwrap());
+        for (w, s) in [(0, 5), (6, 5), (1, 3)] {
+            assert_eq!(design_seed(w, s), Err(NtHashError::InvalidK));
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Vectorised tetramer index computation for the `simd` feature.
+//!
+//! Computing a k‑mer's base hash looks up one `TETRAMER_TAB` entry per 4
+//! bases, each indexed by four dependent `CONVERT_TAB` loads.  Here those
+//! loads are batched: AVX2 gathers eight table entries per instruction, and
+//! NEON resolves sixteen bytes at once with `tbl`/`tbx` lookups.  Both paths
+//! reproduce the scalar arithmetic exactly, including the 8‑bit wrap‑around
+//! for non‑ACGT bytes, and fall back to the scalar loop when the CPU lacks
+//! the instructions.
+//!
+//! Whether this pays off depends on the micro‑architecture: the base hash is
+//! dominated by the serial split‑rotate chain, which the scalar loop already
+//! overlaps with the index arithmetic.  Measure with the `base_hash` criterion
+//! group (`cargo bench -- base_hash`, with and without `--features simd`)
+//! before enabling it.
+
+use crate::constants::{CONVERT_TAB, RC_CONVERT_TAB};
+
+/// Scalar reference: index of each full tetramer of `seq` into `out`.
+///
+/// Forward indices weigh the bytes 64/16/4/1 through `CONVERT_TAB`; reverse
+/// indices weigh them 1/4/16/64 through `RC_CONVERT_TAB`.
+fn indices_scalar(seq: &[u8], reverse: bool, out: &mut [u8]) {
+    for (chunk, o) in seq.chunks_exact(4).zip(out.iter_mut()) {
+        let idx = if reverse {
+            (RC_CONVERT_TAB[chunk[3] as usize] as usize) * 64
+                + (RC_CONVERT_TAB[chunk[2] as usize] as usize) * 16
+                + (RC_CONVERT_TAB[chunk[1] as usize] as usize) * 4
+                + RC_CONVERT_TAB[chunk[0] as usize] as usize
+        } else {
+            (CONVERT_TAB[chunk[0] as usize] as usize) * 64
+                + (CONVERT_TAB[chunk[1] as usize] as usize) * 16
+                + (CONVERT_TAB[chunk[2] as usize] as usize) * 4
+                + CONVERT_TAB[chunk[3] as usize] as usize
+        };
+        *o = (idx & 0xFF) as u8;
+    }
+}
+
+/// `TETRAMER_TAB` index of every full tetramer `seq[4i..4i + 4]`, written to
+/// `out[i]`.
+///
+/// With `reverse`, the index is that of the tetramer's reverse complement
+/// (as used by the reverse‑strand base hash).
+#[inline]
+pub(crate) fn tetramer_indices(seq: &[u8], reverse: bool, out: &mut [u8]) {
+    debug_assert!(out.len() >= seq.len() / 4);
+
+    #[cfg(target_arch = "x86_64")]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        // SAFETY: AVX2 support was just checked.
+        return unsafe { x86::indices_avx2(seq, reverse, out) };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    {
+        // SAFETY: NEON is part of the aarch64 baseline.
+        return unsafe { neon::indices_neon(seq, reverse, out) };
+    }
+
+    #[allow(unreachable_code)]
+    indices_scalar(seq, reverse, out)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+
+    use super::indices_scalar;
+    use crate::constants::{CONVERT_TAB, RC_CONVERT_TAB};
+
+    /// Widen a byte table to `i32` so it can be gathered from.
+    const fn widen(tab: &[u8; 256]) -> [i32; 256] {
+        let mut out = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            out[i] = tab[i] as i32;
+            i += 1;
+        }
+        out
+    }
+
+    static CONVERT_TAB32: [i32; 256] = widen(&CONVERT_TAB);
+    static RC_CONVERT_TAB32: [i32; 256] = widen(&RC_CONVERT_TAB);
+
+    /// Eight tetramers (32 bytes) per iteration.
+    ///
+    /// Each 8‑byte quarter is zero‑extended to 32‑bit lanes, gathered from
+    /// the widened table, and shifted into its index position; two rounds of
+    /// `hadd` sum each group of four lanes, leaving the tetramers in the
+    /// order 0,2,4,6 | 1,3,5,7, which a final permute restores.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn indices_avx2(seq: &[u8], reverse: bool, out: &mut [u8]) {
+        let (table, shifts) = if reverse {
+            (&RC_CONVERT_TAB32, _mm256_setr_epi32(0, 2, 4, 6, 0, 2, 4, 6))
+        } else {
+            (&CONVERT_TAB32, _mm256_setr_epi32(6, 4, 2, 0, 6, 4, 2, 0))
+        };
+        let order = _mm256_setr_epi32(0, 4, 1, 5, 2, 6, 3, 7);
+
+        let mut chunks = seq.chunks_exact(32);
+        let mut o = 0;
+        for chunk in &mut chunks {
+            let mut v = [_mm256_setzero_si256(); 4];
+            for (j, lanes) in v.iter_mut().enumerate() {
+                let bytes = _mm_loadl_epi64(chunk.as_ptr().add(8 * j).cast());
+                let offsets = _mm256_cvtepu8_epi32(bytes);
+                let codes = _mm256_i32gather_epi32::<4>(table.as_ptr(), offsets);
+                *lanes = _mm256_sllv_epi32(codes, shifts);
+            }
+            let pairs_lo = _mm256_hadd_epi32(v[0], v[1]);
+            let pairs_hi = _mm256_hadd_epi32(v[2], v[3]);
+            let sums = _mm256_hadd_epi32(pairs_lo, pairs_hi);
+            let sums = _mm256_permutevar8x32_epi32(sums, order);
+
+            let mut buf = [0u32; 8];
+            _mm256_storeu_si256(buf.as_mut_ptr().cast(), sums);
+            for (d, s) in out[o..o + 8].iter_mut().zip(buf) {
+                *d = s as u8;
+            }
+            o += 8;
+        }
+        indices_scalar(chunks.remainder(), reverse, &mut out[o..]);
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod neon {
+    use std::arch::aarch64::*;
+
+    use super::indices_scalar;
+    use crate::constants::{CONVERT_TAB, RC_CONVERT_TAB};
+
+    /// Four tetramers (16 bytes) per iteration.
+    ///
+    /// The 256‑entry table is split into four 64‑byte `tbl` registers; `tbx`
+    /// leaves lanes whose (rebased) index is out of range untouched, so four
+    /// chained lookups cover every byte value.  Shifting each code into
+    /// place (mod 256, like the scalar `& 0xFF`) and two pairwise widening
+    /// adds yield one index per 32‑bit lane.
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn indices_neon(seq: &[u8], reverse: bool, out: &mut [u8]) {
+        const FWD_SHIFTS: [i8; 16] = [6, 4, 2, 0, 6, 4, 2, 0, 6, 4, 2, 0, 6, 4, 2, 0];
+        const REV_SHIFTS: [i8; 16] = [0, 2, 4, 6, 0, 2, 4, 6, 0, 2, 4, 6, 0, 2, 4, 6];
+        let (table, shifts) = if reverse {
+            (&RC_CONVERT_TAB, vld1q_s8(REV_SHIFTS.as_ptr()))
+        } else {
+            (&CONVERT_TAB, vld1q_s8(FWD_SHIFTS.as_ptr()))
+        };
+        let t0 = vld1q_u8_x4(table.as_ptr());
+        let t1 = vld1q_u8_x4(table.as_ptr().add(64));
+        let t2 = vld1q_u8_x4(table.as_ptr().add(128));
+        let t3 = vld1q_u8_x4(table.as_ptr().add(192));
+
+        let mut chunks = seq.chunks_exact(16);
+        let mut o = 0;
+        for chunk in &mut chunks {
+            let bytes = vld1q_u8(chunk.as_ptr());
+            let mut codes = vqtbl4q_u8(t0, bytes);
+            codes = vqtbx4q_u8(codes, t1, vsubq_u8(bytes, vdupq_n_u8(64)));
+            codes = vqtbx4q_u8(codes, t2, vsubq_u8(bytes, vdupq_n_u8(128)));
+            codes = vqtbx4q_u8(codes, t3, vsubq_u8(bytes, vdupq_n_u8(192)));
+            let sums = vpaddlq_u16(vpaddlq_u8(vshlq_u8(codes, shifts)));
+
+            let mut buf = [0u32; 4];
+            vst1q_u32(buf.as_mut_ptr(), sums);
+            for (d, s) in out[o..o + 4].iter_mut().zip(buf) {
+                *d = s as u8;
+            }
+            o += 4;
+        }
+        indices_scalar(chunks.remainder(), reverse, &mut out[o..]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector_path_matches_scalar_for_all_bytes() {
+        // every byte value in every tetramer slot, plus an unaligned tail
+        let mut seq: Vec<u8> = (0..4)
+            .flat_map(|shift| (0..256).map(move |i| ((i + shift) % 256) as u8))
+            .collect();
+        seq.extend(b"ACGTacgtNNUu".iter().cycle().take(4 * 37 + 3));
+        for len in [0, 4, 28, 32, 36, 64, seq.len()] {
+            for reverse in [false, true] {
+                let n = len / 4;
+                let (mut fast, mut slow) = (vec![0; n], vec![0; n]);
+                tetramer_indices(&seq[..len], reverse, &mut fast);
+                indices_scalar(&seq[..len], reverse, &mut slow);
+                assert_eq!(fast, slow, "len {len}, reverse {reverse}");
+            }
+        }
+    }
+
+    #[test]
+    fn long_kmer_base_hashes_agree_with_rolling() {
+        use crate::kmer::{base_forward_hash, base_reverse_hash, NtHash};
+
+        let seq: Vec<u8> = b"ACGTTGCAAGCT".iter().cycle().take(702).copied().collect();
+        let k = 701u16;
+        let mut rolled = NtHash::new(&seq, k, 1, 0).unwrap();
+        assert!(rolled.roll() && rolled.roll());
+        assert_eq!(rolled.forward_hash(), base_forward_hash(&seq[1..], k));
+        assert_eq!(rolled.reverse_hash(), base_reverse_hash(&seq[1..], k));
+    }
+}
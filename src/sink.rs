@@ -0,0 +1,186 @@
+//! **Hash sinks**: consumers plugged straight into the hashing loop.
+//!
+//! A [`HashSink`] receives every valid k‑mer's position and hash values as
+//! they are computed, so filters, counters, and writers can be fed by
+//! [`NtHash::drive`] (or [`drive_parallel`]) without allocating a `Vec`
+//! per window or going through an iterator.  Any
+//! `FnMut(usize, &[u64])` closure is a sink, as are the crate's filters
+//! and sketches.
+//!
+//! ```
+//! use nthash_rs::{bloom::BloomFilter, NtHash};
+//!
+//! let mut bf = BloomFilter::new(1 << 12, 3);
+//! let n = NtHash::new(b"ACGTACGTTGCA", 5, 3, 0).unwrap().drive(&mut bf);
+//! assert_eq!(n, 8);
+//!
+//! let mut max = 0;
+//! NtHash::new(b"ACGTACGTTGCA", 5, 1, 0)
+//!     .unwrap()
+//!     .drive(&mut |_pos, hashes: &[u64]| max = max.max(hashes[0]));
+//! assert!(max > 0);
+//! ```
+
+use std::{ops::Range, thread};
+
+use crate::{
+    bloom::{BloomFilter, CountingBloomFilter},
+    chunked::split_windows,
+    plan::KmerSetBuilder,
+    sketch::{HyperLogLog, MinHash, ScaledSketch, Sketch},
+    storage::{BitStorage, Buckets},
+    NtHash, Result,
+};
+
+/// Consumer of `(pos, hashes)` for each valid k‑mer.
+pub trait HashSink {
+    /// Receive the hash values of the k‑mer starting at `pos`.
+    fn accept(&mut self, pos: usize, hashes: &[u64]);
+}
+
+impl<F: FnMut(usize, &[u64])> HashSink for F {
+    #[inline]
+    fn accept(&mut self, pos: usize, hashes: &[u64]) {
+        self(pos, hashes)
+    }
+}
+
+impl<S: BitStorage> HashSink for BloomFilter<S> {
+    #[inline]
+    fn accept(&mut self, _pos: usize, hashes: &[u64]) {
+        self.insert(hashes);
+    }
+}
+
+impl<B: Buckets<u8>> HashSink for CountingBloomFilter<B> {
+    #[inline]
+    fn accept(&mut self, _pos: usize, hashes: &[u64]) {
+        self.insert(hashes);
+    }
+}
+
+impl HashSink for KmerSetBuilder {
+    #[inline]
+    fn accept(&mut self, _pos: usize, hashes: &[u64]) {
+        self.insert(hashes);
+    }
+}
+
+macro_rules! sketch_sink {
+    ($($t:ty),*) => {$(
+        impl HashSink for $t {
+            #[inline]
+            fn accept(&mut self, _pos: usize, hashes: &[u64]) {
+                self.insert(hashes[0]);
+            }
+        }
+    )*};
+}
+
+sketch_sink!(MinHash, ScaledSketch, HyperLogLog);
+
+/// Hash `seq` on `threads` threads (0 = available parallelism), feeding
+/// each thread's share of the k‑mers into its own sink from `make_sink`.
+///
+/// `seq` is split into contiguous ranges of k‑mer start positions, one per
+/// sink; positions passed to the sinks are relative to the whole of
+/// `seq`.  The sinks are returned in sequence order, ready to be merged.
+///
+/// ```
+/// use nthash_rs::{
+///     sink::drive_parallel,
+///     sketch::{MinHash, Sketch},
+///     NtHash,
+/// };
+///
+/// let seq = b"ACGTTGCAAGCTTAGCGATCGATCGGGCTAGCATCGACTACG".repeat(4);
+/// let parts = drive_parallel(&seq, 11, 1, 3, || MinHash::new(16)).unwrap();
+/// let mut merged = MinHash::new(16);
+/// for h in parts.iter().flat_map(MinHash::hashes) {
+///     merged.insert(h);
+/// }
+///
+/// let mut whole = MinHash::new(16);
+/// NtHash::new(&seq, 11, 1, 0).unwrap().drive(&mut whole);
+/// assert_eq!(merged, whole);
+/// ```
+///
+/// # Errors
+///
+/// Propagates [`NtHash::new`] errors.
+pub fn drive_parallel<S, F>(
+    seq: &[u8],
+    k: u16,
+    num_hashes: u8,
+    threads: usize,
+    make_sink: F,
+) -> Result<Vec<S>>
+where
+    S: HashSink + Send,
+    F: Fn() -> S + Sync,
+{
+    NtHash::new(seq, k, num_hashes, 0)?;
+    let k_usz = k as usize;
+    let windows = seq.len() - k_usz + 1;
+    let make_sink = &make_sink;
+    Ok(thread::scope(|s| {
+        let handles: Vec<_> = split_windows(windows, threads)
+            .map(|Range { start, end }| {
+                s.spawn(move || {
+                    let mut sink = make_sink();
+                    let mut h = NtHash::new(&seq[start..end + k_usz - 1], k, num_hashes, 0)
+                        .expect("chunk holds at least one k-mer");
+                    h.drive(&mut |pos, hashes: &[u64]| sink.accept(start + pos, hashes));
+                    sink
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("hashing thread panicked"))
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Collect(Vec<(usize, Vec<u64>)>);
+
+    impl HashSink for Collect {
+        fn accept(&mut self, pos: usize, hashes: &[u64]) {
+            self.0.push((pos, hashes.to_vec()));
+        }
+    }
+
+    #[test]
+    fn parallel_sinks_cover_every_kmer_once() {
+        let seq = b"ATCGTACGATGCANGCATGCTGACGTTAGCATCGGNNNATCGATCGGCTAGCTAGCAT";
+        let mut expected = Vec::new();
+        NtHash::new(seq, 7, 2, 0)
+            .unwrap()
+            .drive(&mut |pos, hashes: &[u64]| expected.push((pos, hashes.to_vec())));
+
+        for threads in [1, 2, 5, 64] {
+            let sinks = drive_parallel(seq, 7, 2, threads, Collect::default).unwrap();
+            assert!(sinks.len() <= threads);
+            let got: Vec<(usize, Vec<u64>)> = sinks.into_iter().flat_map(|c| c.0).collect();
+            assert_eq!(got, expected, "threads={threads}");
+        }
+        assert!(drive_parallel(b"ACG", 7, 1, 2, Collect::default).is_err());
+
+        let mut hll = HyperLogLog::new(10).unwrap();
+        let mut mh = MinHash::new(4);
+        let mut h = NtHash::new(seq, 7, 1, 0).unwrap();
+        let n = h.drive(&mut hll);
+        assert_eq!(n, expected.len());
+        NtHash::new(seq, 7, 1, 0).unwrap().drive(&mut mh);
+        assert_eq!(mh.hashes().count(), 4);
+        let mut distinct: Vec<u64> = expected.iter().map(|(_, h)| h[0]).collect();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert!((hll.estimate() - distinct.len() as f64).abs() < 3.0);
+    }
+}
@@ -0,0 +1,661 @@
+//! **Sequence sketches** built from canonical ntHash values.
+//!
+//! Three classic summaries are provided, all fed one canonical k‑mer hash at
+//! a time through the [`Sketch`] trait:
+//!
+//! - [`MinHash`] — the `s` smallest hashes (bottom‑s MinHash);
+//! - [`ScaledSketch`] — every hash below `u64::MAX / scale` (FracMinHash);
+//! - [`HyperLogLog`] — a cardinality estimator with `2^p` registers.
+//!
+//! [`Sketcher`] drives any of them over a sequence that arrives in pieces,
+//! e.g. a read growing during live basecalling: it keeps the rolling hasher
+//! (and the last `k − 1` bases) between calls to
+//! [`extend`](Sketcher::extend), so each update only hashes the new k‑mers.
+//!
+//! ```
+//! use nthash_rs::sketch::{MinHash, Sketcher};
+//!
+//! let mut live = Sketcher::new(7, MinHash::new(16)).unwrap();
+//! live.extend(b"ACGTAGCTAGCTAG");
+//! live.extend(b"CATCGNNACGTTTGCA");
+//!
+//! let mut whole = Sketcher::new(7, MinHash::new(16)).unwrap();
+//! whole.extend(b"ACGTAGCTAGCTAGCATCGNNACGTTTGCA");
+//! assert_eq!(live.sketch(), whole.sketch());
+//! ```
+//!
+//! For cohorts, [`sketch_records_parallel`] sketches every record of a
+//! FASTA/FASTQ stream on a pool of threads.
+
+use std::{
+    collections::BTreeSet,
+    io,
+    sync::{mpsc, Mutex},
+    thread,
+};
+
+use crate::{blind::BlindNtHash, io::Record, kmer::is_acgt, NtHashError, Result};
+
+/// A summary that absorbs canonical k‑mer hashes.
+pub trait Sketch {
+    /// Add one hash value.
+    fn insert(&mut self, hash: u64);
+}
+
+/// Bottom‑s MinHash: the `size` smallest distinct hashes seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinHash {
+    size: usize,
+    hashes: BTreeSet<u64>,
+}
+
+impl MinHash {
+    /// An empty sketch keeping at most `size` hashes.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            hashes: BTreeSet::new(),
+        }
+    }
+
+    /// Maximum number of retained hashes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Retained hashes in ascending order.
+    pub fn hashes(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hashes.iter().copied()
+    }
+
+    /// Estimated Jaccard similarity with `other`.
+    ///
+    /// The smallest `min(size)` hashes of the union are taken as the sample;
+    /// the estimate is the fraction of them present in both sketches.
+    pub fn jaccard(&self, other: &MinHash) -> f64 {
+        let s = self.size.min(other.size);
+        let sample: Vec<u64> = self.hashes.union(&other.hashes).take(s).copied().collect();
+        if sample.is_empty() {
+            return 0.0;
+        }
+        let shared = sample
+            .iter()
+            .filter(|h| self.hashes.contains(h) && other.hashes.contains(h))
+            .count();
+        shared as f64 / sample.len() as f64
+    }
+}
+
+impl Sketch for MinHash {
+    fn insert(&mut self, hash: u64) {
+        if self.hashes.len() < self.size {
+            self.hashes.insert(hash);
+        } else if self.hashes.last().is_some_and(|&max| hash < max) && self.hashes.insert(hash) {
+            self.hashes.pop_last();
+        }
+    }
+}
+
+/// FracMinHash: every hash at or below `u64::MAX / scale`.
+///
+/// The sketch size grows with the number of distinct k‑mers (about one in
+/// `scale` is kept), so sketches of very different sequences stay
+/// comparable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScaledSketch {
+    max_hash: u64,
+    hashes: BTreeSet<u64>,
+}
+
+impl ScaledSketch {
+    /// An empty sketch keeping roughly one hash in `scale` (`scale ≥ 1`).
+    pub fn new(scale: u64) -> Self {
+        Self {
+            max_hash: u64::MAX / scale.max(1),
+            hashes: BTreeSet::new(),
+        }
+    }
+
+    /// Sketch every canonical k‑mer hash of `seq`; k‑mers containing
+    /// non‑ACGT bases are skipped.
+    ///
+    /// Hashes are kept in ascending order, as sourmash stores them, though
+    /// the values themselves are ntHash rather than MurmurHash3 and so are
+    /// not interchangeable with sourmash signatures.
+    ///
+    /// ```
+    /// use nthash_rs::sketch::ScaledSketch;
+    ///
+    /// let a = ScaledSketch::from_seq(b"ACGTAGCTAGCTAGCATCGTTGCAACGT", 5, 2).unwrap();
+    /// let b = ScaledSketch::from_seq(b"ACGTAGCTAGCTAG", 5, 2).unwrap();
+    /// assert_eq!(b.containment(&a), 1.0);
+    /// assert_eq!(a.intersection(&b), b);
+    /// assert_eq!(a.union(&b), a);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k == 0`.
+    pub fn from_seq(seq: &[u8], k: u16, scale: u64) -> Result<Self> {
+        let mut sketcher = Sketcher::new(k, Self::new(scale))?;
+        sketcher.extend(seq);
+        Ok(sketcher.into_sketch())
+    }
+
+    /// Largest hash value retained.
+    pub fn max_hash(&self) -> u64 {
+        self.max_hash
+    }
+
+    /// Retained hashes in ascending order.
+    pub fn hashes(&self) -> impl Iterator<Item = u64> + '_ {
+        self.hashes.iter().copied()
+    }
+
+    /// Number of retained hashes.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if no hash has been retained.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Drop every hash above `u64::MAX / scale`; a no‑op unless `scale` is
+    /// coarser than the current one.
+    pub fn downsample(&mut self, scale: u64) {
+        let max_hash = u64::MAX / scale.max(1);
+        if max_hash < self.max_hash {
+            self.max_hash = max_hash;
+            self.hashes.retain(|&h| h <= max_hash);
+        }
+    }
+
+    // Retained hashes at or below `max_hash`.
+    fn below(&self, max_hash: u64) -> impl Iterator<Item = u64> + '_ {
+        self.hashes.range(..=max_hash).copied()
+    }
+
+    /// Hashes in either sketch, at the coarser of the two scales.
+    pub fn union(&self, other: &ScaledSketch) -> ScaledSketch {
+        let max_hash = self.max_hash.min(other.max_hash);
+        ScaledSketch {
+            max_hash,
+            hashes: self.below(max_hash).chain(other.below(max_hash)).collect(),
+        }
+    }
+
+    /// Hashes in both sketches, at the coarser of the two scales.
+    pub fn intersection(&self, other: &ScaledSketch) -> ScaledSketch {
+        let max_hash = self.max_hash.min(other.max_hash);
+        ScaledSketch {
+            max_hash,
+            hashes: self
+                .below(max_hash)
+                .filter(|h| other.hashes.contains(h))
+                .collect(),
+        }
+    }
+
+    /// Fraction of this sketch's hashes also found in `other`, at the
+    /// coarser of the two scales; 0 if this sketch is empty there.
+    pub fn containment(&self, other: &ScaledSketch) -> f64 {
+        let max_hash = self.max_hash.min(other.max_hash);
+        let total = self.below(max_hash).count();
+        if total == 0 {
+            return 0.0;
+        }
+        self.intersection(other).len() as f64 / total as f64
+    }
+
+    /// Jaccard similarity of the two hash sets, at the coarser of the two
+    /// scales; 0 if both are empty there.
+    pub fn jaccard(&self, other: &ScaledSketch) -> f64 {
+        let union = self.union(other).len();
+        if union == 0 {
+            return 0.0;
+        }
+        self.intersection(other).len() as f64 / union as f64
+    }
+}
+
+impl Sketch for ScaledSketch {
+    fn insert(&mut self, hash: u64) {
+        if hash <= self.max_hash {
+            self.hashes.insert(hash);
+        }
+    }
+}
+
+/// HyperLogLog distinct‑count estimator with `2^p` 6‑bit registers.
+///
+/// With the `serde` feature the precision is re‑validated when loaded,
+/// along with the register count it implies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "HyperLogLogDef")
+)]
+pub struct HyperLogLog {
+    p: u8,
+    registers: Vec<u8>,
+}
+
+/// Serialized form of a [`HyperLogLog`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct HyperLogLogDef {
+    p: u8,
+    registers: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<HyperLogLogDef> for HyperLogLog {
+    type Error = NtHashError;
+
+    fn try_from(def: HyperLogLogDef) -> Result<Self> {
+        let hll = Self::new(def.p)?;
+        if def.registers.len() != hll.registers.len() {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        Ok(Self {
+            registers: def.registers,
+            ..hll
+        })
+    }
+}
+
+impl HyperLogLog {
+    /// An empty estimator with `2^p` registers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidCapacity`] unless `4 ≤ p ≤ 18`.
+    pub fn new(p: u8) -> Result<Self> {
+        if !(4..=18).contains(&p) {
+            return Err(NtHashError::InvalidCapacity);
+        }
+        Ok(Self {
+            p,
+            registers: vec![0; 1 << p],
+        })
+    }
+
+    /// Precision (log₂ of the register count).
+    pub fn precision(&self) -> u8 {
+        self.p
+    }
+
+    /// Estimated number of distinct hashes inserted.
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter().map(|&r| (-(r as f64)).exp2()).sum();
+        let raw = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            // small‑range correction (linear counting)
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+impl Sketch for HyperLogLog {
+    fn insert(&mut self, hash: u64) {
+        let idx = (hash >> (64 - self.p)) as usize;
+        let rank = ((hash << self.p).leading_zeros() + 1).min(64 - self.p as u32 + 1) as u8;
+        if self.registers[idx] < rank {
+            self.registers[idx] = rank;
+        }
+    }
+}
+
+/// Feeds the canonical hashes of a growing sequence into a [`Sketch`].
+///
+/// Bases can be appended in arbitrary pieces; k‑mers spanning two calls are
+/// hashed exactly once, and k‑mers containing non‑ACGT bases are skipped, so
+/// the result equals sketching the concatenated sequence in one go.
+pub struct Sketcher<S> {
+    k: u16,
+    sketch: S,
+    hasher: Option<BlindNtHash>,
+    // valid bases since the last non‑ACGT base, while fewer than k
+    run: Vec<u8>,
+    bases: usize,
+}
+
+impl<S: Sketch> Sketcher<S> {
+    /// Sketch k‑mers of length `k` into `sketch`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NtHashError::InvalidK`] if `k == 0`.
+    pub fn new(k: u16, sketch: S) -> Result<Self> {
+        if k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        Ok(Self {
+            k,
+            sketch,
+            hasher: None,
+            run: Vec::with_capacity(k as usize),
+            bases: 0,
+        })
+    }
+
+    /// Append `bases` to the sequence, sketching every k‑mer they complete.
+    pub fn extend(&mut self, bases: &[u8]) {
+        self.bases += bases.len();
+        for &c in bases {
+            if !is_acgt(c) {
+                self.hasher = None;
+                self.run.clear();
+                continue;
+            }
+            if let Some(h) = &mut self.hasher {
+                h.roll(c);
+                self.sketch.insert(h.hashes()[0]);
+                continue;
+            }
+            self.run.push(c);
+            if self.run.len() == self.k as usize {
+                let h =
+                    BlindNtHash::from_window(&self.run, 1).expect("run holds exactly k > 0 bases");
+                self.sketch.insert(h.hashes()[0]);
+                self.hasher = Some(h);
+                self.run.clear();
+            }
+        }
+    }
+
+    /// Total number of bases appended so far.
+    pub fn len(&self) -> usize {
+        self.bases
+    }
+
+    /// Returns `true` if no base has been appended.
+    pub fn is_empty(&self) -> bool {
+        self.bases == 0
+    }
+
+    /// The sketch so far.
+    pub fn sketch(&self) -> &S {
+        &self.sketch
+    }
+
+    /// Stop sketching and return the sketch.
+    pub fn into_sketch(self) -> S {
+        self.sketch
+    }
+}
+
+/// Configuration for [`sketch_records_parallel`].
+#[derive(Debug, Clone)]
+pub struct SketchParams<S> {
+    k: u16,
+    template: S,
+    threads: usize,
+    queue_depth: usize,
+}
+
+impl<S> SketchParams<S> {
+    /// Sketch `k`‑mers into copies of `template` (usually an empty sketch).
+    pub fn new(k: u16, template: S) -> Self {
+        Self {
+            k,
+            template,
+            threads: 0,
+            queue_depth: 64,
+        }
+    }
+
+    /// Worker threads; 0 (the default) uses the available parallelism.
+    pub fn threads(mut self, n: usize) -> Self {
+        self.threads = n;
+        self
+    }
+
+    /// Records read ahead of the workers (default 64), bounding memory.
+    pub fn queue_depth(mut self, n: usize) -> Self {
+        self.queue_depth = n;
+        self
+    }
+}
+
+/// Sketch every record of `records` in parallel, returning
+/// `(record_id, sketch)` pairs in input order.
+///
+/// At most `queue_depth` records are buffered between the reader and the
+/// workers, so memory stays bounded however long the input is.
+///
+/// ```
+/// use nthash_rs::io::Reader;
+/// use nthash_rs::sketch::{sketch_records_parallel, MinHash, SketchParams};
+///
+/// let fasta = b">a\nACGTACGTTGCA\n>b\nTTGCAACGTA\n";
+/// let params = SketchParams::new(5, MinHash::new(8)).threads(2);
+/// let out = sketch_records_parallel(Reader::new(&fasta[..]), &params).unwrap();
+/// assert_eq!(out[0].0, "a");
+/// assert_eq!(out[1].0, "b");
+/// ```
+///
+/// # Errors
+///
+/// Returns the first read error, or [`io::ErrorKind::InvalidInput`] if
+/// `k == 0`.
+pub fn sketch_records_parallel<I, S>(
+    records: I,
+    params: &SketchParams<S>,
+) -> io::Result<Vec<(String, S)>>
+where
+    I: IntoIterator<Item = io::Result<Record>>,
+    S: Sketch + Clone + Send + Sync,
+{
+    if params.k == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            NtHashError::InvalidK,
+        ));
+    }
+    let threads = match params.threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let (work_tx, work_rx) = mpsc::sync_channel::<(usize, Record)>(params.queue_depth);
+    let work_rx = Mutex::new(work_rx);
+    let (done_tx, done_rx) = mpsc::channel();
+
+    let read: io::Result<()> = thread::scope(|scope| {
+        for _ in 0..threads {
+            let done_tx = done_tx.clone();
+            let work_rx = &work_rx;
+            scope.spawn(move || loop {
+                let next = work_rx.lock().expect("worker panicked").recv();
+                let Ok((idx, rec)) = next else { break };
+                let mut sketcher =
+                    Sketcher::new(params.k, params.template.clone()).expect("k checked above");
+                sketcher.extend(&rec.seq);
+                let id = String::from_utf8_lossy(rec.id()).into_owned();
+                if done_tx.send((idx, id, sketcher.into_sketch())).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(done_tx);
+        // moved in so that returning early closes the queue for the workers
+        let work_tx = work_tx;
+        for (idx, rec) in records.into_iter().enumerate() {
+            // workers only stop early if they panicked, which `scope` re‑raises
+            if work_tx.send((idx, rec?)).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    });
+    read?;
+
+    let mut out: Vec<(usize, String, S)> = done_rx.into_iter().collect();
+    out.sort_unstable_by_key(|r| r.0);
+    Ok(out.into_iter().map(|(_, id, s)| (id, s)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NtHash;
+
+    fn random_dna(len: usize, mut state: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                b"ACGT"[(state >> 60) as usize & 3]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pieces_match_whole_sequence() {
+        let mut seq = random_dna(3000, 11);
+        seq[700] = b'N';
+        seq[1500..1504].fill(b'n');
+        let k = 21;
+
+        let mut expected = MinHash::new(64);
+        let mut h = NtHash::new(&seq, k, 1, 0).unwrap();
+        while h.roll() {
+            expected.insert(h.hashes()[0]);
+        }
+
+        let mut live = Sketcher::new(k, MinHash::new(64)).unwrap();
+        for piece in seq.chunks(37) {
+            live.extend(piece);
+        }
+        assert_eq!(live.len(), seq.len());
+        assert_eq!(live.sketch(), &expected);
+
+        let mut scaled = Sketcher::new(k, ScaledSketch::new(10)).unwrap();
+        scaled.extend(&seq);
+        assert!(scaled.sketch().hashes().all(|x| x <= u64::MAX / 10));
+        assert!(!scaled.sketch().is_empty());
+
+        // control bytes break runs like N does
+        let mut all = Sketcher::new(4, ScaledSketch::new(1)).unwrap();
+        all.extend(b"ACGTT\x01TGCA");
+        assert_eq!(all.sketch().len(), 3);
+    }
+
+    #[test]
+    fn estimates_are_reasonable() {
+        let a = random_dna(20_000, 1);
+        let mut b = a[..10_000].to_vec();
+        b.extend(random_dna(10_000, 2));
+
+        let sketch = |seq: &[u8]| {
+            let mut s = Sketcher::new(21, MinHash::new(1000)).unwrap();
+            s.extend(seq);
+            s.into_sketch()
+        };
+        // ~1/3 of the union is shared
+        let j = sketch(&a).jaccard(&sketch(&b));
+        assert!((0.28..0.39).contains(&j), "jaccard {j}");
+
+        let mut hll = Sketcher::new(21, HyperLogLog::new(12).unwrap()).unwrap();
+        hll.extend(&a);
+        let n = hll.sketch().estimate();
+        assert!((n - 19_980.0).abs() < 0.05 * 19_980.0, "estimate {n}");
+        assert_eq!(HyperLogLog::new(3), Err(NtHashError::InvalidCapacity));
+    }
+
+    #[test]
+    fn scaled_set_operations() {
+        let a = random_dna(20_000, 3);
+        let mut b = a[..10_000].to_vec();
+        b.extend(random_dna(10_000, 4));
+
+        let sa = ScaledSketch::from_seq(&a, 21, 10).unwrap();
+        let mut sb = ScaledSketch::from_seq(&b, 21, 10).unwrap();
+        let mut live = Sketcher::new(21, ScaledSketch::new(10)).unwrap();
+        live.extend(&a);
+        assert_eq!(live.sketch(), &sa);
+
+        let both = sa.intersection(&sb);
+        assert_eq!(sa.union(&sb).len(), sa.len() + sb.len() - both.len());
+        let c = sa.containment(&sb);
+        assert!((0.4..0.6).contains(&c), "containment {c}");
+        let j = sa.jaccard(&sb);
+        assert!((0.28..0.39).contains(&j), "jaccard {j}");
+
+        // mixed scales compare at the coarser one
+        let coarse = ScaledSketch::from_seq(&a, 21, 40).unwrap();
+        assert_eq!(coarse.containment(&sa), 1.0);
+        assert_eq!(sa.intersection(&coarse), coarse);
+        sb.downsample(40);
+        assert_eq!(sb.max_hash(), coarse.max_hash());
+        assert!(sb.hashes().all(|h| h <= u64::MAX / 40));
+        assert_eq!(
+            ScaledSketch::from_seq(&a, 0, 10),
+            Err(NtHashError::InvalidK)
+        );
+    }
+
+    #[test]
+    fn parallel_sketches_keep_input_order() {
+        let mut fasta = Vec::new();
+        let seqs: Vec<Vec<u8>> = (0..50)
+            .map(|i| random_dna(200 + i * 7, i as u64 + 1))
+            .collect();
+        for (i, s) in seqs.iter().enumerate() {
+            fasta.extend(format!(">r{i} desc\n").bytes());
+            fasta.extend(s);
+            fasta.push(b'\n');
+        }
+        let params = SketchParams::new(15, ScaledSketch::new(4))
+            .threads(4)
+            .queue_depth(3);
+        let out = sketch_records_parallel(crate::io::Reader::new(&fasta[..]), &params).unwrap();
+        assert_eq!(out.len(), 50);
+        for (i, (id, sketch)) in out.iter().enumerate() {
+            let mut one = Sketcher::new(15, ScaledSketch::new(4)).unwrap();
+            one.extend(&seqs[i]);
+            assert_eq!(
+                (id.as_str(), sketch),
+                (format!("r{i}").as_str(), one.sketch())
+            );
+        }
+
+        let truncated = crate::io::Reader::new(&b"@r1\nACGT\n+\n"[..]);
+        assert!(sketch_records_parallel(truncated, &params).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn hyperloglog_registers_checked_on_load() {
+        let mut hll = HyperLogLog::new(4).unwrap();
+        hll.insert(0x1234_5678_9abc_def0);
+        let json = serde_json::to_value(&hll).unwrap();
+        assert_eq!(
+            serde_json::from_value::<HyperLogLog>(json.clone()).unwrap(),
+            hll
+        );
+
+        let mut short = json.clone();
+        short["registers"] = serde_json::json!([0, 0, 0]);
+        assert!(serde_json::from_value::<HyperLogLog>(short).is_err());
+        let mut bad_p = json;
+        bad_p["p"] = serde_json::json!(2);
+        assert!(serde_json::from_value::<HyperLogLog>(bad_p).is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! **Sliding windows** over k‑mer hash streams.
+//!
+//! [`HashWindow`] keeps the last `w` canonical hashes in a ring buffer
+//! alongside two monotone queues, so the window's minimum, maximum, and
+//! their positions are read in O(1) and each push costs O(1) amortized.
+//! It is the building block behind minimizer selection, syncmer
+//! thresholds, and winnowed alignment‑free statistics, for callers that
+//! need a policy the [`minimizer`](crate::minimizer) module does not
+//! offer.
+//!
+//! The [`Sliding`] adapter drives one from any `(pos, hashes)` iterator and
+//! reports every full window.  Windows hold `w` consecutive *k‑mers*, not
+//! positions: k‑mers skipped for containing `N` are simply absent.
+//!
+//! ```
+//! use nthash_rs::{minimizer::minimizers, sliding::SlidingExt, NtHashBuilder};
+//!
+//! let seq = b"ACGTTGCAAGCTTAGCGATCGATCGGATC";
+//! let mut picked: Vec<usize> = NtHashBuilder::new(seq)
+//!     .k(5)
+//!     .finish()
+//!     .unwrap()
+//!     .sliding(4)
+//!     .map(|w| w.argmin)
+//!     .collect();
+//! picked.dedup();
+//! let expected: Vec<usize> = minimizers(seq, 5, 4).unwrap().iter().map(|m| m.pos).collect();
+//! assert_eq!(picked, expected);
+//! ```
+
+use std::collections::VecDeque;
+
+/// The last `w` hashes pushed, with their positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashWindow {
+    width: usize,
+    /// Every `(pos, hash)` in the window, oldest first.
+    ring: VecDeque<(usize, u64)>,
+    /// Candidates for the minimum: increasing hashes, oldest first.
+    mins: VecDeque<(usize, u64)>,
+    /// Candidates for the maximum: decreasing hashes, oldest first.
+    maxs: VecDeque<(usize, u64)>,
+}
+
+impl HashWindow {
+    /// An empty window of `width` hashes (`0` is treated as 1).
+    pub fn new(width: usize) -> Self {
+        let width = width.max(1);
+        Self {
+            width,
+            ring: VecDeque::with_capacity(width),
+            mins: VecDeque::new(),
+            maxs: VecDeque::new(),
+        }
+    }
+
+    /// Add the hash of the k‑mer at `pos`, returning the entry that fell
+    /// out of a full window.
+    ///
+    /// Positions are only reported back; they need not be consecutive, but
+    /// should increase for ties to resolve to the oldest entry.
+    pub fn push(&mut self, pos: usize, hash: u64) -> Option<(usize, u64)> {
+        let evicted = if self.ring.len() == self.width {
+            let old = self.ring.pop_front();
+            for queue in [&mut self.mins, &mut self.maxs] {
+                if queue.front() == old.as_ref() {
+                    queue.pop_front();
+                }
+            }
+            old
+        } else {
+            None
+        };
+        while self.mins.back().is_some_and(|&(_, h)| h > hash) {
+            self.mins.pop_back();
+        }
+        while self.maxs.back().is_some_and(|&(_, h)| h < hash) {
+            self.maxs.pop_back();
+        }
+        for queue in [&mut self.ring, &mut self.mins, &mut self.maxs] {
+            queue.push_back((pos, hash));
+        }
+        evicted
+    }
+
+    /// Smallest hash in the window.
+    #[inline]
+    pub fn min(&self) -> Option<u64> {
+        self.mins.front().map(|&(_, h)| h)
+    }
+
+    /// Largest hash in the window.
+    #[inline]
+    pub fn max(&self) -> Option<u64> {
+        self.maxs.front().map(|&(_, h)| h)
+    }
+
+    /// Position of the smallest hash, the oldest one on ties.
+    #[inline]
+    pub fn argmin(&self) -> Option<usize> {
+        self.mins.front().map(|&(p, _)| p)
+    }
+
+    /// Position of the largest hash, the oldest one on ties.
+    #[inline]
+    pub fn argmax(&self) -> Option<usize> {
+        self.maxs.front().map(|&(p, _)| p)
+    }
+
+    /// The window's `(pos, hash)` entries, oldest first.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (usize, u64)> + '_ {
+        self.ring.iter().copied()
+    }
+
+    /// Number of hashes held, at most [`width`](Self::width).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Whether nothing has been pushed since creation or the last
+    /// [`clear`](Self::clear).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    /// Whether the window holds `width` hashes.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.ring.len() == self.width
+    }
+
+    /// Capacity of the window.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Empty the window, e.g. at a sequence boundary.
+    pub fn clear(&mut self) {
+        self.ring.clear();
+        self.mins.clear();
+        self.maxs.clear();
+    }
+}
+
+/// Summary of one full window, as yielded by [`Sliding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowStats {
+    /// Position of the oldest k‑mer in the window.
+    pub start: usize,
+    /// Position of the newest k‑mer in the window.
+    pub last: usize,
+    /// Smallest canonical hash.
+    pub min: u64,
+    /// Its position (the oldest on ties).
+    pub argmin: usize,
+    /// Largest canonical hash.
+    pub max: u64,
+    /// Its position (the oldest on ties).
+    pub argmax: usize,
+}
+
+/// Iterator adapter yielding [`WindowStats`] for every window of `w`
+/// consecutive k‑mers.
+#[derive(Debug, Clone)]
+pub struct Sliding<I> {
+    inner: I,
+    window: HashWindow,
+}
+
+impl<I, P, H> Sliding<I>
+where
+    I: Iterator<Item = (P, H)>,
+    P: Into<usize>,
+    H: AsRef<[u64]>,
+{
+    /// Slide a window of `width` k‑mers (`0` is treated as 1) over `inner`.
+    pub fn new(inner: I, width: usize) -> Self {
+        Self {
+            inner,
+            window: HashWindow::new(width),
+        }
+    }
+
+    /// The window as of the last item yielded.
+    pub fn window(&self) -> &HashWindow {
+        &self.window
+    }
+}
+
+impl<I, P, H> Iterator for Sliding<I>
+where
+    I: Iterator<Item = (P, H)>,
+    P: Into<usize>,
+    H: AsRef<[u64]>,
+{
+    type Item = WindowStats;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (pos, hashes) = self.inner.next()?;
+            let pos = pos.into();
+            self.window.push(pos, hashes.as_ref()[0]);
+            if !self.window.is_full() {
+                continue;
+            }
+            let (mins, maxs) = (&self.window.mins[0], &self.window.maxs[0]);
+            return Some(WindowStats {
+                start: self.window.ring[0].0,
+                last: pos,
+                min: mins.1,
+                argmin: mins.0,
+                max: maxs.1,
+                argmax: maxs.0,
+            });
+        }
+    }
+}
+
+/// Adds [`sliding`](SlidingExt::sliding) to every `(pos, hashes)`
+/// iterator, where `pos` is a [`KmerPos`](crate::KmerPos) (reported by its
+/// start) or a plain `usize`.
+pub trait SlidingExt: Iterator + Sized {
+    /// Report the minimum and maximum of each window of `width` k‑mers.
+    fn sliding(self, width: usize) -> Sliding<Self>;
+}
+
+impl<I, P, H> SlidingExt for I
+where
+    I: Iterator<Item = (P, H)>,
+    P: Into<usize>,
+    H: AsRef<[u64]>,
+{
+    fn sliding(self, width: usize) -> Sliding<Self> {
+        Sliding::new(self, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NtHashBuilder;
+
+    #[test]
+    fn matches_brute_force() {
+        let seq = b"ACGTCAGTACGTNNACGTTTGACAGGATCCAAAAAAAAGTCA";
+        let all: Vec<(usize, u64)> = NtHashBuilder::new(seq)
+            .k(4)
+            .finish()
+            .unwrap()
+            .map(|(p, h)| (p.start, h[0]))
+            .collect();
+        for w in [1, 2, 5, 9, all.len(), all.len() + 1] {
+            let got: Vec<_> = all.iter().map(|&(p, h)| (p, [h])).sliding(w).collect();
+            let want: Vec<_> = all
+                .windows(w)
+                .map(|win| {
+                    let lo = win.iter().min_by_key(|e| e.1).unwrap();
+                    let hi = win.iter().rev().max_by_key(|e| e.1).unwrap();
+                    WindowStats {
+                        start: win[0].0,
+                        last: win[w - 1].0,
+                        min: lo.1,
+                        argmin: lo.0,
+                        max: hi.1,
+                        argmax: hi.0,
+                    }
+                })
+                .collect();
+            assert_eq!(got, want, "w = {w}");
+        }
+    }
+
+    #[test]
+    fn push_evicts_and_ties_keep_the_oldest() {
+        let mut w = HashWindow::new(3);
+        assert_eq!((w.min(), w.argmax()), (None, None));
+        assert_eq!(w.push(10, 5), None);
+        assert_eq!(w.push(11, 5), None);
+        assert_eq!(w.push(12, 7), None);
+        assert!(w.is_full());
+        assert_eq!((w.argmin(), w.argmax()), (Some(10), Some(12)));
+        assert_eq!(w.push(13, 5), Some((10, 5)));
+        assert_eq!((w.min(), w.argmin()), (Some(5), Some(11)));
+        assert_eq!(w.iter().collect::<Vec<_>>(), [(11, 5), (12, 7), (13, 5)]);
+        w.clear();
+        assert!(w.is_empty());
+        assert_eq!(HashWindow::new(0).width(), 1);
+    }
+}
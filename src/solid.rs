@@ -0,0 +1,248 @@
+//! **Solid k‑mer filters**, as built by ntHits.
+//!
+//! Error‑correction and assembly tools (ntEdit, ABySS, …) only trust k‑mers
+//! seen often enough across the reads: sequencing errors create k‑mers that
+//! occur once or twice, while genuine ones recur with the coverage.
+//! [`SolidKmersBuilder`] counts every k‑mer of a read set in a
+//! [`CountingBloomFilter`] and keeps those whose count lies in
+//! `min_count..=max_count` in a compact [`BloomFilter`].
+//!
+//! Without an upper bound this takes a single pass: a k‑mer enters the
+//! output filter the moment its count reaches `min_count`.  With one (to
+//! drop repeats as well as errors) the reads are counted first and scanned
+//! again to select k‑mers by their final count.
+//!
+//! ```
+//! use nthash_rs::{hash_kmer, solid::SolidKmersBuilder};
+//!
+//! let reads: [&[u8]; 3] = [b"ACGTTGCAAGT", b"ACGTTGCAAGT", b"ACGTAGCAAGT"];
+//! let solid = SolidKmersBuilder::new(5).min_count(2).build(reads).unwrap();
+//! assert!(solid.is_solid(&hash_kmer(b"GTTGC", 3).unwrap())); // seen twice
+//! assert!(!solid.is_solid(&hash_kmer(b"GTAGC", 3).unwrap())); // error k‑mer
+//! ```
+
+use crate::{
+    bloom::{BloomFilter, CountingBloomFilter},
+    NtHash, NtHashError, Result,
+};
+
+/// Configures and builds a [`SolidKmers`] filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolidKmersBuilder {
+    k: u16,
+    num_hashes: u8,
+    min_count: u8,
+    max_count: u8,
+    num_counters: usize,
+    num_bits: usize,
+}
+
+impl SolidKmersBuilder {
+    /// Solid `k`‑mers: by default seen at least twice, counted in 2²⁴
+    /// counters and kept in 2²⁶ bits, with 3 hashes per k‑mer.
+    pub fn new(k: u16) -> Self {
+        Self {
+            k,
+            num_hashes: 3,
+            min_count: 2,
+            max_count: u8::MAX,
+            num_counters: 1 << 24,
+            num_bits: 1 << 26,
+        }
+    }
+
+    /// Hashes per k‑mer in both filters (default `3`).
+    pub fn num_hashes(mut self, m: u8) -> Self {
+        self.num_hashes = m;
+        self
+    }
+
+    /// Keep k‑mers seen at least `n` times (default `2`).
+    pub fn min_count(mut self, n: u8) -> Self {
+        self.min_count = n;
+        self
+    }
+
+    /// Keep only k‑mers seen at most `n` times (default unbounded).
+    ///
+    /// Counters saturate at 255, so `255` means no bound.  Any lower bound
+    /// makes [`build`](Self::build) read its input twice.
+    pub fn max_count(mut self, n: u8) -> Self {
+        self.max_count = n;
+        self
+    }
+
+    /// Size of the counting filter (default 2²⁴ one‑byte counters).
+    pub fn counters(mut self, n: usize) -> Self {
+        self.num_counters = n;
+        self
+    }
+
+    /// Size of the output filter (default 2²⁶ bits).
+    pub fn bits(mut self, n: usize) -> Self {
+        self.num_bits = n;
+        self
+    }
+
+    /// Count the k‑mers of `seqs` and collect the solid ones.  Sequences
+    /// shorter than `k` are skipped.
+    ///
+    /// `seqs` is cloned and iterated a second time only when
+    /// [`max_count`](Self::max_count) is bounded.
+    ///
+    /// # Errors
+    ///
+    /// [`NtHashError::InvalidK`] for `k == 0`,
+    /// [`NtHashError::InvalidNumHashes`] for zero hashes, and
+    /// [`NtHashError::InvalidCountRange`] unless
+    /// `1 ≤ min_count ≤ max_count`.
+    pub fn build<I>(self, seqs: I) -> Result<SolidKmers>
+    where
+        I: IntoIterator + Clone,
+        I::Item: AsRef<[u8]>,
+    {
+        if self.k == 0 {
+            return Err(NtHashError::InvalidK);
+        }
+        if self.num_hashes == 0 {
+            return Err(NtHashError::InvalidNumHashes);
+        }
+        if self.min_count == 0 || self.min_count > self.max_count {
+            return Err(NtHashError::InvalidCountRange {
+                min: self.min_count,
+                max: self.max_count,
+            });
+        }
+        let m = self.num_hashes as usize;
+        let mut counts = CountingBloomFilter::new(self.num_counters, m);
+        let mut solid = BloomFilter::new(self.num_bits, m);
+        if self.max_count == u8::MAX {
+            self.for_each_kmer(seqs, |hashes| {
+                if counts.insert(hashes) == self.min_count {
+                    solid.insert(hashes);
+                }
+            })?;
+        } else {
+            self.for_each_kmer(seqs.clone(), |hashes| {
+                counts.insert(hashes);
+            })?;
+            let range = self.min_count..=self.max_count;
+            self.for_each_kmer(seqs, |hashes| {
+                if range.contains(&counts.count(hashes)) {
+                    solid.insert(hashes);
+                }
+            })?;
+        }
+        Ok(SolidKmers {
+            k: self.k,
+            filter: solid,
+        })
+    }
+
+    fn for_each_kmer<I>(&self, seqs: I, mut f: impl FnMut(&[u64])) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        for seq in seqs {
+            let seq = seq.as_ref();
+            if seq.len() < self.k as usize {
+                continue;
+            }
+            let mut h = NtHash::new(seq, self.k, self.num_hashes, 0)?;
+            while h.roll() {
+                f(h.hashes());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The solid k‑mers of a read set, in a Bloom filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolidKmers {
+    k: u16,
+    filter: BloomFilter,
+}
+
+impl SolidKmers {
+    /// Whether the k‑mer with these hash values is (probably) solid.
+    pub fn is_solid(&self, hashes: &[u64]) -> bool {
+        self.filter.contains(hashes)
+    }
+
+    /// k‑mer length the filter was built for.
+    pub fn k(&self) -> u16 {
+        self.k
+    }
+
+    /// Borrow the underlying filter, e.g. for
+    /// [`scan_substitutions`](crate::bloom::scan_substitutions).
+    pub fn filter(&self) -> &BloomFilter {
+        &self.filter
+    }
+
+    /// Unwrap the underlying filter.
+    pub fn into_filter(self) -> BloomFilter {
+        self.filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_kmer;
+
+    #[test]
+    fn count_bounds_select_kmers() {
+        // GCATG ×1, ACGTA ×2, TTTTT ×5 (in one read), CCCCC ×3
+        let reads: Vec<&[u8]> = vec![
+            b"GCATG",
+            b"ACGTA",
+            b"NNACGTANN",
+            b"TTTTTTTTT",
+            b"CCCCC",
+            b"CCCCC",
+            b"CCCCC",
+            b"ACG",
+        ];
+        let solid = |min, max| {
+            let kmers = SolidKmersBuilder::new(5)
+                .min_count(min)
+                .max_count(max)
+                .counters(1 << 12)
+                .bits(1 << 14)
+                .build(&reads)
+                .unwrap();
+            ["GCATG", "ACGTA", "TTTTT", "CCCCC"]
+                .map(|kmer| kmers.is_solid(&hash_kmer(kmer.as_bytes(), 3).unwrap()))
+        };
+        assert_eq!(solid(1, 255), [true, true, true, true]);
+        assert_eq!(solid(2, 255), [false, true, true, true]);
+        assert_eq!(solid(2, 4), [false, true, false, true]);
+        assert_eq!(solid(1, 1), [true, false, false, false]);
+        assert_eq!(solid(3, 3), [false, false, false, true]);
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let reads: [&[u8]; 1] = [b"ACGTACGT"];
+        let build = |b: SolidKmersBuilder| b.counters(64).bits(64).build(reads).err();
+        assert_eq!(
+            build(SolidKmersBuilder::new(0)),
+            Some(NtHashError::InvalidK)
+        );
+        assert_eq!(
+            build(SolidKmersBuilder::new(4).num_hashes(0)),
+            Some(NtHashError::InvalidNumHashes)
+        );
+        assert_eq!(
+            build(SolidKmersBuilder::new(4).min_count(3).max_count(2)),
+            Some(NtHashError::InvalidCountRange { min: 3, max: 2 })
+        );
+        assert_eq!(
+            build(SolidKmersBuilder::new(4).min_count(0)),
+            Some(NtHashError::InvalidCountRange { min: 0, max: 255 })
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! **Throughput counters** and a benchmark‑safe bulk entry point.
+//!
+//! With the `stats` feature every [`NtHash`] counts the bases it reads,
+//! the windows it skips for containing a non‑ACGT base, and the hash values
+//! it computes.  The counts are plain per‑hasher additions, readable with
+//! [`NtHash::counters`], and are added to process‑wide [`totals`] when the
+//! hasher is dropped, so a pipeline can report its hashing work without
+//! wrapping any iterator:
+//!
+//! ```
+//! use nthash_rs::{stats, NtHashBuilder};
+//!
+//! let before = stats::totals();
+//! let kmers = NtHashBuilder::new(b"ACGTNACGTACG").k(4).into_iter().count();
+//! let work = stats::totals() - before;
+//! if stats::enabled() {
+//!     assert!(work.hashes >= kmers as u64); // other threads may add more
+//! } else {
+//!     assert_eq!(work, stats::Counters::default());
+//! }
+//! ```
+//!
+//! Without the feature the counters compile away and [`totals`] stays zero.
+//! With it each hasher pays a few register additions per k‑mer and one
+//! atomic addition per counter when dropped.
+//!
+//! [`digest`] hashes a whole sequence down to two integers.  Benchmarks
+//! can pass just that result to `black_box`: every hash value feeds the
+//! checksum, so none of the rolling can be optimised away, and no
+//! per‑k‑mer output is allocated or stored.
+
+use std::ops::Sub;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+use crate::{NtHash, Result};
+
+/// Work done by one or more hashers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Counters {
+    /// Bases read into a hash: `k` per (re)initialisation, one per roll.
+    pub bases: u64,
+    /// Windows passed over because they contain a non‑ACGT base.
+    pub skipped: u64,
+    /// Hash values computed, `num_hashes` per k‑mer.
+    pub hashes: u64,
+}
+
+impl Sub for Counters {
+    type Output = Self;
+
+    /// Work done between two snapshots (saturating, as [`reset`] may run
+    /// in between).
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            bases: self.bases.saturating_sub(rhs.bases),
+            skipped: self.skipped.saturating_sub(rhs.skipped),
+            hashes: self.hashes.saturating_sub(rhs.hashes),
+        }
+    }
+}
+
+static BASES: AtomicU64 = AtomicU64::new(0);
+static SKIPPED: AtomicU64 = AtomicU64::new(0);
+static HASHES: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the crate was built with the `stats` feature.
+pub const fn enabled() -> bool {
+    cfg!(feature = "stats")
+}
+
+/// Work of every hasher dropped so far in this process (all zero without
+/// the `stats` feature).
+pub fn totals() -> Counters {
+    Counters {
+        bases: BASES.load(Relaxed),
+        skipped: SKIPPED.load(Relaxed),
+        hashes: HASHES.load(Relaxed),
+    }
+}
+
+/// Zero the process‑wide [`totals`].
+pub fn reset() {
+    for total in [&BASES, &SKIPPED, &HASHES] {
+        total.store(0, Relaxed);
+    }
+}
+
+/// Per‑hasher counters; zero‑sized without the `stats` feature.
+///
+/// A clone starts from zero so the work before it is not reported twice.
+#[derive(Debug, Default)]
+pub(crate) struct Tally {
+    #[cfg(feature = "stats")]
+    counts: Counters,
+}
+
+impl Tally {
+    #[inline(always)]
+    pub(crate) fn bases(&mut self, _n: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.counts.bases += _n as u64;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn skipped(&mut self, _n: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.counts.skipped += _n as u64;
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn hashes(&mut self, _n: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.counts.hashes += _n as u64;
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn get(&self) -> Counters {
+        self.counts
+    }
+}
+
+impl Clone for Tally {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "stats")]
+impl Drop for Tally {
+    fn drop(&mut self) {
+        BASES.fetch_add(self.counts.bases, Relaxed);
+        SKIPPED.fetch_add(self.counts.skipped, Relaxed);
+        HASHES.fetch_add(self.counts.hashes, Relaxed);
+    }
+}
+
+/// Result of [`digest`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Digest {
+    /// Valid k‑mers hashed.
+    pub kmers: u64,
+    /// XOR of every hash value of every k‑mer.
+    pub checksum: u64,
+}
+
+/// Roll over all of `seq`, folding the `num_hashes` hashes of each valid
+/// `k`‑mer into a [`Digest`].
+///
+/// ```
+/// use nthash_rs::{stats::digest, NtHashBuilder};
+///
+/// let seq = b"ACGTTGCANACGTAC";
+/// let d = digest(seq, 5, 2).unwrap();
+/// let all: Vec<_> = NtHashBuilder::new(seq).k(5).num_hashes(2).into_iter().collect();
+/// assert_eq!(d.kmers, all.len() as u64);
+/// assert_eq!(d.checksum, all.iter().flat_map(|(_, h)| h).fold(0, |a, h| a ^ h));
+/// ```
+///
+/// # Errors
+///
+/// As [`NtHash::new`].
+pub fn digest(seq: &[u8], k: u16, num_hashes: u8) -> Result<Digest> {
+    let mut h = NtHash::new(seq, k, num_hashes, 0)?;
+    let mut d = Digest::default();
+    while h.roll() {
+        d.kmers += 1;
+        d.checksum = h.hashes().iter().fold(d.checksum, |acc, &x| acc ^ x);
+    }
+    Ok(d)
+}
+
+#[cfg(all(test, feature = "stats"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashers_count_their_work() {
+        // windows of 4 at 0..=8; the N at 5 rules out 2..=5
+        let seq = b"ACGTANCGTAAC";
+        let mut h = NtHash::new(seq, 4, 3, 0).unwrap();
+        while h.roll() {}
+        let emitted = 5;
+        assert_eq!(
+            h.counters(),
+            Counters {
+                bases: 4 + 1 + 4 + 2,
+                skipped: 4,
+                hashes: 3 * emitted,
+            }
+        );
+        assert_eq!(h.clone().counters(), Counters::default());
+
+        let before = totals();
+        drop(h);
+        let after = totals() - before;
+        assert!(after.bases >= 11 && after.skipped >= 4 && after.hashes >= 15);
+    }
+}
@@ -0,0 +1,128 @@
+//! **Bring‑your‑own storage** for filters and indexes.
+//!
+//! The probabilistic filters ([`bloom`](crate::bloom)) and indexes in this
+//! crate never allocate their backing arrays directly; they are generic over
+//! two small traits:
+//!
+//! - [`BitStorage`] — a fixed‑size bit array (Bloom filters);
+//! - [`Buckets`] — a fixed‑size array of `Copy` slots (counters, index
+//!   tables).
+//!
+//! Both are implemented for anything that derefs to a mutable slice of the
+//! right element type (`Vec<u64>`, `Box<[u8]>`, `&mut [u32]`, …), so memory
+//! obtained from `mmap`, shared memory, or a custom allocator can be used by
+//! handing over a slice view of it.  Types with other layouts (compressed,
+//! remote, …) simply implement the trait themselves.
+//!
+//! ```
+//! use nthash_rs::bloom::BloomFilter;
+//!
+//! // e.g. a region of a memory‑mapped file, viewed as words
+//! let mut region = vec![0u64; 16];
+//! let mut bf = BloomFilter::with_storage(&mut region[..], 3).unwrap();
+//! bf.insert(&[1, 2, 3]);
+//! assert!(bf.contains(&[1, 2, 3]));
+//! ```
+
+/// A fixed‑size array of bits.
+pub trait BitStorage {
+    /// Number of addressable bits.
+    fn num_bits(&self) -> usize;
+
+    /// Value of bit `i` (`i < num_bits()`).
+    fn get_bit(&self, i: usize) -> bool;
+
+    /// Set bit `i`, returning its previous value.
+    fn set_bit(&mut self, i: usize) -> bool;
+
+    /// Number of set bits.
+    fn count_ones(&self) -> usize {
+        (0..self.num_bits()).filter(|&i| self.get_bit(i)).count()
+    }
+}
+
+impl<S: AsRef<[u64]> + AsMut<[u64]> + ?Sized> BitStorage for S {
+    #[inline]
+    fn num_bits(&self) -> usize {
+        self.as_ref().len() * 64
+    }
+
+    #[inline]
+    fn get_bit(&self, i: usize) -> bool {
+        self.as_ref()[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    #[inline]
+    fn set_bit(&mut self, i: usize) -> bool {
+        let word = &mut self.as_mut()[i / 64];
+        let mask = 1u64 << (i % 64);
+        let old = *word & mask != 0;
+        *word |= mask;
+        old
+    }
+
+    fn count_ones(&self) -> usize {
+        self.as_ref().iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+/// A fixed‑size array of `T` slots.
+pub trait Buckets<T: Copy> {
+    /// Number of slots.
+    fn num_buckets(&self) -> usize;
+
+    /// Value of slot `i` (`i < num_buckets()`).
+    fn get(&self, i: usize) -> T;
+
+    /// Overwrite slot `i`.
+    fn set(&mut self, i: usize, value: T);
+}
+
+impl<T: Copy, S: AsRef<[T]> + AsMut<[T]> + ?Sized> Buckets<T> for S {
+    #[inline]
+    fn num_buckets(&self) -> usize {
+        self.as_ref().len()
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> T {
+        self.as_ref()[i]
+    }
+
+    #[inline]
+    fn set(&mut self, i: usize, value: T) {
+        self.as_mut()[i] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bits kept in a `Vec<bool>`: a custom layout implementing the trait.
+    struct BoolBits(Vec<bool>);
+
+    impl BitStorage for BoolBits {
+        fn num_bits(&self) -> usize {
+            self.0.len()
+        }
+        fn get_bit(&self, i: usize) -> bool {
+            self.0[i]
+        }
+        fn set_bit(&mut self, i: usize) -> bool {
+            std::mem::replace(&mut self.0[i], true)
+        }
+    }
+
+    #[test]
+    fn word_and_custom_storage_agree() {
+        let mut words = vec![0u64; 2];
+        let mut bools = BoolBits(vec![false; 128]);
+        for i in [0, 5, 63, 64, 100, 5] {
+            assert_eq!(words.set_bit(i), bools.set_bit(i));
+        }
+        assert_eq!(words.count_ones(), 5);
+        assert_eq!(bools.count_ones(), 5);
+        assert!((0..128).all(|i| words.get_bit(i) == bools.get_bit(i)));
+    }
+}
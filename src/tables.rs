@@ -10,7 +10,11 @@
 //! All functions are marked `#[inline(always)]` for maximum performance — each
 //! compiles down to a handful of shifts, masks, and table lookups.
 
-use crate::constants::{MS_TAB_31L, MS_TAB_33R};
+use crate::constants::{
+    CONVERT_TAB, CP_OFF, DIMER_TAB, MS_TAB_31L, MS_TAB_33R, RC_CONVERT_TAB, SEED_A, SEED_C, SEED_G,
+    SEED_T, SEED_TAB, TETRAMER_TAB, TRIMER_TAB,
+};
+use crate::{NtHashError, Result};
 
 /// One‑bit split‑rotate left (33 + 31 bit halves).
 ///
@@ -32,12 +36,26 @@ pub const fn srol(x: u64) -> u64 {
     ((x << 1) & 0xFFFF_FFFD_FFFF_FFFF) | m
 }
 
-/// Arbitrary‑distance split‑rotate left (0 ≤ d < 64).
+/// Multi‑bit split‑rotate left, equal to `d` applications of [`srol`] for
+/// `0 ≤ d ≤ 31`.
 ///
-/// This implements `d` repeated one‑bit split‑rotates efficiently:
+/// This implements the repeated one‑bit split‑rotates efficiently:
 /// 1. Perform a full 64‑bit rotate left by `d`.
 /// 2. "Unscramble" any bits that crossed the 33/31 boundary to match
 ///    the effect of split‑rotating each half independently.
+///
+/// The correction only accounts for bits wrapping once, so for `d ≥ 32`
+/// the result differs from `d` one‑bit rotates; it is kept bit‑identical to
+/// the reference implementation's `srol_n` over the whole `0 ≤ d < 64`
+/// range.  [`sror_n`] undoes it for `d ≤ 31`.
+///
+/// ```
+/// use nthash_rs::{srol, srol_n, sror_n};
+///
+/// let x = 0x0123_4567_89AB_CDEF;
+/// assert_eq!(srol_n(x, 3), srol(srol(srol(x))));
+/// assert_eq!(sror_n(srol_n(x, 17), 17), x);
+/// ```
 #[inline(always)]
 pub const fn srol_n(x: u64, d: u32) -> u64 {
     if d == 0 {
@@ -64,6 +82,35 @@ pub const fn sror(x: u64) -> u64 {
     ((x >> 1) & 0xFFFF_FFFE_FFFF_FFFF) | m
 }
 
+/// Multi‑bit split‑rotate right: exactly `d` applications of [`sror`],
+/// for any `d`.
+///
+/// Each half is rotated right on its own — the 33‑bit half holding bits
+/// 0–32 by `d mod 33`, the 31‑bit half holding bits 33–63 by `d mod 31` —
+/// so this is the inverse of [`srol_n`] for `d ≤ 31` and of `d`
+/// one‑bit [`srol`]s for any `d`, e.g. to undo a rotation applied by a
+/// custom (gapped, spaced) update rule.
+#[inline(always)]
+pub const fn sror_n(x: u64, d: u32) -> u64 {
+    const LO_BITS: u32 = 33;
+    const HI_BITS: u32 = 31;
+    const LO_MASK: u64 = (1 << LO_BITS) - 1;
+    let lo = x & LO_MASK;
+    let hi = x >> LO_BITS;
+    let (dl, dh) = (d % LO_BITS, d % HI_BITS);
+    let lo = if dl == 0 {
+        lo
+    } else {
+        ((lo >> dl) | (lo << (LO_BITS - dl))) & LO_MASK
+    };
+    let hi = if dh == 0 {
+        hi
+    } else {
+        ((hi >> dh) | (hi << (HI_BITS - dh))) & (LO_MASK >> 2)
+    };
+    hi << LO_BITS | lo
+}
+
 /// Lookup‑based split‑rotate left.
 ///
 /// Applies a split‑rotate of distance `d` to the 64‑bit seed constant for
@@ -79,6 +126,205 @@ pub fn srol_table(c: u8, d: u32) -> u64 {
     MS_TAB_31L[c as usize][idx31] | MS_TAB_33R[c as usize][idx33]
 }
 
+// ──────────────────────────────────────────────────────────────
+// Table consistency checks
+// --------------------------------------------------------------------------
+//
+// Every table in `constants` is derivable from the four base seeds.  The
+// checks below are `const fn`s so the built‑in tables are verified at compile
+// time; `verify_tables` runs the same checks on demand.
+
+/// Base seeds indexed by nucleotide code (A=0, C=1, G=2, T=3).
+const SEEDS: [u64; 4] = [SEED_A, SEED_C, SEED_G, SEED_T];
+/// ASCII bases the tables must agree on.
+const BASES: &[u8; 8] = b"ACGTacgt";
+
+/// First ASCII code whose `SEED_TAB` entry (or that of its `CP_OFF`
+/// complement index) disagrees with the base seeds.
+const fn seed_tab_mismatch() -> Option<usize> {
+    let mut i = 0;
+    while i < BASES.len() {
+        let b = BASES[i];
+        let code = CONVERT_TAB[b as usize] as usize;
+        if SEED_TAB[b as usize] != SEEDS[code] {
+            return Some(b as usize);
+        }
+        if SEED_TAB[(b & CP_OFF) as usize] != SEEDS[3 - code] {
+            return Some((b & CP_OFF) as usize);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// First ASCII code where `RC_CONVERT_TAB` is not the complement of `CONVERT_TAB`.
+const fn convert_tab_mismatch() -> Option<usize> {
+    let mut i = 0;
+    while i < CONVERT_TAB.len() {
+        let (c, rc) = (CONVERT_TAB[i], RC_CONVERT_TAB[i]);
+        let ok = if c <= 3 { rc == 3 - c } else { rc == c };
+        if !ok {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// First `code * 64 + d` for which the split‑rotate tables disagree with
+/// `d` successive one‑bit split‑rotates of the seed, checked for every base
+/// and its complement index and every `d < 64`.
+const fn ms_tab_mismatch() -> Option<usize> {
+    let mut i = 0;
+    while i < 2 * BASES.len() {
+        let b = if i < BASES.len() {
+            BASES[i]
+        } else {
+            BASES[i - BASES.len()] & CP_OFF
+        } as usize;
+        let mut x = SEED_TAB[b];
+        let mut d = 0;
+        while d < 64 {
+            if MS_TAB_31L[b][d % 31] | MS_TAB_33R[b][d % 33] != x {
+                return Some(b * 64 + d);
+            }
+            x = srol(x);
+            d += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+/// First index of `tab` that is not the forward hash of the `len`‑mer it
+/// encodes (base codes as big‑endian base‑4 digits).
+const fn kmer_tab_mismatch(tab: &[u64], len: u32) -> Option<usize> {
+    let mut idx = 0;
+    while idx < tab.len() {
+        let mut h = 0u64;
+        let mut i = 0;
+        while i < len {
+            let code = (idx >> (2 * (len - 1 - i))) & 3;
+            h ^= srol_n(SEEDS[code], len - 1 - i);
+            i += 1;
+        }
+        if tab[idx] != h {
+            return Some(idx);
+        }
+        idx += 1;
+    }
+    None
+}
+
+const _: () = assert!(seed_tab_mismatch().is_none(), "SEED_TAB inconsistent");
+const _: () = assert!(convert_tab_mismatch().is_none(), "CONVERT_TAB inconsistent");
+const _: () = assert!(ms_tab_mismatch().is_none(), "MS_TAB inconsistent");
+const _: () = assert!(
+    kmer_tab_mismatch(&DIMER_TAB, 2).is_none(),
+    "DIMER_TAB inconsistent"
+);
+const _: () = assert!(
+    kmer_tab_mismatch(&TRIMER_TAB, 3).is_none(),
+    "TRIMER_TAB inconsistent"
+);
+const _: () = assert!(
+    kmer_tab_mismatch(&TETRAMER_TAB, 4).is_none(),
+    "TETRAMER_TAB inconsistent"
+);
+
+/// Check that the seed, conversion, split‑rotate, and pre‑hashed k‑mer
+/// tables are mutually consistent.
+///
+/// The built‑in tables are already checked at compile time; this exposes the
+/// same checks at runtime, e.g. for test suites guarding edits to
+/// `constants`.
+///
+/// # Errors
+///
+/// Returns [`NtHashError::TableMismatch`] naming the first inconsistent table
+/// and the offending index.
+pub fn verify_tables() -> Result<()> {
+    let checks = [
+        ("SEED_TAB", seed_tab_mismatch()),
+        ("RC_CONVERT_TAB", convert_tab_mismatch()),
+        ("MS_TAB", ms_tab_mismatch()),
+        ("DIMER_TAB", kmer_tab_mismatch(&DIMER_TAB, 2)),
+        ("TRIMER_TAB", kmer_tab_mismatch(&TRIMER_TAB, 3)),
+        ("TETRAMER_TAB", kmer_tab_mismatch(&TETRAMER_TAB, 4)),
+    ];
+    for (table, mismatch) in checks {
+        if let Some(index) = mismatch {
+            return Err(NtHashError::TableMismatch { table, index });
+        }
+    }
+    Ok(())
+}
+
+// --------------------------------------------------------------------------
+// Runtime table generation
+// --------------------------------------------------------------------------
+
+/// Split‑rotate tables for five symbol seeds, laid out like the built‑in
+/// `MS_TAB_31L` / `MS_TAB_33R` entries for A, C, G, T, N.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsTabs {
+    /// Bits 33–63 of the seed after `d` one‑bit split‑rotates, `d < 31`.
+    pub l31: [[u64; 31]; 5],
+    /// Bits 0–32 of the seed after `d` one‑bit split‑rotates, `d < 33`.
+    pub r33: [[u64; 33]; 5],
+}
+
+impl MsTabs {
+    /// Seed of `symbol` (0–4) after `d` one‑bit split‑rotates, for any `d`;
+    /// the counterpart of [`srol_table`].
+    #[inline(always)]
+    pub const fn srol(&self, symbol: usize, d: u32) -> u64 {
+        self.l31[symbol][(d % 31) as usize] | self.r33[symbol][(d % 33) as usize]
+    }
+}
+
+/// Generate split‑rotate tables for custom `seeds` (e.g. salted seeds or a
+/// non‑ACGT alphabet), compatible with the built‑in ones.
+///
+/// This is a `const fn`, so tables can be baked in at compile time without
+/// a build script:
+///
+/// ```
+/// use nthash_rs::{build_ms_tabs, constants::SEED_A, seed_for, srol_table, MsTabs};
+///
+/// // seeds XOR‑salted at compile time; N stays zero
+/// const SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+/// const SALTED: MsTabs = build_ms_tabs([SEED_A ^ SALT, 2, 3, 4, 0]);
+/// assert_eq!(SALTED.srol(0, 1), nthash_rs::srol(SEED_A ^ SALT));
+/// assert_eq!(SALTED.srol(4, 17), 0);
+///
+/// let builtin = build_ms_tabs([b'A', b'C', b'G', b'T', b'N'].map(seed_for));
+/// assert_eq!(builtin.srol(0, 45), srol_table(b'A', 45));
+/// assert_eq!(seed_for(b'a'), SEED_A);
+/// ```
+pub const fn build_ms_tabs(seeds: [u64; 5]) -> MsTabs {
+    const LO_MASK: u64 = (1 << 33) - 1;
+    let mut tabs = MsTabs {
+        l31: [[0; 31]; 5],
+        r33: [[0; 33]; 5],
+    };
+    let mut s = 0;
+    while s < 5 {
+        let mut x = seeds[s];
+        let mut d = 0;
+        while d < 33 {
+            if d < 31 {
+                tabs.l31[s][d] = x & !LO_MASK;
+            }
+            tabs.r33[s][d] = x & LO_MASK;
+            x = srol(x);
+            d += 1;
+        }
+        s += 1;
+    }
+    tabs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,6 +417,44 @@ mod tests {
         assert_eq!(srol_n(0x0000_0000_FFFF_FFFF, 32), 0x0000_0001_7FFF_FFFF);
     }
 
+    #[test]
+    fn generated_tables_match_builtin() {
+        use crate::constants::*;
+        let tabs = build_ms_tabs([b'A', b'C', b'G', b'T', b'N'].map(seed_for));
+        assert_eq!(tabs.l31, [A31L, C31L, G31L, T31L, N31L]);
+        assert_eq!(tabs.r33, [A33R, C33R, G33R, T33R, N33R]);
+        for d in 0..100 {
+            assert_eq!(tabs.srol(3, d), srol_table(b't', d));
+        }
+        assert_eq!(seed_for(b'N'), SEED_N);
+        assert_eq!(seed_for(b'g'), SEED_G);
+    }
+
+    #[test]
+    fn sror_n_inverts_srol_n() {
+        let mut x = 0x0123_4567_89AB_CDEFu64;
+        for _ in 0..64 {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            for d in 0..32 {
+                assert_eq!(sror_n(srol_n(x, d), d), x, "d={d}");
+                assert_eq!(srol_n(sror_n(x, d), d), x, "d={d}");
+            }
+            // beyond 31, sror_n still matches repeated one‑bit rotates
+            let (mut l, mut r) = (x, x);
+            for d in 0..=96 {
+                assert_eq!(sror_n(x, d), r, "d={d}");
+                assert_eq!(sror_n(l, d), x, "d={d}");
+                l = srol(l);
+                r = sror(r);
+            }
+        }
+        assert_eq!(sror_n(0x0000_0000_0000_0001, 1), sror(1));
+        assert_eq!(sror_n(0x8000_0000_0000_0000, 31), 0x8000_0000_0000_0000);
+        assert_eq!(sror_n(0x0000_0001_0000_0000, 33), 0x0000_0001_0000_0000);
+    }
+
     #[test]
     fn sror_boundaries() {
         // Case 1: all zeros → zero
@@ -218,15 +502,15 @@ mod tests {
         //   64  (large)     – large rotation to force multi‑wrap behavior
 
         // PICT-generated (c, d) → expected
-        assert_eq!(srol_table(0, 0),  0x0000_0000_0000_0000);
+        assert_eq!(srol_table(0, 0), 0x0000_0000_0000_0000);
         assert_eq!(srol_table(3, 32), 0x4064_7DA0_412B_9192);
-        assert_eq!(srol_table(4, 0),  0x3C8B_FBB3_95C6_0474);
-        assert_eq!(srol_table(1, 0),  0x2955_49F5_4BE2_4456);
-        assert_eq!(srol_table(7, 1),  0x6327_8308_C540_5699);
+        assert_eq!(srol_table(4, 0), 0x3C8B_FBB3_95C6_0474);
+        assert_eq!(srol_table(1, 0), 0x2955_49F5_4BE2_4456);
+        assert_eq!(srol_table(7, 1), 0x6327_8308_C540_5699);
         assert_eq!(srol_table(1, 33), 0xA555_27D1_4BE2_4456);
         assert_eq!(srol_table(4, 33), 0xF22F_EEC9_95C6_0474);
         assert_eq!(srol_table(4, 30), 0x9E45_FDD9_32B8_C08E);
-        assert_eq!(srol_table(0, 1),  0x0000_0000_0000_0000);
+        assert_eq!(srol_table(0, 1), 0x0000_0000_0000_0000);
         assert_eq!(srol_table(0, 31), 0x0000_0000_0000_0000);
         assert_eq!(srol_table(7, 33), 0xC64F_0611_62A0_2B4C);
         assert_eq!(srol_table(1, 64), 0xA555_27D1_52F8_9115);
@@ -234,21 +518,21 @@ mod tests {
         assert_eq!(srol_table(0, 32), 0x0000_0000_0000_0000);
         assert_eq!(srol_table(3, 64), 0x80C8_FB40_2095_C8C9);
         assert_eq!(srol_table(7, 30), 0x18C9_E0C3_2C54_0569);
-        assert_eq!(srol_table(7, 0),  0x3193_C185_62A0_2B4C);
-        assert_eq!(srol_table(1, 1),  0x52AA_93E8_97C4_88AD);
-        assert_eq!(srol_table(3, 0),  0x2032_3ED0_8257_2324);
+        assert_eq!(srol_table(7, 0), 0x3193_C185_62A0_2B4C);
+        assert_eq!(srol_table(1, 1), 0x52AA_93E8_97C4_88AD);
+        assert_eq!(srol_table(3, 0), 0x2032_3ED0_8257_2324);
         assert_eq!(srol_table(4, 64), 0xF22F_EEC8_6571_811D);
         assert_eq!(srol_table(4, 31), 0x3C8B_FBB2_6571_811D);
         assert_eq!(srol_table(3, 33), 0x80C8_FB40_8257_2324);
         assert_eq!(srol_table(0, 30), 0x0000_0000_0000_0000);
         assert_eq!(srol_table(0, 64), 0x0000_0000_0000_0000);
         assert_eq!(srol_table(7, 31), 0x3193_C184_58A8_0AD3);
-        assert_eq!(srol_table(4, 1),  0x7917_F765_2B8C_08E9);
+        assert_eq!(srol_table(4, 1), 0x7917_F765_2B8C_08E9);
         assert_eq!(srol_table(3, 31), 0x2032_3ED0_2095_C8C9);
         assert_eq!(srol_table(1, 30), 0x14AA_A4FB_A97C_488A);
         assert_eq!(srol_table(1, 32), 0x52AA_93E8_A5F1_222B);
         assert_eq!(srol_table(7, 32), 0x6327_8308_B150_15A6);
-        assert_eq!(srol_table(3, 1),  0x4064_7DA1_04AE_4648);
+        assert_eq!(srol_table(3, 1), 0x4064_7DA1_04AE_4648);
         assert_eq!(srol_table(7, 64), 0xC64F_0610_58A8_0AD3);
         assert_eq!(srol_table(4, 32), 0x7917_F764_CAE3_023A);
         assert_eq!(srol_table(3, 30), 0x1019_1F69_104A_E464);
@@ -265,4 +549,16 @@ mod tests {
         }
         assert_eq!(x, 0xDEADBEEF_DEADBEEF);
     }
+
+    #[test]
+    fn builtin_tables_are_consistent() {
+        assert_eq!(verify_tables(), Ok(()));
+    }
+
+    #[test]
+    fn kmer_table_check_detects_corruption() {
+        let mut tab = TRIMER_TAB;
+        tab[37] ^= 1 << 40;
+        assert_eq!(kmer_tab_mismatch(&tab, 3), Some(37));
+    }
 }
@@ -0,0 +1,272 @@
+//! **Genome‑browser track export** for per‑position hash signals.
+//!
+//! Hash‑derived signals such as filter hits, k‑mer uniqueness, or minimizer
+//! density are naturally produced as `(pos, value)` pairs by the iterators in
+//! this crate.  [`TrackWriter`] turns such streams into the three plain‑text
+//! formats understood by every genome browser (IGV, UCSC, JBrowse):
+//!
+//! - **BED9** — boolean hits merged into intervals, coloured via `itemRgb`.
+//! - **bedGraph** — numeric signal, runs of equal values merged.
+//! - **wiggle** — numeric signal in `fixedStep` blocks.
+//!
+//! Positions are 0‑based k‑mer start offsets, i.e. the
+//! [`KmerPos::start`](crate::kmer::KmerPos::start) of each k‑mer the
+//! hashers yield; the 1‑based wiggle convention is handled internally.
+//! Input must be sorted by position.
+
+use std::fmt;
+use std::io::{self, Write};
+
+/// An `itemRgb` / track colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// Linear interpolation between `self` (t = 0) and `other` (t = 1).
+    pub fn lerp(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.0, self.1, self.2)
+    }
+}
+
+/// Writes hash‑derived signals for one chromosome / contig as browser tracks.
+///
+/// ```
+/// use nthash_rs::track::TrackWriter;
+///
+/// let mut out = Vec::new();
+/// let mut w = TrackWriter::new(&mut out, "chr1").name("hits");
+/// w.bed([(0, true), (1, true), (5, false), (9, true)], 4).unwrap();
+/// let text = String::from_utf8(out).unwrap();
+/// assert!(text.contains("chr1\t0\t5\t"));
+/// ```
+pub struct TrackWriter<W: Write> {
+    out: W,
+    chrom: String,
+    name: String,
+    color: Rgb,
+    low_color: Rgb,
+}
+
+impl<W: Write> TrackWriter<W> {
+    /// Create a writer emitting records for `chrom`.
+    pub fn new(out: W, chrom: impl Into<String>) -> Self {
+        Self {
+            out,
+            chrom: chrom.into(),
+            name: "nthash".to_string(),
+            color: Rgb(0, 0, 200),
+            low_color: Rgb(200, 200, 255),
+        }
+    }
+
+    /// Set the track name written in the `track` header line.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the colour used for the densest BED intervals and the track line.
+    pub fn color(mut self, color: Rgb) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the colour used for the sparsest BED intervals.
+    pub fn low_color(mut self, color: Rgb) -> Self {
+        self.low_color = color;
+        self
+    }
+
+    /// Write boolean per‑k‑mer hits as BED9 intervals.
+    ///
+    /// Overlapping or adjacent hit k‑mers (each spanning `k` bases) are merged
+    /// into one interval.  The score is the fraction of k‑mer starts inside
+    /// the interval that were hits (scaled to 0–1000), and `itemRgb`
+    /// interpolates from the low colour to the track colour accordingly.
+    pub fn bed<I>(&mut self, hits: I, k: usize) -> io::Result<()>
+    where
+        I: IntoIterator<Item = (usize, bool)>,
+    {
+        writeln!(
+            self.out,
+            "track name=\"{}\" itemRgb=\"On\" color={}",
+            self.name, self.color
+        )?;
+        // (start, end, first hit pos, last hit pos, hit count)
+        let mut cur: Option<(usize, usize, usize, usize, usize)> = None;
+        let mut idx = 0usize;
+        for (pos, hit) in hits {
+            if !hit {
+                continue;
+            }
+            match cur.as_mut() {
+                Some(c) if pos <= c.1 => {
+                    c.1 = c.1.max(pos + k);
+                    c.3 = pos;
+                    c.4 += 1;
+                }
+                _ => {
+                    if let Some(c) = cur.take() {
+                        self.bed_record(c, idx)?;
+                        idx += 1;
+                    }
+                    cur = Some((pos, pos + k, pos, pos, 1));
+                }
+            }
+        }
+        if let Some(c) = cur {
+            self.bed_record(c, idx)?;
+        }
+        Ok(())
+    }
+
+    fn bed_record(
+        &mut self,
+        (start, end, first, last, count): (usize, usize, usize, usize, usize),
+        idx: usize,
+    ) -> io::Result<()> {
+        let density = count as f64 / (last - first + 1) as f64;
+        let score = (density * 1000.0).round() as u32;
+        let rgb = self.low_color.lerp(self.color, density);
+        writeln!(
+            self.out,
+            "{}\t{start}\t{end}\t{}_{idx}\t{score}\t.\t{start}\t{end}\t{rgb}",
+            self.chrom, self.name
+        )
+    }
+
+    /// Write a numeric per‑position signal as bedGraph.
+    ///
+    /// Each value covers one base at its position; consecutive positions with
+    /// equal values are merged into a single record.
+    pub fn bedgraph<I>(&mut self, signal: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = (usize, f64)>,
+    {
+        writeln!(
+            self.out,
+            "track type=bedGraph name=\"{}\" color={}",
+            self.name, self.color
+        )?;
+        let mut run: Option<(usize, usize, f64)> = None;
+        for (pos, value) in signal {
+            match run.as_mut() {
+                Some(r) if r.1 == pos && r.2 == value => r.1 += 1,
+                _ => {
+                    if let Some((s, e, v)) = run.take() {
+                        writeln!(self.out, "{}\t{s}\t{e}\t{v}", self.chrom)?;
+                    }
+                    run = Some((pos, pos + 1, value));
+                }
+            }
+        }
+        if let Some((s, e, v)) = run {
+            writeln!(self.out, "{}\t{s}\t{e}\t{v}", self.chrom)?;
+        }
+        Ok(())
+    }
+
+    /// Write a numeric per‑position signal as `fixedStep` wiggle.
+    ///
+    /// A new `fixedStep` block is started whenever positions are not
+    /// consecutive (e.g. across skipped `N` windows).
+    pub fn wiggle<I>(&mut self, signal: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = (usize, f64)>,
+    {
+        writeln!(
+            self.out,
+            "track type=wiggle_0 name=\"{}\" color={}",
+            self.name, self.color
+        )?;
+        let mut next: Option<usize> = None;
+        for (pos, value) in signal {
+            if next != Some(pos) {
+                writeln!(
+                    self.out,
+                    "fixedStep chrom={} start={} step=1",
+                    self.chrom,
+                    pos + 1
+                )?;
+            }
+            writeln!(self.out, "{value}")?;
+            next = Some(pos + 1);
+        }
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(f: impl FnOnce(&mut TrackWriter<&mut Vec<u8>>)) -> Vec<String> {
+        let mut buf = Vec::new();
+        let mut w = TrackWriter::new(&mut buf, "chr2").name("t");
+        f(&mut w);
+        String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn bed_merges_overlapping_hits() {
+        let lines = render(|w| {
+            let hits = [(0, true), (2, true), (3, false), (4, true), (20, true)];
+            w.bed(hits, 3).unwrap();
+        });
+        assert_eq!(lines.len(), 3);
+        // 0..3, 2..5 and 4..7 overlap; 3 of the 5 starts in 0..=4 are hits
+        assert_eq!(lines[1], "chr2\t0\t7\tt_0\t600\t.\t0\t7\t80,80,222");
+        assert_eq!(lines[2], "chr2\t20\t23\tt_1\t1000\t.\t20\t23\t0,0,200");
+    }
+
+    #[test]
+    fn bedgraph_merges_equal_runs() {
+        let lines = render(|w| {
+            w.bedgraph([(0, 1.0), (1, 1.0), (2, 0.5), (5, 0.5)])
+                .unwrap();
+        });
+        assert_eq!(
+            &lines[1..],
+            ["chr2\t0\t2\t1", "chr2\t2\t3\t0.5", "chr2\t5\t6\t0.5"]
+        );
+    }
+
+    #[test]
+    fn wiggle_restarts_after_gaps() {
+        let lines = render(|w| {
+            w.wiggle([(0, 1.0), (1, 2.0), (7, 3.0)]).unwrap();
+        });
+        assert_eq!(
+            &lines[1..],
+            [
+                "fixedStep chrom=chr2 start=1 step=1",
+                "1",
+                "2",
+                "fixedStep chrom=chr2 start=8 step=1",
+                "3"
+            ]
+        );
+    }
+}
@@ -5,14 +5,27 @@
 //!
 //! - **`canonical`** — combine forward and reverse‐complement hashes into a
 //!   strand‐independent value by wrapping addition.
+//! - **`canonical_min`** / **`canonical_xor`** and **`CanonicalPolicy`** —
+//!   the alternative combinations some other tools use, selectable on the
+//!   builders.
+//!
 //! - **`extend_hashes`** — generate a sequence of "extra" hash values from
 //!   one canonical base hash, matching the C++ reference’s multiplicative
 //!   mixing and shift scheme.
 //!
+//! - **`HashMixer`** — the scheme deriving those extra values, pluggable
+//!   into every hasher: the reference one, an xxHash‑style finalizer, or
+//!   a custom implementation.
+//!
+//! - **`salt_hash`** — re‑key a hash with a 64‑bit salt so several hashers
+//!   over the same input produce independent streams.
+//!
 //! These functions are marked `#[inline]` for zero‐overhead calls in hot paths,
 //! and the code is dependency‐free (only `core`/`std`), so it can be used
 //! in no‐std contexts if needed.
 
+use std::fmt;
+
 use crate::constants::{MULTISEED, MULTISHIFT};
 
 /// Combine forward and reverse‐complement strand hashes into a single
@@ -35,6 +48,90 @@ pub const fn canonical(fwd: u64, rev: u64) -> u64 {
     fwd.wrapping_add(rev)
 }
 
+/// Canonical hash as the smaller of the two strand hashes.
+///
+/// ```
+/// # use nthash_rs::util::canonical_min;
+/// assert_eq!(canonical_min(7, 3), 3);
+/// ```
+#[inline(always)]
+pub const fn canonical_min(fwd: u64, rev: u64) -> u64 {
+    if fwd < rev {
+        fwd
+    } else {
+        rev
+    }
+}
+
+/// Canonical hash as the XOR of the two strand hashes.
+///
+/// ```
+/// # use nthash_rs::util::canonical_xor;
+/// assert_eq!(canonical_xor(0b1100, 0b1010), 0b0110);
+/// ```
+#[inline(always)]
+pub const fn canonical_xor(fwd: u64, rev: u64) -> u64 {
+    fwd ^ rev
+}
+
+/// How the builders combine a k‑mer's strand hashes into its canonical
+/// hash, the first value of every hash buffer and the base of the extra
+/// ones.
+///
+/// All three are strand‑independent; they differ in what other tools
+/// expect:
+///
+/// - [`Sum`](Self::Sum) (the default) is the ntHash definition, so output
+///   matches the C++ library and every other part of this crate.
+/// - [`Min`](Self::Min) matches tools keeping the smaller strand hash.  It
+///   is not uniform (the minimum of two values skews low), so thresholds
+///   tuned on uniform hashes, such as a FracMinHash scale, select more
+///   k‑mers than intended.
+/// - [`Xor`](Self::Xor) matches tools XOR‑ing the strands.  A
+///   reverse‑complement palindrome has equal strand hashes and so hashes
+///   to `0`, like every other palindrome.
+///
+/// Policies only change the combination: filters, sketches, and indexes
+/// must be built and queried under the same one.
+///
+/// ```
+/// use nthash_rs::{util::CanonicalPolicy, NtHash, NtHashBuilder};
+///
+/// let first = |policy| {
+///     let mut it = NtHashBuilder::new(b"ACGTTGCA").k(4).canonical_policy(policy).into_iter();
+///     it.next().unwrap().1[0]
+/// };
+/// let mut h = NtHash::new(b"ACGT", 4, 1, 0).unwrap();
+/// assert!(h.roll());
+/// let (fwd, rev) = (h.forward_hash(), h.reverse_hash());
+/// assert_eq!(first(CanonicalPolicy::Sum), fwd.wrapping_add(rev));
+/// assert_eq!(first(CanonicalPolicy::Min), fwd.min(rev));
+/// assert_eq!(first(CanonicalPolicy::Xor), 0); // ACGT is its own reverse complement
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CanonicalPolicy {
+    /// Wrapping sum, as [`canonical`].
+    #[default]
+    Sum,
+    /// Smaller strand hash, as [`canonical_min`].
+    Min,
+    /// XOR of the strand hashes, as [`canonical_xor`].
+    Xor,
+}
+
+impl CanonicalPolicy {
+    /// Combine `fwd` and `rev` under this policy.
+    #[inline(always)]
+    pub const fn combine(self, fwd: u64, rev: u64) -> u64 {
+        match self {
+            Self::Sum => canonical(fwd, rev),
+            Self::Min => canonical_min(fwd, rev),
+            Self::Xor => canonical_xor(fwd, rev),
+        }
+    }
+}
+
 /// Expand a single canonical hash into a user‐provided slice of additional
 /// hash values.
 ///
@@ -50,7 +147,7 @@ pub const fn canonical(fwd: u64, rev: u64) -> u64 {
 /// - `fwd`, `rev`  — forward and reverse‐complement strand hashes.
 /// - `k`           — k‑mer span or seed weight, used in the mixing step.
 /// - `hashes`      — output slice; the length determines how many values
-///                   (including the canonical hash at index 0) are generated.
+///   (including the canonical hash at index 0) are generated.
 ///
 /// If `hashes` is empty, this function returns immediately, avoiding any
 /// unnecessary branching in callers.
@@ -90,6 +187,174 @@ pub fn extend_hashes(fwd: u64, rev: u64, k: u32, hashes: &mut [u64]) {
     }
 }
 
+/// [`extend_hashes`] into a fresh `[u64; M]`, for callers that know the
+/// hash count at compile time and want the values on the stack.
+///
+/// [`extend_hashes`] itself fills any `&mut [u64]`, so a `SmallVec` or an
+/// existing array can be passed to it directly.  `M` is not limited to the
+/// 255 hashes a hasher's `num_hashes` allows: index `i` enters the mixing
+/// as a full 64‑bit value, so it never wraps.
+///
+/// ```
+/// use nthash_rs::{extend_hashes, util::extend_hashes_array};
+///
+/// let fixed = extend_hashes_array::<10>(0x1234, 0x5678, 21);
+/// let mut buf = vec![0; 10];
+/// extend_hashes(0x1234, 0x5678, 21, &mut buf);
+/// assert_eq!(fixed[..], buf[..]);
+/// ```
+#[inline]
+pub fn extend_hashes_array<const M: usize>(fwd: u64, rev: u64, k: u32) -> [u64; M] {
+    let mut out = [0; M];
+    extend_hashes(fwd, rev, k, &mut out);
+    out
+}
+
+/// Derives a k‑mer's extra hash values from its canonical hash.
+///
+/// Every hasher uses [`ReferenceMixer`] (the [`extend_hashes`] scheme)
+/// unless given another mixer with its `with_mixer` method or its
+/// builder's `mixer` option.  The reference scheme keeps outputs identical
+/// to the C++ ntHash; [`XxhMixer`] gives better‑distributed secondary
+/// hashes when a downstream Bloom filter needs them.  Index 0 is the
+/// canonical hash under every mixer.
+///
+/// Mixers are shared as `&'static dyn HashMixer`; stateless ones are unit
+/// structs, and a configured one can be put in a `static` or leaked.
+///
+/// ```
+/// use nthash_rs::{util::XxhMixer, NtHashBuilder};
+///
+/// let hashes = |seq: &[u8], mixed: bool| -> Vec<Vec<u64>> {
+///     let b = NtHashBuilder::new(seq).k(5).num_hashes(3);
+///     let b = if mixed { b.mixer(&XxhMixer) } else { b };
+///     b.finish().unwrap().map(|(_, h)| h).collect()
+/// };
+/// let (reference, xxh) = (hashes(b"ACGTTGCA", false), hashes(b"ACGTTGCA", true));
+/// for (r, x) in reference.iter().zip(&xxh) {
+///     assert_eq!(r[0], x[0]);
+///     assert_ne!(r[1..], x[1..]);
+/// }
+/// ```
+pub trait HashMixer: fmt::Debug + Send + Sync {
+    /// Hash `i` (`i ≥ 1`) of a k‑mer with canonical hash `base`; `k` is the
+    /// k‑mer span or seed weight.
+    fn mix(&self, base: u64, k: u32, i: u64) -> u64;
+
+    /// Fill `hashes` with the canonical hash followed by
+    /// [`mix`](Self::mix) for each further index, like [`extend_hashes`].
+    #[inline]
+    fn extend(&self, fwd: u64, rev: u64, k: u32, hashes: &mut [u64]) {
+        let Some((first, rest)) = hashes.split_first_mut() else {
+            return;
+        };
+        *first = canonical(fwd, rev);
+        for (i, slot) in rest.iter_mut().enumerate() {
+            *slot = self.mix(*first, k, i as u64 + 1);
+        }
+    }
+}
+
+/// The C++ reference mixing (`MULTISEED` / `MULTISHIFT`), as in
+/// [`extend_hashes`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReferenceMixer;
+
+impl HashMixer for ReferenceMixer {
+    #[inline]
+    fn mix(&self, base: u64, k: u32, i: u64) -> u64 {
+        let h = base.wrapping_mul(i ^ (k as u64).wrapping_mul(MULTISEED));
+        h ^ h >> MULTISHIFT
+    }
+
+    #[inline]
+    fn extend(&self, fwd: u64, rev: u64, k: u32, hashes: &mut [u64]) {
+        extend_hashes(fwd, rev, k, hashes);
+    }
+}
+
+/// The XXH64 avalanche finalizer over the canonical hash offset by a
+/// multiple of the hash index, so every output bit depends on every input
+/// bit.
+///
+/// The reference scheme multiplies the canonical hash, so its secondary
+/// hashes keep its low zero bits and differ little for nearby inputs;
+/// this one does not, at the cost of a few more multiplications.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct XxhMixer;
+
+impl HashMixer for XxhMixer {
+    #[inline]
+    fn mix(&self, base: u64, k: u32, i: u64) -> u64 {
+        const PRIME64_1: u64 = 0x9e37_79b1_85eb_ca87;
+        const PRIME64_2: u64 = 0xc2b2_ae3d_27d4_eb4f;
+        const PRIME64_3: u64 = 0x1656_67b1_9e37_79f9;
+        let mut h = base ^ (k as u64).wrapping_mul(MULTISEED);
+        h = h.wrapping_add(i.wrapping_mul(PRIME64_1));
+        h ^= h >> 33;
+        h = h.wrapping_mul(PRIME64_2);
+        h ^= h >> 29;
+        h = h.wrapping_mul(PRIME64_3);
+        h ^ h >> 32
+    }
+}
+
+/// A hasher's mixer; `None` is the reference scheme, inlined.
+pub(crate) type Mixer = Option<&'static dyn HashMixer>;
+
+/// [`extend_hashes`] under `mixer`, with the canonical hash combined
+/// under `policy`.
+#[inline(always)]
+pub(crate) fn extend_hashes_with(
+    mixer: Mixer,
+    policy: CanonicalPolicy,
+    fwd: u64,
+    rev: u64,
+    k: u32,
+    hashes: &mut [u64],
+) {
+    let (fwd, rev) = match policy {
+        CanonicalPolicy::Sum => (fwd, rev),
+        _ => (policy.combine(fwd, rev), 0),
+    };
+    match mixer {
+        None => extend_hashes(fwd, rev, k, hashes),
+        Some(m) => m.extend(fwd, rev, k, hashes),
+    }
+}
+
+/// Re‑key a hash with a 64‑bit `salt`.
+///
+/// A salt of `0` is the identity, so unsalted hashers are unaffected.  Any
+/// other salt XORs it in and applies the SplitMix64 finalizer, a bijection,
+/// so distinct salts yield independent‑looking streams without adding
+/// collisions.
+///
+/// # Examples
+///
+/// ```
+/// # use nthash_rs::util::salt_hash;
+/// assert_eq!(salt_hash(42, 0), 42);
+/// assert_ne!(salt_hash(42, 1), salt_hash(42, 2));
+/// ```
+#[inline]
+pub const fn salt_hash(h: u64, salt: u64) -> u64 {
+    if salt == 0 {
+        return h;
+    }
+    let mut x = h ^ salt;
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Whether two hashers read the same sequence: the same buffer, or equal
+/// contents.
+#[inline]
+pub(crate) fn same_seq(a: &[u8], b: &[u8]) -> bool {
+    std::ptr::eq(a, b) || a == b
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,6 +373,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn extend_matches_cpp_reference() {
         const F: u64 = 0x1234_5678_9ABC_DEF0;
         const R: u64 = 0x0FED_CBA9_8765_4321;
@@ -126,4 +392,39 @@ mod tests {
             assert_eq!(v[i], expected);
         }
     }
+
+    #[test]
+    fn mixers_keep_the_canonical_hash() {
+        let (mut reference, mut by_trait, mut xxh) = ([0u64; 12], [0u64; 12], [0u64; 12]);
+        extend_hashes(0xdead_beef, 0x1234, 31, &mut reference);
+        HashMixer::extend(&ReferenceMixer, 0xdead_beef, 0x1234, 31, &mut by_trait);
+        XxhMixer.extend(0xdead_beef, 0x1234, 31, &mut xxh);
+        assert_eq!(by_trait, reference);
+        assert_eq!(xxh[0], reference[0]);
+        for (i, &h) in reference.iter().enumerate().skip(1) {
+            assert_eq!(ReferenceMixer.mix(reference[0], 31, i as u64), h);
+        }
+        // distinct per index, and unlike the reference
+        let mut sorted = xxh.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 12);
+        assert!(xxh[1..].iter().zip(&reference[1..]).all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn array_extension_mixes_past_255() {
+        const M: usize = 600;
+        let wide = extend_hashes_array::<M>(0xdead_beef, 0x1234, 31);
+        let mut v = vec![0; M];
+        extend_hashes(0xdead_beef, 0x1234, 31, &mut v);
+        assert_eq!(wide[..], v[..]);
+        // every index mixes differently, including those that would
+        // collide if it were truncated to a byte
+        let mut sorted = wide.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), M);
+        assert_eq!(extend_hashes_array::<0>(1, 2, 3), [0u64; 0]);
+    }
 }
@@ -0,0 +1,72 @@
+//! **JavaScript bindings** via `wasm-bindgen`.
+//!
+//! The crate builds as an rlib only, so compile the module with
+//! `cargo rustc --release --target wasm32-unknown-unknown --features
+//! wasm-bindgen --crate-type cdylib`, run `wasm-bindgen` on the resulting
+//! `.wasm`, and call from JS:
+//!
+//! ```js
+//! import init, { hash_sequence, pos_hashes } from "./pkg/nthash_rs.js";
+//!
+//! await init();
+//! const hashes = hash_sequence("ACGTCAGTNNNNACGTACGT", 4); // BigUint64Array
+//! const pairs = pos_hashes("ACGTCAGTNNNNACGTACGT", 4);   // pos, hash, ...
+//! const SKIPPED = 0xFFFFFFFFFFFFFFFFn;  // hashes[5..12]: windows with an N
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    hash_into,
+    records::{as_u64s, PosHash},
+    Result,
+};
+
+/// Canonical hash of every k‑mer start; windows containing non‑ACGT bases
+/// hold [`SKIPPED_HASH`](crate::SKIPPED_HASH), as with [`hash_into`].
+fn hashes_per_position(seq: &[u8], k: u16) -> Result<Vec<u64>> {
+    let mut out = vec![0; (seq.len() + 1).saturating_sub(k as usize)];
+    hash_into(seq, k, &mut out)?;
+    Ok(out)
+}
+
+/// Canonical ntHash of each k‑mer of `seq`, indexed by start position.
+///
+/// Returns a `BigUint64Array` of length `seq.length - k + 1`; k‑mers
+/// containing bases other than A/C/G/T are reported as
+/// `0xFFFFFFFFFFFFFFFFn` ([`SKIPPED_HASH`](crate::SKIPPED_HASH)).  Throws
+/// on `k == 0` or a sequence shorter than `k`.
+#[wasm_bindgen]
+pub fn hash_sequence(seq: &str, k: u16) -> std::result::Result<Vec<u64>, JsError> {
+    hashes_per_position(seq.as_bytes(), k).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Valid k‑mers of `seq` as a flat `BigUint64Array` of `pos, hash` pairs
+/// — the memory layout of [`PosHash`] records.
+///
+/// Throws on `k == 0` or a sequence shorter than `k`.
+#[wasm_bindgen]
+pub fn pos_hashes(seq: &str, k: u16) -> std::result::Result<Vec<u64>, JsError> {
+    PosHash::collect(seq.as_bytes(), k)
+        .map(|recs| as_u64s(&recs).to_vec())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NtHash, SKIPPED_HASH};
+
+    #[test]
+    fn positions_line_up_with_nthash() {
+        let seq = b"ACGTCAGTNNNNACGTACGT";
+        let out = hashes_per_position(seq, 4).unwrap();
+        assert_eq!(out.len(), 17);
+        let mut h = NtHash::new(seq, 4, 1, 0).unwrap();
+        while h.roll() {
+            assert_eq!(out[h.pos()], h.hashes()[0]);
+        }
+        assert!(out[5..12].iter().all(|&x| x == SKIPPED_HASH));
+        assert!(hashes_per_position(b"ACG", 4).is_err());
+    }
+}
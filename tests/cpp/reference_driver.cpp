@@ -0,0 +1,60 @@
+// Hashes test cases with the C++ reference ntHash (https://github.com/bcgsc/ntHash)
+// for the `cpp-verify` tests in tests/reference_cpp.rs.
+//
+// Build against an installed ntHash, e.g.
+//
+//     g++ -O2 -std=c++17 tests/cpp/reference_driver.cpp -lnthash -o nthash-ref
+//
+// and point NTHASH_REFERENCE_DRIVER at the binary.
+//
+// Input, one case per line:   k <TAB> num_hashes <TAB> masks <TAB> sequence
+// where `masks` is `-` for contiguous NtHash or a comma-separated list of
+// spaced-seed masks for SeedNtHash.  For every case the driver prints one
+// line per valid k-mer, `pos <TAB> hash...` in lowercase hex, followed by
+// an empty line.
+
+#include <cstdint>
+#include <iostream>
+#include <sstream>
+#include <string>
+#include <vector>
+
+#include <nthash/nthash.hpp>
+
+static void print(size_t pos, const uint64_t* hashes, size_t n) {
+    std::cout << pos;
+    for (size_t i = 0; i < n; ++i) {
+        std::cout << '\t' << std::hex << hashes[i] << std::dec;
+    }
+    std::cout << '\n';
+}
+
+int main() {
+    std::ios::sync_with_stdio(false);
+    std::string line;
+    while (std::getline(std::cin, line)) {
+        std::istringstream fields(line);
+        unsigned k, m;
+        std::string masks, seq;
+        fields >> k >> m >> masks >> seq;
+
+        if (masks == "-") {
+            nthash::NtHash h(seq, m, k);
+            while (h.roll()) {
+                print(h.get_pos(), h.hashes(), m);
+            }
+        } else {
+            std::vector<std::string> seeds;
+            std::istringstream list(masks);
+            for (std::string mask; std::getline(list, mask, ',');) {
+                seeds.push_back(mask);
+            }
+            nthash::SeedNtHash h(seq, seeds, m, k);
+            while (h.roll()) {
+                print(h.get_pos(), h.hashes(), seeds.size() * m);
+            }
+        }
+        std::cout << '\n';
+    }
+    return 0;
+}
@@ -0,0 +1,145 @@
+//! Property tests for the rolling equivalences the bit‑twiddling must
+//! preserve: rolling vs. hashing each window from scratch, `roll_back` vs.
+//! `roll`, `peek` vs. the next `roll`, and the blind and spaced‑seed
+//! hashers vs. the contiguous one.
+//!
+//! The runner is seeded with a fixed value and never writes regression
+//! files, so every run checks the same cases.
+
+use nthash_rs::{hash_kmer, BlindNtHash, NtHash, SeedNtHash};
+use proptest::prelude::*;
+use proptest::test_runner::RngSeed;
+
+fn config() -> ProptestConfig {
+    ProptestConfig {
+        cases: 1024,
+        rng_seed: RngSeed::Fixed(0x6e74_6861_7368),
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    }
+}
+
+/// Mostly ACGT in both cases, with the occasional `N`.
+fn base() -> impl Strategy<Value = u8> + Clone {
+    prop_oneof![
+        20 => prop::sample::select(b"ACGTacgt".to_vec()),
+        1 => Just(b'N'),
+    ]
+}
+
+fn acgt() -> impl Strategy<Value = u8> + Clone {
+    prop::sample::select(b"ACGT".to_vec())
+}
+
+/// `(k, seq)` with `seq.len() >= k`.
+fn case(bases: impl Strategy<Value = u8> + Clone) -> impl Strategy<Value = (u16, Vec<u8>)> {
+    (1u16..=40).prop_flat_map(move |k| {
+        let len = k as usize..k as usize + 150;
+        (Just(k), prop::collection::vec(bases.clone(), len))
+    })
+}
+
+/// Every valid window of `seq`, hashed on its own.
+fn direct(seq: &[u8], k: u16, m: u8) -> Vec<(usize, Vec<u64>)> {
+    seq.windows(k as usize)
+        .enumerate()
+        .filter_map(|(pos, w)| hash_kmer(w, m).ok().map(|h| (pos, h)))
+        .collect()
+}
+
+fn rolled(h: &mut NtHash) -> Vec<(usize, Vec<u64>)> {
+    let mut out = Vec::new();
+    while h.roll() {
+        out.push((h.pos(), h.hashes().to_vec()));
+    }
+    out
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn roll_matches_direct_hashing((k, seq) in case(base()), m in 1u8..5) {
+        let mut h = NtHash::new(&seq, k, m, 0).unwrap();
+        prop_assert_eq!(rolled(&mut h), direct(&seq, k, m));
+    }
+
+    #[test]
+    fn roll_back_retraces_roll((k, seq) in case(acgt()), m in 1u8..4) {
+        let mut h = NtHash::new(&seq, k, m, 0).unwrap();
+        let mut forward = rolled(&mut h);
+        let mut backward = vec![forward.pop().unwrap()];
+        while h.roll_back() {
+            backward.push((h.pos(), h.hashes().to_vec()));
+        }
+        backward.reverse();
+        forward.push(backward.last().unwrap().clone());
+        prop_assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn peeks_predict_rolls((k, seq) in case(base()), m in 1u8..4) {
+        let mut h = NtHash::new(&seq, k, m, 0).unwrap();
+        while h.roll() {
+            let current = h.hashes().to_vec();
+            if h.peek_back() {
+                let prev = &seq[h.pos() - 1..h.pos() - 1 + k as usize];
+                prop_assert_eq!(h.peeked_hashes(), hash_kmer(prev, m).unwrap());
+            }
+            let peeked = h.peek().then(|| h.peeked_hashes().to_vec());
+            prop_assert_eq!(h.hashes(), &current[..]);
+            if let Some(next) = peeked {
+                let pos = h.pos();
+                prop_assert!(h.roll());
+                prop_assert_eq!((h.pos(), h.hashes()), (pos + 1, &next[..]));
+                prop_assert!(h.roll_back());
+            }
+        }
+    }
+
+    #[test]
+    fn blind_matches_contiguous((k, seq) in case(acgt()), m in 1u8..4) {
+        let expected = direct(&seq, k, m);
+        let mut h = BlindNtHash::new(&seq, k, m, 0).unwrap();
+        prop_assert_eq!(h.hashes(), &expected[0].1[..]);
+        for (pos, hashes) in &expected[1..] {
+            h.roll(seq[pos + k as usize - 1]);
+            prop_assert_eq!(h.hashes(), &hashes[..]);
+        }
+        for (pos, hashes) in expected.iter().rev().skip(1) {
+            h.roll_back(seq[*pos]);
+            prop_assert_eq!(h.hashes(), &hashes[..]);
+        }
+    }
+
+    #[test]
+    fn seed_roll_matches_fresh_start(
+        (k, seq) in case(base()),
+        bits in prop::collection::vec(any::<bool>(), 40),
+        m in 1usize..3,
+    ) {
+        // a symmetric mask with both ends set, so every k is a valid span
+        let ku = k as usize;
+        let mask: String = (0..ku)
+            .map(|i| if i == 0 || i == ku - 1 || bits[i.min(ku - 1 - i)] { '1' } else { '0' })
+            .collect();
+        let masks = [mask];
+        let mut h = SeedNtHash::new(&seq, &masks, m, k, 0).unwrap();
+        while h.roll() {
+            let mut fresh = SeedNtHash::new(&seq, &masks, m, k, h.pos()).unwrap();
+            prop_assert!(fresh.roll());
+            prop_assert_eq!((fresh.pos(), fresh.hashes()), (h.pos(), h.hashes()));
+
+            // bases under a don't‑care position do not affect the hash
+            let mut variant = seq.clone();
+            for (i, c) in masks[0].bytes().enumerate() {
+                if c == b'0' {
+                    variant[h.pos() + i] = b'N';
+                }
+            }
+            let mut other = SeedNtHash::new(&variant, &masks, m, k, h.pos()).unwrap();
+            prop_assert!(other.roll());
+            prop_assert_eq!(other.hashes(), h.hashes());
+        }
+    }
+}
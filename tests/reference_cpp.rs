@@ -0,0 +1,161 @@
+//! Cross‑check against the C++ reference ntHash (`cpp-verify` feature).
+//!
+//! Thousands of pseudo‑random `(seq, k, num_hashes, masks)` cases are
+//! generated from a fixed seed and piped through the driver in
+//! `tests/cpp/reference_driver.cpp`, located by the
+//! `NTHASH_REFERENCE_DRIVER` environment variable; every position and hash
+//! must match [`NtHash`] / [`SeedNtHash`].
+//!
+//! ```text
+//! g++ -O2 -std=c++17 tests/cpp/reference_driver.cpp -lnthash -o nthash-ref
+//! NTHASH_REFERENCE_DRIVER=./nthash-ref cargo test --features cpp-verify --test reference_cpp
+//! ```
+
+#![cfg(feature = "cpp-verify")]
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Command, Stdio},
+    thread,
+};
+
+use nthash_rs::{NtHash, SeedNtHash};
+
+const CASES: usize = 5000;
+
+struct Case {
+    k: u16,
+    num_hashes: u8,
+    masks: Vec<String>,
+    seq: Vec<u8>,
+}
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn cases() -> Vec<Case> {
+    let mut rng = XorShift(0x6e74_6861_7368);
+    (0..CASES)
+        .map(|i| {
+            let k = 1 + rng.below(64) as u16;
+            let num_hashes = 1 + rng.below(4) as u8;
+            let len = k as usize + rng.below(300) as usize;
+            let seq = (0..len)
+                .map(|_| match rng.below(100) {
+                    0 | 1 => b'N',
+                    r => b"ACGTacgt"[r as usize % 8],
+                })
+                .collect();
+            // every third case uses one to three spaced seeds
+            let masks = if i % 3 == 2 {
+                (0..1 + rng.below(3))
+                    .map(|_| {
+                        (0..k)
+                            .map(|j| {
+                                let care = j == 0 || j == k - 1 || rng.below(2) == 0;
+                                if care {
+                                    '1'
+                                } else {
+                                    '0'
+                                }
+                            })
+                            .collect()
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Case {
+                k,
+                num_hashes,
+                masks,
+                seq,
+            }
+        })
+        .collect()
+}
+
+/// `pos <TAB> hash...` lines, as the driver prints them.
+fn expected(case: &Case) -> Vec<String> {
+    fn line(pos: usize, hashes: &[u64]) -> String {
+        let mut s = pos.to_string();
+        for h in hashes {
+            s.push_str(&format!("\t{h:x}"));
+        }
+        s
+    }
+    let mut out = Vec::new();
+    if case.masks.is_empty() {
+        let mut h = NtHash::new(&case.seq, case.k, case.num_hashes, 0).unwrap();
+        while h.roll() {
+            out.push(line(h.pos(), h.hashes()));
+        }
+    } else {
+        let mut h =
+            SeedNtHash::new(&case.seq, &case.masks, case.num_hashes as usize, case.k, 0).unwrap();
+        while h.roll() {
+            out.push(line(h.pos(), h.hashes()));
+        }
+    }
+    out
+}
+
+#[test]
+fn matches_cpp_reference() {
+    let driver = std::env::var("NTHASH_REFERENCE_DRIVER")
+        .expect("set NTHASH_REFERENCE_DRIVER to the built tests/cpp/reference_driver.cpp");
+    let mut child = Command::new(&driver)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("cannot run {driver}: {e}"));
+
+    let cases = cases();
+    let mut input = String::new();
+    for c in &cases {
+        let masks = if c.masks.is_empty() {
+            "-".to_string()
+        } else {
+            c.masks.join(",")
+        };
+        let seq = std::str::from_utf8(&c.seq).unwrap();
+        input.push_str(&format!("{}\t{}\t{masks}\t{seq}\n", c.k, c.num_hashes));
+    }
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    for (i, c) in cases.iter().enumerate() {
+        let mut got = Vec::new();
+        loop {
+            let line = lines.next().expect("driver output ended early").unwrap();
+            if line.is_empty() {
+                break;
+            }
+            got.push(line);
+        }
+        assert_eq!(
+            got,
+            expected(c),
+            "case {i}: k={} num_hashes={} masks={:?} seq={}",
+            c.k,
+            c.num_hashes,
+            c.masks,
+            String::from_utf8_lossy(&c.seq)
+        );
+    }
+    writer.join().unwrap().unwrap();
+    assert!(child.wait().unwrap().success());
+}
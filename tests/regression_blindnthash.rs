@@ -46,7 +46,7 @@ fn regression_simple_nthash() {
     ];
 
     let k_usize = k as usize;
-    let results: Vec<(usize, Vec<u64>)> = iter.collect();
+    let results: Vec<(usize, Vec<u64>)> = iter.map(|(pos, hashes)| (pos.start, hashes)).collect();
     assert_eq!(results.len(), expected_kmers.len());
 
     for (i, (pos, hashes)) in results.iter().enumerate() {
@@ -50,7 +50,7 @@ fn regression_simple_seednthash() {
     ];
 
     let k_usize = k as usize;
-    let results: Vec<(usize, Vec<u64>)> = iter.collect();
+    let results: Vec<(usize, Vec<u64>)> = iter.map(|(pos, hashes)| (pos.start, hashes)).collect();
     assert_eq!(results.len(), expected_kmers.len());
 
     for (i, (pos, hashes)) in results.iter().enumerate() {